@@ -2,4 +2,4 @@ use anchor_lang::prelude::*;
 
 declare_program!(damm_v2);
 
-pub use damm_v2::*;
+pub use self::damm_v2::*;
@@ -0,0 +1,103 @@
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+declare_id!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
+
+/// Seed of the DAMM v2 pool authority PDA owning the pool vaults
+pub const POOL_AUTHORITY_SEED: &[u8] = b"pool_authority";
+
+/// Minimal DAMM v2 stand-in used by the tests to exercise the position fee claim CPI.
+/// Deployed at the DAMM v2 address, it pays out whatever the pool vaults hold as the
+/// position's pending fees
+#[program]
+pub mod mock_damm_v2 {
+    use super::*;
+
+    pub fn claim_position_fee(ctx: Context<ClaimPositionFee>) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[POOL_AUTHORITY_SEED, &[ctx.bumps.pool_authority]]];
+        let fee_a = ctx.accounts.token_a_vault.amount;
+        let fee_b = ctx.accounts.token_b_vault.amount;
+
+        for (vault, destination, amount) in [
+            (&ctx.accounts.token_a_vault, &ctx.accounts.token_a_account, fee_a),
+            (&ctx.accounts.token_b_vault, &ctx.accounts.token_b_account, fee_b),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+            anchor_spl::token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_a_program.to_account_info(),
+                    anchor_spl::token::Transfer {
+                        from: vault.to_account_info(),
+                        to: destination.to_account_info(),
+                        authority: ctx.accounts.pool_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+        }
+
+        msg!("Mock position fee claim: {} base, {} quote", fee_a, fee_b);
+
+        Ok(())
+    }
+}
+
+/// Same account order as the DAMM v2 `claim_position_fee` instruction
+#[derive(Accounts)]
+pub struct ClaimPositionFee<'info> {
+    /// CHECK: Pool authority PDA, signing the vault transfers
+    #[account(seeds = [POOL_AUTHORITY_SEED], bump)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Pool account (not read by the mock)
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Position account (not read by the mock)
+    #[account(mut)]
+    pub position: UncheckedAccount<'info>,
+
+    /// Receives the base fees
+    #[account(mut)]
+    pub token_a_account: Account<'info, TokenAccount>,
+
+    /// Receives the quote fees
+    #[account(mut)]
+    pub token_b_account: Account<'info, TokenAccount>,
+
+    /// Pool base vault, its whole balance is paid out as base fees
+    #[account(mut, token::authority = pool_authority)]
+    pub token_a_vault: Account<'info, TokenAccount>,
+
+    /// Pool quote vault, its whole balance is paid out as quote fees
+    #[account(mut, token::authority = pool_authority)]
+    pub token_b_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Base mint
+    pub token_a_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Quote mint
+    pub token_b_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Position NFT account
+    pub position_nft_account: UncheckedAccount<'info>,
+
+    /// Position owner
+    pub owner: Signer<'info>,
+
+    /// Base token program
+    pub token_a_program: Program<'info, Token>,
+
+    /// Quote token program
+    pub token_b_program: Program<'info, Token>,
+
+    /// CHECK: Event authority (unused by the mock)
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: This program
+    pub program: UncheckedAccount<'info>,
+}
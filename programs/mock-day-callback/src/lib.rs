@@ -0,0 +1,59 @@
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+declare_id!("EaSCyPskuVaHqMPPEuk76xbAdEFJh4d76tGPmVZYgzER");
+
+/// star-fee-distribution program, the only accepted caller
+pub const STAR_FEE_DISTRIBUTION_ID: Pubkey = pubkey!("FAAk54pcwJFvHD76YaB5sZzqXCEhUCVpP3cBvggKofuS");
+
+/// Minimal day close callback used by the tests to observe the notification
+#[program]
+pub mod mock_day_callback {
+    use super::*;
+
+    pub fn on_distribution_day_closed(
+        _ctx: Context<OnDistributionDayClosed>,
+        day: u32,
+        total_distributed_to_investors: u64,
+        quote_routed_at_close: u64,
+        investors_processed: u32,
+    ) -> Result<()> {
+        msg!(
+            "Day close callback: day {}, distributed {}, routed {}, investors {}",
+            day,
+            total_distributed_to_investors,
+            quote_routed_at_close,
+            investors_processed
+        );
+
+        emit!(DayClosedNotification {
+            day,
+            total_distributed_to_investors,
+            quote_routed_at_close,
+            investors_processed,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct OnDistributionDayClosed<'info> {
+    /// CHECK: Distribution config of the notifying program
+    #[account(owner = STAR_FEE_DISTRIBUTION_ID)]
+    pub distribution_config: UncheckedAccount<'info>,
+
+    /// CHECK: Crank state of the notifying program
+    #[account(owner = STAR_FEE_DISTRIBUTION_ID)]
+    pub crank_state: UncheckedAccount<'info>,
+}
+
+/// Event emitted for every day close notification received
+#[event]
+pub struct DayClosedNotification {
+    pub day: u32,
+    pub total_distributed_to_investors: u64,
+    pub quote_routed_at_close: u64,
+    pub investors_processed: u32,
+}
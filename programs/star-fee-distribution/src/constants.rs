@@ -1,3 +1,5 @@
+use anchor_lang::prelude::*;
+
 // PDA Seeds
 pub const FEE_COLLECTOR_SEED: &[u8] = b"fee_collector";
 pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
@@ -5,11 +7,69 @@ pub const DEPOSIT_VAULT_SEED: &[u8] = b"deposit_vault";
 pub const INVESTOR_RECORD_SEED: &[u8] = b"investor_record";
 pub const CRANK_STATE_SEED: &[u8] = b"crank_state";
 pub const DISTRIBUTION_CONFIG_SEED: &[u8] = b"distribution_config";
+pub const POSITION_STATE_SEED: &[u8] = b"position_state";
+pub const DAY_RECORD_SEED: &[u8] = b"day_record";
+pub const PACKED_INVESTORS_SEED: &[u8] = b"packed_investors";
+pub const KEEPER_INCENTIVE_SEED: &[u8] = b"keeper_incentive";
+pub const POSITION_REGISTRY_SEED: &[u8] = b"position_registry";
 
 // Default policy parameters
 pub const DEFAULT_INVESTOR_FEE_SHARE_BPS: u16 = 5000; // 50%
 pub const DEFAULT_MIN_PAYOUT_LAMPORTS: u64 = 10_000; // 0.00001 SOL minimum
 pub const DEFAULT_DAILY_CAP_LAMPORTS: u64 = 0; // 0 = no cap
+pub const DEFAULT_CREATOR_WALLET_TIMELOCK_SECONDS: i64 = 172_800; // 48 hours
+pub const DEFAULT_FEE_SHARE_MULTIPLIER_BPS: u16 = 10000; // 1x
+pub const DEFAULT_ELIGIBILITY_DELAY_DAYS: u32 = 1; // Eligible from the next distribution day
+pub const DEFAULT_DAY_MAX_DURATION_SECONDS: i64 = 172_800; // 48 hours
+
+// Remainder routing policies for DistributionConfig::remainder_policy
+pub const REMAINDER_POLICY_CREATOR: u8 = 0; // Route the remainder to the creator wallet
+pub const REMAINDER_POLICY_INVESTORS: u8 = 1; // Distribute the remainder to investors by weight
+pub const REMAINDER_POLICY_TREASURY: u8 = 2; // Route the remainder to the treasury wallet
+
+// Carry-over handling for DistributionConfig::carry_over_policy
+pub const CARRY_OVER_POLICY_RETAIN: u8 = 0; // Keep carry-over out of the day's pool until flushed
+pub const CARRY_OVER_POLICY_INCLUDE: u8 = 1; // Add carry-over to every day's investor pool
+pub const CARRY_OVER_POLICY_HOLD: u8 = 2; // Add carry-over only once a day's claimed quote covers it
+
+// Destination of carry-over above DistributionConfig::max_carry_over at day close
+pub const CARRY_OVER_CAP_POLICY_CREATOR: u8 = 0; // Sweep the excess with the remainder (to the creator under the investor policy)
+pub const CARRY_OVER_CAP_POLICY_TOP_INVESTOR: u8 = 1; // Pay the excess to the day's largest-weight investor
+
+// Handling of fully withdrawn depositor records in distribute_to_investor
+pub const ZERO_BALANCE_POLICY_SKIP: u8 = 0; // Return without counting the record as paid
+pub const ZERO_BALANCE_POLICY_REJECT: u8 = 1; // Fail so the keeper drops the record from its pages
+
+// Packed investors account
+pub const MAX_PACKED_INVESTORS: usize = 128; // Keeps the account under the 10KB CPI allocation limit
+pub const PACKED_INVESTOR_FLAG_ACTIVE: u8 = 1 << 0; // Slot holds an investor with a balance
+pub const MAX_INVESTORS_PER_PAGE: u32 = 32; // Keeps a page's PackedPagePayouts event well under the log limit
+
+// Position registry
+pub const MAX_REGISTERED_POSITIONS: usize = 16; // Honorary positions one config may claim from
+
+// Deposit age brackets
+pub const MAX_AGE_BRACKETS: usize = 4; // Entries in DistributionConfig::age_brackets
+
+// Quote mints accepted by InitializeDistributionConfig
+pub const ALLOWED_QUOTE_MINTS: [Pubkey; 2] = [
+    pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"), // USDC
+    pubkey!("So11111111111111111111111111111111111111112"), // Wrapped SOL
+];
+
+// Decimals of each entry in ALLOWED_QUOTE_MINTS (same order)
+pub const ALLOWED_QUOTE_MINT_DECIMALS: [u8; 2] = [USDC_DECIMALS, SOL_DECIMALS];
+
+// Stable deposit mints accepted by InitializeDistributionConfig (6 decimals, classic token program,
+// so the USDC-denominated deposit limits apply unchanged)
+pub const ALLOWED_DEPOSIT_MINTS: [Pubkey; 2] = [
+    pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"), // USDC
+    pubkey!("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB"), // USDT
+];
+
+// Token decimals
+pub const SOL_DECIMALS: u8 = 9;
+pub const USDC_DECIMALS: u8 = 6;
 
 // Validation constants
 pub const MIN_SOL_DEPOSIT: u64 = 1_000_000; // 0.001 SOL minimum
@@ -20,8 +80,24 @@ pub const MAX_USDC_DEPOSIT: u64 = 1_000_000_000_000; // 1M USDC maximum
 // Fee distribution constants
 pub const MAX_INVESTOR_FEE_SHARE_BPS: u16 = 10000; // 100% maximum
 pub const MIN_INVESTOR_FEE_SHARE_BPS: u16 = 0; // 0% minimum
+pub const MAX_FEE_SHARE_MULTIPLIER_BPS: u16 = 30000; // 3x maximum
 pub const DISTRIBUTION_BATCH_SIZE: u32 = 10; // Process 10 investors per batch
 pub const SECONDS_PER_DAY: i64 = 86400; // 24 hours in seconds
+pub const MAX_ROUNDS_PER_DAY: u8 = 24; // At most hourly distribution rounds
+pub const MAX_REMAINING_LOCK_SECONDS: u64 = 4 * 365 * 86400; // Remaining lock counted toward weights (4 years)
+pub const PPM: u32 = 1_000_000; // Parts per million, the precision f_locked is allocated at
+pub const PPM_PER_BPS: u32 = 100; // Parts per million in one basis point
+
+// DAMM v2 fee numerators are over 1e9 and fee tiers over 1e6 (hundredths of a basis point)
+pub const DAMM_V2_FEE_NUMERATOR_PER_FEE_TIER: u64 = 1_000;
+
+// DAMM v2 pool account size (discriminator included) the CPI bindings were generated against
+pub const SUPPORTED_DAMM_V2_POOL_LEN: usize = 8 + std::mem::size_of::<damm_v2::accounts::Pool>();
+
+// Day close callback instruction: Anchor sighash of `on_distribution_day_closed`, followed by
+// day (u32), total_distributed_to_investors (u64), quote_routed_at_close (u64) and
+// investors_processed (u32), all little-endian
+pub const DAY_CLOSED_CALLBACK_DISCRIMINATOR: [u8; 8] = [54, 136, 139, 66, 146, 54, 127, 194];
 
 // Error codes
 pub const ERROR_INVALID_DEPOSIT_AMOUNT: u32 = 0x0;
@@ -60,4 +60,194 @@ pub enum ErrorCode {
     CreatorWalletNotProvided,
     #[msg("Insufficient balance for operation")]
     InsufficientBalance,
+    #[msg("Quote vault does not match the vault used by the crank")]
+    QuoteVaultMismatch,
+    #[msg("Creator quote account required to auto-close the day")]
+    CreatorQuoteAccountRequired,
+    #[msg("Vesting account is invalid or not owned by the configured vesting program")]
+    VestingAccountInvalid,
+    #[msg("Vested investor pool exhausted for this day")]
+    VestedPoolExhausted,
+    #[msg("Signer is not the distribution config admin")]
+    UnauthorizedAdmin,
+    #[msg("Creator quote account is not owned by the creator wallet")]
+    CreatorQuoteAccountMismatch,
+    #[msg("Invalid creator wallet timelock")]
+    InvalidCreatorWalletTimelock,
+    #[msg("Quote mint is not on the allowlist")]
+    QuoteMintNotAllowed,
+    #[msg("Payout root does not match the submitted leaves or the day's distributed total")]
+    PayoutRootMismatch,
+    #[msg("Day must be closed before submitting a payout root")]
+    DayNotClosed,
+    #[msg("Signer is not allowed to submit the payout root")]
+    UnauthorizedPayoutRootSubmitter,
+    #[msg("Depositor record still holds a balance")]
+    DepositorRecordNotEmpty,
+    #[msg("Carry-over is not backed by quote in the vault")]
+    CarryOverNotBacked,
+    #[msg("Fee share multiplier must be between 1 and 30000 bps")]
+    InvalidFeeShareMultiplier,
+    #[msg("Division by zero")]
+    DivisionByZero,
+    #[msg("Invalid remainder policy")]
+    InvalidRemainderPolicy,
+    #[msg("Treasury wallet required for the treasury remainder policy")]
+    TreasuryWalletNotProvided,
+    #[msg("SOL vault must be a system-owned account without data")]
+    SolVaultNotSystemAccount,
+    #[msg("Distribution config cannot change while a distribution day is in progress")]
+    ConfigLockedDuringDistribution,
+    #[msg("New quote mint must differ from the current one")]
+    QuoteMintUnchanged,
+    #[msg("Eligibility root is not configured")]
+    EligibilityRootNotSet,
+    #[msg("Eligibility proof does not match the configured root")]
+    EligibilityProofInvalid,
+    #[msg("Cannot flush carry-over while a distribution day is in progress")]
+    DistributionInProgress,
+    #[msg("Flush recipients must cover the total weighted stake")]
+    FlushCoverageIncomplete,
+    #[msg("Depositor record does not match its investor PDA or is duplicated")]
+    InvalidDepositorRecord,
+    #[msg("Investor quote account must be owned by the investor and use the quote mint")]
+    InvalidInvestorQuoteAccount,
+    #[msg("Position has pending base fees, refusing to claim")]
+    PendingBaseFeesDetected,
+    #[msg("No distribution page has been processed today")]
+    NoPagesProcessed,
+    #[msg("Packed investors account has no free slot")]
+    PackedInvestorsFull,
+    #[msg("Packed investor slot does not belong to this investor")]
+    PackedInvestorMismatch,
+    #[msg("Investor already has a packed investor slot")]
+    PackedInvestorAlreadyExists,
+    #[msg("The distribution day has not been begun")]
+    DayNotBegun,
+    #[msg("Deposits and withdrawals are locked while the distribution day is in progress")]
+    DepositsLocked,
+    #[msg("Day max duration must be 0 (default) or at least one day")]
+    InvalidDayMaxDuration,
+    #[msg("Distribution day has not reached its maximum duration")]
+    DayNotExpired,
+    #[msg("Minimum deposit interval cannot be negative")]
+    InvalidDepositInterval,
+    #[msg("Deposit made too soon after the investor's last activity")]
+    DepositTooFrequent,
+    #[msg("Invalid carry-over policy")]
+    InvalidCarryOverPolicy,
+    #[msg("f_locked floor must not exceed the ceiling, and both must be at most 10000 bps")]
+    InvalidFLockedBand,
+    #[msg("Summed depositor balances drift from vault_stats.current_total_usdc beyond the tolerance")]
+    VaultStatsDrift,
+    #[msg("DAMM v2 swap accounts are required to pay the creator in creator_payout_mint")]
+    CreatorPayoutSwapAccountsRequired,
+    #[msg("A non-zero min_out is required when the creator remainder is swapped")]
+    CreatorPayoutMinOutRequired,
+    #[msg("Creator payout swap returned less than min_out")]
+    CreatorPayoutSlippageExceeded,
+    #[msg("Clock is behind the last distribution day start")]
+    ClockWentBackward,
+    #[msg("Page size exceeds MAX_INVESTORS_PER_PAGE")]
+    PageTooLarge,
+    #[msg("Deposits are not accepted outside the deposit window")]
+    DepositWindowClosed,
+    #[msg("Deposit window bounds must be non-negative and start before the end")]
+    InvalidDepositWindow,
+    #[msg("Investor quote account is not the one registered in the depositor record")]
+    QuoteAccountNotRegistered,
+    #[msg("Invalid carry-over cap policy")]
+    InvalidCarryOverCapPolicy,
+    #[msg("Top investor quote account is missing or not the day's largest-weight investor")]
+    TopInvestorQuoteAccountMismatch,
+    #[msg("Fee tier does not match the pool's base fee")]
+    FeeTierMismatch,
+    #[msg("Invalid zero-balance policy")]
+    InvalidZeroBalancePolicy,
+    #[msg("Depositor record has a zero balance")]
+    ZeroBalanceRecord,
+    #[msg("Deposit vault is required to compound the payout")]
+    DepositVaultRequired,
+    #[msg("Investor quote account cannot be the program quote vault")]
+    InvalidQuoteAccount,
+    #[msg("Invalid rounds per day")]
+    InvalidRoundsPerDay,
+    #[msg("Invalid distribution round index")]
+    InvalidRoundIndex,
+    #[msg("Distribution round has not opened yet")]
+    RoundNotOpen,
+    #[msg("Prior distribution day is past its deadline and must be force-closed")]
+    PriorDayNotClosed,
+    #[msg("Creator inactivity window must not be negative")]
+    InvalidCreatorInactivity,
+    #[msg("Position NFT mint must have a supply of 1 and 0 decimals")]
+    InvalidPositionNft,
+    #[msg("Investor has not approved a batch withdrawal")]
+    BatchWithdrawNotApproved,
+    #[msg("Creator minimum share must be at most 10000 bps, leave room for a non-zero investor share and not be combined with the investor remainder policy")]
+    InvalidCreatorMinShare,
+    #[msg("Total investor fee does not match the amount computed by the crank")]
+    FeeAmountMismatch,
+    #[msg("Mint is not the configured deposit mint")]
+    WrongDepositMint,
+    #[msg("Non-final crank pages must process exactly DISTRIBUTION_BATCH_SIZE investors")]
+    InvalidPageSize,
+    #[msg("Canonical fee and deposit vaults cannot be rescued")]
+    CanonicalVaultProtected,
+    #[msg("The program has ended: deposits are disabled and the flag cannot be cleared")]
+    ProgramEnded,
+    #[msg("Non-zero liquidity must sit in a single-sided range on the quote side of the current price")]
+    QuoteOnlyViolation,
+    #[msg("Callback program does not match the configured day close callback")]
+    CallbackProgramMismatch,
+    #[msg("Deposit would take the investor above the maximum share of the vault")]
+    ConcentrationLimitExceeded,
+    #[msg("Too many depositor records to simulate in one call")]
+    TooManySimulatedRecords,
+    #[msg("Fee destination must be a fee_collector-owned quote account other than a deposit vault")]
+    InvalidFeeDestination,
+    #[msg("DAMM v2 pool layout is not supported by this program")]
+    UnsupportedAmmVersion,
+    #[msg("Withdrawal exceeds the principal balance while earnings are locked")]
+    EarningsLocked,
+    #[msg("Age brackets must be ascending with multipliers between 1x and the maximum")]
+    InvalidAgeBrackets,
+    #[msg("A payout root has already been submitted for this day")]
+    PayoutRootAlreadySubmitted,
+    #[msg("Creator wallet, quote mint and deposit mint cannot be replaced, use their own instructions")]
+    ConfigFieldNotReplaceable,
+    #[msg("No quote fees were claimed into the vault since the day began")]
+    NoFreshClaim,
+    #[msg("No day record exists for this day")]
+    DayRecordNotFound,
+    #[msg("Auto-claim requires the honorary position accounts")]
+    AutoClaimAccountsRequired,
+    #[msg("Minimum payout must be below the non-zero daily cap")]
+    InvalidConfigCombination,
+    #[msg("New distribution days are paused")]
+    NewDaysPaused,
+    #[msg("Deposit would take the investor above the Y0-relative deposit cap")]
+    Y0ShareCapExceeded,
+    #[msg("Config change cool-off cannot be negative")]
+    InvalidConfigCooloff,
+    #[msg("Claimed quote exceeds the maximum claim per transaction")]
+    ClaimExceedsMax,
+    #[msg("Position is not in the position registry")]
+    PositionNotRegistered,
+    #[msg("Position is already in the position registry")]
+    PositionAlreadyRegistered,
+    #[msg("Position registry is full")]
+    PositionRegistryFull,
+    #[msg("Dust recipient quote account required to close the day")]
+    DustRecipientQuoteAccountRequired,
+    #[msg("Dust recipient quote account is not owned by the dust recipient")]
+    DustRecipientQuoteAccountMismatch,
+    #[msg("Payout quote account has a delegate")]
+    DelegatedPayoutAccount,
+    #[msg("Investor has already been paid this round")]
+    InvestorAlreadyPaid,
+    #[msg("Day record account is required to close the day")]
+    DayRecordRequired,
+    #[msg("Payout exceeds the day's investor allocation")]
+    InvestorPoolExhausted,
 }
\ No newline at end of file
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::constants::MAX_AGE_BRACKETS;
+use crate::states::AgeBracket;
 
 /// Event emitted when the honorary DAMM v2 position is initialized
 #[event]
@@ -23,6 +25,8 @@ pub struct HonoraryPositionInitialized {
     pub upper_tick: i32,
     /// Fee tier
     pub fee_tier: u16,
+    /// Liquidity added to the position (0 for the honorary position)
+    pub liquidity_delta: u128,
     /// Timestamp of initialization
     pub timestamp: i64,
 }
@@ -51,6 +55,8 @@ pub struct QuoteFeesClaimed {
 pub struct InvestorPayoutPage {
     /// Current distribution day number
     pub day: u32,
+    /// Distribution round within the day
+    pub round_index: u8,
     /// Page index
     pub page_index: u32,
     /// Number of investors processed in this page
@@ -73,11 +79,15 @@ pub struct InvestorPayoutPage {
     pub page_distributed: u64,
     /// Dust carried over
     pub carry_over: u64,
+    /// Portion of the creator remainder reserved for fully-vested investors
+    pub vested_pool: u64,
+    /// Accumulated quote held back by the distribution rate for later days
+    pub buffered_quote: u64,
     /// Total distributed so far today
     pub daily_distributed: u64,
-    /// Daily cap (0 = no cap)
+    /// Effective daily cap in quote native units (0 = no cap)
     pub daily_cap: u64,
-    /// Is this the final page of the day?
+    /// Is this the final page of the round?
     pub is_final_page: bool,
     /// Timestamp of distribution
     pub timestamp: i64,
@@ -88,11 +98,13 @@ pub struct InvestorPayoutPage {
 pub struct InvestorPayout {
     /// Current distribution day number
     pub day: u32,
+    /// Distribution round within the day
+    pub round_index: u8,
     /// Investor's wallet address
     pub investor: Pubkey,
-    /// Investor's locked balance (from DepositorRecord)
+    /// Investor's locked balance (multiplier-scaled DepositorRecord balance or external vesting amount)
     pub investor_locked_balance: u64,
-    /// Total locked across all investors
+    /// Total locked across all investors (weight denominator)
     pub total_locked: u64,
     /// Investor's weight in basis points
     pub weight_bps: u64,
@@ -108,6 +120,35 @@ pub struct InvestorPayout {
     pub min_payout: u64,
     /// Investor's quote token account
     pub investor_quote_account: Pubkey,
+    /// Payout was added to the investor's USDC balance instead of their quote account
+    pub compounded: bool,
+    /// Daily cap left after this payout (0 when uncapped)
+    pub remaining_daily_cap: u64,
+    /// Timestamp of payout
+    pub timestamp: i64,
+}
+
+/// Event emitted when a fully-vested investor receives their reduced share
+#[event]
+pub struct VestedInvestorPayout {
+    /// Current distribution day number
+    pub day: u32,
+    /// Investor's wallet address
+    pub investor: Pubkey,
+    /// Investor's historical USDC deposits (weight basis)
+    pub investor_total_deposited: u64,
+    /// Historical USDC deposits across all investors
+    pub total_deposited: u64,
+    /// Vested investor share in basis points
+    pub vested_investor_share_bps: u16,
+    /// Pool reserved for fully-vested investors this day
+    pub vested_pool: u64,
+    /// Actual payout after dust threshold
+    pub actual_payout: u64,
+    /// Dust amount (payout below minimum, left for the creator)
+    pub dust: u64,
+    /// Investor's quote token account
+    pub investor_quote_account: Pubkey,
     /// Timestamp of payout
     pub timestamp: i64,
 }
@@ -117,22 +158,79 @@ pub struct InvestorPayout {
 pub struct CreatorPayoutDayClosed {
     /// Distribution day number that was closed
     pub day: u32,
-    /// Creator's wallet address
+    /// Wallet that received the remainder (default under the investor policy)
     pub creator_wallet: Pubkey,
-    /// Creator's quote token account
+    /// Quote token account that received the remainder (default under the investor policy)
     pub creator_quote_account: Pubkey,
-    /// Amount of quote fees sent to creator (remainder)
+    /// Amount of quote fees routed out of the vault (remainder)
     pub creator_remainder: u64,
+    /// Remainder routing policy applied at close
+    pub remainder_policy: u8,
     /// Total amount distributed to investors this day
     pub total_distributed_to_investors: u64,
+    /// Total amount distributed to fully-vested investors this day
+    pub total_distributed_to_vested_investors: u64,
+    /// Amount the creator claimed before the day closed
+    pub creator_claimed_before_close: u64,
     /// Total investors processed this day
     pub total_investors_processed: u32,
     /// Carry-over dust from this day
     pub final_carry_over: u64,
+    /// Creator remainder left in the vault because the recipient could not be reached
+    pub unrouted_creator_remainder: u64,
     /// Timestamp when day was closed
     pub timestamp: i64,
 }
 
+/// Event emitted when a day closes without routing the creator remainder
+#[event]
+pub struct CreatorRemainderDeferred {
+    /// Distribution day number that was closed
+    pub day: u32,
+    /// Wallet the remainder is owed to
+    pub recipient_wallet: Pubkey,
+    /// Remainder already left unrouted by earlier days
+    pub previously_unrouted: u64,
+    /// Total remainder now held in the vault for the recipient
+    pub unrouted_creator_remainder: u64,
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when the day close callback program is notified
+#[event]
+pub struct DayCloseCallbackInvoked {
+    /// Distribution day number that was closed
+    pub day: u32,
+    /// Program that received the notification
+    pub callback_program: Pubkey,
+    /// Quote paid to investors over the day (including vested payouts)
+    pub total_distributed_to_investors: u64,
+    /// Quote that left the fee vault when the day was closed
+    pub quote_routed_at_close: u64,
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Event emitted when the creator claims part of the remainder mid-day
+#[event]
+pub struct CreatorPartialClaimed {
+    /// Distribution day number
+    pub day: u32,
+    /// Creator's wallet address
+    pub creator_wallet: Pubkey,
+    /// Creator's quote token account
+    pub creator_quote_account: Pubkey,
+    /// Amount claimed
+    pub amount: u64,
+    /// Quote balance kept back for investor payouts
+    pub reserved_for_investors: u64,
+    /// Total claimed by the creator this day
+    pub creator_claimed_today: u64,
+    /// Timestamp of the claim
+    pub timestamp: i64,
+}
+
 /// Event emitted when distribution config is initialized
 #[event]
 pub struct DistributionConfigInitialized {
@@ -150,10 +248,143 @@ pub struct DistributionConfigInitialized {
     pub creator_wallet: Pubkey,
     /// Quote mint address
     pub quote_mint: Pubkey,
+    /// Whether the crank closes the day on its final page
+    pub auto_close_on_final_page: bool,
+    /// External vesting program (default = DepositorRecord balances)
+    pub vesting_program: Pubkey,
+    /// Share of the creator remainder paid to fully-vested investors
+    pub vested_investor_share_bps: u16,
+    /// Admin allowed to schedule config changes
+    pub admin: Pubkey,
+    /// Delay before a creator wallet update takes effect (in seconds)
+    pub creator_wallet_timelock_seconds: i64,
+    /// Remainder routing policy (0 = creator, 1 = investors, 2 = treasury)
+    pub remainder_policy: u8,
+    /// Treasury wallet for the treasury remainder policy
+    pub treasury_wallet: Pubkey,
+    /// Whether per-page and per-investor payout events are emitted
+    pub emit_events: bool,
+    /// Days after the current distribution day before a new depositor becomes eligible
+    pub eligibility_delay_days: u32,
+    /// Merkle root of investors allowed to receive payouts (all zeros = no allowlist)
+    pub eligibility_root: [u8; 32],
+    /// Daily distribution cap in quote native units (0 = legacy `daily_cap_lamports`)
+    pub daily_cap_quote_units: u64,
+    /// Decimals of the quote mint
+    pub quote_decimals: u8,
+    /// Seconds after a day begins before anyone can force-close it
+    pub day_max_duration_seconds: i64,
+    /// Minimum time between an investor's deposits (0 = no limit)
+    pub min_deposit_interval_seconds: i64,
+    /// Carry-over policy (0 = retain, 1 = include, 2 = hold until covered)
+    pub carry_over_policy: u8,
+    /// Lower bound for f_locked in basis points
+    pub f_locked_floor_bps: u16,
+    /// Upper bound for f_locked in basis points (0 = none)
+    pub f_locked_ceiling_bps: u16,
+    /// Whether page 0 cross-checks vault stats against the depositor records
+    pub verify_total_locked: bool,
+    /// Allowed drift between the summed records and vault stats
+    pub total_locked_tolerance_units: u64,
+    /// Mint the creator remainder is paid in (default = quote mint)
+    pub creator_payout_mint: Pubkey,
+    /// Deposit window start (0 = no start bound)
+    pub deposit_window_start_ts: i64,
+    /// Deposit window end (0 = no end bound)
+    pub deposit_window_end_ts: i64,
+    /// Carry-over cap (0 = no cap)
+    pub max_carry_over: u64,
+    /// Carry-over cap policy
+    pub carry_over_cap_policy: u8,
+    /// Zero-balance record policy
+    pub zero_balance_policy: u8,
+    /// Distribution rounds per day
+    pub rounds_per_day: u8,
+    /// Minimum locked total to distribute
+    pub min_total_locked_to_distribute: u64,
+    /// Payouts weighted by remaining lock time
+    pub weight_by_remaining_lock: bool,
+    /// Creator inactivity window in seconds
+    pub creator_inactivity_seconds: i64,
+    /// Creator minimum share in basis points
+    pub creator_min_share_bps: u16,
+    /// Stable token accepted for deposits
+    pub deposit_mint: Pubkey,
+    /// Dust threshold scaling factor (0 = fixed minimum payout)
+    pub dust_threshold_k: u32,
+    /// Whether non-final crank pages must hold exactly `DISTRIBUTION_BATCH_SIZE` investors
+    pub fixed_page_size: bool,
+    /// Program notified when a day is closed (default = no callback)
+    pub callback_program: Pubkey,
+    /// Largest share of the vault's USDC a single investor may deposit up to (0 = no cap)
+    pub max_investor_share_bps: u16,
+    /// SOL paid to the keeper per crank page (0 = no reward)
+    pub keeper_reward_lamports: u64,
+    /// Timestamp until which compounded earnings cannot be withdrawn (0 = never locked)
+    pub earnings_unlock_ts: i64,
+    /// Deposit age brackets scaling investor weights
+    pub age_brackets: [AgeBracket; MAX_AGE_BRACKETS],
+    /// Share of the accumulated quote released each day, in basis points (0 = everything)
+    pub distribution_rate_bps: u16,
+    /// Whether the day's first page requires a fee claim during the day
+    pub require_fresh_claim: bool,
+    /// Quote vault balance below which an auto-claiming crank claims first
+    pub min_crankable_quote: u64,
+    /// Largest total USDC deposit per investor, in basis points of Y0 (0 = no cap)
+    pub max_investor_share_of_y0_bps: u16,
+    /// Delay after a config change before the crank uses the new fee share (in seconds)
+    pub config_change_cooloff_seconds: i64,
+    /// Largest quote amount a single position fee claim may bring in (0 = no maximum)
+    pub max_claim_per_tx: u64,
+    /// Wallet receiving the carry-over dust at day close (default = retained)
+    pub dust_recipient: Pubkey,
+    /// Whether investor payouts into delegated quote accounts are rejected
+    pub reject_delegated_accounts: bool,
     /// Timestamp of initialization
     pub timestamp: i64,
 }
 
+/// Event emitted when a creator wallet update is scheduled
+#[event]
+pub struct CreatorWalletUpdateScheduled {
+    /// Current creator wallet
+    pub current_creator_wallet: Pubkey,
+    /// Creator wallet that will take effect after the timelock
+    pub pending_creator_wallet: Pubkey,
+    /// Timestamp after which the pending wallet takes effect
+    pub effective_timestamp: i64,
+    /// Timestamp of scheduling
+    pub timestamp: i64,
+}
+
+/// Event emitted when a pending creator wallet is adopted
+#[event]
+pub struct CreatorWalletUpdated {
+    /// Previous creator wallet
+    pub previous_creator_wallet: Pubkey,
+    /// New creator wallet
+    pub new_creator_wallet: Pubkey,
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Event emitted when a day's payout root is submitted
+#[event]
+pub struct PayoutRootSubmitted {
+    /// Distribution day
+    pub day: u32,
+    /// Merkle root of the day's payouts
+    pub payout_root: [u8; 32],
+    /// Sum of all payout leaves
+    pub total_distributed: u64,
+    /// Number of payout leaves
+    pub leaf_count: u32,
+    /// Signer who submitted the root
+    pub submitted_by: Pubkey,
+    /// Timestamp of submission
+    pub timestamp: i64,
+}
+
 /// Event emitted when a deposit is made
 #[event]
 pub struct DepositMade {
@@ -177,6 +408,49 @@ pub struct DepositMade {
     pub timestamp: i64,
 }
 
+/// Event emitted when the number of depositors with a non-zero balance changes
+#[event]
+pub struct DepositorCountChanged {
+    /// Investor whose deposit or withdrawal triggered the change
+    pub investor: Pubkey,
+    /// New depositor count
+    pub depositor_count: u32,
+    /// True on a first (or returning) deposit, false on a full withdrawal
+    pub increased: bool,
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Event emitted when the admin sets an investor's fee share multiplier
+#[event]
+pub struct InvestorMultiplierSet {
+    /// Investor's wallet address
+    pub investor: Pubkey,
+    /// Previous multiplier in basis points
+    pub previous_multiplier_bps: u16,
+    /// New multiplier in basis points
+    pub fee_share_multiplier_bps: u16,
+    /// Sum of multiplier-scaled USDC balances after the update
+    pub total_weighted_usdc: u64,
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Event emitted when a depositor record is closed
+#[event]
+pub struct DepositorRecordClosed {
+    /// Investor's wallet address
+    pub investor: Pubkey,
+    /// Lifetime SOL deposited before closing
+    pub total_sol_deposited: u64,
+    /// Lifetime USDC deposited before closing
+    pub total_usdc_deposited: u64,
+    /// Number of deposits made before closing
+    pub deposit_count: u32,
+    /// Timestamp of closing
+    pub timestamp: i64,
+}
+
 /// Event emitted when a withdrawal is made
 #[event]
 pub struct WithdrawalMade {
@@ -199,3 +473,503 @@ pub struct WithdrawalMade {
     /// Timestamp of withdrawal
     pub timestamp: i64,
 }
+
+/// Event emitted when the admin updates the distribution config
+#[event]
+pub struct DistributionConfigUpdated {
+    /// Y0 allocation after the update
+    pub y0_allocation: u64,
+    /// Investor fee share in basis points after the update
+    pub investor_fee_share_bps: u16,
+    /// Minimum payout threshold after the update
+    pub min_payout_lamports: u64,
+    /// Daily distribution cap after the update (0 = no cap)
+    pub daily_cap_lamports: u64,
+    /// Whether the crank closes the day on its final page
+    pub auto_close_on_final_page: bool,
+    /// External vesting program after the update
+    pub vesting_program: Pubkey,
+    /// Share of the creator remainder paid to fully-vested investors after the update
+    pub vested_investor_share_bps: u16,
+    /// Whether per-page and per-investor payout events are emitted
+    pub emit_events: bool,
+    /// Merkle root of investors allowed to receive payouts after the update
+    pub eligibility_root: [u8; 32],
+    /// Daily distribution cap in quote native units after the update (0 = legacy `daily_cap_lamports`)
+    pub daily_cap_quote_units: u64,
+    /// Seconds after a day begins before anyone can force-close it, after the update
+    pub day_max_duration_seconds: i64,
+    /// Minimum time between an investor's deposits after the update (0 = no limit)
+    pub min_deposit_interval_seconds: i64,
+    /// Carry-over policy after the update
+    pub carry_over_policy: u8,
+    /// Lower bound for f_locked after the update
+    pub f_locked_floor_bps: u16,
+    /// Upper bound for f_locked after the update (0 = none)
+    pub f_locked_ceiling_bps: u16,
+    /// Total-locked verification after the update
+    pub verify_total_locked: bool,
+    /// Total-locked drift tolerance after the update
+    pub total_locked_tolerance_units: u64,
+    /// Creator payout mint after the update
+    pub creator_payout_mint: Pubkey,
+    /// Deposit window start after the update
+    pub deposit_window_start_ts: i64,
+    /// Deposit window end after the update
+    pub deposit_window_end_ts: i64,
+    /// Carry-over cap after the update
+    pub max_carry_over: u64,
+    /// Carry-over cap policy after the update
+    pub carry_over_cap_policy: u8,
+    /// Zero-balance record policy after the update
+    pub zero_balance_policy: u8,
+    /// Distribution rounds per day after the update
+    pub rounds_per_day: u8,
+    /// Minimum locked total to distribute after the update
+    pub min_total_locked_to_distribute: u64,
+    /// Payouts weighted by remaining lock time after the update
+    pub weight_by_remaining_lock: bool,
+    /// Creator inactivity window in seconds after the update
+    pub creator_inactivity_seconds: i64,
+    /// Creator minimum share in basis points after the update
+    pub creator_min_share_bps: u16,
+    /// Dust threshold scaling factor after the update
+    pub dust_threshold_k: u32,
+    /// Whether non-final crank pages must hold exactly `DISTRIBUTION_BATCH_SIZE` investors after the update
+    pub fixed_page_size: bool,
+    /// Whether the program has ended (deposits disabled, withdrawals unlocked)
+    pub program_ended: bool,
+    /// Program notified when a day is closed after the update (default = no callback)
+    pub callback_program: Pubkey,
+    /// Largest share of the vault's USDC a single investor may deposit up to after the update
+    pub max_investor_share_bps: u16,
+    /// SOL paid to the keeper per crank page after the update
+    pub keeper_reward_lamports: u64,
+    /// Timestamp until which compounded earnings cannot be withdrawn after the update
+    pub earnings_unlock_ts: i64,
+    /// Deposit age brackets scaling investor weights after the update
+    pub age_brackets: [AgeBracket; MAX_AGE_BRACKETS],
+    /// Share of the accumulated quote released each day, in basis points (0 = everything)
+    pub distribution_rate_bps: u16,
+    /// Whether the day's first page requires a fee claim during the day
+    pub require_fresh_claim: bool,
+    /// Quote vault balance below which an auto-claiming crank claims first
+    pub min_crankable_quote: u64,
+    /// Whether new days are paused (the day in progress can still finish)
+    pub new_days_paused: bool,
+    /// Largest total USDC deposit per investor, in basis points of Y0 (0 = no cap)
+    pub max_investor_share_of_y0_bps: u16,
+    /// Delay after a config change before the crank uses the new fee share (in seconds)
+    pub config_change_cooloff_seconds: i64,
+    /// Timestamp from which the crank uses the updated fee share
+    pub config_effective_ts: i64,
+    /// Largest quote amount a single position fee claim may bring in (0 = no maximum)
+    pub max_claim_per_tx: u64,
+    /// Wallet receiving the carry-over dust at day close (default = retained)
+    pub dust_recipient: Pubkey,
+    /// Whether investor payouts into delegated quote accounts are rejected
+    pub reject_delegated_accounts: bool,
+    /// Timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Event emitted when an investor proves inclusion in the eligibility root
+#[event]
+pub struct DepositorEligibilityVerified {
+    /// Investor whose record was marked eligible
+    pub investor: Pubkey,
+    /// Eligibility root the proof was checked against
+    pub eligibility_root: [u8; 32],
+    /// Timestamp of verification
+    pub timestamp: i64,
+}
+
+/// Event emitted when the admin changes the quote mint
+#[event]
+pub struct QuoteMintUpdated {
+    /// Quote mint before the change
+    pub previous_quote_mint: Pubkey,
+    /// Quote mint after the change
+    pub new_quote_mint: Pubkey,
+    /// Carry-over and unrouted creator remainder swept out of the old quote vault
+    pub swept_carry_over: u64,
+    /// Token account that received the swept carry-over
+    pub sweep_destination: Pubkey,
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Event emitted when the accumulated carry-over is flushed to investors
+#[event]
+pub struct CarryOverFlushed {
+    /// Day of the last distribution
+    pub day: u32,
+    /// Carry-over released by the flush
+    pub carry_over: u64,
+    /// Amount actually transferred to investors (rest is rounding dust)
+    pub total_flushed: u64,
+    /// Number of investors in the flush
+    pub investors_count: u32,
+    /// Timestamp of the flush
+    pub timestamp: i64,
+}
+
+/// Event emitted when a packed investor slot changes
+#[event]
+pub struct PackedInvestorUpdated {
+    /// Investor owning the slot
+    pub investor: Pubkey,
+    /// Slot index
+    pub index: u32,
+    /// Balance after the change
+    pub usdc_balance: u64,
+    /// Whether the slot is still held by the investor
+    pub active: bool,
+    /// Number of active slots
+    pub active_count: u32,
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Event emitted when a page of packed investors is paid
+#[event]
+pub struct PackedInvestorPagePaid {
+    /// Distribution day
+    pub day: u32,
+    /// Distribution round within the day
+    pub round_index: u8,
+    /// First slot of the page
+    pub start_index: u32,
+    /// Number of slots in the page
+    pub count: u32,
+    /// Active slots processed
+    pub investors_paid: u32,
+    /// Amount transferred to the page's investors
+    pub page_distributed: u64,
+    /// Amount added to carry-over
+    pub dust: u64,
+    /// Timestamp of the payout
+    pub timestamp: i64,
+}
+
+/// Event emitted when a distribution day is begun
+#[event]
+pub struct DistributionDayBegun {
+    /// Distribution day
+    pub day: u32,
+    /// Total locked USDC snapshot for the day
+    pub total_locked: u64,
+    /// Depositor count snapshot for the day
+    pub expected_investors: u32,
+    /// Depositor set commitment snapshot for the day
+    pub investor_commitment: [u8; 32],
+    /// Deposits and withdrawals are locked until this timestamp while the day is in progress
+    pub deposits_locked_until: i64,
+    /// Quote vault the day is distributed from
+    pub quote_vault: Pubkey,
+    /// Timestamp the day was begun
+    pub timestamp: i64,
+}
+
+/// Event emitted when an expired day is force-closed instead of closed by its keeper
+#[event]
+pub struct DayForceClosed {
+    /// Distribution day that was force-closed
+    pub day: u32,
+    /// Timestamp the day was begun
+    pub day_started_at: i64,
+    /// Timestamp from which the day could be force-closed
+    pub deadline: i64,
+    /// Crank pages processed before the close
+    pub pages_processed: u32,
+    /// Investors announced by the day's pages
+    pub investors_processed: u32,
+    /// Investor payouts executed before the close
+    pub investors_paid: u32,
+    /// Timestamp of the force close
+    pub timestamp: i64,
+}
+
+/// Event emitted when the program singletons are created by bootstrap_program
+#[event]
+pub struct ProgramBootstrapped {
+    /// Admin who bootstrapped the program
+    pub admin: Pubkey,
+    /// Distribution config PDA
+    pub distribution_config: Pubkey,
+    /// Vault stats PDA
+    pub vault_stats: Pubkey,
+    /// Crank state PDA
+    pub crank_state: Pubkey,
+    /// Quote mint of the config
+    pub quote_mint: Pubkey,
+    /// Quote fee vault PDA
+    pub program_quote_vault: Pubkey,
+    /// Timestamp of the bootstrap
+    pub timestamp: i64,
+}
+
+/// Event emitted when the creator remainder is swapped into the creator payout mint
+#[event]
+pub struct CreatorRemainderSwapped {
+    /// Distribution day the remainder belongs to
+    pub day: u32,
+    /// DAMM v2 pool used for the swap
+    pub pool: Pubkey,
+    /// Mint the creator was paid in
+    pub payout_mint: Pubkey,
+    /// Quote swapped (in quote native units)
+    pub amount_in: u64,
+    /// Payout mint received by the creator
+    pub amount_out: u64,
+    /// Slippage guard passed by the caller
+    pub min_out: u64,
+    /// Timestamp
+    pub timestamp: i64,
+}
+
+/// Investor and amount paid within a page
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PagePayout {
+    /// Investor paid
+    pub investor: Pubkey,
+    /// Amount transferred (in quote native units)
+    pub amount: u64,
+}
+
+/// Event emitted with every non-zero transfer of a packed investors page
+#[event]
+pub struct PackedPagePayouts {
+    /// Distribution day
+    pub day: u32,
+    /// Distribution round within the day
+    pub round_index: u8,
+    /// First slot of the page
+    pub start_index: u32,
+    /// Investors paid in slot order (at most MAX_INVESTORS_PER_PAGE)
+    pub payouts: Vec<PagePayout>,
+    /// Timestamp of the payout
+    pub timestamp: i64,
+}
+
+/// Event emitted when an investor registers the quote account their payouts are sent to
+#[event]
+pub struct QuoteAccountRegistered {
+    /// Investor
+    pub investor: Pubkey,
+    /// Previously registered quote account (default if none)
+    pub previous_quote_account: Pubkey,
+    /// Newly registered quote account
+    pub quote_account: Pubkey,
+    /// Timestamp of the registration
+    pub timestamp: i64,
+}
+
+/// Event emitted when carry-over above `max_carry_over` is moved out of the vault at day close
+#[event]
+pub struct CarryOverCapped {
+    /// Day number
+    pub day: u32,
+    /// Configured carry-over cap
+    pub max_carry_over: u64,
+    /// Carry-over above the cap that left the vault
+    pub excess: u64,
+    /// Cap policy applied (0 = creator, 1 = largest-weight investor)
+    pub cap_policy: u8,
+    /// Token account that received the excess
+    pub destination: Pubkey,
+    /// Timestamp of the day close
+    pub timestamp: i64,
+}
+
+/// Event emitted when the day's carry-over dust is sent to the dust recipient at day close
+#[event]
+pub struct DustRouted {
+    /// Day number
+    pub day: u32,
+    /// Configured dust recipient wallet
+    pub dust_recipient: Pubkey,
+    /// Token account that received the dust
+    pub dust_recipient_quote_account: Pubkey,
+    /// Carry-over dust that left the vault
+    pub amount: u64,
+    /// Timestamp of the day close
+    pub timestamp: i64,
+}
+
+/// Event emitted when an investor turns auto-compounding of their payouts on or off
+#[event]
+pub struct AutoCompoundSet {
+    /// Investor
+    pub investor: Pubkey,
+    /// Payouts are added to the investor's USDC balance
+    pub auto_compound: bool,
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Event emitted when a day's locked total is below `min_total_locked_to_distribute` and its
+/// fees skip the investor distribution
+#[event]
+pub struct BelowMinLocked {
+    /// Distribution day
+    pub day: u32,
+    /// Locked total snapshotted when the day was begun
+    pub total_locked: u64,
+    /// Configured minimum locked total
+    pub min_total_locked_to_distribute: u64,
+    /// Quote fees in the vault (carry-over included)
+    pub quote_fees_available: u64,
+    /// Timestamp of the skipped page
+    pub timestamp: i64,
+}
+
+/// Event emitted when the day's first page finds no investors in the day's snapshot and
+/// routes the fees without paginating
+#[event]
+pub struct NoInvestorsToDistribute {
+    /// Distribution day
+    pub day: u32,
+    /// Quote fees in the vault (carry-over included)
+    pub quote_fees_available: u64,
+    /// Carry-over retained in the vault
+    pub carry_over: u64,
+    /// Timestamp of the skipped page
+    pub timestamp: i64,
+}
+
+/// Event emitted when the admin corrects drift in the vault stats totals
+#[event]
+pub struct VaultStatsCorrected {
+    /// Admin who ran the correction
+    pub admin: Pubkey,
+    /// Totals were summed from depositor records rather than read from the vaults
+    pub from_records: bool,
+    /// Current total SOL before the correction
+    pub old_total_sol: u64,
+    /// Current total SOL after the correction
+    pub new_total_sol: u64,
+    /// Current total USDC before the correction
+    pub old_total_usdc: u64,
+    /// Current total USDC after the correction
+    pub new_total_usdc: u64,
+    /// Timestamp of the correction
+    pub timestamp: i64,
+}
+
+/// Event emitted when a day's creator remainder goes to investors because the creator has been
+/// inactive for `creator_inactivity_seconds`
+#[event]
+pub struct CreatorInactivityTriggered {
+    /// Distribution day
+    pub day: u32,
+    /// Creator wallet that was skipped
+    pub creator_wallet: Pubkey,
+    /// Last remainder routed to or claimed by the creator
+    pub last_creator_payout_ts: i64,
+    /// Configured inactivity window
+    pub creator_inactivity_seconds: i64,
+    /// Remainder kept as carry-over for investors
+    pub remainder_to_investors: u64,
+    /// Timestamp of the day close
+    pub timestamp: i64,
+}
+
+/// Event emitted when an investor approves or revokes an admin-assisted batch withdrawal
+#[event]
+pub struct BatchWithdrawApprovalSet {
+    /// Investor
+    pub investor: Pubkey,
+    /// The admin may withdraw the investor's USDC to their registered quote account
+    pub approved: bool,
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Event emitted once per batch withdrawal, after the per-investor `WithdrawalMade` events
+#[event]
+pub struct BatchWithdrawal {
+    /// Admin who ran the batch
+    pub admin: Pubkey,
+    /// Depositor records in the batch
+    pub investors_count: u32,
+    /// USDC withdrawn across the batch
+    pub total_usdc_withdrawn: u64,
+    /// Current total USDC in the vault stats after the batch
+    pub current_total_usdc: u64,
+    /// Timestamp of the batch
+    pub timestamp: i64,
+}
+
+/// Event emitted when the admin recovers tokens sent to a non-canonical fee_collector account
+#[event]
+pub struct TokensRescued {
+    /// Admin who ran the rescue
+    pub admin: Pubkey,
+    /// Stray token account owned by the fee_collector PDA
+    pub source: Pubkey,
+    /// Admin token account that received the balance
+    pub destination: Pubkey,
+    /// Mint of the rescued tokens
+    pub mint: Pubkey,
+    /// Amount rescued
+    pub amount: u64,
+    /// Timestamp of the rescue
+    pub timestamp: i64,
+}
+
+/// Event emitted when the keeper incentive SOL balance is topped up
+#[event]
+pub struct KeeperIncentiveFunded {
+    /// Account that funded the rewards
+    pub funder: Pubkey,
+    /// Lamports added
+    pub amount: u64,
+    /// Keeper incentive balance after funding
+    pub balance: u64,
+    /// Timestamp of the funding
+    pub timestamp: i64,
+}
+
+/// Event emitted when a crank page pays its payer the keeper reward
+#[event]
+pub struct KeeperRewardPaid {
+    /// Distribution day of the page
+    pub day: u32,
+    /// Page index within the round
+    pub page_index: u32,
+    /// Payer of the crank page
+    pub keeper: Pubkey,
+    /// Lamports paid
+    pub amount: u64,
+    /// Keeper incentive balance left after the reward
+    pub remaining_balance: u64,
+    /// Timestamp of the payment
+    pub timestamp: i64,
+}
+
+/// Event emitted when an investor opts out of or back into fee distributions
+#[event]
+pub struct OptOutSet {
+    /// Investor
+    pub investor: Pubkey,
+    /// The investor forgoes fee distributions
+    pub opted_out: bool,
+    /// Weighted USDC total payouts are renormalized over after the change
+    pub total_weighted_usdc: u64,
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Event emitted when a position is added to or removed from the position registry
+#[event]
+pub struct PositionRegistryChanged {
+    /// Position account
+    pub position: Pubkey,
+    /// The position was added (false = removed)
+    pub registered: bool,
+    /// Number of registered positions after the change
+    pub position_count: u8,
+    /// Timestamp of the change
+    pub timestamp: i64,
+}
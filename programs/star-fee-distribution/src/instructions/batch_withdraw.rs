@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::constants::{
+    FEE_COLLECTOR_SEED, DEPOSIT_VAULT_SEED, INVESTOR_RECORD_SEED, CRANK_STATE_SEED,
+    DISTRIBUTION_CONFIG_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::math::add_u64;
+use crate::states::{DepositorRecord, DistributionConfig, VaultStats};
+use crate::utils::depositor_member_hash;
+use super::deposit::require_deposits_unlocked;
+
+/// Admin-assisted full USDC withdrawal for investors who approved it, for program wind-down.
+/// `remaining_accounts` holds `(depositor_record, registered_quote_account)` pairs
+#[derive(Accounts)]
+pub struct BatchWithdraw<'info> {
+    /// Admin who initialized the config
+    #[account(
+        constraint = admin.key() == distribution_config.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    /// Distribution configuration PDA
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Box<Account<'info, DistributionConfig>>,
+
+    /// CHECK: Program authority (our program)
+    #[account(
+        seeds = [FEE_COLLECTOR_SEED],
+        bump
+    )]
+    pub fee_collector: UncheckedAccount<'info>,
+
+    /// Program's USDC vault for deposits
+    #[account(
+        mut,
+        seeds = [DEPOSIT_VAULT_SEED, usdc_mint.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = fee_collector
+    )]
+    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Deposit mint, only used to derive the USDC vault (must be the configured deposit mint)
+    #[account(
+        constraint = usdc_mint.key() == distribution_config.deposit_mint @ ErrorCode::WrongDepositMint
+    )]
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    /// Global vault statistics
+    #[account(
+        mut,
+        seeds = [DEPOSIT_VAULT_SEED, b"stats"],
+        bump = vault_stats.bump
+    )]
+    pub vault_stats: Account<'info, VaultStats>,
+
+    /// CHECK: Crank state PDA, read for the deposit lock once a day has been begun
+    #[account(
+        seeds = [CRANK_STATE_SEED],
+        bump
+    )]
+    pub crank_state: UncheckedAccount<'info>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> BatchWithdraw<'info> {
+    pub fn handle(ctx: Context<'_, '_, 'info, 'info, BatchWithdraw<'info>>) -> Result<()> {
+        msg!("Processing batch withdrawal");
+
+        // Once the program has ended the wind-down is not held up by a day in progress
+        if !ctx.accounts.distribution_config.program_ended {
+            require_deposits_unlocked(&ctx.accounts.crank_state)?;
+        }
+
+        let remaining_accounts = ctx.remaining_accounts;
+        require!(
+            !remaining_accounts.is_empty() && remaining_accounts.len().is_multiple_of(2),
+            ErrorCode::InvalidDepositorRecord
+        );
+
+        let usdc_mint = ctx.accounts.usdc_mint.key();
+        let mut investors: Vec<Pubkey> = Vec::with_capacity(remaining_accounts.len() / 2);
+        let mut total_usdc: u64 = 0;
+        let mut previous_weighted_usdc: u64 = 0;
+        let mut current_weighted_usdc: u64 = 0;
+        let mut deactivated: Vec<Pubkey> = Vec::new();
+
+        for pair in remaining_accounts.chunks(2) {
+            let mut record = Account::<DepositorRecord>::try_from(&pair[0])?;
+            let (expected_record, _) = Pubkey::find_program_address(
+                &[INVESTOR_RECORD_SEED, record.investor.as_ref()],
+                ctx.program_id,
+            );
+            require!(pair[0].key() == expected_record, ErrorCode::InvalidDepositorRecord);
+            require!(!investors.contains(&record.investor), ErrorCode::InvalidDepositorRecord);
+            require!(record.batch_withdraw_approved, ErrorCode::BatchWithdrawNotApproved);
+
+            // Funds only go to the account the investor registered
+            require!(
+                pair[1].key() == record.registered_quote_account,
+                ErrorCode::QuoteAccountNotRegistered
+            );
+            let destination = Account::<TokenAccount>::try_from(&pair[1])?;
+            require!(destination.mint == usdc_mint, ErrorCode::InvalidInvestorQuoteAccount);
+
+            investors.push(record.investor);
+            let amount = record.current_usdc_balance;
+            if amount == 0 {
+                msg!("Skipping investor {} with no USDC balance", record.investor);
+                continue;
+            }
+
+            anchor_spl::token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    anchor_spl::token::Transfer {
+                        from: ctx.accounts.usdc_vault.to_account_info(),
+                        to: pair[1].clone(),
+                        authority: ctx.accounts.fee_collector.to_account_info(),
+                    },
+                    &[&[
+                        FEE_COLLECTOR_SEED,
+                        &[ctx.bumps.fee_collector]
+                    ]]
+                ),
+                amount,
+            )?;
+
+            let was_active = record.is_active();
+            previous_weighted_usdc = add_u64(previous_weighted_usdc, record.weighted_usdc_balance()?)?;
+            record.add_withdrawal(0, amount)?;
+            record.batch_withdraw_approved = false;
+            current_weighted_usdc = add_u64(current_weighted_usdc, record.weighted_usdc_balance()?)?;
+            if was_active && !record.is_active() {
+                deactivated.push(record.investor);
+            }
+            total_usdc = add_u64(total_usdc, amount)?;
+
+            msg!("Withdrew {} USDC units for investor {}", amount, record.investor);
+
+            emit!(crate::events::WithdrawalMade {
+                investor: record.investor,
+                sol_amount: 0,
+                usdc_amount: amount,
+                total_sol_withdrawn: record.total_sol_withdrawn,
+                total_usdc_withdrawn: record.total_usdc_withdrawn,
+                current_sol_balance: record.current_sol_balance,
+                current_usdc_balance: record.current_usdc_balance,
+                withdrawal_count: record.withdrawal_count,
+                timestamp: record.last_activity_timestamp,
+            });
+
+            record.exit(ctx.program_id)?;
+        }
+
+        // Vault totals are updated once for the whole batch
+        let vault_stats = &mut ctx.accounts.vault_stats;
+        vault_stats.add_withdrawals(0, total_usdc)?;
+        vault_stats.update_weighted_usdc(previous_weighted_usdc, current_weighted_usdc)?;
+
+        for investor in deactivated.iter() {
+            vault_stats.remove_depositor(&depositor_member_hash(investor))?;
+
+            emit!(crate::events::DepositorCountChanged {
+                investor: *investor,
+                depositor_count: vault_stats.depositor_count,
+                increased: false,
+                timestamp: vault_stats.last_update_timestamp,
+            });
+        }
+
+        msg!("Batch withdrew {} USDC units for {} investors", total_usdc, investors.len());
+        msg!("Current total USDC: {} units", vault_stats.current_total_usdc);
+
+        emit!(crate::events::BatchWithdrawal {
+            admin: ctx.accounts.admin.key(),
+            investors_count: u32::try_from(investors.len()).map_err(|_| ErrorCode::MathOverflow)?,
+            total_usdc_withdrawn: total_usdc,
+            current_total_usdc: vault_stats.current_total_usdc,
+            timestamp: vault_stats.last_update_timestamp,
+        });
+
+        Ok(())
+    }
+}
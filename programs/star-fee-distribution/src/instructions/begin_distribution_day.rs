@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::constants::{FEE_COLLECTOR_SEED, FEE_VAULT_SEED, DEPOSIT_VAULT_SEED, CRANK_STATE_SEED, DISTRIBUTION_CONFIG_SEED};
+use crate::errors::ErrorCode;
+use crate::states::{CrankState, DistributionConfig, VaultStats};
+
+/// Begins a distribution day (permissionless): snapshots the locked total and depositor
+/// count and locks deposits and withdrawals, so every crank page of the day sees the same inputs
+#[derive(Accounts)]
+pub struct BeginDistributionDay<'info> {
+    /// Payer for account initialization
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Program authority (our program)
+    #[account(
+        seeds = [FEE_COLLECTOR_SEED],
+        bump
+    )]
+    pub fee_collector: UncheckedAccount<'info>,
+
+    /// Program's quote token vault the day is distributed from
+    #[account(
+        seeds = [FEE_VAULT_SEED, distribution_config.quote_mint.as_ref()],
+        bump,
+        token::mint = distribution_config.quote_mint,
+        token::authority = fee_collector
+    )]
+    pub program_quote_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Global vault statistics to snapshot
+    #[account(
+        seeds = [DEPOSIT_VAULT_SEED, b"stats"],
+        bump = vault_stats.bump
+    )]
+    pub vault_stats: Account<'info, VaultStats>,
+
+    /// Distribution configuration
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    /// Crank state to track distribution timing
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = CrankState::DISCRIMINATOR.len() + CrankState::INIT_SPACE,
+        seeds = [CRANK_STATE_SEED],
+        bump
+    )]
+    pub crank_state: Account<'info, CrankState>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BeginDistributionDay<'info> {
+    pub fn handle(ctx: Context<BeginDistributionDay>) -> Result<()> {
+        let crank_state = &mut ctx.accounts.crank_state;
+        let vault_stats = &ctx.accounts.vault_stats;
+
+        // Initialize crank state if needed
+        if crank_state.last_distribution_timestamp == 0 {
+            crank_state.set_inner(CrankState::new(ctx.bumps.crank_state));
+        }
+
+        // A day left open past its deadline points the keeper at force_close_day
+        crank_state.require_not_stale(ctx.accounts.distribution_config.day_max_duration_seconds)?;
+        require!(
+            !crank_state.is_day_in_progress(),
+            ErrorCode::DistributionInProgress
+        );
+
+        // Winding down stops new days only; the crank and routing never check this
+        require!(
+            !ctx.accounts.distribution_config.new_days_paused,
+            ErrorCode::NewDaysPaused
+        );
+
+        // start_new_day enforces the 24h gap after the previous day
+        crank_state.start_new_day()?;
+
+        let now = Clock::get()?.unix_timestamp;
+        crank_state.quote_vault = ctx.accounts.program_quote_vault.key();
+        crank_state.day_start_quote_balance = ctx.accounts.program_quote_vault.amount;
+        crank_state.day_total_locked = vault_stats.current_total_usdc;
+        crank_state.day_total_weighted_usdc = vault_stats.current_total_weighted_usdc;
+        crank_state.expected_investors = vault_stats.depositor_count;
+        crank_state.day_investor_commitment = vault_stats.depositor_set_commitment;
+        // Balances stay fixed for as long as the day can take pages, until it closes or expires
+        crank_state.lock_deposits_until_deadline(ctx.accounts.distribution_config.day_max_duration_seconds);
+
+        msg!("Total locked snapshot: {} units", crank_state.day_total_locked);
+        msg!("Total weighted snapshot: {} units", crank_state.day_total_weighted_usdc);
+        msg!("Expected investors: {}", crank_state.expected_investors);
+        msg!("Deposits locked until: {}", crank_state.deposits_locked_until);
+
+        emit!(crate::events::DistributionDayBegun {
+            day: crank_state.current_day,
+            total_locked: crank_state.day_total_locked,
+            expected_investors: crank_state.expected_investors,
+            investor_commitment: crank_state.day_investor_commitment,
+            deposits_locked_until: crank_state.deposits_locked_until,
+            quote_vault: crank_state.quote_vault,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::constants::{FEE_COLLECTOR_SEED, FEE_VAULT_SEED, DEPOSIT_VAULT_SEED, CRANK_STATE_SEED, DISTRIBUTION_CONFIG_SEED};
+use crate::errors::ErrorCode;
+use crate::states::{CrankState, DistributionConfig, VaultStats};
+use super::initialize_distribution_config::{initialize_config, InitializeDistributionConfigParams};
+
+/// Creates the program-level singletons in one transaction: the distribution config,
+/// vault stats, crank state and the quote fee vault. The base fee vault belongs to the
+/// pool's base mint and is left to the pool setup
+#[derive(Accounts)]
+#[instruction(params: InitializeDistributionConfigParams)]
+pub struct BootstrapProgram<'info> {
+    /// Admin who bootstraps the program (stored as the config admin)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Distribution configuration PDA
+    #[account(
+        init,
+        payer = admin,
+        space = DistributionConfig::DISCRIMINATOR.len() + DistributionConfig::INIT_SPACE,
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump
+    )]
+    pub distribution_config: Box<Account<'info, DistributionConfig>>,
+
+    /// Global vault statistics
+    #[account(
+        init,
+        payer = admin,
+        space = VaultStats::DISCRIMINATOR.len() + VaultStats::INIT_SPACE,
+        seeds = [DEPOSIT_VAULT_SEED, b"stats"],
+        bump
+    )]
+    pub vault_stats: Account<'info, VaultStats>,
+
+    /// Crank state to track distribution timing
+    #[account(
+        init,
+        payer = admin,
+        space = CrankState::DISCRIMINATOR.len() + CrankState::INIT_SPACE,
+        seeds = [CRANK_STATE_SEED],
+        bump
+    )]
+    pub crank_state: Box<Account<'info, CrankState>>,
+
+    /// CHECK: Program authority (our program)
+    #[account(
+        seeds = [FEE_COLLECTOR_SEED],
+        bump
+    )]
+    pub fee_collector: UncheckedAccount<'info>,
+
+    /// Quote token mint (must match `params.quote_mint`)
+    #[account(
+        constraint = quote_mint.key() == params.quote_mint @ ErrorCode::InvalidPosition
+    )]
+    pub quote_mint: Account<'info, Mint>,
+
+    /// Program's quote fee vault
+    #[account(
+        init,
+        payer = admin,
+        seeds = [FEE_VAULT_SEED, quote_mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = fee_collector
+    )]
+    pub program_quote_vault: Account<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BootstrapProgram<'info> {
+    pub fn handle(ctx: Context<BootstrapProgram>, params: InitializeDistributionConfigParams) -> Result<()> {
+        msg!("Bootstrapping program singletons");
+
+        initialize_config(
+            &mut ctx.accounts.distribution_config,
+            ctx.accounts.admin.key(),
+            ctx.bumps.distribution_config,
+            &params,
+        )?;
+
+        ctx.accounts.vault_stats.set_inner(VaultStats::new(ctx.bumps.vault_stats));
+        ctx.accounts.crank_state.set_inner(CrankState::new(ctx.bumps.crank_state));
+
+        msg!("Vault stats: {}", ctx.accounts.vault_stats.key());
+        msg!("Crank state: {}", ctx.accounts.crank_state.key());
+        msg!("Quote fee vault: {}", ctx.accounts.program_quote_vault.key());
+
+        emit!(crate::events::ProgramBootstrapped {
+            admin: ctx.accounts.admin.key(),
+            distribution_config: ctx.accounts.distribution_config.key(),
+            vault_stats: ctx.accounts.vault_stats.key(),
+            crank_state: ctx.accounts.crank_state.key(),
+            quote_mint: ctx.accounts.quote_mint.key(),
+            program_quote_vault: ctx.accounts.program_quote_vault.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
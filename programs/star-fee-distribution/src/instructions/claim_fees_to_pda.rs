@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
 use crate::errors::ErrorCode;
-use crate::constants::{FEE_COLLECTOR_SEED, FEE_VAULT_SEED};
+use crate::constants::{FEE_COLLECTOR_SEED, FEE_VAULT_SEED, POSITION_STATE_SEED, CRANK_STATE_SEED, DISTRIBUTION_CONFIG_SEED, POSITION_REGISTRY_SEED};
+use crate::states::{CrankState, DistributionConfig, PositionRegistry, PositionState};
+use crate::utils::{is_deposit_vault, load_if_initialized, read_zero_copy, require_supported_amm_layout};
 
 #[derive(Accounts)]
 pub struct ClaimFeesToPDA<'info> {
@@ -21,7 +23,7 @@ pub struct ClaimFeesToPDA<'info> {
     #[account(mut)]
     pub pool: UncheckedAccount<'info>,
     
-    /// CHECK: Position account (owned by our PDA)
+    /// CHECK: Position account (owned by our PDA, listed in the position registry)
     #[account(mut)]
     pub position: UncheckedAccount<'info>,
     
@@ -58,16 +60,49 @@ pub struct ClaimFeesToPDA<'info> {
     )]
     pub program_token_a_vault: Box<Account<'info, TokenAccount>>,
     
-    /// Program's quote token vault for fee collection
+    /// Destination for the quote fees: the program's quote fee vault or any other
+    /// fee_collector-owned quote token account, such as a separate fee pool (never a deposit vault)
     #[account(
         mut,
-        seeds = [FEE_VAULT_SEED, quote_mint.key().as_ref()],
-        bump,
         token::mint = quote_mint,
-        token::authority = fee_collector
+        token::authority = fee_collector,
+        constraint = !is_deposit_vault(&program_token_b_vault.key(), &quote_mint.key(), &crate::ID)
+            @ ErrorCode::InvalidFeeDestination
     )]
     pub program_token_b_vault: Box<Account<'info, TokenAccount>>,
     
+    /// Honorary position state, stamped with the day in progress when the claim refills the
+    /// day's quote vault (checked by the crank under `require_fresh_claim`)
+    #[account(
+        mut,
+        seeds = [POSITION_STATE_SEED, pool.key().as_ref()],
+        bump = position_state.bump,
+        constraint = position_state.position == position.key() @ ErrorCode::InvalidPosition
+    )]
+    pub position_state: Option<Box<Account<'info, PositionState>>>,
+    
+    /// Crank state, read for the day in progress and its quote vault
+    #[account(
+        seeds = [CRANK_STATE_SEED],
+        bump = crank_state.bump
+    )]
+    pub crank_state: Option<Box<Account<'info, CrankState>>>,
+    
+    /// CHECK: Distribution config PDA, read for the maximum claim once initialized
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump
+    )]
+    pub distribution_config: UncheckedAccount<'info>,
+    
+    /// Position registry; only registered positions may claim into the shared fee vaults
+    #[account(
+        seeds = [POSITION_REGISTRY_SEED],
+        bump = position_registry.bump,
+        constraint = position_registry.contains(&position.key()) @ ErrorCode::PositionNotRegistered
+    )]
+    pub position_registry: Box<Account<'info, PositionRegistry>>,
+    
     /// CHECK: Token program
     pub token_program: Program<'info, Token>,
     
@@ -78,113 +113,221 @@ pub struct ClaimFeesToPDA<'info> {
 impl<'info> ClaimFeesToPDA<'info> {
     pub fn handle(ctx: Context<ClaimFeesToPDA>) -> Result<()> {
         msg!("Claiming fees to program PDA for pool: {}", ctx.accounts.pool.key());
+        msg!("Quote fee destination: {}", ctx.accounts.program_token_b_vault.key());
         
-        // Validate that the position exists and is valid
-        validate_position_accounts_pda(&ctx)?;
-        
-        // Record balances before claim
-        let base_balance_before = ctx.accounts.program_token_a_vault.amount;
-        let quote_balance_before = ctx.accounts.program_token_b_vault.amount;
+        let claim = PositionFeeClaim {
+            fee_collector: ctx.accounts.fee_collector.to_account_info(),
+            fee_collector_bump: ctx.bumps.fee_collector,
+            amm_program: ctx.accounts.amm_program.to_account_info(),
+            pool: ctx.accounts.pool.to_account_info(),
+            position: ctx.accounts.position.to_account_info(),
+            position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+            pool_authority: ctx.accounts.pool_authority.to_account_info(),
+            base_mint: ctx.accounts.base_mint.to_account_info(),
+            quote_mint: ctx.accounts.quote_mint.to_account_info(),
+            token_a_vault: ctx.accounts.token_a_vault.to_account_info(),
+            token_b_vault: ctx.accounts.token_b_vault.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            event_authority: ctx.accounts.event_authority.to_account_info(),
+        };
+        let max_claim_per_tx = load_if_initialized::<DistributionConfig>(&ctx.accounts.distribution_config)?
+            .map_or(0, |config| config.max_claim_per_tx);
+        let (base_claimed, quote_claimed) = claim_quote_only_fees(
+            &claim,
+            &mut ctx.accounts.program_token_a_vault,
+            &mut ctx.accounts.program_token_b_vault,
+            max_claim_per_tx,
+        )?;
         
-        msg!("Base vault balance before: {} units", base_balance_before);
-        msg!("Quote vault balance before: {} units", quote_balance_before);
+        msg!("Fees claimed successfully to program PDA!");
         
-        // Use DAMM v2 CPI to claim position fees to our program's token vaults
-        match damm_v2::cpi::claim_position_fee(
-            CpiContext::new_with_signer(
-                ctx.accounts.amm_program.to_account_info(),
-                damm_v2::cpi::accounts::ClaimPositionFee {
-                    pool_authority: ctx.accounts.pool_authority.to_account_info(),
-                    pool: ctx.accounts.pool.to_account_info(),
-                    position: ctx.accounts.position.to_account_info(),
-                    token_a_account: ctx.accounts.program_token_a_vault.to_account_info(),
-                    token_b_account: ctx.accounts.program_token_b_vault.to_account_info(),
-                    token_a_vault: ctx.accounts.token_a_vault.to_account_info(),
-                    token_b_vault: ctx.accounts.token_b_vault.to_account_info(),
-                    token_a_mint: ctx.accounts.base_mint.to_account_info(),
-                    token_b_mint: ctx.accounts.quote_mint.to_account_info(),
-                    position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
-                    owner: ctx.accounts.fee_collector.to_account_info(),
-                    token_a_program: ctx.accounts.token_program.to_account_info(),
-                    token_b_program: ctx.accounts.token_program.to_account_info(),
-                    event_authority: ctx.accounts.event_authority.to_account_info(),
-                    program: ctx.accounts.amm_program.to_account_info(),
-                },
-                &[&[
-                    FEE_COLLECTOR_SEED,
-                    &[ctx.bumps.fee_collector]
-                ]]
-            ),
-        ) {
-            Ok(_) => {
-                // Reload accounts to get updated balances
-                ctx.accounts.program_token_a_vault.reload()?;
-                ctx.accounts.program_token_b_vault.reload()?;
-                
-                let base_balance_after = ctx.accounts.program_token_a_vault.amount;
-                let quote_balance_after = ctx.accounts.program_token_b_vault.amount;
-                
-                let base_claimed = base_balance_after.saturating_sub(base_balance_before);
-                let quote_claimed = quote_balance_after.saturating_sub(quote_balance_before);
-                
-                msg!("Base fees claimed: {} units", base_claimed);
-                msg!("Quote fees claimed: {} units", quote_claimed);
-                
-                // CRITICAL: Enforce quote-only fees
-                // If ANY base fees were claimed, fail the transaction
-                require!(
-                    base_claimed == 0,
-                    ErrorCode::BaseFeesDetected
-                );
-                
-                msg!("✅ Quote-only validation passed - no base fees detected");
-                msg!("Fees claimed successfully to program PDA!");
-                
-                // Emit event
-                emit!(crate::events::QuoteFeesClaimed {
-                    pool: ctx.accounts.pool.key(),
-                    position: ctx.accounts.position.key(),
-                    base_fees_claimed: base_claimed,
-                    quote_fees_claimed: quote_claimed,
-                    program_base_vault: ctx.accounts.program_token_a_vault.key(),
-                    program_quote_vault: ctx.accounts.program_token_b_vault.key(),
-                    timestamp: Clock::get()?.unix_timestamp,
-                });
-                
-                Ok(())
-            }
-            Err(e) => {
-                msg!("Failed to claim fees to PDA: {:?}", e);
-                // Check if it's a "no fees to claim" error
-                if e.to_string().contains("no fees") || e.to_string().contains("insufficient") {
-                    return Err(ErrorCode::NoFeesToClaim.into());
-                }
-                Err(e)
+        // A claim into the vault the day is distributed from counts as the day's fresh claim
+        let destination = ctx.accounts.program_token_b_vault.key();
+        if let (Some(position_state), Some(crank_state)) =
+            (ctx.accounts.position_state.as_mut(), ctx.accounts.crank_state.as_ref())
+        {
+            if quote_claimed > 0
+                && crank_state.is_day_in_progress()
+                && destination == crank_state.quote_vault
+            {
+                position_state.last_claim_day = crank_state.current_day;
+                msg!("Fresh claim recorded for day {}", crank_state.current_day);
             }
         }
+        
+        // Emit event
+        emit!(crate::events::QuoteFeesClaimed {
+            pool: ctx.accounts.pool.key(),
+            position: ctx.accounts.position.key(),
+            base_fees_claimed: base_claimed,
+            quote_fees_claimed: quote_claimed,
+            program_base_vault: ctx.accounts.program_token_a_vault.key(),
+            program_quote_vault: ctx.accounts.program_token_b_vault.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
     }
 }
 
+/// Accounts of a DAMM v2 `claim_position_fee` CPI into the program's fee vaults, shared by
+/// `claim_fees_to_pda` and the crank's auto-claim
+pub(crate) struct PositionFeeClaim<'info> {
+    pub fee_collector: AccountInfo<'info>,
+    pub fee_collector_bump: u8,
+    pub amm_program: AccountInfo<'info>,
+    pub pool: AccountInfo<'info>,
+    pub position: AccountInfo<'info>,
+    pub position_nft_account: AccountInfo<'info>,
+    pub pool_authority: AccountInfo<'info>,
+    pub base_mint: AccountInfo<'info>,
+    pub quote_mint: AccountInfo<'info>,
+    pub token_a_vault: AccountInfo<'info>,
+    pub token_b_vault: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub event_authority: AccountInfo<'info>,
+}
+
+/// Claims the position's fees into the program vaults under the quote-only rules: rejects
+/// pending base fees before the CPI and any base fees it pays out after, and more quote than
+/// `max_claim_per_tx` (0 = no maximum). Returns the base and quote amounts claimed, with both
+/// vaults reloaded
+pub(crate) fn claim_quote_only_fees<'info>(
+    claim: &PositionFeeClaim<'info>,
+    program_token_a_vault: &mut Account<'info, TokenAccount>,
+    program_token_b_vault: &mut Account<'info, TokenAccount>,
+    max_claim_per_tx: u64,
+) -> Result<(u64, u64)> {
+    // Validate that the position exists and is valid
+    validate_position_accounts_pda(claim)?;
+    
+    // The claim CPI and the pre-check below assume the pool layout the bindings were built for
+    require_supported_amm_layout(&claim.pool)?;
+    
+    // QUOTE-ONLY PRE-CHECK: Reject before paying for the claim CPI
+    require!(
+        !has_pending_base_fees(claim)?,
+        ErrorCode::PendingBaseFeesDetected
+    );
+    
+    // Record balances before claim
+    let base_balance_before = program_token_a_vault.amount;
+    let quote_balance_before = program_token_b_vault.amount;
+    
+    msg!("Base vault balance before: {} units", base_balance_before);
+    msg!("Quote vault balance before: {} units", quote_balance_before);
+    
+    // Use DAMM v2 CPI to claim position fees to our program's token vaults
+    if let Err(e) = damm_v2::cpi::claim_position_fee(
+        CpiContext::new_with_signer(
+            claim.amm_program.clone(),
+            damm_v2::cpi::accounts::ClaimPositionFee {
+                pool_authority: claim.pool_authority.clone(),
+                pool: claim.pool.clone(),
+                position: claim.position.clone(),
+                token_a_account: program_token_a_vault.to_account_info(),
+                token_b_account: program_token_b_vault.to_account_info(),
+                token_a_vault: claim.token_a_vault.clone(),
+                token_b_vault: claim.token_b_vault.clone(),
+                token_a_mint: claim.base_mint.clone(),
+                token_b_mint: claim.quote_mint.clone(),
+                position_nft_account: claim.position_nft_account.clone(),
+                owner: claim.fee_collector.clone(),
+                token_a_program: claim.token_program.clone(),
+                token_b_program: claim.token_program.clone(),
+                event_authority: claim.event_authority.clone(),
+                program: claim.amm_program.clone(),
+            },
+            &[&[
+                FEE_COLLECTOR_SEED,
+                &[claim.fee_collector_bump]
+            ]]
+        ),
+    ) {
+        msg!("Failed to claim fees to PDA: {:?}", e);
+        // Check if it's a "no fees to claim" error
+        if e.to_string().contains("no fees") || e.to_string().contains("insufficient") {
+            return Err(ErrorCode::NoFeesToClaim.into());
+        }
+        return Err(e);
+    }
+    
+    // Reload accounts to get updated balances
+    program_token_a_vault.reload()?;
+    program_token_b_vault.reload()?;
+    
+    let base_claimed = program_token_a_vault.amount.saturating_sub(base_balance_before);
+    let quote_claimed = program_token_b_vault.amount.saturating_sub(quote_balance_before);
+    
+    msg!("Base fees claimed: {} units", base_claimed);
+    msg!("Quote fees claimed: {} units", quote_claimed);
+    
+    // CRITICAL: Enforce quote-only fees
+    // If ANY base fees were claimed, fail the transaction
+    require!(
+        base_claimed == 0,
+        ErrorCode::BaseFeesDetected
+    );
+    
+    msg!("✅ Quote-only validation passed - no base fees detected");
+    
+    // An oversized claim points at a compromised position or pool, so it is rejected for
+    // investigation rather than distributed
+    require!(
+        max_claim_per_tx == 0 || quote_claimed <= max_claim_per_tx,
+        ErrorCode::ClaimExceedsMax
+    );
+    
+    Ok((base_claimed, quote_claimed))
+}
+
 /// Validates that the position and related accounts are properly configured for PDA collection
-fn validate_position_accounts_pda(ctx: &Context<ClaimFeesToPDA>) -> Result<()> {
+fn validate_position_accounts_pda(claim: &PositionFeeClaim) -> Result<()> {
     // Validate that the position account is not empty
     require!(
-        ctx.accounts.position.data_is_empty() == false,
+        !claim.position.data_is_empty(),
         ErrorCode::InvalidPosition
     );
     
     // Validate that the pool account is not empty
     require!(
-        ctx.accounts.pool.data_is_empty() == false,
+        !claim.pool.data_is_empty(),
         ErrorCode::InvalidPosition
     );
     
     // Validate that the position NFT account is not empty
     require!(
-        ctx.accounts.position_nft_account.data_is_empty() == false,
+        !claim.position_nft_account.data_is_empty(),
+        ErrorCode::InvalidPosition
+    );
+    
+    // A pool quoting a mint against itself has no distinct quote side to collect
+    require!(
+        claim.base_mint.key() != claim.quote_mint.key(),
         ErrorCode::InvalidPosition
     );
     
     msg!("Position accounts validated successfully for PDA collection");
     Ok(())
 }
+
+/// Checks the position for base fees that the claim would pay out: fees already
+/// checkpointed into `fee_a_pending`, or pool fee growth since the position's checkpoint
+fn has_pending_base_fees(claim: &PositionFeeClaim) -> Result<bool> {
+    let position = read_zero_copy::<damm_v2::accounts::Position>(&claim.position)?;
+    let pool = read_zero_copy::<damm_v2::accounts::Pool>(&claim.pool)?;
+    
+    require!(
+        position.pool == claim.pool.key(),
+        ErrorCode::InvalidPosition
+    );
+    
+    let has_liquidity = position.unlocked_liquidity > 0
+        || position.vested_liquidity > 0
+        || position.permanent_locked_liquidity > 0;
+    let base_fee_growth = pool.fee_a_per_liquidity != position.fee_a_per_token_checkpoint;
+    
+    msg!("Pending base fees: {} units", position.fee_a_pending);
+    
+    Ok(position.fee_a_pending > 0 || (has_liquidity && base_fee_growth))
+}
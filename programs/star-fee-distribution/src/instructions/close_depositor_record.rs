@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::constants::INVESTOR_RECORD_SEED;
+use crate::states::DepositorRecord;
+
+#[derive(Accounts)]
+pub struct CloseDepositorRecord<'info> {
+    /// Investor closing their record (receives the rent)
+    #[account(mut)]
+    pub investor: Signer<'info>,
+    
+    /// Depositor record to close (must have a zero balance)
+    #[account(
+        mut,
+        seeds = [INVESTOR_RECORD_SEED, investor.key().as_ref()],
+        bump = depositor_record.bump,
+        has_one = investor,
+        constraint = !depositor_record.is_active() @ ErrorCode::DepositorRecordNotEmpty,
+        close = investor
+    )]
+    pub depositor_record: Account<'info, DepositorRecord>,
+}
+
+impl<'info> CloseDepositorRecord<'info> {
+    pub fn handle(ctx: Context<CloseDepositorRecord>) -> Result<()> {
+        let depositor_record = &ctx.accounts.depositor_record;
+        
+        msg!("Closing depositor record for investor: {}", ctx.accounts.investor.key());
+        msg!("Lifetime USDC deposited: {} units", depositor_record.total_usdc_deposited);
+        
+        emit!(crate::events::DepositorRecordClosed {
+            investor: ctx.accounts.investor.key(),
+            total_sol_deposited: depositor_record.total_sol_deposited,
+            total_usdc_deposited: depositor_record.total_usdc_deposited,
+            deposit_count: depositor_record.deposit_count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
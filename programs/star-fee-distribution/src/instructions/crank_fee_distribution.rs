@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
 use crate::errors::ErrorCode;
-use crate::constants::{FEE_COLLECTOR_SEED, FEE_VAULT_SEED, DEPOSIT_VAULT_SEED, INVESTOR_RECORD_SEED, CRANK_STATE_SEED, DISTRIBUTION_CONFIG_SEED};
-use crate::states::{DepositorRecord, VaultStats, DistributionConfig, CrankState};
+use crate::constants::{FEE_COLLECTOR_SEED, FEE_VAULT_SEED, DEPOSIT_VAULT_SEED, INVESTOR_RECORD_SEED, CRANK_STATE_SEED, DISTRIBUTION_CONFIG_SEED, REMAINDER_POLICY_CREATOR, REMAINDER_POLICY_INVESTORS, CARRY_OVER_CAP_POLICY_TOP_INVESTOR, ZERO_BALANCE_POLICY_REJECT, MAX_REMAINING_LOCK_SECONDS, DISTRIBUTION_BATCH_SIZE, DAY_CLOSED_CALLBACK_DISCRIMINATOR, PPM, PPM_PER_BPS, KEEPER_INCENTIVE_SEED, DAY_RECORD_SEED, POSITION_REGISTRY_SEED};
+use crate::states::{DepositorRecord, VaultStats, DistributionConfig, CrankState, ExternalVesting, DayRecord, PositionRegistry, PositionState};
+use crate::utils::{depositor_member_hash, read_zero_copy, require_undelegated_payout_account};
+use crate::math::{add_u64, mul_div_u64};
+use super::claim_fees_to_pda::{claim_quote_only_fees, PositionFeeClaim};
 
 
 /// Crank instruction to distribute fees to all investors based on their shares
@@ -56,23 +59,99 @@ pub struct CrankFeeDistribution<'info> {
     )]
     pub vault_stats: Account<'info, VaultStats>,
     
-    /// Distribution configuration
+    /// Distribution configuration (mutable to adopt a pending creator wallet)
     #[account(
+        mut,
         seeds = [DISTRIBUTION_CONFIG_SEED],
         bump = distribution_config.bump
     )]
     pub distribution_config: Account<'info, DistributionConfig>,
     
-    /// Crank state to track distribution timing
+    /// Crank state to track distribution timing (the day is begun by begin_distribution_day)
     #[account(
-        init_if_needed,
-        payer = payer,
-        space = CrankState::DISCRIMINATOR.len() + CrankState::INIT_SPACE,
+        mut,
         seeds = [CRANK_STATE_SEED],
-        bump
+        bump = crank_state.bump
     )]
     pub crank_state: Account<'info, CrankState>,
     
+    /// Remainder recipient's quote token account (required when auto-closing the day unless
+    /// investors receive the remainder, owner checked on routing)
+    #[account(mut)]
+    pub creator_quote_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// Quote account of the day's largest-weight paid investor (required when carry-over above
+    /// `max_carry_over` is paid out under the top-investor cap policy)
+    #[account(mut)]
+    pub top_investor_quote_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// Dust recipient's quote token account (required when closing the day with carry-over dust
+    /// under a configured `dust_recipient`, owner checked on routing)
+    #[account(mut)]
+    pub dust_recipient_quote_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// Keeper incentive SOL account paying the payer `keeper_reward_lamports` for the page
+    /// (the reward is skipped when omitted or underfunded)
+    #[account(
+        mut,
+        seeds = [KEEPER_INCENTIVE_SEED],
+        bump
+    )]
+    pub keeper_incentive: Option<SystemAccount<'info>>,
+    
+    /// Honorary position state holding the last fresh claim day (required on the day's first
+    /// page under `require_fresh_claim`)
+    #[account(
+        constraint = position_state.quote_mint == distribution_config.quote_mint @ ErrorCode::NoFreshClaim
+    )]
+    pub position_state: Option<Box<Account<'info, PositionState>>>,
+    
+    /// CHECK: DAMM v2 program, with the position accounts below required when `auto_claim` claims
+    #[account(address = damm_v2::ID)]
+    pub amm_program: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool of the honorary position
+    #[account(mut)]
+    pub pool: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Honorary position (owned by our PDA)
+    #[account(mut)]
+    pub position: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Position NFT account
+    #[account(mut)]
+    pub position_nft_account: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool authority
+    #[account(mut)]
+    pub pool_authority: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool base token vault
+    #[account(mut)]
+    pub token_a_vault: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool quote token vault
+    #[account(mut)]
+    pub token_b_vault: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: DAMM v2 event authority
+    pub event_authority: Option<UncheckedAccount<'info>>,
+    
+    /// Position registry, which must list the auto-claimed position
+    #[account(
+        seeds = [POSITION_REGISTRY_SEED],
+        bump = position_registry.bump
+    )]
+    pub position_registry: Option<Box<Account<'info, PositionRegistry>>>,
+    
+    /// CHECK: Day record for the current day, created when this page closes the day (required then)
+    #[account(
+        mut,
+        seeds = [DAY_RECORD_SEED, crank_state.current_day.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub day_record: Option<UncheckedAccount<'info>>,
+    
     /// CHECK: Token program
     pub token_program: Program<'info, Token>,
     
@@ -82,39 +161,54 @@ pub struct CrankFeeDistribution<'info> {
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct DistributionParams {
+    /// Distribution round within the day (page 0 of the next round starts it)
+    pub round_index: u8,
     /// Page index for pagination
     pub page_index: u32,
     /// Number of investors in this page
     pub investors_count: u32,
-    /// Is this the final page of the day?
+    /// Is this the final page of the round?
     pub is_final_page: bool,
+    /// Claim the position's fees first when page 0 finds the quote vault below `min_crankable_quote`
+    pub auto_claim: bool,
 }
 
 impl<'info> CrankFeeDistribution<'info> {
-    pub fn handle(ctx: Context<CrankFeeDistribution>, params: DistributionParams) -> Result<()> {
-        msg!("Starting crank fee distribution - Page: {}", params.page_index);
+    pub fn handle(ctx: Context<'_, '_, 'info, 'info, CrankFeeDistribution<'info>>, params: DistributionParams) -> Result<()> {
+        msg!("Starting crank fee distribution - Round: {}, Page: {}", params.round_index, params.page_index);
         
-        let config = &ctx.accounts.distribution_config;
+        // A day begun while a config change was cooling off keeps the fee share from before it
+        let config = &*ctx.accounts.distribution_config.effective_at(ctx.accounts.crank_state.last_distribution_timestamp);
         let crank_state = &mut ctx.accounts.crank_state;
-        let vault_stats = &ctx.accounts.vault_stats;
-        
-        // Initialize crank state if needed
-        if crank_state.last_distribution_timestamp == 0 {
-            crank_state.last_distribution_timestamp = 0;
-            crank_state.current_day = 0;
-            crank_state.distribution_count = 0;
-            crank_state.pagination_cursor = 0;
-            crank_state.investors_processed_today = 0;
-            crank_state.daily_distributed = 0;
-            crank_state.carry_over = 0;
-            crank_state.day_state = 0;
-            crank_state.bump = ctx.bumps.crank_state;
-        }
         
-        // Start new day if needed
-        if !crank_state.is_day_in_progress() {
-            require!(!crank_state.is_day_closed(), ErrorCode::DayAlreadyClosed);
-            crank_state.start_new_day()?;
+        // The day's snapshot and deposit lock are taken by begin_distribution_day
+        require!(
+            crank_state.is_day_in_progress(),
+            ErrorCode::DayNotBegun
+        );
+        crank_state.require_not_stale(config.day_max_duration_seconds)?;
+        
+        // The quote vault must stay the same for every page of the day
+        require!(
+            ctx.accounts.program_token_b_vault.key() == crank_state.quote_vault,
+            ErrorCode::QuoteVaultMismatch
+        );
+        
+        // Page 0 of the next round starts it once the round's slot of the day has opened
+        if params.round_index != crank_state.current_round {
+            require!(
+                crank_state.current_round.checked_add(1) == Some(params.round_index)
+                    && params.round_index < config.rounds_per_day
+                    && params.page_index == 0,
+                ErrorCode::InvalidRoundIndex
+            );
+            
+            let round_opens_at = crank_state.last_distribution_timestamp
+                .checked_add(config.round_start_offset(params.round_index))
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(crank_state.checked_now()? >= round_opens_at, ErrorCode::RoundNotOpen);
+            
+            crank_state.start_next_round()?;
         }
         
         // Validate pagination cursor
@@ -123,6 +217,76 @@ impl<'info> CrankFeeDistribution<'info> {
             ErrorCode::InvalidPaginationCursor
         );
         
+        // Save the keeper a separate claim_fees_to_pda when the vault holds too little to crank
+        let mut auto_claimed_quote: u64 = 0;
+        if params.auto_claim
+            && params.page_index == 0
+            && ctx.accounts.program_token_b_vault.amount < config.min_crankable_quote
+        {
+            msg!("Quote vault below {} units, claiming position fees", config.min_crankable_quote);
+            
+            let claim = match (
+                ctx.accounts.amm_program.as_ref(),
+                ctx.accounts.pool.as_ref(),
+                ctx.accounts.position.as_ref(),
+                ctx.accounts.position_nft_account.as_ref(),
+                ctx.accounts.pool_authority.as_ref(),
+                ctx.accounts.token_a_vault.as_ref(),
+                ctx.accounts.token_b_vault.as_ref(),
+                ctx.accounts.event_authority.as_ref(),
+                ctx.accounts.position_registry.as_ref(),
+            ) {
+                (
+                    Some(amm_program),
+                    Some(pool),
+                    Some(position),
+                    Some(position_nft_account),
+                    Some(pool_authority),
+                    Some(token_a_vault),
+                    Some(token_b_vault),
+                    Some(event_authority),
+                    Some(position_registry),
+                ) => {
+                    // Only registered positions may claim into the shared fee vaults
+                    require!(
+                        position_registry.contains(&position.key()),
+                        ErrorCode::PositionNotRegistered
+                    );
+                    PositionFeeClaim {
+                        fee_collector: ctx.accounts.fee_collector.to_account_info(),
+                        fee_collector_bump: ctx.bumps.fee_collector,
+                        amm_program: amm_program.to_account_info(),
+                        pool: pool.to_account_info(),
+                        position: position.to_account_info(),
+                        position_nft_account: position_nft_account.to_account_info(),
+                        pool_authority: pool_authority.to_account_info(),
+                        base_mint: ctx.accounts.base_mint.to_account_info(),
+                        quote_mint: ctx.accounts.quote_mint.to_account_info(),
+                        token_a_vault: token_a_vault.to_account_info(),
+                        token_b_vault: token_b_vault.to_account_info(),
+                        token_program: ctx.accounts.token_program.to_account_info(),
+                        event_authority: event_authority.to_account_info(),
+                    }
+                }
+                _ => return Err(ErrorCode::AutoClaimAccountsRequired.into()),
+            };
+            (_, auto_claimed_quote) = claim_quote_only_fees(
+                &claim,
+                &mut ctx.accounts.program_token_a_vault,
+                &mut ctx.accounts.program_token_b_vault,
+                config.max_claim_per_tx,
+            )?;
+            
+            emit!(crate::events::QuoteFeesClaimed {
+                pool: claim.pool.key(),
+                position: claim.position.key(),
+                base_fees_claimed: 0,
+                quote_fees_claimed: auto_claimed_quote,
+                program_base_vault: ctx.accounts.program_token_a_vault.key(),
+                program_quote_vault: ctx.accounts.program_token_b_vault.key(),
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
         // QUOTE-ONLY ENFORCEMENT: Fail if base fees detected
         let base_fees_available = ctx.accounts.program_token_a_vault.amount;
         let quote_fees_available = ctx.accounts.program_token_b_vault.amount;
@@ -148,29 +312,164 @@ impl<'info> CrankFeeDistribution<'info> {
             ErrorCode::InvalidPosition
         );
         
-        // Calculate total locked amounts (from current depositor balances)
-        let locked_total = vault_stats.current_total_usdc; // Using USDC as quote token
+        // Only quote claimed into the vault since the day began may start the day's distribution
+        if config.require_fresh_claim && params.page_index == 0 && crank_state.current_round == 0 {
+            require!(
+                auto_claimed_quote > 0
+                    || has_fresh_claim(
+                        ctx.accounts.position_state.as_ref().map(|position_state| position_state.last_claim_day),
+                        crank_state,
+                        quote_fees_available,
+                    ),
+                ErrorCode::NoFreshClaim
+            );
+            msg!("Fresh claim: vault grew from {} to {} units", crank_state.day_start_quote_balance, quote_fees_available);
+        }
+        
+        // Total locked as snapshotted when the day was begun
+        let locked_total = crank_state.day_total_locked;
         
-        msg!("Total locked (depositor balances): {} units", locked_total);
+        msg!("Total locked (day snapshot): {} units", locked_total);
         msg!("Y0 allocation: {} units", config.y0_allocation);
         
-        // Calculate f_locked(t) = locked_total(t) / Y0
-        let f_locked_bps = if config.y0_allocation > 0 {
-            ((locked_total as u128 * 10000) / config.y0_allocation as u128) as u16
-        } else {
-            0
-        };
+        // Nobody was depositing when the day was begun, so there is nothing to paginate: the
+        // day's first page routes the fees like route_creator_remainder instead
+        if params.page_index == 0 && crank_state.expected_investors == 0 {
+            msg!("No investors in the day's snapshot, routing fees to the remainder recipient");
+            
+            emit!(crate::events::NoInvestorsToDistribute {
+                day: crank_state.current_day,
+                quote_fees_available,
+                carry_over: crank_state.carry_over,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+            
+            create_day_record(
+                ctx.accounts.day_record.as_ref(),
+                ctx.bumps.day_record,
+                &ctx.accounts.payer,
+                &ctx.accounts.system_program,
+                crank_state.current_day,
+            )?;
+            return route_remainder_and_close_day(
+                crank_state,
+                &mut ctx.accounts.distribution_config,
+                &mut ctx.accounts.vault_stats,
+                &ctx.accounts.program_token_b_vault,
+                ctx.accounts.creator_quote_account.as_deref(),
+                ctx.accounts.top_investor_quote_account.as_deref(),
+                ctx.accounts.dust_recipient_quote_account.as_deref(),
+                &ctx.accounts.fee_collector.to_account_info(),
+                ctx.bumps.fee_collector,
+                &ctx.accounts.token_program.to_account_info(),
+                None,
+                false,
+            );
+        }
+        
+        // Too little is locked for a distribution to be worth running: the day's first page
+        // routes the fees like a forced close instead
+        if params.page_index == 0 && locked_total < config.min_total_locked_to_distribute {
+            msg!("Total locked below minimum of {} units, skipping investor distribution",
+                config.min_total_locked_to_distribute);
+            
+            emit!(crate::events::BelowMinLocked {
+                day: crank_state.current_day,
+                total_locked: locked_total,
+                min_total_locked_to_distribute: config.min_total_locked_to_distribute,
+                quote_fees_available,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+            
+            create_day_record(
+                ctx.accounts.day_record.as_ref(),
+                ctx.bumps.day_record,
+                &ctx.accounts.payer,
+                &ctx.accounts.system_program,
+                crank_state.current_day,
+            )?;
+            return route_remainder_and_close_day(
+                crank_state,
+                &mut ctx.accounts.distribution_config,
+                &mut ctx.accounts.vault_stats,
+                &ctx.accounts.program_token_b_vault,
+                ctx.accounts.creator_quote_account.as_deref(),
+                ctx.accounts.top_investor_quote_account.as_deref(),
+                ctx.accounts.dust_recipient_quote_account.as_deref(),
+                &ctx.accounts.fee_collector.to_account_info(),
+                ctx.bumps.fee_collector,
+                &ctx.accounts.token_program.to_account_info(),
+                None,
+                false,
+            );
+        }
+        
+        // Fixed-size pages keep the pagination deterministic; only the final page may be short
+        if config.fixed_page_size {
+            require!(
+                is_valid_fixed_page(params.investors_count, params.is_final_page),
+                ErrorCode::InvalidPageSize
+            );
+        }
         
-        msg!("f_locked: {} bps", f_locked_bps);
+        // Optionally cross-check the day's snapshotted total locked against the depositor records
+        if config.verify_total_locked && params.page_index == 0 {
+            let summed_total = sum_depositor_balances(ctx.remaining_accounts, ctx.program_id)?;
+            check_total_locked_drift(
+                summed_total,
+                locked_total,
+                config.total_locked_tolerance_units,
+            )?;
+            
+            msg!("Verified total locked: {} units across {} records", summed_total, ctx.remaining_accounts.len());
+        }
+        
+        // Calculate f_locked(t) = locked_total(t) / Y0, capped at 100% and clamped into the configured band
+        let f_locked_bps = calculate_f_locked_bps(
+            locked_total,
+            config.y0_allocation,
+            config.f_locked_floor_bps,
+            config.f_locked_ceiling_bps,
+        )?;
+        
+        msg!("f_locked: {} bps (band: {}-{} bps)", f_locked_bps, config.f_locked_floor_bps, config.f_locked_ceiling_bps);
+        
+        // Calculate eligible_investor_share = min(investor_fee_share_bps, f_locked) in parts per
+        // million, so f_locked is not floored to whole basis points before the allocation
+        let eligible_investor_share_ppm = effective_eligible_share_ppm(config, locked_total)?;
+        let eligible_investor_share_bps = u16::try_from(eligible_investor_share_ppm / PPM_PER_BPS)
+            .map_err(|_| ErrorCode::MathOverflow)?;
+        
+        msg!("Eligible investor share: {} ppm (max: {} bps)", 
+            eligible_investor_share_ppm, config.investor_fee_share_bps);
         
-        // Calculate eligible_investor_share_bps = min(investor_fee_share_bps, f_locked_bps)
-        let eligible_investor_share_bps = std::cmp::min(config.investor_fee_share_bps, f_locked_bps);
+        // Carry-over dust and earlier rounds' quote are retained in the vault, so only the
+        // rest is freshly claimed quote
+        let mut claimed_quote = crank_state.fresh_round_quote(quote_fees_available);
         
-        msg!("Eligible investor share: {} bps (max: {} bps)", 
-            eligible_investor_share_bps, config.investor_fee_share_bps);
+        // Smooth lumpy fee revenue: the round's first page releases `distribution_rate_bps` of
+        // the accumulated quote and buffers the rest for later days
+        if params.page_index == 0 && (config.distribution_rate_bps > 0 || crank_state.buffered_quote > 0) {
+            let (smoothed_quote, buffered_quote) = smooth_round_quote(
+                config,
+                claimed_quote,
+                crank_state.buffered_quote,
+                crank_state.current_round == 0,
+            )?;
+            claimed_quote = smoothed_quote;
+            crank_state.buffered_quote = buffered_quote;
+            
+            msg!("Smoothed claim: {} units released, {} units buffered ({} bps)",
+                claimed_quote, crank_state.buffered_quote, config.distribution_rate_bps);
+        }
         
-        // Calculate investor_fee_quote = floor(claimed_quote * eligible_investor_share_bps / 10000)
-        let investor_fee_quote = ((quote_fees_available as u128 * eligible_investor_share_bps as u128) / 10000) as u64;
+        // Calculate investor_fee_quote = floor(claimed_quote * eligible_investor_share_ppm / 1e6);
+        // under the investor remainder policy the creator remainder is folded into the allocation
+        let investor_fee_quote = if config.remainder_policy == REMAINDER_POLICY_INVESTORS {
+            claimed_quote
+        } else {
+            investor_fee_quote_from_ppm(claimed_quote, eligible_investor_share_ppm)?
+        };
         
         msg!("Total investor allocation: {} units", investor_fee_quote);
         
@@ -180,10 +479,54 @@ impl<'info> CrankFeeDistribution<'info> {
         
         msg!("Total distributable (with carry-over): {} units", total_distributable);
         
+        // Invariant: carry-over dust is physically present in the vault alongside the claimed quote
+        require!(
+            total_distributable <= quote_fees_available,
+            ErrorCode::CarryOverNotBacked
+        );
+        
+        // Snapshot the round's investor allocation and reserve the fully-vested investors'
+        // share of the creator remainder on the first page; rounds add to the day's totals
+        if params.page_index == 0 {
+            crank_state.round_investor_fee_quote = investor_fee_quote;
+            
+            // The carry-over policy decides whether retained dust joins this round's pool
+            if config.includes_carry_over(claimed_quote, crank_state.carry_over) {
+                crank_state.round_investor_fee_quote = total_distributable;
+                msg!("Carry-over added to the round's pool: {} units", crank_state.carry_over);
+                crank_state.carry_over = 0;
+            } else if crank_state.carry_over > 0 {
+                msg!("Carry-over held: {} units (policy {})", crank_state.carry_over, config.carry_over_policy);
+            }
+            
+            crank_state.day_investor_fee_quote = crank_state.day_investor_fee_quote
+                .checked_add(crank_state.round_investor_fee_quote)
+                .ok_or(ErrorCode::MathOverflow)?;
+            crank_state.day_claimed_quote = crank_state.day_claimed_quote
+                .checked_add(claimed_quote)
+                .ok_or(ErrorCode::MathOverflow)?;
+            
+            let creator_remainder = claimed_quote.saturating_sub(investor_fee_quote);
+            let round_vested_pool = u64::try_from(
+                (creator_remainder as u128 * config.vested_investor_share_bps as u128) / 10000
+            ).map_err(|_| ErrorCode::MathOverflow)?;
+            crank_state.vested_pool = crank_state.vested_pool
+                .checked_add(round_vested_pool)
+                .ok_or(ErrorCode::MathOverflow)?;
+            msg!("Vested investor pool: {} units", crank_state.vested_pool);
+            
+            crank_state.round_vault_baseline = quote_fees_available;
+            crank_state.round_outflow_baseline = crank_state.day_outflows();
+        }
+        
+        // Every page of the round pays from the round's snapshotted allocation
+        crank_state.current_page_investor_fee = crank_state.round_investor_fee_quote;
+        
         // Check daily cap if configured
-        if config.daily_cap_lamports > 0 {
-            let remaining_cap = config.daily_cap_lamports
-                .checked_sub(crank_state.daily_distributed)
+        let daily_cap = config.daily_cap();
+        if daily_cap > 0 {
+            let remaining_cap = daily_cap
+                .checked_sub(crank_state.investor_distributed_today())
                 .ok_or(ErrorCode::DailyCapExceeded)?;
             
             require!(
@@ -202,30 +545,408 @@ impl<'info> CrankFeeDistribution<'info> {
         msg!("Total investors processed today: {}", crank_state.investors_processed_today);
         msg!("Day state: {}", crank_state.day_state);
         
-        // Emit event
-        emit!(crate::events::InvestorPayoutPage {
-            day: crank_state.current_day,
-            page_index: params.page_index,
-            investors_count: params.investors_count,
-            total_investors_processed_today: crank_state.investors_processed_today,
-            quote_fees_available,
-            total_locked: locked_total,
-            y0_allocation: config.y0_allocation,
-            f_locked_bps,
-            eligible_investor_share_bps,
-            investor_fee_quote,
-            page_distributed: 0, // This will be updated by individual investor payouts
-            carry_over: crank_state.carry_over,
-            daily_distributed: crank_state.daily_distributed,
-            daily_cap: config.daily_cap_lamports,
-            is_final_page: params.is_final_page,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+        // Emit event (page 0 marks the day start and is always emitted)
+        if config.emit_events || params.page_index == 0 {
+            emit!(crate::events::InvestorPayoutPage {
+                day: crank_state.current_day,
+                round_index: crank_state.current_round,
+                page_index: params.page_index,
+                investors_count: params.investors_count,
+                total_investors_processed_today: crank_state.investors_processed_today,
+                quote_fees_available,
+                total_locked: locked_total,
+                y0_allocation: config.y0_allocation,
+                f_locked_bps,
+                eligible_investor_share_bps,
+                investor_fee_quote,
+                page_distributed: 0, // This will be updated by individual investor payouts
+                carry_over: crank_state.carry_over,
+                vested_pool: crank_state.vested_pool,
+                buffered_quote: crank_state.buffered_quote,
+                daily_distributed: crank_state.daily_distributed,
+                daily_cap,
+                is_final_page: params.is_final_page,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+        
+        // Only pages that carry investors of the day's snapshot are rewarded, so repeated
+        // empty pages cannot drain the keeper incentive
+        if config.keeper_reward_lamports > 0
+            && earns_keeper_reward(params.page_index, params.investors_count, crank_state.expected_investors)
+        {
+            pay_keeper_reward(
+                ctx.accounts.keeper_incentive.as_ref(),
+                ctx.bumps.keeper_incentive,
+                &ctx.accounts.payer,
+                &ctx.accounts.system_program,
+                config.keeper_reward_lamports,
+                crank_state.current_day,
+                params.page_index,
+            )?;
+        }
+        
+        // Auto-close the day on the final page once every processed investor has been paid;
+        // earlier rounds leave the day open for the next one
+        if params.is_final_page && config.auto_close_on_final_page {
+            if !config.is_last_round(crank_state.current_round) {
+                msg!("Round {} of {} complete, day left open for the next round",
+                    crank_state.current_round, config.rounds_per_day);
+            } else if crank_state.is_reconciled() {
+                create_day_record(
+                    ctx.accounts.day_record.as_ref(),
+                    ctx.bumps.day_record,
+                    &ctx.accounts.payer,
+                    &ctx.accounts.system_program,
+                    crank_state.current_day,
+                )?;
+                route_remainder_and_close_day(
+                    crank_state,
+                    &mut ctx.accounts.distribution_config,
+                    &mut ctx.accounts.vault_stats,
+                    &ctx.accounts.program_token_b_vault,
+                    ctx.accounts.creator_quote_account.as_deref(),
+                    ctx.accounts.top_investor_quote_account.as_deref(),
+                    ctx.accounts.dust_recipient_quote_account.as_deref(),
+                    &ctx.accounts.fee_collector.to_account_info(),
+                    ctx.bumps.fee_collector,
+                    &ctx.accounts.token_program.to_account_info(),
+                    None,
+                    false,
+                )?;
+            } else {
+                msg!("Final page not reconciled ({} of {} investors paid), day left open",
+                    crank_state.investors_paid_today, crank_state.investors_processed_today);
+            }
+        }
         
         Ok(())
     }
 }
 
+/// Checks if the keeper incentive balance covers `reward` and stays rent exempt afterwards
+pub fn can_pay_keeper_reward(balance: u64, rent_exempt_minimum: u64, reward: u64) -> bool {
+    balance
+        .checked_sub(rent_exempt_minimum)
+        .is_some_and(|spendable| spendable >= reward)
+}
+
+/// Checks if a page earns the keeper reward: it must process investors and fall within the
+/// pages needed to cover the day's expected investors in batches of `DISTRIBUTION_BATCH_SIZE`
+pub fn earns_keeper_reward(page_index: u32, investors_count: u32, expected_investors: u32) -> bool {
+    investors_count > 0 && page_index < expected_investors.div_ceil(DISTRIBUTION_BATCH_SIZE)
+}
+
+/// Pays the page's payer the keeper reward from the keeper incentive PDA. Only that account is
+/// ever debited, never the deposit vaults, and an omitted or underfunded account skips the reward
+/// so keepers are not blocked from cranking
+fn pay_keeper_reward<'info>(
+    keeper_incentive: Option<&SystemAccount<'info>>,
+    keeper_incentive_bump: Option<u8>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    reward: u64,
+    day: u32,
+    page_index: u32,
+) -> Result<()> {
+    let (Some(keeper_incentive), Some(bump)) = (keeper_incentive, keeper_incentive_bump) else {
+        msg!("No keeper incentive account passed, skipping the keeper reward");
+        return Ok(());
+    };
+    
+    let balance = keeper_incentive.lamports();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    if !can_pay_keeper_reward(balance, rent_exempt_minimum, reward) {
+        msg!("Keeper incentive balance {} lamports cannot cover the {} lamport reward, skipping",
+            balance, reward);
+        return Ok(());
+    }
+    
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: keeper_incentive.to_account_info(),
+                to: payer.to_account_info(),
+            },
+            &[&[
+                KEEPER_INCENTIVE_SEED,
+                &[bump]
+            ]]
+        ),
+        reward,
+    )?;
+    
+    let remaining_balance = keeper_incentive.lamports();
+    msg!("Paid keeper {} a reward of {} lamports", payer.key(), reward);
+    
+    emit!(crate::events::KeeperRewardPaid {
+        day,
+        page_index,
+        keeper: payer.key(),
+        amount: reward,
+        remaining_balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Creates the record of the day a crank page is closing, as the `init` in
+/// `route_creator_remainder` does; an existing record means the day was already closed
+fn create_day_record<'info>(
+    day_record: Option<&UncheckedAccount<'info>>,
+    day_record_bump: Option<u8>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    day: u32,
+) -> Result<()> {
+    let (Some(day_record), Some(bump)) = (day_record, day_record_bump) else {
+        return err!(ErrorCode::DayRecordRequired);
+    };
+    require!(
+        day_record.data_is_empty() && day_record.owner == &System::id(),
+        ErrorCode::DayAlreadyClosed
+    );
+    
+    let space = DayRecord::DISCRIMINATOR.len() + DayRecord::INIT_SPACE;
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(space);
+    let day_bytes = day.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[DAY_RECORD_SEED, day_bytes.as_ref(), &[bump]]];
+    
+    // Like Anchor's init, tolerate a PDA someone has already sent lamports to
+    let current_lamports = day_record.lamports();
+    if current_lamports == 0 {
+        anchor_lang::system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                anchor_lang::system_program::CreateAccount {
+                    from: payer.to_account_info(),
+                    to: day_record.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            rent_exempt_minimum,
+            space as u64,
+            &crate::ID,
+        )?;
+    } else {
+        let top_up = rent_exempt_minimum.saturating_sub(current_lamports);
+        if top_up > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: payer.to_account_info(),
+                        to: day_record.to_account_info(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        anchor_lang::system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                anchor_lang::system_program::Allocate {
+                    account_to_allocate: day_record.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            space as u64,
+        )?;
+        anchor_lang::system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                anchor_lang::system_program::Assign {
+                    account_to_assign: day_record.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            &crate::ID,
+        )?;
+    }
+    
+    // The payout root is filled in later by submit_payout_root
+    let record = DayRecord {
+        day,
+        payout_root: [0u8; 32],
+        total_distributed: 0,
+        leaf_count: 0,
+        submitted_by: Pubkey::default(),
+        submitted_timestamp: 0,
+        bump,
+    };
+    record.try_serialize(&mut &mut day_record.try_borrow_mut_data()?[..])?;
+    
+    msg!("Day record created for day {}", day);
+    Ok(())
+}
+
+/// Sums `current_usdc_balance` across the depositor records in `accounts`, rejecting
+/// accounts that are not depositor record PDAs and records passed more than once
+pub fn sum_depositor_balances<'info>(accounts: &'info [AccountInfo<'info>], program_id: &Pubkey) -> Result<u64> {
+    let mut investors: Vec<Pubkey> = Vec::with_capacity(accounts.len());
+    let mut total: u64 = 0;
+    for account in accounts.iter() {
+        let record = Account::<DepositorRecord>::try_from(account)?;
+        let (expected_record, _) = Pubkey::find_program_address(
+            &[INVESTOR_RECORD_SEED, record.investor.as_ref()],
+            program_id,
+        );
+        require!(account.key() == expected_record, ErrorCode::InvalidDepositorRecord);
+        require!(!investors.contains(&record.investor), ErrorCode::InvalidDepositorRecord);
+        
+        investors.push(record.investor);
+        total = total.checked_add(record.current_usdc_balance).ok_or(ErrorCode::MathOverflow)?;
+    }
+    
+    Ok(total)
+}
+
+/// Fails with `VaultStatsDrift` when the summed depositor balances and the recorded
+/// vault total differ by more than `tolerance`
+pub fn check_total_locked_drift(summed_total: u64, recorded_total: u64, tolerance: u64) -> Result<()> {
+    let drift = summed_total.abs_diff(recorded_total);
+    if drift > tolerance {
+        msg!("Vault stats drift: records sum to {} units, vault stats record {} units", summed_total, recorded_total);
+        return err!(ErrorCode::VaultStatsDrift);
+    }
+    
+    Ok(())
+}
+
+/// Checks a page size under `fixed_page_size`: exactly `DISTRIBUTION_BATCH_SIZE` investors,
+/// or at most that many on the final page
+pub fn is_valid_fixed_page(investors_count: u32, is_final_page: bool) -> bool {
+    if is_final_page {
+        investors_count <= DISTRIBUTION_BATCH_SIZE
+    } else {
+        investors_count == DISTRIBUTION_BATCH_SIZE
+    }
+}
+
+/// Calculates f_locked(t) = locked_total(t) / Y0 in parts per million, capped at 1_000_000 and
+/// then clamped into [floor_bps, ceiling_bps] (a ceiling of 0 means no ceiling)
+pub fn calculate_f_locked_ppm(locked_total: u64, y0_allocation: u64, floor_bps: u16, ceiling_bps: u16) -> Result<u32> {
+    let f_locked = if y0_allocation > 0 {
+        ((locked_total as u128 * PPM as u128) / y0_allocation as u128).min(PPM as u128)
+    } else {
+        0
+    };
+    let f_locked_ppm = u32::try_from(f_locked).map_err(|_| ErrorCode::MathOverflow)?;
+    
+    let ceiling_bps = if ceiling_bps == 0 { 10000 } else { ceiling_bps };
+    require!(floor_bps <= ceiling_bps, ErrorCode::InvalidFLockedBand);
+    
+    Ok(f_locked_ppm.clamp(floor_bps as u32 * PPM_PER_BPS, ceiling_bps as u32 * PPM_PER_BPS))
+}
+
+/// Calculates f_locked(t) in basis points, floored from `calculate_f_locked_ppm` (for events and
+/// logs; the allocation uses the ppm value)
+pub fn calculate_f_locked_bps(locked_total: u64, y0_allocation: u64, floor_bps: u16, ceiling_bps: u16) -> Result<u16> {
+    let f_locked_ppm = calculate_f_locked_ppm(locked_total, y0_allocation, floor_bps, ceiling_bps)?;
+    u16::try_from(f_locked_ppm / PPM_PER_BPS).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// Eligible investor share in parts per million for `locked_total`: min(investor_fee_share_bps, f_locked),
+/// with f_locked capped at 100% and clamped into the config's band, and at most
+/// 10000 - creator_min_share_bps. Clients can call this to compute exactly the share the crank will use
+pub fn effective_eligible_share_ppm(config: &DistributionConfig, locked_total: u64) -> Result<u32> {
+    let f_locked_ppm = calculate_f_locked_ppm(
+        locked_total,
+        config.y0_allocation,
+        config.f_locked_floor_bps,
+        config.f_locked_ceiling_bps,
+    )?;
+    
+    let creator_floor_cap_bps = 10000u16.saturating_sub(config.creator_min_share_bps);
+    let share_cap_ppm = config.investor_fee_share_bps.min(creator_floor_cap_bps) as u32 * PPM_PER_BPS;
+    
+    Ok(f_locked_ppm.min(share_cap_ppm))
+}
+
+/// Eligible investor share in basis points, floored from `effective_eligible_share_ppm`
+pub fn effective_eligible_share_bps(config: &DistributionConfig, locked_total: u64) -> Result<u16> {
+    let share_ppm = effective_eligible_share_ppm(config, locked_total)?;
+    u16::try_from(share_ppm / PPM_PER_BPS).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// Investor allocation floor(claimed_quote * eligible_share_ppm / 1_000_000)
+pub fn investor_fee_quote_from_ppm(claimed_quote: u64, eligible_share_ppm: u32) -> Result<u64> {
+    u64::try_from(
+        (claimed_quote as u128 * eligible_share_ppm as u128) / PPM as u128
+    ).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// Checks that fees were claimed into the day's quote vault after the day began: the position
+/// recorded a claim for the day in progress (`last_claim_day`, None without a position state)
+/// and the vault holds more than at day start
+pub fn has_fresh_claim(last_claim_day: Option<u32>, crank_state: &CrankState, vault_amount: u64) -> bool {
+    last_claim_day == Some(crank_state.current_day) && vault_amount > crank_state.day_start_quote_balance
+}
+
+/// Splits a round's fresh quote into the claimed quote and the new buffer under
+/// `distribution_rate_bps`. The first round also releases the buffer earlier days held back,
+/// so the rate applies to everything accumulated; later rounds only buffer what arrived
+pub fn smooth_round_quote(
+    config: &DistributionConfig,
+    fresh_quote: u64,
+    buffered_quote: u64,
+    first_round: bool,
+) -> Result<(u64, u64)> {
+    let (accumulated_quote, kept_buffer) = if first_round {
+        (add_u64(fresh_quote, buffered_quote)?, 0)
+    } else {
+        (fresh_quote, buffered_quote)
+    };
+    let claimed_quote = config.smoothed_claim(accumulated_quote);
+    let buffered_quote = add_u64(kept_buffer, accumulated_quote - claimed_quote)?;
+    Ok((claimed_quote, buffered_quote))
+}
+
+/// Weight of `locked` tokens with `remaining_lock_seconds` left, in locked-token units:
+/// locked * remaining / MAX_REMAINING_LOCK_SECONDS with the remaining time capped at the
+/// maximum, so the weights still sum to at most Y0
+pub fn remaining_lock_weight(locked: u64, remaining_lock_seconds: u64) -> Result<u64> {
+    mul_div_u64(
+        locked,
+        remaining_lock_seconds.min(MAX_REMAINING_LOCK_SECONDS),
+        MAX_REMAINING_LOCK_SECONDS,
+    )
+}
+
+/// Validates the creator's minimum share against the investor share and remainder policy: it
+/// cannot take the whole claim from a non-zero investor share, and the investor policy pays
+/// investors everything
+pub fn validate_creator_min_share(creator_min_share_bps: u16, investor_fee_share_bps: u16, remainder_policy: u8) -> Result<()> {
+    require!(
+        creator_min_share_bps <= 10000
+            && (creator_min_share_bps < 10000 || investor_fee_share_bps == 0)
+            && (creator_min_share_bps == 0 || remainder_policy != REMAINDER_POLICY_INVESTORS),
+        ErrorCode::InvalidCreatorMinShare
+    );
+    Ok(())
+}
+
+/// Validates an f_locked band as stored in the config (a ceiling of 0 means no ceiling)
+pub fn validate_f_locked_band(floor_bps: u16, ceiling_bps: u16) -> Result<()> {
+    require!(
+        ceiling_bps <= 10000 && (ceiling_bps == 0 || floor_bps <= ceiling_bps) && floor_bps <= 10000,
+        ErrorCode::InvalidFLockedBand
+    );
+    Ok(())
+}
+
+/// Calculates weight_i(t) = locked_i(t) / locked_total(t) in basis points
+pub fn calculate_weight_bps(investor_balance: u64, total_locked: u64) -> Result<u64> {
+    if total_locked == 0 {
+        return Ok(0);
+    }
+    
+    u64::try_from((investor_balance as u128 * 10000) / total_locked as u128)
+        .map_err(|_| ErrorCode::MathOverflow.into())
+}
+
 /// Calculates investor payout with dust handling
 pub fn calculate_investor_payout(
     investor_balance: u64,
@@ -234,14 +955,11 @@ pub fn calculate_investor_payout(
     min_payout: u64,
 ) -> Result<(u64, u64)> {
     // Calculate weight_i(t) = locked_i(t) / locked_total(t)
-    let weight_bps = if total_locked > 0 {
-        ((investor_balance as u128 * 10000) / total_locked as u128) as u64
-    } else {
-        0
-    };
+    let weight_bps = calculate_weight_bps(investor_balance, total_locked)?;
     
     // Calculate payout = floor(investor_fee_quote * weight_i(t))
-    let payout = ((total_investor_fee as u128 * weight_bps as u128) / 10000) as u64;
+    let payout = u64::try_from((total_investor_fee as u128 * weight_bps as u128) / 10000)
+        .map_err(|_| ErrorCode::MathOverflow)?;
     
     // Apply dust threshold
     let (actual_payout, dust) = if payout < min_payout {
@@ -254,6 +972,22 @@ pub fn calculate_investor_payout(
     Ok((actual_payout, dust))
 }
 
+/// Scales `weight` by `age_multiplier_bps / max_age_multiplier_bps`, so age-scaled weights never
+/// sum past the `total_weight` they are renormalized by and the pool cannot be exceeded. Returns
+/// the scaled weight and the part of the investor's full share of `total_investor_fee` withheld
+pub fn apply_age_multiplier(
+    weight: u64,
+    total_weight: u64,
+    total_investor_fee: u64,
+    age_multiplier_bps: u16,
+    max_age_multiplier_bps: u16,
+) -> Result<(u64, u64)> {
+    let aged_weight = mul_div_u64(weight, age_multiplier_bps as u64, max_age_multiplier_bps as u64)?;
+    let (full_share, _) = calculate_investor_payout(weight, total_weight, total_investor_fee, 0)?;
+    let (aged_share, _) = calculate_investor_payout(aged_weight, total_weight, total_investor_fee, 0)?;
+    Ok((aged_weight, full_share.saturating_sub(aged_share)))
+}
+
 /// Individual fee distribution instruction for a specific investor
 #[derive(Accounts)]
 pub struct DistributeToInvestor<'info> {
@@ -271,15 +1005,19 @@ pub struct DistributeToInvestor<'info> {
         seeds = [FEE_VAULT_SEED, quote_mint.key().as_ref()],
         bump,
         token::mint = quote_mint,
-        token::authority = fee_collector
+        token::authority = fee_collector,
+        constraint = program_quote_vault.key() == crank_state.quote_vault @ ErrorCode::QuoteVaultMismatch
     )]
     pub program_quote_vault: Box<Account<'info, TokenAccount>>,
     
-    /// CHECK: Quote token mint
-    #[account(mut)]
+    /// CHECK: Quote token mint (validated against config)
+    #[account(
+        mut,
+        constraint = quote_mint.key() == distribution_config.quote_mint @ ErrorCode::InvalidPosition
+    )]
     pub quote_mint: UncheckedAccount<'info>,
     
-    /// Investor's quote token account
+    /// Investor's quote token account (must be the one registered in the depositor record)
     #[account(mut)]
     pub investor_quote_account: Box<Account<'info, TokenAccount>>,
     
@@ -292,8 +1030,19 @@ pub struct DistributeToInvestor<'info> {
     )]
     pub depositor_record: Account<'info, DepositorRecord>,
     
-    /// Global vault statistics
+    /// Program's deposit vault for the quote mint (required when the investor auto-compounds)
+    #[account(
+        mut,
+        seeds = [DEPOSIT_VAULT_SEED, quote_mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = fee_collector
+    )]
+    pub deposit_vault: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// Global vault statistics (mutable to credit compounded payouts)
     #[account(
+        mut,
         seeds = [DEPOSIT_VAULT_SEED, b"stats"],
         bump = vault_stats.bump
     )]
@@ -317,6 +1066,10 @@ pub struct DistributeToInvestor<'info> {
     /// The investor receiving the distribution
     pub investor: Signer<'info>,
     
+    /// CHECK: Investor's external vesting account, validated in ExternalVesting::load
+    /// (required when distribution_config.vesting_program is set)
+    pub vesting_account: Option<UncheckedAccount<'info>>,
+    
     /// CHECK: Token program
     pub token_program: Program<'info, Token>,
 }
@@ -332,8 +1085,7 @@ impl<'info> DistributeToInvestor<'info> {
         msg!("Distributing quote fees to investor: {}", ctx.accounts.investor.key());
         
         let depositor_record = &ctx.accounts.depositor_record;
-        let vault_stats = &ctx.accounts.vault_stats;
-        let config = &ctx.accounts.distribution_config;
+        let config = &*ctx.accounts.distribution_config.effective_at(ctx.accounts.crank_state.last_distribution_timestamp);
         let crank_state = &mut ctx.accounts.crank_state;
         
         // Ensure distribution is in progress
@@ -341,38 +1093,162 @@ impl<'info> DistributeToInvestor<'info> {
             crank_state.is_day_in_progress(),
             ErrorCode::DistributionNotStarted
         );
+        crank_state.require_not_stale(config.day_max_duration_seconds)?;
+        
+        // The fee pool is the one the crank computed, not whatever the caller passes
+        require!(
+            params.total_investor_fee == crank_state.current_page_investor_fee,
+            ErrorCode::FeeAmountMismatch
+        );
+        
+        // Each record is paid at most once per round, on the default and the vested path alike
+        require!(
+            (depositor_record.last_paid_day, depositor_record.last_paid_round)
+                != (crank_state.current_day, crank_state.current_round),
+            ErrorCode::InvestorAlreadyPaid
+        );
+        
+        // Opted-out records carry no weight, so their share already went to the other investors
+        if depositor_record.opted_out {
+            msg!("Skipping opted-out investor: {}", ctx.accounts.investor.key());
+            return record_snapshot_payout(crank_state, &mut ctx.accounts.depositor_record);
+        }
+        
+        // Paying the vault into itself would mark the investor paid without moving funds
+        require!(
+            ctx.accounts.investor_quote_account.key() != ctx.accounts.program_quote_vault.key(),
+            ErrorCode::InvalidQuoteAccount
+        );
+        
+        // Payouts only go to the quote account the investor registered
+        require!(
+            ctx.accounts.investor_quote_account.key() == depositor_record.registered_quote_account,
+            ErrorCode::QuoteAccountNotRegistered
+        );
         
-        // Get investor's current balance (locked amount)
-        let investor_balance = depositor_record.current_usdc_balance;
-        let total_locked = vault_stats.current_total_usdc;
+        // Get investor's locked amount, from the external vesting program if configured.
+        // External locked amounts are weighted against Y0, the sum of all allocations,
+        // so payouts can never exceed the investor fee pool.
+        let (investor_balance, total_locked) = if config.vesting_program != Pubkey::default() {
+            let vesting_account = ctx.accounts.vesting_account
+                .as_ref()
+                .ok_or(ErrorCode::VestingAccountInvalid)?;
+            let vesting = ExternalVesting::load(
+                &vesting_account.to_account_info(),
+                &config.vesting_program,
+                &ctx.accounts.investor.key(),
+            )?;
+            // Vesting is evaluated on a clock that has not moved behind the day start
+            let now = crank_state.checked_now()?;
+            let locked = vesting.locked_amount(now)?.min(config.y0_allocation);
+            
+            msg!("Using external vesting account: {}", vesting_account.key());
+            if config.weight_by_remaining_lock {
+                let remaining_lock_seconds = vesting.remaining_lock_seconds(now);
+                msg!("Remaining lock: {} seconds", remaining_lock_seconds);
+                (remaining_lock_weight(locked, remaining_lock_seconds)?, config.y0_allocation)
+            } else {
+                (locked, config.y0_allocation)
+            }
+        } else {
+            // Weights use multiplier-scaled balances, renormalized by their sum as of the day's start
+            (
+                depositor_record.day_start_weighted_usdc_balance(crank_state.current_day)?,
+                crank_state.day_total_weighted_usdc,
+            )
+        };
         
         msg!("Investor balance: {} units", investor_balance);
         msg!("Total locked: {} units", total_locked);
         
+        // Fully-vested investors receive the reduced share funded from the creator remainder
+        if investor_balance == 0 && config.vested_investor_share_bps > 0 {
+            return distribute_vested_share(ctx);
+        }
+        
+        // A fully withdrawn record has no weight, so it is neither paid nor counted in
+        // investors_paid_today; one withdrawn after the snapshot still counts toward reconciliation
+        if config.vesting_program == Pubkey::default() && depositor_record.current_usdc_balance == 0 {
+            require!(
+                config.zero_balance_policy != ZERO_BALANCE_POLICY_REJECT
+                    || counts_toward_snapshot(crank_state, depositor_record),
+                ErrorCode::ZeroBalanceRecord
+            );
+            
+            msg!("Skipping zero-balance record for investor: {}", ctx.accounts.investor.key());
+            return record_snapshot_payout(crank_state, &mut ctx.accounts.depositor_record);
+        }
+        
+        // Older deposits earn their age bracket's multiplier; the share a lower bracket
+        // withholds stays in the vault as carry-over
+        let (investor_balance, age_withheld) = if config.vesting_program == Pubkey::default() && config.has_age_brackets() {
+            let age_seconds = crank_state.checked_now()?.saturating_sub(depositor_record.first_deposit_timestamp);
+            let age_multiplier_bps = config.age_multiplier_bps(age_seconds);
+            msg!("Deposit age: {} seconds, multiplier: {} bps", age_seconds, age_multiplier_bps);
+            apply_age_multiplier(
+                investor_balance,
+                total_locked,
+                params.total_investor_fee,
+                age_multiplier_bps,
+                config.max_age_multiplier_bps(),
+            )?
+        } else {
+            (investor_balance, 0)
+        };
+        
         // Calculate investor payout with dust handling
+        let min_payout = config.min_payout(params.total_investor_fee, crank_state.expected_investors);
         let (payout, dust) = calculate_investor_payout(
             investor_balance,
             total_locked,
             params.total_investor_fee,
-            config.min_payout_lamports,
+            min_payout,
         )?;
+        let dust = dust.checked_add(age_withheld).ok_or(ErrorCode::MathOverflow)?;
+        
+        // Investors who deposited too recently, or haven't proven allowlist inclusion,
+        // roll their share into carry-over
+        let (payout, dust) = if !is_eligible(config, crank_state, depositor_record) {
+            msg!("Investor not eligible (first eligible day {}, proven {})",
+                depositor_record.first_eligible_day, depositor_record.eligible);
+            (0, payout.checked_add(dust).ok_or(ErrorCode::MathOverflow)?)
+        } else {
+            (payout, dust)
+        };
         
         msg!("Calculated payout: {} units", payout);
         msg!("Dust amount: {} units", dust);
         
+        let compounded = depositor_record.auto_compound;
+        
         // Distribute quote tokens if payout > 0
         if payout > 0 {
-            // Check daily cap if configured
-            if config.daily_cap_lamports > 0 {
-                let new_total = crank_state.daily_distributed
-                    .checked_add(payout)
-                    .ok_or(ErrorCode::MathOverflow)?;
-                
-                require!(
-                    new_total <= config.daily_cap_lamports,
-                    ErrorCode::DailyCapExceeded
-                );
-            }
+            let new_total = crank_state.daily_distributed
+                .checked_add(payout)
+                .ok_or(ErrorCode::MathOverflow)?;
+            
+            // Payouts are weighted against the day's snapshot; none may draw past its allocation
+            require!(
+                new_total <= crank_state.day_investor_fee_quote,
+                ErrorCode::InvestorPoolExhausted
+            );
+            require_within_daily_cap(config, crank_state, payout)?;
+            
+            // Auto-compounding investors have the payout moved into the deposit vault, which
+            // only counts toward the locked totals while quote and deposit mints match
+            let destination = if compounded {
+                require!(
+                    config.quote_mint == config.deposit_mint,
+                    ErrorCode::WrongDepositMint
+                );
+                ctx.accounts.deposit_vault
+                    .as_ref()
+                    .ok_or(ErrorCode::DepositVaultRequired)?
+                    .to_account_info()
+            } else {
+                require_undelegated_payout_account(config, &ctx.accounts.investor_quote_account)?;
+                ctx.accounts.investor_quote_account.to_account_info()
+            };
             
             // Transfer quote tokens
             anchor_spl::token::transfer(
@@ -380,7 +1256,7 @@ impl<'info> DistributeToInvestor<'info> {
                     ctx.accounts.token_program.to_account_info(),
                     anchor_spl::token::Transfer {
                         from: ctx.accounts.program_quote_vault.to_account_info(),
-                        to: ctx.accounts.investor_quote_account.to_account_info(),
+                        to: destination,
                         authority: ctx.accounts.fee_collector.to_account_info(),
                     },
                     &[&[
@@ -391,15 +1267,26 @@ impl<'info> DistributeToInvestor<'info> {
                 payout,
             )?;
             
+            if compounded {
+                compound_payout(&mut ctx.accounts.depositor_record, &mut ctx.accounts.vault_stats, payout, crank_state.current_day)?;
+                msg!("Compounded {} quote tokens into investor balance: {} units",
+                    payout, ctx.accounts.depositor_record.current_usdc_balance);
+            }
+            
             // Update daily distributed
-            crank_state.daily_distributed = crank_state.daily_distributed
-                .checked_add(payout)
-                .ok_or(ErrorCode::MathOverflow)?;
+            crank_state.daily_distributed = new_total;
+            
+            crank_state.record_top_investor(investor_balance, ctx.accounts.investor_quote_account.key());
             
             msg!("Distributed {} quote tokens to investor", payout);
             msg!("Total distributed today: {} units", crank_state.daily_distributed);
         }
         
+        crank_state.investors_paid_today = crank_state.investors_paid_today
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        record_snapshot_payout(crank_state, &mut ctx.accounts.depositor_record)?;
+        
         // Update carry-over with dust
         if dust > 0 {
             crank_state.carry_over = crank_state.carry_over
@@ -411,34 +1298,517 @@ impl<'info> DistributeToInvestor<'info> {
         msg!("Quote fee distribution to investor completed!");
         
         // Emit event
-        let weight_bps = if total_locked > 0 {
-            ((investor_balance as u128 * 10000) / total_locked as u128) as u64
-        } else {
-            0
-        };
+        if config.emit_events {
+            let weight_bps = calculate_weight_bps(investor_balance, total_locked)?;
+            
+            emit!(crate::events::InvestorPayout {
+                day: crank_state.current_day,
+                round_index: crank_state.current_round,
+                investor: ctx.accounts.investor.key(),
+                investor_locked_balance: investor_balance,
+                total_locked,
+                weight_bps,
+                total_investor_fee: params.total_investor_fee,
+                calculated_payout: payout + dust,
+                actual_payout: payout,
+                dust,
+                min_payout,
+                investor_quote_account: ctx.accounts.investor_quote_account.key(),
+                compounded: compounded && payout > 0,
+                remaining_daily_cap: config.remaining_daily_cap(crank_state.investor_distributed_today()),
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+        
+        Ok(())
+    }
+}
+
+/// Checks if the investor has reached their first eligible day and, under an allowlist,
+/// proven inclusion in it
+fn is_eligible(config: &DistributionConfig, crank_state: &CrankState, depositor_record: &DepositorRecord) -> bool {
+    depositor_record.first_eligible_day <= crank_state.current_day
+        && (!config.requires_eligibility_proof() || depositor_record.eligible)
+}
+
+/// Rejects a payout that would take the day's investor payouts past the daily cap, if configured
+fn require_within_daily_cap(config: &DistributionConfig, crank_state: &CrankState, payout: u64) -> Result<()> {
+    let daily_cap = config.daily_cap();
+    if daily_cap > 0 {
+        let new_total = crank_state.investor_distributed_today()
+            .checked_add(payout)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        require!(
+            new_total <= daily_cap,
+            ErrorCode::DailyCapExceeded
+        );
+    }
+    Ok(())
+}
+
+/// Credits a compounded payout to the investor's USDC balance, keeping the vault totals in sync.
+/// The day's payouts are weighted against its start-of-day snapshot, so the added weight
+/// applies from the next day
+fn compound_payout(depositor_record: &mut DepositorRecord, vault_stats: &mut VaultStats, payout: u64, day: u32) -> Result<()> {
+    let was_active = depositor_record.is_active();
+    let previous_weighted_usdc = depositor_record.weighted_usdc_balance()?;
+    
+    depositor_record.add_compounded_usdc(payout, day)?;
+    vault_stats.add_compounded_usdc(payout)?;
+    vault_stats.update_weighted_usdc(previous_weighted_usdc, depositor_record.weighted_usdc_balance()?)?;
+    
+    // Only possible in external vesting mode, where payouts don't depend on the deposit balance
+    if !was_active {
+        vault_stats.add_depositor(&depositor_member_hash(&depositor_record.investor))?;
         
-        emit!(crate::events::InvestorPayout {
+        emit!(crate::events::DepositorCountChanged {
+            investor: depositor_record.investor,
+            depositor_count: vault_stats.depositor_count,
+            increased: true,
+            timestamp: vault_stats.last_update_timestamp,
+        });
+    }
+    
+    Ok(())
+}
+
+/// Checks if the investor was in the day's snapshot and has not been counted yet this round
+fn counts_toward_snapshot(crank_state: &CrankState, depositor_record: &DepositorRecord) -> bool {
+    (depositor_record.last_paid_day, depositor_record.last_paid_round) != (crank_state.current_day, crank_state.current_round)
+        && depositor_record.was_active_at(crank_state.last_distribution_timestamp)
+}
+
+/// Marks the investor paid for the round, counting the payout toward reconciliation if they
+/// were in the day's snapshot; depositors who joined after the snapshot are not counted
+fn record_snapshot_payout(crank_state: &mut CrankState, depositor_record: &mut DepositorRecord) -> Result<()> {
+    let counted = counts_toward_snapshot(crank_state, depositor_record);
+    
+    depositor_record.last_paid_day = crank_state.current_day;
+    depositor_record.last_paid_round = crank_state.current_round;
+    
+    if !counted {
+        return Ok(());
+    }
+    crank_state.record_snapshot_payout(&depositor_member_hash(&depositor_record.investor))
+}
+
+/// Pays a fully-vested investor their share of the day's vested pool
+fn distribute_vested_share(ctx: Context<DistributeToInvestor>) -> Result<()> {
+    let depositor_record = &ctx.accounts.depositor_record;
+    let vault_stats = &ctx.accounts.vault_stats;
+    let config = &ctx.accounts.distribution_config;
+    let crank_state = &mut ctx.accounts.crank_state;
+    
+    // Locked balance is zero, so weight by historical deposits instead
+    let (payout, dust) = calculate_investor_payout(
+        depositor_record.total_usdc_deposited,
+        vault_stats.total_usdc_deposited,
+        crank_state.vested_pool,
+        config.min_payout(crank_state.vested_pool, crank_state.expected_investors),
+    )?;
+    
+    // The vested share is gated like a regular payout; an unpaid share stays with the creator remainder
+    let payout = if is_eligible(config, crank_state, depositor_record) {
+        payout
+    } else {
+        msg!("Investor not eligible (first eligible day {}, proven {})",
+            depositor_record.first_eligible_day, depositor_record.eligible);
+        0
+    };
+    
+    msg!("Vested investor payout: {} units", payout);
+    
+    if payout > 0 {
+        let new_total = crank_state.vested_distributed_today
+            .checked_add(payout)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        require!(
+            new_total <= crank_state.vested_pool,
+            ErrorCode::VestedPoolExhausted
+        );
+        require_within_daily_cap(config, crank_state, payout)?;
+        require_undelegated_payout_account(config, &ctx.accounts.investor_quote_account)?;
+        
+        anchor_spl::token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::Transfer {
+                    from: ctx.accounts.program_quote_vault.to_account_info(),
+                    to: ctx.accounts.investor_quote_account.to_account_info(),
+                    authority: ctx.accounts.fee_collector.to_account_info(),
+                },
+                &[&[
+                    FEE_COLLECTOR_SEED,
+                    &[ctx.bumps.fee_collector]
+                ]]
+            ),
+            payout,
+        )?;
+        
+        crank_state.vested_distributed_today = new_total;
+        msg!("Total distributed to vested investors today: {} units", new_total);
+    }
+    
+    crank_state.investors_paid_today = crank_state.investors_paid_today
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    
+    if config.emit_events {
+        emit!(crate::events::VestedInvestorPayout {
             day: crank_state.current_day,
             investor: ctx.accounts.investor.key(),
-            investor_locked_balance: investor_balance,
-            total_locked,
-            weight_bps,
-            total_investor_fee: params.total_investor_fee,
-            calculated_payout: payout + dust,
+            investor_total_deposited: depositor_record.total_usdc_deposited,
+            total_deposited: vault_stats.total_usdc_deposited,
+            vested_investor_share_bps: config.vested_investor_share_bps,
+            vested_pool: crank_state.vested_pool,
             actual_payout: payout,
             dust,
-            min_payout: config.min_payout_lamports,
             investor_quote_account: ctx.accounts.investor_quote_account.key(),
             timestamp: Clock::get()?.unix_timestamp,
         });
+    }
+    
+    record_snapshot_payout(crank_state, &mut ctx.accounts.depositor_record)
+}
+
+/// Close day and route remainder according to the remainder policy
+#[derive(Accounts)]
+pub struct RouteCreatorRemainder<'info> {
+    /// Account paying for the closed day's record
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Program authority (our program)
+    #[account(
+        mut,
+        seeds = [FEE_COLLECTOR_SEED],
+        bump
+    )]
+    pub fee_collector: UncheckedAccount<'info>,
+    
+    /// Program's quote token vault for fee collection
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, quote_mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = fee_collector,
+        constraint = program_quote_vault.key() == crank_state.quote_vault @ ErrorCode::QuoteVaultMismatch
+    )]
+    pub program_quote_vault: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Quote token mint (validated against config)
+    #[account(
+        mut,
+        constraint = quote_mint.key() == distribution_config.quote_mint @ ErrorCode::InvalidPosition
+    )]
+    pub quote_mint: UncheckedAccount<'info>,
+    
+    /// Remainder recipient's quote token account (not needed when investors receive the remainder,
+    /// owner checked after adopting a pending creator wallet). Holds `creator_payout_mint` instead
+    /// when the creator remainder is swapped
+    #[account(mut)]
+    pub creator_quote_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// Quote account of the day's largest-weight paid investor (required when carry-over above
+    /// `max_carry_over` is paid out under the top-investor cap policy)
+    #[account(mut)]
+    pub top_investor_quote_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// Dust recipient's quote token account (required when closing the day with carry-over dust
+    /// under a configured `dust_recipient`, owner checked on routing)
+    #[account(mut)]
+    pub dust_recipient_quote_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// Distribution configuration (mutable to adopt a pending creator wallet)
+    #[account(
+        mut,
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+    
+    /// Crank state for tracking
+    #[account(
+        mut,
+        seeds = [CRANK_STATE_SEED],
+        bump = crank_state.bump
+    )]
+    pub crank_state: Account<'info, CrankState>,
+    
+    /// Global vault statistics (to tell an unpaged day from one with no depositors, and
+    /// for the lifetime totals)
+    #[account(
+        mut,
+        seeds = [DEPOSIT_VAULT_SEED, b"stats"],
+        bump = vault_stats.bump
+    )]
+    pub vault_stats: Account<'info, VaultStats>,
+    
+    /// Day record for the day being closed (created here, so closing a day twice fails)
+    #[account(
+        init,
+        payer = payer,
+        space = DayRecord::DISCRIMINATOR.len() + DayRecord::INIT_SPACE,
+        seeds = [DAY_RECORD_SEED, crank_state.current_day.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub day_record: Box<Account<'info, DayRecord>>,
+    
+    /// CHECK: Token program
+    pub token_program: Program<'info, Token>,
+    
+    /// System program
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: DAMM v2 program (only needed when the creator remainder is swapped)
+    #[account(address = damm_v2::ID)]
+    pub amm_program: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: DAMM v2 pool trading quote_mint against creator_payout_mint (mints and vaults validated)
+    #[account(mut)]
+    pub pool: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool authority (validated by DAMM v2)
+    pub pool_authority: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool's token A vault (validated against the pool)
+    #[account(mut)]
+    pub pool_token_a_vault: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool's token B vault (validated against the pool)
+    #[account(mut)]
+    pub pool_token_b_vault: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool's token A mint (validated against the pool)
+    pub pool_token_a_mint: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool's token B mint (validated against the pool)
+    pub pool_token_b_mint: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: DAMM v2 event authority (validated by DAMM v2)
+    pub event_authority: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Configured day close callback program (notification skipped when not passed)
+    #[account(
+        executable,
+        constraint = callback_program.key() == distribution_config.callback_program @ ErrorCode::CallbackProgramMismatch
+    )]
+    pub callback_program: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RouteCreatorRemainderParams {
+    /// Minimum payout mint amount the creator must receive when the remainder is swapped
+    /// (ignored when the remainder is paid in quote)
+    pub min_out: u64,
+}
+
+/// DAMM v2 accounts and slippage guard used to swap the creator remainder
+/// into `creator_payout_mint` before it is transferred
+pub struct CreatorPayoutSwap<'a, 'info> {
+    pub payout_mint: Pubkey,
+    pub min_out: u64,
+    pub amm_program: &'a AccountInfo<'info>,
+    pub pool: &'a AccountInfo<'info>,
+    pub pool_authority: &'a AccountInfo<'info>,
+    pub token_a_vault: &'a AccountInfo<'info>,
+    pub token_b_vault: &'a AccountInfo<'info>,
+    pub token_a_mint: &'a AccountInfo<'info>,
+    pub token_b_mint: &'a AccountInfo<'info>,
+    pub event_authority: &'a AccountInfo<'info>,
+}
+
+impl<'info> RouteCreatorRemainder<'info> {
+    pub fn handle(ctx: Context<RouteCreatorRemainder>, params: RouteCreatorRemainderParams) -> Result<()> {
+        msg!("Routing creator remainder and closing day");
+        
+        // Ensure day is in progress
+        require!(
+            ctx.accounts.crank_state.is_day_in_progress(),
+            ErrorCode::DistributionNotStarted
+        );
+        
+        // Closing before any page would route the investors' share to the remainder recipient
+        require!(
+            ctx.accounts.crank_state.has_processed_pages()
+                || ctx.accounts.vault_stats.depositor_count == 0,
+            ErrorCode::NoPagesProcessed
+        );
+        
+        // Adopting a pending creator wallet never changes the payout mint, so the swap can be
+        // resolved before the remainder is routed
+        let quote_mint = ctx.accounts.distribution_config.quote_mint;
+        let payout_swap = match ctx.accounts.distribution_config.creator_payout_swap_mint() {
+            Some(payout_mint) => {
+                let (
+                    Some(amm_program),
+                    Some(pool),
+                    Some(pool_authority),
+                    Some(token_a_vault),
+                    Some(token_b_vault),
+                    Some(token_a_mint),
+                    Some(token_b_mint),
+                    Some(event_authority),
+                ) = (
+                    ctx.accounts.amm_program.as_ref(),
+                    ctx.accounts.pool.as_ref(),
+                    ctx.accounts.pool_authority.as_ref(),
+                    ctx.accounts.pool_token_a_vault.as_ref(),
+                    ctx.accounts.pool_token_b_vault.as_ref(),
+                    ctx.accounts.pool_token_a_mint.as_ref(),
+                    ctx.accounts.pool_token_b_mint.as_ref(),
+                    ctx.accounts.event_authority.as_ref(),
+                ) else {
+                    return err!(ErrorCode::CreatorPayoutSwapAccountsRequired);
+                };
+                
+                let payout_swap = CreatorPayoutSwap {
+                    payout_mint,
+                    min_out: params.min_out,
+                    amm_program: amm_program.as_ref(),
+                    pool: pool.as_ref(),
+                    pool_authority: pool_authority.as_ref(),
+                    token_a_vault: token_a_vault.as_ref(),
+                    token_b_vault: token_b_vault.as_ref(),
+                    token_a_mint: token_a_mint.as_ref(),
+                    token_b_mint: token_b_mint.as_ref(),
+                    event_authority: event_authority.as_ref(),
+                };
+                validate_creator_payout_swap(&payout_swap, quote_mint)?;
+                
+                Some(payout_swap)
+            }
+            None => None,
+        };
+        
+        let vault_before_close = ctx.accounts.program_quote_vault.amount;
+        route_remainder_and_close_day(
+            &mut ctx.accounts.crank_state,
+            &mut ctx.accounts.distribution_config,
+            &mut ctx.accounts.vault_stats,
+            &ctx.accounts.program_quote_vault,
+            ctx.accounts.creator_quote_account.as_deref(),
+            ctx.accounts.top_investor_quote_account.as_deref(),
+            ctx.accounts.dust_recipient_quote_account.as_deref(),
+            &ctx.accounts.fee_collector.to_account_info(),
+            ctx.bumps.fee_collector,
+            &ctx.accounts.token_program.to_account_info(),
+            payout_swap,
+            false,
+        )?;
+        
+        // The payout root is filled in later by submit_payout_root
+        let day_record = &mut ctx.accounts.day_record;
+        day_record.day = ctx.accounts.crank_state.current_day;
+        day_record.bump = ctx.bumps.day_record;
+        
+        msg!("Day record created for day {}", day_record.day);
+        
+        // Best-effort notification: skipped when no callback is configured or passed in
+        let callback_program = ctx.accounts.distribution_config.callback_program;
+        if callback_program == Pubkey::default() {
+            return Ok(());
+        }
+        let Some(callback_account) = ctx.accounts.callback_program.as_ref() else {
+            msg!("Day close callback {} not passed, skipping notification", callback_program);
+            return Ok(());
+        };
+        
+        ctx.accounts.program_quote_vault.reload()?;
+        let crank_state = &ctx.accounts.crank_state;
+        let total_distributed_to_investors = crank_state.daily_distributed
+            .checked_add(crank_state.vested_distributed_today)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let quote_routed_at_close = vault_before_close.saturating_sub(ctx.accounts.program_quote_vault.amount);
+        let data = day_closed_callback_data(
+            crank_state.current_day,
+            total_distributed_to_investors,
+            quote_routed_at_close,
+            crank_state.investors_processed_today,
+        );
+        
+        // The callback gets read-only views of the config and crank state to verify the caller
+        let instruction = anchor_lang::solana_program::instruction::Instruction {
+            program_id: callback_program,
+            accounts: vec![
+                AccountMeta::new_readonly(ctx.accounts.distribution_config.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.crank_state.key(), false),
+            ],
+            data,
+        };
+        anchor_lang::solana_program::program::invoke(
+            &instruction,
+            &[
+                ctx.accounts.distribution_config.to_account_info(),
+                ctx.accounts.crank_state.to_account_info(),
+                callback_account.to_account_info(),
+            ],
+        )?;
+        
+        msg!("Notified day close callback {}", callback_program);
+        
+        emit!(crate::events::DayCloseCallbackInvoked {
+            day: ctx.accounts.crank_state.current_day,
+            callback_program,
+            total_distributed_to_investors,
+            quote_routed_at_close,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
         
         Ok(())
     }
 }
 
-/// Close day and route remainder to creator
+/// Instruction data for the day close callback (layout in `DAY_CLOSED_CALLBACK_DISCRIMINATOR`)
+pub fn day_closed_callback_data(
+    day: u32,
+    total_distributed_to_investors: u64,
+    quote_routed_at_close: u64,
+    investors_processed: u32,
+) -> Vec<u8> {
+    let mut data = Vec::with_capacity(32);
+    data.extend_from_slice(&DAY_CLOSED_CALLBACK_DISCRIMINATOR);
+    data.extend_from_slice(&day.to_le_bytes());
+    data.extend_from_slice(&total_distributed_to_investors.to_le_bytes());
+    data.extend_from_slice(&quote_routed_at_close.to_le_bytes());
+    data.extend_from_slice(&investors_processed.to_le_bytes());
+    data
+}
+
+/// Checks the slippage guard and that the swap pool trades the quote mint against the payout mint
+/// through the vaults and mints passed in
+fn validate_creator_payout_swap(payout_swap: &CreatorPayoutSwap, quote_mint: Pubkey) -> Result<()> {
+    require!(payout_swap.min_out > 0, ErrorCode::CreatorPayoutMinOutRequired);
+    
+    let pool = read_zero_copy::<damm_v2::accounts::Pool>(payout_swap.pool)?;
+    let pool_mints = (pool.token_a_mint, pool.token_b_mint);
+    require!(
+        pool_mints == (quote_mint, payout_swap.payout_mint)
+            || pool_mints == (payout_swap.payout_mint, quote_mint),
+        ErrorCode::InvalidPosition
+    );
+    require!(
+        payout_swap.token_a_mint.key() == pool.token_a_mint
+            && payout_swap.token_b_mint.key() == pool.token_b_mint
+            && payout_swap.token_a_vault.key() == pool.token_a_vault
+            && payout_swap.token_b_vault.key() == pool.token_b_vault,
+        ErrorCode::InvalidPosition
+    );
+    
+    Ok(())
+}
+
+/// Close a day whose keeper never finished it (permissionless once the day's maximum
+/// duration has elapsed), routing the remainder according to the remainder policy
 #[derive(Accounts)]
-pub struct RouteCreatorRemainder<'info> {
+pub struct ForceCloseDay<'info> {
+    /// Account paying for the closed day's record
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
     /// CHECK: Program authority (our program)
     #[account(
         mut,
@@ -453,23 +1823,177 @@ pub struct RouteCreatorRemainder<'info> {
         seeds = [FEE_VAULT_SEED, quote_mint.key().as_ref()],
         bump,
         token::mint = quote_mint,
-        token::authority = fee_collector
+        token::authority = fee_collector,
+        constraint = program_quote_vault.key() == crank_state.quote_vault @ ErrorCode::QuoteVaultMismatch
     )]
     pub program_quote_vault: Box<Account<'info, TokenAccount>>,
     
-    /// CHECK: Quote token mint
+    /// CHECK: Quote token mint (validated against config)
+    #[account(
+        mut,
+        constraint = quote_mint.key() == distribution_config.quote_mint @ ErrorCode::InvalidPosition
+    )]
+    pub quote_mint: UncheckedAccount<'info>,
+    
+    /// Remainder recipient's quote token account (not needed when investors receive the remainder,
+    /// owner checked after adopting a pending creator wallet)
+    #[account(mut)]
+    pub creator_quote_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// Quote account of the day's largest-weight paid investor (required when carry-over above
+    /// `max_carry_over` is paid out under the top-investor cap policy)
     #[account(mut)]
+    pub top_investor_quote_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// Dust recipient's quote token account (required when closing the day with carry-over dust
+    /// under a configured `dust_recipient`, owner checked on routing)
+    #[account(mut)]
+    pub dust_recipient_quote_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    /// Distribution configuration (mutable to adopt a pending creator wallet)
+    #[account(
+        mut,
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+    
+    /// Crank state for tracking
+    #[account(
+        mut,
+        seeds = [CRANK_STATE_SEED],
+        bump = crank_state.bump
+    )]
+    pub crank_state: Account<'info, CrankState>,
+    
+    /// Global vault statistics for the lifetime totals
+    #[account(
+        mut,
+        seeds = [DEPOSIT_VAULT_SEED, b"stats"],
+        bump = vault_stats.bump
+    )]
+    pub vault_stats: Account<'info, VaultStats>,
+    
+    /// Day record for the day being closed (created here, so closing a day twice fails)
+    #[account(
+        init,
+        payer = payer,
+        space = DayRecord::DISCRIMINATOR.len() + DayRecord::INIT_SPACE,
+        seeds = [DAY_RECORD_SEED, crank_state.current_day.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub day_record: Box<Account<'info, DayRecord>>,
+    
+    /// CHECK: Token program
+    pub token_program: Program<'info, Token>,
+    
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ForceCloseDay<'info> {
+    pub fn handle(ctx: Context<ForceCloseDay>) -> Result<()> {
+        msg!("Force-closing expired distribution day");
+        
+        require!(
+            ctx.accounts.crank_state.is_day_in_progress(),
+            ErrorCode::DistributionNotStarted
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        let max_duration_seconds = ctx.accounts.distribution_config.day_max_duration_seconds;
+        require!(
+            ctx.accounts.crank_state.is_day_expired(now, max_duration_seconds),
+            ErrorCode::DayNotExpired
+        );
+        
+        let crank_state = &ctx.accounts.crank_state;
+        let day_started_at = crank_state.last_distribution_timestamp;
+        let pages_processed = crank_state.pagination_cursor;
+        let investors_processed = crank_state.investors_processed_today;
+        let investors_paid = crank_state.investors_paid_today;
+        
+        msg!("Day started at {}, {} of {} investors paid", day_started_at, investors_paid, investors_processed);
+        
+        route_remainder_and_close_day(
+            &mut ctx.accounts.crank_state,
+            &mut ctx.accounts.distribution_config,
+            &mut ctx.accounts.vault_stats,
+            &ctx.accounts.program_quote_vault,
+            ctx.accounts.creator_quote_account.as_deref(),
+            ctx.accounts.top_investor_quote_account.as_deref(),
+            ctx.accounts.dust_recipient_quote_account.as_deref(),
+            &ctx.accounts.fee_collector.to_account_info(),
+            ctx.bumps.fee_collector,
+            &ctx.accounts.token_program.to_account_info(),
+            None,
+            true,
+        )?;
+        
+        // The payout root is filled in later by submit_payout_root
+        let day_record = &mut ctx.accounts.day_record;
+        day_record.day = ctx.accounts.crank_state.current_day;
+        day_record.bump = ctx.bumps.day_record;
+        
+        msg!("Day record created for day {}", day_record.day);
+        
+        emit!(crate::events::DayForceClosed {
+            day: ctx.accounts.crank_state.current_day,
+            day_started_at,
+            deadline: day_started_at.saturating_add(max_duration_seconds),
+            pages_processed,
+            investors_processed,
+            investors_paid,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+/// Claim the creator's non-reserved quote balance before the day closes
+#[derive(Accounts)]
+pub struct CreatorPartialClaim<'info> {
+    /// Creator wallet from the config
+    #[account(
+        constraint = creator.key() == distribution_config.creator_wallet @ ErrorCode::CreatorQuoteAccountMismatch
+    )]
+    pub creator: Signer<'info>,
+    
+    /// CHECK: Program authority (our program)
+    #[account(
+        seeds = [FEE_COLLECTOR_SEED],
+        bump
+    )]
+    pub fee_collector: UncheckedAccount<'info>,
+    
+    /// Program's quote token vault for fee collection
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, quote_mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = fee_collector,
+        constraint = program_quote_vault.key() == crank_state.quote_vault @ ErrorCode::QuoteVaultMismatch
+    )]
+    pub program_quote_vault: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Quote token mint (validated against config)
+    #[account(
+        constraint = quote_mint.key() == distribution_config.quote_mint @ ErrorCode::InvalidPosition
+    )]
     pub quote_mint: UncheckedAccount<'info>,
     
     /// Creator's quote token account
     #[account(
         mut,
-        constraint = creator_quote_account.owner == distribution_config.creator_wallet
+        constraint = creator_quote_account.owner == distribution_config.creator_wallet @ ErrorCode::CreatorQuoteAccountMismatch
     )]
     pub creator_quote_account: Box<Account<'info, TokenAccount>>,
     
-    /// Distribution configuration
+    /// Distribution configuration (mutable to record the creator payout)
     #[account(
+        mut,
         seeds = [DISTRIBUTION_CONFIG_SEED],
         bump = distribution_config.bump
     )]
@@ -487,65 +2011,744 @@ pub struct RouteCreatorRemainder<'info> {
     pub token_program: Program<'info, Token>,
 }
 
-impl<'info> RouteCreatorRemainder<'info> {
-    pub fn handle(ctx: Context<RouteCreatorRemainder>) -> Result<()> {
-        msg!("Routing creator remainder and closing day");
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CreatorPartialClaimParams {
+    /// Maximum amount to claim (capped at the non-reserved balance)
+    pub max_amount: u64,
+}
+
+impl<'info> CreatorPartialClaim<'info> {
+    pub fn handle(ctx: Context<CreatorPartialClaim>, params: CreatorPartialClaimParams) -> Result<()> {
+        msg!("Processing creator partial claim");
         
         let crank_state = &mut ctx.accounts.crank_state;
         
-        // Ensure day is in progress
+        // The creator only owns the remainder under the creator policy
+        require!(
+            ctx.accounts.distribution_config.remainder_policy == REMAINDER_POLICY_CREATOR,
+            ErrorCode::InvalidRemainderPolicy
+        );
+        
+        // Investor allocation is only known once the day has started
         require!(
             crank_state.is_day_in_progress(),
             ErrorCode::DistributionNotStarted
         );
         
-        // Get remaining balance (this is the creator's remainder)
-        let remainder = ctx.accounts.program_quote_vault.amount;
+        // Only the balance not owed to investors or buffered for later days can be claimed
+        let reserved_for_investors = crank_state.reserved_for_investors();
+        let claimable = ctx.accounts.program_quote_vault.amount
+            .saturating_sub(reserved_for_investors)
+            .saturating_sub(crank_state.buffered_quote);
+        let amount = std::cmp::min(params.max_amount, claimable);
+        
+        msg!("Vault balance: {} units", ctx.accounts.program_quote_vault.amount);
+        msg!("Reserved for investors: {} units", reserved_for_investors);
+        msg!("Claimable: {} units", claimable);
         
-        msg!("Creator remainder: {} units", remainder);
-        msg!("Carry-over dust: {} units", crank_state.carry_over);
+        require!(amount > 0, ErrorCode::InsufficientBalance);
         
-        if remainder > 0 {
-            // Transfer remainder to creator
+        anchor_spl::token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::Transfer {
+                    from: ctx.accounts.program_quote_vault.to_account_info(),
+                    to: ctx.accounts.creator_quote_account.to_account_info(),
+                    authority: ctx.accounts.fee_collector.to_account_info(),
+                },
+                &[&[
+                    FEE_COLLECTOR_SEED,
+                    &[ctx.bumps.fee_collector]
+                ]]
+            ),
+            amount,
+        )?;
+        
+        crank_state.creator_claimed_today = crank_state.creator_claimed_today
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        // Earlier days' unrouted remainder is claimed first
+        crank_state.unrouted_creator_remainder = crank_state.unrouted_creator_remainder.saturating_sub(amount);
+        
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.distribution_config.last_creator_payout_ts = now;
+        
+        msg!("Creator claimed {} units (total today: {})", amount, crank_state.creator_claimed_today);
+        
+        emit!(crate::events::CreatorPartialClaimed {
+            day: crank_state.current_day,
+            creator_wallet: ctx.accounts.creator.key(),
+            creator_quote_account: ctx.accounts.creator_quote_account.key(),
+            amount,
+            reserved_for_investors,
+            creator_claimed_today: crank_state.creator_claimed_today,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
+
+/// Transfers the remaining quote balance to the creator and closes the day. A remainder that
+/// cannot reach the recipient (frozen account, no account with `defer_without_recipient`, or a
+/// payout mint to swap into without `payout_swap`) stays in the vault as
+/// `unrouted_creator_remainder` instead of blocking the close
+#[allow(clippy::too_many_arguments)]
+fn route_remainder_and_close_day<'info>(
+    crank_state: &mut Account<'info, CrankState>,
+    distribution_config: &mut Account<'info, DistributionConfig>,
+    vault_stats: &mut Account<'info, VaultStats>,
+    program_quote_vault: &Account<'info, TokenAccount>,
+    creator_quote_account: Option<&Account<'info, TokenAccount>>,
+    top_investor_quote_account: Option<&Account<'info, TokenAccount>>,
+    dust_recipient_quote_account: Option<&Account<'info, TokenAccount>>,
+    fee_collector: &AccountInfo<'info>,
+    fee_collector_bump: u8,
+    token_program: &AccountInfo<'info>,
+    payout_swap: Option<CreatorPayoutSwap<'_, 'info>>,
+    defer_without_recipient: bool,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    
+    // Adopt a scheduled creator wallet only once its timelock has elapsed
+    let previous_creator_wallet = distribution_config.creator_wallet;
+    if distribution_config.apply_pending_creator_wallet(now) {
+        msg!("Creator wallet updated: {} -> {}", previous_creator_wallet, distribution_config.creator_wallet);
+        
+        emit!(crate::events::CreatorWalletUpdated {
+            previous_creator_wallet,
+            new_creator_wallet: distribution_config.creator_wallet,
+            timestamp: now,
+        });
+    }
+    
+    let remainder_policy = distribution_config.remainder_policy;
+    msg!("Remainder policy: {}", remainder_policy);
+    
+    // Retain carry-over dust for future investor payouts and the buffered quote for later
+    // days' claims; the rest is the remainder
+    let unreserved = program_quote_vault.amount
+        .saturating_sub(crank_state.carry_over)
+        .saturating_sub(crank_state.buffered_quote);
+    
+    // A creator who has gone too long without a payout forfeits the remainder to investors
+    let creator_inactive = distribution_config.is_creator_inactive(now);
+    if creator_inactive {
+        msg!("Creator inactive since {}, routing {} units to investors", distribution_config.last_creator_payout_ts, unreserved);
+        
+        emit!(crate::events::CreatorInactivityTriggered {
+            day: crank_state.current_day,
+            creator_wallet: distribution_config.creator_wallet,
+            last_creator_payout_ts: distribution_config.last_creator_payout_ts,
+            creator_inactivity_seconds: distribution_config.creator_inactivity_seconds,
+            remainder_to_investors: unreserved,
+            timestamp: now,
+        });
+    }
+    
+    // A configured dust recipient takes the day's carry-over dust, so it is neither retained for
+    // later days nor bundled into the remainder through the carry-over cap
+    if let Some(dust_recipient) = distribution_config.dust_recipient().filter(|_| crank_state.carry_over > 0) {
+        match dust_recipient_quote_account {
+            Some(dust_recipient_quote_account) => {
+                require!(
+                    dust_recipient_quote_account.owner == dust_recipient,
+                    ErrorCode::DustRecipientQuoteAccountMismatch
+                );
+                
+                let dust = crank_state.carry_over;
+                anchor_spl::token::transfer(
+                    CpiContext::new_with_signer(
+                        token_program.clone(),
+                        anchor_spl::token::Transfer {
+                            from: program_quote_vault.to_account_info(),
+                            to: dust_recipient_quote_account.to_account_info(),
+                            authority: fee_collector.clone(),
+                        },
+                        &[&[
+                            FEE_COLLECTOR_SEED,
+                            &[fee_collector_bump]
+                        ]]
+                    ),
+                    dust,
+                )?;
+                crank_state.carry_over = 0;
+                
+                msg!("Carry-over dust of {} units sent to {}", dust, dust_recipient);
+                
+                emit!(crate::events::DustRouted {
+                    day: crank_state.current_day,
+                    dust_recipient,
+                    dust_recipient_quote_account: dust_recipient_quote_account.key(),
+                    amount: dust,
+                    timestamp: now,
+                });
+            }
+            None if defer_without_recipient => {
+                msg!("Dust recipient quote account not passed, {} units of dust retained", crank_state.carry_over);
+            }
+            None => return err!(ErrorCode::DustRecipientQuoteAccountRequired),
+        }
+    }
+    
+    // Only route_creator_remainder carries the swap into the creator's payout mint, so the
+    // other close paths leave the remainder unrouted for it rather than paying quote
+    let awaits_payout_swap = payout_swap.is_none() && distribution_config.creator_payout_swap_mint().is_some();
+    
+    let remainder_recipient = distribution_config.remainder_recipient().filter(|_| !creator_inactive);
+    let mut remainder_deferred = false;
+    let (mut recipient_wallet, mut recipient_quote_account, mut remainder) = match remainder_recipient {
+        Some(recipient_wallet) => {
+            let recipient_quote_account = match creator_quote_account {
+                _ if awaits_payout_swap => None,
+                Some(account) => {
+                    require!(
+                        account.owner == recipient_wallet,
+                        ErrorCode::CreatorQuoteAccountMismatch
+                    );
+                    Some(account).filter(|account| !account.is_frozen())
+                }
+                None if defer_without_recipient => None,
+                None => return err!(ErrorCode::CreatorQuoteAccountRequired),
+            };
+            remainder_deferred = recipient_quote_account.is_none();
+            
+            (recipient_wallet, recipient_quote_account, unreserved)
+        }
+        None => {
+            // Investors were allocated the whole claim; whatever is left unpaid stays in the
+            // vault as carry-over for the next day's investor payouts
+            crank_state.carry_over = crank_state.carry_over
+                .checked_add(unreserved)
+                .ok_or(ErrorCode::MathOverflow)?;
+            
+            (Pubkey::default(), None, 0)
+        }
+    };
+    
+    // Keep the retained dust bounded; whatever exceeds the cap leaves the vault now
+    let carry_over_excess = distribution_config.carry_over_excess(crank_state.carry_over);
+    let mut carry_over_to_investor = 0;
+    if carry_over_excess > 0 {
+        let cap_policy = distribution_config.carry_over_cap_policy;
+        let destination = if cap_policy == CARRY_OVER_CAP_POLICY_TOP_INVESTOR {
+            let top_investor_quote_account = top_investor_quote_account
+                .filter(|account| {
+                    crank_state.day_top_investor_quote_account != Pubkey::default()
+                        && account.key() == crank_state.day_top_investor_quote_account
+                })
+                .ok_or(ErrorCode::TopInvestorQuoteAccountMismatch)?;
+            require_undelegated_payout_account(distribution_config, top_investor_quote_account)?;
+            
             anchor_spl::token::transfer(
                 CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
+                    token_program.clone(),
                     anchor_spl::token::Transfer {
-                        from: ctx.accounts.program_quote_vault.to_account_info(),
-                        to: ctx.accounts.creator_quote_account.to_account_info(),
-                        authority: ctx.accounts.fee_collector.to_account_info(),
+                        from: program_quote_vault.to_account_info(),
+                        to: top_investor_quote_account.to_account_info(),
+                        authority: fee_collector.clone(),
                     },
                     &[&[
                         FEE_COLLECTOR_SEED,
-                        &[ctx.bumps.fee_collector]
+                        &[fee_collector_bump]
                     ]]
                 ),
-                remainder,
+                carry_over_excess,
             )?;
+            carry_over_to_investor = carry_over_excess;
             
-            msg!("Distributed {} quote tokens to creator", remainder);
-        }
+            top_investor_quote_account.key()
+        } else {
+            // Under the investor policy there is no remainder recipient, so the creator takes it
+            if recipient_quote_account.is_none() && !remainder_deferred {
+                let creator_account = creator_quote_account
+                    .ok_or(ErrorCode::CreatorQuoteAccountRequired)?;
+                require!(
+                    creator_account.owner == distribution_config.creator_wallet,
+                    ErrorCode::CreatorQuoteAccountMismatch
+                );
+                recipient_wallet = distribution_config.creator_wallet;
+                recipient_quote_account = Some(creator_account);
+            }
+            remainder = remainder
+                .checked_add(carry_over_excess)
+                .ok_or(ErrorCode::MathOverflow)?;
+            
+            recipient_quote_account.map_or(Pubkey::default(), |account| account.key())
+        };
         
-        // Close the day
-        crank_state.close_day()?;
+        crank_state.carry_over = crank_state.carry_over
+            .checked_sub(carry_over_excess)
+            .ok_or(ErrorCode::MathOverflow)?;
         
-        msg!("Day {} closed successfully", crank_state.current_day);
-        msg!("Total investors processed: {}", crank_state.investors_processed_today);
-        msg!("Total distributed to investors: {} units", crank_state.daily_distributed);
-        msg!("Creator received: {} units", remainder);
+        msg!("Carry-over above cap of {} units: {} units to {}", distribution_config.max_carry_over, carry_over_excess, destination);
         
-        // Emit event
-        emit!(crate::events::CreatorPayoutDayClosed {
+        emit!(crate::events::CarryOverCapped {
             day: crank_state.current_day,
-            creator_wallet: ctx.accounts.distribution_config.creator_wallet,
-            creator_quote_account: ctx.accounts.creator_quote_account.key(),
-            creator_remainder: remainder,
-            total_distributed_to_investors: crank_state.daily_distributed,
-            total_investors_processed: crank_state.investors_processed_today,
-            final_carry_over: crank_state.carry_over,
-            timestamp: Clock::get()?.unix_timestamp,
+            max_carry_over: distribution_config.max_carry_over,
+            excess: carry_over_excess,
+            cap_policy,
+            destination,
+            timestamp: now,
         });
+    }
+    
+    // The remainder stays in the vault until a later close can route it; it already
+    // includes whatever earlier days left unrouted
+    let previously_unrouted = crank_state.unrouted_creator_remainder;
+    crank_state.unrouted_creator_remainder = 0;
+    if remainder_deferred {
+        crank_state.unrouted_creator_remainder = remainder;
+        remainder = 0;
         
-        Ok(())
+        msg!("Remainder recipient unreachable, {} units left unrouted", crank_state.unrouted_creator_remainder);
+        
+        emit!(crate::events::CreatorRemainderDeferred {
+            day: crank_state.current_day,
+            recipient_wallet,
+            previously_unrouted,
+            unrouted_creator_remainder: crank_state.unrouted_creator_remainder,
+            timestamp: now,
+        });
+    } else if previously_unrouted > 0 {
+        msg!("Routing {} units left unrouted by earlier days", previously_unrouted);
+    }
+    
+    msg!("Remainder: {} units", remainder);
+    msg!("Carry-over dust retained in vault: {} units", crank_state.carry_over);
+    
+    if let (Some(recipient_quote_account), Some(payout_swap)) = (recipient_quote_account, payout_swap.as_ref()) {
+        require!(
+            recipient_quote_account.mint == payout_swap.payout_mint,
+            ErrorCode::CreatorQuoteAccountMismatch
+        );
+    }
+    
+    if let Some(payout_swap) = payout_swap.filter(|_| remainder > 0) {
+        // Swap the remainder into the payout mint straight into the creator's account
+        let recipient_account = recipient_quote_account.ok_or(ErrorCode::CreatorQuoteAccountRequired)?;
+        let balance_before = recipient_account.amount;
+        
+        damm_v2::cpi::swap(
+            CpiContext::new_with_signer(
+                payout_swap.amm_program.clone(),
+                damm_v2::cpi::accounts::Swap {
+                    pool_authority: payout_swap.pool_authority.clone(),
+                    pool: payout_swap.pool.clone(),
+                    input_token_account: program_quote_vault.to_account_info(),
+                    output_token_account: recipient_account.to_account_info(),
+                    token_a_vault: payout_swap.token_a_vault.clone(),
+                    token_b_vault: payout_swap.token_b_vault.clone(),
+                    token_a_mint: payout_swap.token_a_mint.clone(),
+                    token_b_mint: payout_swap.token_b_mint.clone(),
+                    payer: fee_collector.clone(),
+                    token_a_program: token_program.clone(),
+                    token_b_program: token_program.clone(),
+                    referral_token_account: None,
+                    event_authority: payout_swap.event_authority.clone(),
+                    program: payout_swap.amm_program.clone(),
+                },
+                &[&[
+                    FEE_COLLECTOR_SEED,
+                    &[fee_collector_bump]
+                ]]
+            ),
+            damm_v2::types::SwapParameters {
+                amount_in: remainder,
+                minimum_amount_out: payout_swap.min_out,
+            },
+        )?;
+        
+        // Enforce the guard on what actually arrived rather than trusting the pool's check alone
+        let balance_after = anchor_spl::token::accessor::amount(&recipient_account.to_account_info())?;
+        let amount_out = balance_after.saturating_sub(balance_before);
+        require!(amount_out >= payout_swap.min_out, ErrorCode::CreatorPayoutSlippageExceeded);
+        
+        msg!("Swapped {} quote units into {} units of {} for {}", remainder, amount_out, payout_swap.payout_mint, recipient_wallet);
+        
+        emit!(crate::events::CreatorRemainderSwapped {
+            day: crank_state.current_day,
+            pool: payout_swap.pool.key(),
+            payout_mint: payout_swap.payout_mint,
+            amount_in: remainder,
+            amount_out,
+            min_out: payout_swap.min_out,
+            timestamp: now,
+        });
+    } else if let Some(recipient_quote_account) = recipient_quote_account.filter(|_| remainder > 0) {
+        // Transfer remainder to the creator or treasury
+        anchor_spl::token::transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                anchor_spl::token::Transfer {
+                    from: program_quote_vault.to_account_info(),
+                    to: recipient_quote_account.to_account_info(),
+                    authority: fee_collector.clone(),
+                },
+                &[&[
+                    FEE_COLLECTOR_SEED,
+                    &[fee_collector_bump]
+                ]]
+            ),
+            remainder,
+        )?;
+        
+        msg!("Distributed {} quote tokens to {}", remainder, recipient_wallet);
+    }
+    
+    if remainder > 0 && recipient_wallet == distribution_config.creator_wallet {
+        distribution_config.last_creator_payout_ts = now;
+    }
+    
+    // Lifetime totals; a day closed before any page only claimed what it routed
+    let day_quote_claimed = if crank_state.has_processed_pages() {
+        crank_state.day_claimed_quote
+    } else {
+        unreserved
+    };
+    let day_to_investors = crank_state.daily_distributed
+        .checked_add(crank_state.vested_distributed_today)
+        .and_then(|total| total.checked_add(carry_over_to_investor))
+        .ok_or(ErrorCode::MathOverflow)?;
+    let day_to_creator = remainder
+        .checked_add(crank_state.creator_claimed_today)
+        .ok_or(ErrorCode::MathOverflow)?;
+    vault_stats.record_day_close(day_quote_claimed, day_to_investors, day_to_creator)?;
+    
+    // Close the day
+    crank_state.close_day()?;
+    
+    msg!("Day {} closed successfully", crank_state.current_day);
+    msg!("Total investors processed: {}", crank_state.investors_processed_today);
+    msg!("Total distributed to investors: {} units", crank_state.daily_distributed);
+    msg!("Remainder routed: {} units", remainder);
+    
+    // Emit event
+    emit!(crate::events::CreatorPayoutDayClosed {
+        day: crank_state.current_day,
+        creator_wallet: recipient_wallet,
+        creator_quote_account: recipient_quote_account.map_or(Pubkey::default(), |account| account.key()),
+        creator_remainder: remainder,
+        remainder_policy,
+        total_distributed_to_investors: crank_state.daily_distributed,
+        total_distributed_to_vested_investors: crank_state.vested_distributed_today,
+        creator_claimed_before_close: crank_state.creator_claimed_today,
+        total_investors_processed: crank_state.investors_processed_today,
+        final_carry_over: crank_state.carry_over,
+        unrouted_creator_remainder: crank_state.unrouted_creator_remainder,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::states::AgeBracket;
+    use crate::instructions::initialize_distribution_config::validate_age_brackets;
+
+    #[test]
+    fn day_closed_callback_data_follows_the_documented_layout() {
+        let data = day_closed_callback_data(7, 1_000, 250, 3);
+        assert_eq!(data.len(), 32);
+        let sighash = anchor_lang::solana_program::hash::hash(b"global:on_distribution_day_closed");
+        assert_eq!(data[..8], sighash.to_bytes()[..8]);
+        assert_eq!(data[8..12], 7u32.to_le_bytes());
+        assert_eq!(data[12..20], 1_000u64.to_le_bytes());
+        assert_eq!(data[20..28], 250u64.to_le_bytes());
+        assert_eq!(data[28..32], 3u32.to_le_bytes());
+    }
+
+    #[test]
+    fn f_locked_without_band_follows_locked_share() {
+        assert_eq!(calculate_f_locked_bps(0, 1_000, 0, 0).unwrap(), 0);
+        assert_eq!(calculate_f_locked_bps(250, 1_000, 0, 0).unwrap(), 2_500);
+        assert_eq!(calculate_f_locked_bps(5_000, 1_000, 0, 0).unwrap(), 10_000);
+        assert_eq!(calculate_f_locked_bps(1_000, 0, 0, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn ppm_share_pays_investors_the_fraction_a_bps_floor_drops() {
+        // 1/3 locked: 3333 bps floored, 333333 ppm
+        let config = DistributionConfig { y0_allocation: 3, ..share_config(5_000) };
+        assert_eq!(effective_eligible_share_bps(&config, 1).unwrap(), 3_333);
+        assert_eq!(effective_eligible_share_ppm(&config, 1).unwrap(), 333_333);
+        
+        let claimed_quote = 1_000_000_000;
+        let bps_floored = (claimed_quote as u128 * 3_333 / 10000) as u64;
+        let ppm = investor_fee_quote_from_ppm(claimed_quote, 333_333).unwrap();
+        assert_eq!(bps_floored, 333_300_000);
+        assert_eq!(ppm, 333_333_000);
+        assert!(ppm > bps_floored);
+        
+        // The clamps still apply in ppm
+        assert_eq!(calculate_f_locked_ppm(1, 3, 4_000, 0).unwrap(), 400_000);
+        assert_eq!(effective_eligible_share_ppm(&config, 3).unwrap(), 500_000);
+    }
+
+    #[test]
+    fn f_locked_is_clamped_at_band_boundaries() {
+        // Below, at and above the floor
+        assert_eq!(calculate_f_locked_bps(0, 1_000, 2_000, 8_000).unwrap(), 2_000);
+        assert_eq!(calculate_f_locked_bps(200, 1_000, 2_000, 8_000).unwrap(), 2_000);
+        assert_eq!(calculate_f_locked_bps(201, 1_000, 2_000, 8_000).unwrap(), 2_010);
+        // Below, at and above the ceiling
+        assert_eq!(calculate_f_locked_bps(799, 1_000, 2_000, 8_000).unwrap(), 7_990);
+        assert_eq!(calculate_f_locked_bps(800, 1_000, 2_000, 8_000).unwrap(), 8_000);
+        assert_eq!(calculate_f_locked_bps(1_000, 1_000, 2_000, 8_000).unwrap(), 8_000);
+        // A zero Y0 still gets the floor; a degenerate band pins the value
+        assert_eq!(calculate_f_locked_bps(1_000, 0, 2_000, 8_000).unwrap(), 2_000);
+        assert_eq!(calculate_f_locked_bps(900, 1_000, 5_000, 5_000).unwrap(), 5_000);
+    }
+
+    fn share_config(investor_fee_share_bps: u16) -> DistributionConfig {
+        DistributionConfig {
+            y0_allocation: 1_000,
+            investor_fee_share_bps,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn eligible_share_follows_f_locked_below_the_fee_share() {
+        let config = share_config(5_000);
+        assert_eq!(effective_eligible_share_bps(&config, 0).unwrap(), 0);
+        assert_eq!(effective_eligible_share_bps(&config, 300).unwrap(), 3_000);
+    }
+
+    #[test]
+    fn eligible_share_at_the_fee_share() {
+        let config = share_config(5_000);
+        assert_eq!(effective_eligible_share_bps(&config, 500).unwrap(), 5_000);
+    }
+
+    #[test]
+    fn eligible_share_is_capped_above_the_fee_share() {
+        let config = share_config(5_000);
+        assert_eq!(effective_eligible_share_bps(&config, 800).unwrap(), 5_000);
+        // f_locked itself is capped at 10000 when more than Y0 is locked
+        assert_eq!(effective_eligible_share_bps(&share_config(10_000), 5_000).unwrap(), 10_000);
+        // The band's floor applies before the fee share cap
+        let banded = DistributionConfig { f_locked_floor_bps: 4_000, ..share_config(5_000) };
+        assert_eq!(effective_eligible_share_bps(&banded, 100).unwrap(), 4_000);
+    }
+
+    #[test]
+    fn creator_floor_caps_a_high_f_locked() {
+        let config = DistributionConfig { creator_min_share_bps: 2_000, ..share_config(9_000) };
+        // Fully locked: f_locked is 10000 and the fee share 9000, but the creator keeps 2000
+        assert_eq!(effective_eligible_share_bps(&config, 1_000).unwrap(), 8_000);
+        // Below the cap the floor does not bind
+        assert_eq!(effective_eligible_share_bps(&config, 500).unwrap(), 5_000);
+    }
+
+    #[test]
+    fn fee_share_change_applies_once_its_cooloff_has_passed() {
+        let mut config = DistributionConfig { config_change_cooloff_seconds: 100, ..share_config(5_000) };
+        let cooloff_seconds = config.config_change_cooloff_seconds;
+        config.snapshot_fee_share(1_000);
+        config.investor_fee_share_bps = 8_000;
+        config.start_config_cooloff(1_000, cooloff_seconds).unwrap();
+
+        // Days begun during the cool-off keep the old share, later ones get the new one
+        assert_eq!(effective_eligible_share_bps(&config.effective_at(1_099), 1_000).unwrap(), 5_000);
+        assert_eq!(effective_eligible_share_bps(&config.effective_at(1_100), 1_000).unwrap(), 8_000);
+
+        // A second change during the cool-off keeps the share from before the first one
+        config.snapshot_fee_share(1_050);
+        config.investor_fee_share_bps = 9_000;
+        config.start_config_cooloff(1_050, cooloff_seconds).unwrap();
+        assert_eq!(effective_eligible_share_bps(&config.effective_at(1_149), 1_000).unwrap(), 5_000);
+        assert_eq!(effective_eligible_share_bps(&config.effective_at(1_150), 1_000).unwrap(), 9_000);
+    }
+
+    #[test]
+    fn creator_min_share_validation() {
+        assert!(validate_creator_min_share(0, 10_000, REMAINDER_POLICY_INVESTORS).is_ok());
+        assert!(validate_creator_min_share(2_000, 9_000, REMAINDER_POLICY_CREATOR).is_ok());
+        assert!(validate_creator_min_share(10_000, 0, REMAINDER_POLICY_CREATOR).is_ok());
+        assert!(validate_creator_min_share(10_000, 5_000, REMAINDER_POLICY_CREATOR).is_err());
+        assert!(validate_creator_min_share(10_001, 0, REMAINDER_POLICY_CREATOR).is_err());
+        assert!(validate_creator_min_share(2_000, 9_000, REMAINDER_POLICY_INVESTORS).is_err());
+    }
+
+    #[test]
+    fn remaining_lock_weight_favours_the_longer_lock() {
+        let year = 365 * 86400;
+        let short_lock = remaining_lock_weight(1_000_000, year).unwrap();
+        let long_lock = remaining_lock_weight(1_000_000, 3 * year).unwrap();
+        assert_eq!(short_lock, 250_000);
+        assert_eq!(long_lock, 750_000);
+        assert_eq!(
+            calculate_weight_bps(long_lock, 2_000_000).unwrap(),
+            3 * calculate_weight_bps(short_lock, 2_000_000).unwrap()
+        );
+        
+        // Locks past the horizon and fully unlocked streams
+        assert_eq!(remaining_lock_weight(1_000_000, 10 * year).unwrap(), 1_000_000);
+        assert_eq!(remaining_lock_weight(1_000_000, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn scaled_dust_threshold_drops_with_more_investors() {
+        let fixed = DistributionConfig { min_payout_lamports: 10_000, ..Default::default() };
+        assert_eq!(fixed.min_payout(1_000_000, 10), 10_000);
+        assert_eq!(fixed.min_payout(1_000_000, 1_000), 10_000);
+        
+        let scaled = DistributionConfig { min_payout_lamports: 10_000, dust_threshold_k: 4, ..Default::default() };
+        assert_eq!(scaled.min_payout(1_000_000, 10), 25_000);
+        assert_eq!(scaled.min_payout(1_000_000, 100), 2_500);
+        assert_eq!(scaled.min_payout(1_000_000, 1_000), 250);
+        assert!(scaled.min_payout(1_000_000, 1_000) < scaled.min_payout(1_000_000, 100));
+        
+        // An even share always clears the threshold once k >= 1
+        let even_share = 1_000_000 / 100;
+        assert!(even_share >= scaled.min_payout(1_000_000, 100));
+        
+        // No snapshotted investors divides by k alone
+        assert_eq!(scaled.min_payout(1_000_000, 0), 250_000);
+        assert_eq!(scaled.min_payout(0, 10), 0);
+    }
+
+    #[test]
+    fn remaining_daily_cap_is_zero_when_uncapped() {
+        assert_eq!(DistributionConfig::default().remaining_daily_cap(500), 0);
+        
+        let capped = DistributionConfig { daily_cap_lamports: 1_000, ..Default::default() };
+        assert_eq!(capped.remaining_daily_cap(0), 1_000);
+        assert_eq!(capped.remaining_daily_cap(400), 600);
+        assert_eq!(capped.remaining_daily_cap(1_000), 0);
+        
+        // The quote-unit cap takes precedence like it does for the cap check
+        let quote_capped = DistributionConfig { daily_cap_lamports: 1_000, daily_cap_quote_units: 300, ..Default::default() };
+        assert_eq!(quote_capped.remaining_daily_cap(100), 200);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn unrouted_remainder_is_not_freshly_claimed() {
+        let mut crank_state = CrankState::new(0);
+        crank_state.carry_over = 100;
+        crank_state.unrouted_creator_remainder = 500_000;
+        assert_eq!(crank_state.fresh_round_quote(1_500_100), 1_000_000);
+        // Nothing new arrived since the skipped routing
+        assert_eq!(crank_state.fresh_round_quote(500_100), 0);
+    }
+
+    #[test]
+    fn f_locked_band_validation() {
+        assert!(validate_f_locked_band(0, 0).is_ok());
+        assert!(validate_f_locked_band(10_000, 0).is_ok());
+        assert!(validate_f_locked_band(5_000, 5_000).is_ok());
+        assert!(validate_f_locked_band(0, 10_000).is_ok());
+        assert!(validate_f_locked_band(5_001, 5_000).is_err());
+        assert!(validate_f_locked_band(0, 10_001).is_err());
+        assert!(validate_f_locked_band(10_001, 0).is_err());
+        assert!(calculate_f_locked_bps(500, 1_000, 6_000, 5_000).is_err());
+    }
+
+    #[test]
+    fn total_locked_drift_within_tolerance() {
+        assert!(check_total_locked_drift(1_000, 1_000, 0).is_ok());
+        assert!(check_total_locked_drift(1_005, 1_000, 5).is_ok());
+        assert!(check_total_locked_drift(995, 1_000, 5).is_ok());
+        assert!(check_total_locked_drift(1_006, 1_000, 5).is_err());
+        assert!(check_total_locked_drift(994, 1_000, 5).is_err());
+        assert!(check_total_locked_drift(0, u64::MAX, u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn fixed_pages_hold_a_full_batch_until_the_final_page() {
+        assert!(is_valid_fixed_page(DISTRIBUTION_BATCH_SIZE, false));
+        assert!(!is_valid_fixed_page(DISTRIBUTION_BATCH_SIZE - 1, false));
+        assert!(!is_valid_fixed_page(DISTRIBUTION_BATCH_SIZE + 1, false));
+        assert!(is_valid_fixed_page(DISTRIBUTION_BATCH_SIZE, true));
+        assert!(is_valid_fixed_page(3, true));
+        assert!(is_valid_fixed_page(0, true));
+        assert!(!is_valid_fixed_page(DISTRIBUTION_BATCH_SIZE + 1, true));
+    }
+
+    #[test]
+    fn keeper_reward_is_only_earned_by_pages_covering_expected_investors() {
+        // 25 expected investors need pages 0, 1 and 2
+        assert!(earns_keeper_reward(0, DISTRIBUTION_BATCH_SIZE, 25));
+        assert!(earns_keeper_reward(2, 5, 25));
+        assert!(!earns_keeper_reward(3, 5, 25));
+        assert!(!earns_keeper_reward(1, 0, 25));
+        assert!(!earns_keeper_reward(0, 1, 0));
+    }
+
+    #[test]
+    fn keeper_reward_leaves_the_incentive_account_rent_exempt() {
+        assert!(can_pay_keeper_reward(1_000_000 + 5_000, 1_000_000, 5_000));
+        assert!(!can_pay_keeper_reward(1_000_000 + 4_999, 1_000_000, 5_000));
+        assert!(!can_pay_keeper_reward(999_999, 1_000_000, 1));
+    }
+
+    #[test]
+    fn age_brackets_pay_older_deposits_more_without_exceeding_the_pool() {
+        let config = DistributionConfig {
+            age_brackets: [
+                AgeBracket { min_age_seconds: 30 * 86_400, multiplier_bps: 15_000 },
+                AgeBracket::default(),
+                AgeBracket::default(),
+                AgeBracket::default(),
+            ],
+            ..Default::default()
+        };
+        let old_multiplier = config.age_multiplier_bps(31 * 86_400);
+        let new_multiplier = config.age_multiplier_bps(86_400);
+        assert_eq!((old_multiplier, new_multiplier), (15_000, 10_000));
+
+        // Two equal balances sharing a pool of 1_000_000
+        let max = config.max_age_multiplier_bps();
+        let (old_weight, old_withheld) = apply_age_multiplier(500, 1_000, 1_000_000, old_multiplier, max).unwrap();
+        let (new_weight, new_withheld) = apply_age_multiplier(500, 1_000, 1_000_000, new_multiplier, max).unwrap();
+        let (old_payout, _) = calculate_investor_payout(old_weight, 1_000, 1_000_000, 0).unwrap();
+        let (new_payout, _) = calculate_investor_payout(new_weight, 1_000, 1_000_000, 0).unwrap();
+
+        assert_eq!((old_payout, old_withheld), (500_000, 0));
+        assert_eq!(new_payout, 333_000);
+        assert_eq!(new_payout + new_withheld, 500_000);
+        assert!(old_payout + new_payout <= 1_000_000);
+
+        assert!(validate_age_brackets(&config.age_brackets).is_ok());
+        let mut unordered = config.age_brackets;
+        unordered[1] = AgeBracket { min_age_seconds: 86_400, multiplier_bps: 12_000 };
+        assert!(validate_age_brackets(&unordered).is_err());
+    }
+
+    #[test]
+    fn distribution_rate_releases_a_share_of_the_accumulated_quote_each_day() {
+        let config = DistributionConfig {
+            distribution_rate_bps: 5_000,
+            ..Default::default()
+        };
+
+        // 1_000_000 claimed on day 1 and nothing afterwards: half of what remains each day
+        let (day1, buffered) = smooth_round_quote(&config, 1_000_000, 0, true).unwrap();
+        let (day2, buffered) = smooth_round_quote(&config, 0, buffered, true).unwrap();
+        let (day3, buffered) = smooth_round_quote(&config, 0, buffered, true).unwrap();
+        assert_eq!((day1, day2, day3), (500_000, 250_000, 125_000));
+        assert_eq!(buffered, 125_000);
+
+        // Later rounds add to the buffer without releasing it
+        assert_eq!(smooth_round_quote(&config, 1_000, 125_000, false).unwrap(), (500, 125_500));
+
+        // A zero rate releases everything, including an earlier buffer
+        let unsmoothed = DistributionConfig::default();
+        assert_eq!(smooth_round_quote(&unsmoothed, 1_000, 125_000, true).unwrap(), (126_000, 0));
+    }
+
+    #[test]
+    fn fresh_claim_needs_a_claim_today_that_grew_the_vault() {
+        let mut crank_state = CrankState::new(255);
+        crank_state.current_day = 3;
+        crank_state.day_start_quote_balance = 1_000;
+
+        assert!(has_fresh_claim(Some(3), &crank_state, 1_500));
+        assert!(!has_fresh_claim(Some(2), &crank_state, 1_500));
+        assert!(!has_fresh_claim(Some(3), &crank_state, 1_000));
+        assert!(!has_fresh_claim(None, &crank_state, 1_500));
+    }
+}
@@ -1,8 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{TokenAccount, Token};
 use crate::errors::ErrorCode;
-use crate::constants::{FEE_COLLECTOR_SEED, DEPOSIT_VAULT_SEED, INVESTOR_RECORD_SEED};
-use crate::states::{DepositorRecord, VaultStats};
+use crate::constants::{
+    FEE_COLLECTOR_SEED, DEPOSIT_VAULT_SEED, INVESTOR_RECORD_SEED, CRANK_STATE_SEED,
+    DISTRIBUTION_CONFIG_SEED, DEFAULT_ELIGIBILITY_DELAY_DAYS,
+};
+use crate::utils::{depositor_member_hash, is_plain_system_account, load_if_initialized};
+use crate::states::{DepositorRecord, VaultStats, CrankState, DistributionConfig};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct DepositParams {
@@ -26,7 +30,8 @@ pub struct Deposit<'info> {
     #[account(
         mut,
         seeds = [DEPOSIT_VAULT_SEED, b"sol"],
-        bump
+        bump,
+        constraint = is_plain_system_account(&sol_vault) @ ErrorCode::SolVaultNotSystemAccount
     )]
     pub sol_vault: SystemAccount<'info>,
     
@@ -68,6 +73,20 @@ pub struct Deposit<'info> {
         bump
     )]
     pub vault_stats: Account<'info, VaultStats>,
+    
+    /// CHECK: Crank state PDA, read for the current day and deposit lock once a day has been begun
+    #[account(
+        seeds = [CRANK_STATE_SEED],
+        bump
+    )]
+    pub crank_state: UncheckedAccount<'info>,
+    
+    /// CHECK: Distribution config PDA, read for the eligibility delay and deposit interval once initialized
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump
+    )]
+    pub distribution_config: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
@@ -75,42 +94,68 @@ pub struct Deposit<'info> {
 
 impl<'info> Deposit<'info> {
     pub fn handle(mut ctx: Context<Deposit>, params: DepositParams) -> Result<()> {
-        msg!("Processing deposit from investor: {}", ctx.accounts.investor.key());
-        msg!("SOL amount: {} lamports", params.sol_amount);
-        msg!("USDC amount: {} units", params.usdc_amount);
-        
-        validate_deposit_amounts(&params)?;
-        
-        if params.sol_amount > 0 {
-            process_sol_deposit(&ctx, params.sol_amount)?;
-        }
-        
-        if params.usdc_amount > 0 {
-            process_usdc_deposit(&ctx, params.usdc_amount)?;
-        }
-        
-        update_depositor_record(&mut ctx, params.sol_amount, params.usdc_amount)?;
-        
-        update_vault_stats(&mut ctx, params.sol_amount, params.usdc_amount)?;
-        
-        msg!("Deposit completed successfully!");
-        
-        // Emit event
-        let depositor_record = &ctx.accounts.depositor_record;
-        emit!(crate::events::DepositMade {
-            investor: ctx.accounts.investor.key(),
-            sol_amount: params.sol_amount,
-            usdc_amount: params.usdc_amount,
-            total_sol_deposited: depositor_record.total_sol_deposited,
-            total_usdc_deposited: depositor_record.total_usdc_deposited,
-            current_sol_balance: depositor_record.current_sol_balance,
-            current_usdc_balance: depositor_record.current_usdc_balance,
-            deposit_count: depositor_record.deposit_count,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
-        
-        Ok(())
+        process_deposit(&mut ctx, &params)
+    }
+}
+
+/// Moves the deposited funds and updates the depositor record and vault stats
+pub(crate) fn process_deposit(ctx: &mut Context<Deposit>, params: &DepositParams) -> Result<()> {
+    msg!("Processing deposit from investor: {}", ctx.accounts.investor.key());
+    msg!("SOL amount: {} lamports", params.sol_amount);
+    msg!("USDC amount: {} units", params.usdc_amount);
+    
+    validate_deposit_amounts(params)?;
+    require_program_active(&ctx.accounts.distribution_config)?;
+    require_deposits_unlocked(&ctx.accounts.crank_state)?;
+    require_deposit_window_open(&ctx.accounts.distribution_config)?;
+    require_deposit_mint(&ctx.accounts.distribution_config, &ctx.accounts.usdc_mint.key())?;
+    require_deposit_interval_elapsed(ctx)?;
+    
+    if params.sol_amount > 0 {
+        process_sol_deposit(ctx, params.sol_amount)?;
     }
+    
+    if params.usdc_amount > 0 {
+        process_usdc_deposit(ctx, params.usdc_amount)?;
+    }
+    
+    reset_depositor_record_if_new(ctx)?;
+    require_within_concentration_limit(
+        &ctx.accounts.distribution_config,
+        ctx.accounts.depositor_record.current_usdc_balance,
+        ctx.accounts.vault_stats.current_total_usdc,
+        params.usdc_amount,
+    )?;
+    require_within_y0_cap(
+        &ctx.accounts.distribution_config,
+        ctx.accounts.depositor_record.total_usdc_deposited,
+        params.usdc_amount,
+    )?;
+    
+    let was_active = ctx.accounts.depositor_record.is_active();
+    let previous_weighted_usdc = ctx.accounts.depositor_record.weighted_usdc_balance()?;
+    
+    update_depositor_record(ctx, params.sol_amount, params.usdc_amount)?;
+    
+    update_vault_stats(ctx, params.sol_amount, params.usdc_amount, was_active, previous_weighted_usdc)?;
+    
+    msg!("Deposit completed successfully!");
+    
+    // Emit event
+    let depositor_record = &ctx.accounts.depositor_record;
+    emit!(crate::events::DepositMade {
+        investor: ctx.accounts.investor.key(),
+        sol_amount: params.sol_amount,
+        usdc_amount: params.usdc_amount,
+        total_sol_deposited: depositor_record.total_sol_deposited,
+        total_usdc_deposited: depositor_record.total_usdc_deposited,
+        current_sol_balance: depositor_record.current_sol_balance,
+        current_usdc_balance: depositor_record.current_usdc_balance,
+        deposit_count: depositor_record.deposit_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
 }
 
 fn validate_deposit_amounts(params: &DepositParams) -> Result<()> {
@@ -173,31 +218,241 @@ fn process_usdc_deposit(ctx: &Context<Deposit>, amount: u64) -> Result<()> {
     Ok(())
 }
 
-fn update_depositor_record(ctx: &mut Context<Deposit>, sol_amount: u64, usdc_amount: u64) -> Result<()> {
-    let depositor_record = &mut ctx.accounts.depositor_record;
+/// Starts a new or re-opened record from a clean state
+fn reset_depositor_record_if_new(ctx: &mut Context<Deposit>) -> Result<()> {
+    let investor = ctx.accounts.investor.key();
+    
+    // A record that was never used, or one closed without being zeroed, must not keep stale counters
+    if ctx.accounts.depositor_record.investor != investor || ctx.accounts.depositor_record.deposit_count == 0 {
+        msg!("Initializing fresh depositor record for investor: {}", investor);
+        
+        let first_eligible_day = first_eligible_day(
+            &ctx.accounts.crank_state,
+            &ctx.accounts.distribution_config,
+        )?;
+        // The account funding the first deposit receives payouts until another is registered,
+        // provided it holds the quote mint
+        let funding_account = &ctx.accounts.investor_usdc_account;
+        let registers_funding_account = load_if_initialized::<DistributionConfig>(&ctx.accounts.distribution_config)?
+            .is_none_or(|config| config.quote_mint == funding_account.mint);
+        let registered_quote_account = if registers_funding_account {
+            funding_account.key()
+        } else {
+            Pubkey::default()
+        };
+        
+        let depositor_record = &mut ctx.accounts.depositor_record;
+        depositor_record.set_inner(DepositorRecord::new(investor, ctx.bumps.depositor_record));
+        depositor_record.first_eligible_day = first_eligible_day;
+        depositor_record.registered_quote_account = registered_quote_account;
+        
+        msg!("First eligible distribution day: {}", first_eligible_day);
+        msg!("Registered quote account: {}", registered_quote_account);
+    }
     
-    // Initialize investor field if this is a new record (deposit_count == 0)
-    if depositor_record.deposit_count == 0 {
-        depositor_record.investor = ctx.accounts.investor.key();
-        depositor_record.bump = ctx.bumps.depositor_record;
+    Ok(())
+}
+
+/// New depositors join after the configured delay so they don't dilute the day in progress
+pub(crate) fn first_eligible_day(crank_state: &AccountInfo, distribution_config: &AccountInfo) -> Result<u32> {
+    let current_day = load_if_initialized::<CrankState>(crank_state)?
+        .map_or(0, |crank_state| crank_state.current_day);
+    let delay_days = load_if_initialized::<DistributionConfig>(distribution_config)?
+        .map_or(DEFAULT_ELIGIBILITY_DELAY_DAYS, |config| config.eligibility_delay_days);
+    
+    current_day.checked_add(delay_days).ok_or(error!(ErrorCode::MathOverflow))
+}
+
+/// Repeat deposits must wait out the configured interval; a first deposit is exempt
+fn require_deposit_interval_elapsed(ctx: &Context<Deposit>) -> Result<()> {
+    let depositor_record = &ctx.accounts.depositor_record;
+    if depositor_record.investor != ctx.accounts.investor.key() || depositor_record.deposit_count == 0 {
+        return Ok(());
     }
     
+    let min_interval = load_if_initialized::<DistributionConfig>(&ctx.accounts.distribution_config)?
+        .map_or(0, |config| config.min_deposit_interval_seconds);
+    let now = Clock::get()?.unix_timestamp;
+    
+    require!(
+        now >= depositor_record.last_activity_timestamp.saturating_add(min_interval),
+        ErrorCode::DepositTooFrequent
+    );
+    Ok(())
+}
+
+/// Deposits are only accepted inside the configured funding window; withdrawals are not restricted
+pub(crate) fn require_deposit_window_open(distribution_config: &AccountInfo) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let open = load_if_initialized::<DistributionConfig>(distribution_config)?
+        .is_none_or(|config| config.is_deposit_window_open(now));
+    
+    require!(open, ErrorCode::DepositWindowClosed);
+    Ok(())
+}
+
+/// Only the configured deposit mint's vault counts toward the locked totals, so other mints are
+/// rejected once the config is initialized
+pub(crate) fn require_deposit_mint(distribution_config: &AccountInfo, mint: &Pubkey) -> Result<()> {
+    let accepted = load_if_initialized::<DistributionConfig>(distribution_config)?
+        .is_none_or(|config| config.deposit_mint == *mint);
+    
+    require!(accepted, ErrorCode::WrongDepositMint);
+    Ok(())
+}
+
+/// Deposits close for good once the admin has ended the program
+pub(crate) fn require_program_active(distribution_config: &AccountInfo) -> Result<()> {
+    let ended = load_if_initialized::<DistributionConfig>(distribution_config)?
+        .is_some_and(|config| config.program_ended);
+    
+    require!(!ended, ErrorCode::ProgramEnded);
+    Ok(())
+}
+
+/// A deposit must not take the investor above `max_investor_share_bps` of the vault's USDC
+pub(crate) fn require_within_concentration_limit(
+    distribution_config: &AccountInfo,
+    investor_balance: u64,
+    total_usdc: u64,
+    amount: u64,
+) -> Result<()> {
+    let Some(config) = load_if_initialized::<DistributionConfig>(distribution_config)? else {
+        return Ok(());
+    };
+    
+    require!(
+        !exceeds_concentration_limit(investor_balance, total_usdc, amount, config.max_investor_share_bps)?,
+        ErrorCode::ConcentrationLimitExceeded
+    );
+    Ok(())
+}
+
+/// Whether `(investor_balance + amount) * 10000 / (total_usdc + amount)` exceeds `max_share_bps`.
+/// No cap is set at 0, and nothing is measured against an empty vault (the first deposit is
+/// always the whole vault) or for a SOL-only deposit
+pub fn exceeds_concentration_limit(investor_balance: u64, total_usdc: u64, amount: u64, max_share_bps: u16) -> Result<bool> {
+    if max_share_bps == 0 || total_usdc == 0 || amount == 0 {
+        return Ok(false);
+    }
+    
+    let balance_after = investor_balance as u128 + amount as u128;
+    let total_after = total_usdc as u128 + amount as u128;
+    Ok(balance_after * 10000 / total_after > max_share_bps as u128)
+}
+
+/// A deposit must not take the investor's deposited total above `max_investor_share_of_y0_bps` of Y0
+pub(crate) fn require_within_y0_cap(distribution_config: &AccountInfo, total_deposited: u64, amount: u64) -> Result<()> {
+    let Some(config) = load_if_initialized::<DistributionConfig>(distribution_config)? else {
+        return Ok(());
+    };
+    
+    require!(
+        !exceeds_y0_cap(total_deposited, amount, config.y0_allocation, config.max_investor_share_of_y0_bps),
+        ErrorCode::Y0ShareCapExceeded
+    );
+    Ok(())
+}
+
+/// Whether `total_deposited + amount` exceeds `y0_allocation * max_share_bps / 10000`,
+/// with no cap at 0 and no check for a SOL-only deposit
+pub fn exceeds_y0_cap(total_deposited: u64, amount: u64, y0_allocation: u64, max_share_bps: u16) -> bool {
+    if max_share_bps == 0 || amount == 0 {
+        return false;
+    }
+    
+    let deposited_after = total_deposited as u128 + amount as u128;
+    deposited_after * 10000 > y0_allocation as u128 * max_share_bps as u128
+}
+
+/// Withdrawals wait out the day's lock like deposits, except once the program has ended
+pub(crate) fn require_withdrawals_unlocked(crank_state: &AccountInfo, distribution_config: &AccountInfo) -> Result<()> {
+    let ended = load_if_initialized::<DistributionConfig>(distribution_config)?
+        .is_some_and(|config| config.program_ended);
+    if ended {
+        return Ok(());
+    }
+    
+    require_deposits_unlocked(crank_state)
+}
+
+/// Balances must not move while a begun day is paying out of its snapshot
+pub(crate) fn require_deposits_unlocked(crank_state: &AccountInfo) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let locked = load_if_initialized::<CrankState>(crank_state)?
+        .is_some_and(|crank_state| crank_state.are_deposits_locked(now));
+    
+    require!(!locked, ErrorCode::DepositsLocked);
+    Ok(())
+}
+
+fn update_depositor_record(ctx: &mut Context<Deposit>, sol_amount: u64, usdc_amount: u64) -> Result<()> {
+    let depositor_record = &mut ctx.accounts.depositor_record;
+    
     depositor_record.add_deposit(sol_amount, usdc_amount)?;
 
     Ok(())
 }
 
-fn update_vault_stats(ctx: &mut Context<Deposit>, sol_amount: u64, usdc_amount: u64) -> Result<()> {
+fn update_vault_stats(
+    ctx: &mut Context<Deposit>,
+    sol_amount: u64,
+    usdc_amount: u64,
+    was_active: bool,
+    previous_weighted_usdc: u64,
+) -> Result<()> {
     let vault_stats = &mut ctx.accounts.vault_stats;
     
     vault_stats.add_deposits(sol_amount, usdc_amount)?;
+    vault_stats.update_weighted_usdc(
+        previous_weighted_usdc,
+        ctx.accounts.depositor_record.weighted_usdc_balance()?,
+    )?;
     
-    if ctx.accounts.depositor_record.deposit_count == 1 {
-        vault_stats.depositor_count = vault_stats.depositor_count
-            .checked_add(1)
-            .ok_or(ErrorCode::MathOverflow)?;
+    // First deposit, or a returning investor after a full withdrawal
+    if !was_active {
+        vault_stats.add_depositor(&depositor_member_hash(&ctx.accounts.investor.key()))?;
+        
+        emit!(crate::events::DepositorCountChanged {
+            investor: ctx.accounts.investor.key(),
+            depositor_count: vault_stats.depositor_count,
+            increased: true,
+            timestamp: vault_stats.last_update_timestamp,
+        });
     }
     
     Ok(())
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concentration_limit_caps_the_share_after_the_deposit() {
+        // 1000 units held by others: depositing 250 gives 250 / 1250 = 2000 bps
+        assert!(!exceeds_concentration_limit(0, 1_000, 250, 2_000).unwrap());
+        assert!(exceeds_concentration_limit(0, 1_000, 251, 2_000).unwrap());
+        // An existing balance counts toward the share
+        assert!(exceeds_concentration_limit(100, 1_000, 250, 2_000).unwrap());
+    }
+
+    #[test]
+    fn concentration_limit_skips_uncapped_and_empty_vaults() {
+        assert!(!exceeds_concentration_limit(0, 1_000, 1_000_000, 0).unwrap());
+        assert!(!exceeds_concentration_limit(0, 0, 1_000_000, 2_000).unwrap());
+        assert!(!exceeds_concentration_limit(900, 1_000, 0, 2_000).unwrap());
+    }
+
+    #[test]
+    fn y0_cap_counts_everything_deposited() {
+        // 10% of a 1_000_000 Y0 allows 100_000 deposited in total
+        assert!(!exceeds_y0_cap(0, 100_000, 1_000_000, 1_000));
+        assert!(exceeds_y0_cap(0, 100_001, 1_000_000, 1_000));
+        assert!(exceeds_y0_cap(60_000, 40_001, 1_000_000, 1_000));
+        // Uncapped, and SOL-only deposits, are never rejected
+        assert!(!exceeds_y0_cap(0, u64::MAX, 1_000_000, 0));
+        assert!(!exceeds_y0_cap(200_000, 0, 1_000_000, 1_000));
+    }
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::utils::{eligibility_leaf_hash, load_if_initialized, verify_merkle_proof};
+use crate::states::DistributionConfig;
+use super::deposit::{process_deposit, Deposit, DepositParams};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DepositWithProofParams {
+    pub sol_amount: u64,
+    pub usdc_amount: u64,
+    /// Sibling hashes from the investor's eligibility leaf up to the config's eligibility root
+    pub proof: Vec<[u8; 32]>,
+}
+
+impl<'info> Deposit<'info> {
+    /// Deposits and marks the record eligible once the investor proves allowlist inclusion
+    pub fn handle_with_proof(mut ctx: Context<Deposit>, params: DepositWithProofParams) -> Result<()> {
+        let investor = ctx.accounts.investor.key();
+        msg!("Verifying eligibility proof for investor: {}", investor);
+        
+        let config = load_if_initialized::<DistributionConfig>(&ctx.accounts.distribution_config)?
+            .ok_or(ErrorCode::EligibilityRootNotSet)?;
+        
+        require!(
+            config.requires_eligibility_proof(),
+            ErrorCode::EligibilityRootNotSet
+        );
+        
+        require!(
+            verify_merkle_proof(eligibility_leaf_hash(&investor), &params.proof, &config.eligibility_root),
+            ErrorCode::EligibilityProofInvalid
+        );
+        
+        process_deposit(&mut ctx, &DepositParams {
+            sol_amount: params.sol_amount,
+            usdc_amount: params.usdc_amount,
+        })?;
+        
+        ctx.accounts.depositor_record.eligible = true;
+        
+        msg!("Investor marked eligible");
+        
+        emit!(crate::events::DepositorEligibilityVerified {
+            investor,
+            eligibility_root: config.eligibility_root,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
@@ -1,6 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::errors::ErrorCode;
-use crate::constants::{INVESTOR_RECORD_SEED, DEPOSIT_VAULT_SEED};
+use crate::constants::{FEE_COLLECTOR_SEED, INVESTOR_RECORD_SEED, DEPOSIT_VAULT_SEED};
 use crate::states::{DepositorRecord, VaultStats};
 
 /// Query instruction to get depositor information
@@ -83,7 +82,7 @@ pub struct VaultInfo {
 pub struct QueryVault<'info> {
     /// CHECK: Program authority (our program)
     #[account(
-        seeds = [b"fee_collector"],
+        seeds = [FEE_COLLECTOR_SEED],
         bump
     )]
     pub fee_collector: UncheckedAccount<'info>,
@@ -42,11 +42,17 @@ pub struct DistributeFees<'info> {
     pub quote_mint: UncheckedAccount<'info>,
     
     /// Recipient's base token account
-    #[account(mut)]
+    #[account(
+        mut,
+        token::mint = base_mint
+    )]
     pub recipient_token_a_account: Box<Account<'info, TokenAccount>>,
     
     /// Recipient's quote token account
-    #[account(mut)]
+    #[account(
+        mut,
+        token::mint = quote_mint
+    )]
     pub recipient_token_b_account: Box<Account<'info, TokenAccount>>,
     
     /// CHECK: Token program
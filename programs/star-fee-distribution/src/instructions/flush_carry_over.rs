@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::errors::ErrorCode;
+use crate::constants::{FEE_COLLECTOR_SEED, FEE_VAULT_SEED, DEPOSIT_VAULT_SEED, INVESTOR_RECORD_SEED, CRANK_STATE_SEED, DISTRIBUTION_CONFIG_SEED};
+use crate::math::{add_u64, mul_div_u64};
+use crate::states::{CrankState, DepositorRecord, DistributionConfig, VaultStats};
+use crate::utils::require_undelegated_payout_account;
+
+/// Distribute the accumulated carry-over pro-rata across all active investors
+///
+/// Remaining accounts are `(depositor_record, investor_quote_account)` pairs that must
+/// together cover `vault_stats.current_total_weighted_usdc`.
+#[derive(Accounts)]
+pub struct FlushCarryOver<'info> {
+    /// Admin who initialized the config
+    #[account(
+        constraint = admin.key() == distribution_config.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Program authority (our program)
+    #[account(
+        seeds = [FEE_COLLECTOR_SEED],
+        bump
+    )]
+    pub fee_collector: UncheckedAccount<'info>,
+
+    /// Program's quote token vault holding the carry-over
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, distribution_config.quote_mint.as_ref()],
+        bump,
+        token::mint = distribution_config.quote_mint,
+        token::authority = fee_collector,
+        constraint = program_quote_vault.key() == crank_state.quote_vault @ ErrorCode::QuoteVaultMismatch
+    )]
+    pub program_quote_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Global vault statistics for the weighted total
+    #[account(
+        seeds = [DEPOSIT_VAULT_SEED, b"stats"],
+        bump = vault_stats.bump
+    )]
+    pub vault_stats: Account<'info, VaultStats>,
+
+    /// Distribution configuration
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    /// Crank state holding the carry-over
+    #[account(
+        mut,
+        seeds = [CRANK_STATE_SEED],
+        bump = crank_state.bump
+    )]
+    pub crank_state: Account<'info, CrankState>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> FlushCarryOver<'info> {
+    pub fn handle(ctx: Context<'_, '_, 'info, 'info, FlushCarryOver<'info>>) -> Result<()> {
+        msg!("Flushing carry-over to investors");
+
+        // Today's payouts would double count the dust still reserved in carry-over
+        require!(
+            !ctx.accounts.crank_state.is_day_in_progress(),
+            ErrorCode::DistributionInProgress
+        );
+
+        let carry_over = ctx.accounts.crank_state.carry_over;
+        require!(carry_over > 0, ErrorCode::NoFeesToClaim);
+        require!(
+            carry_over <= ctx.accounts.program_quote_vault.amount,
+            ErrorCode::CarryOverNotBacked
+        );
+
+        let remaining_accounts = ctx.remaining_accounts;
+        require!(
+            !remaining_accounts.is_empty() && remaining_accounts.len().is_multiple_of(2),
+            ErrorCode::FlushCoverageIncomplete
+        );
+
+        // Validate every recipient before moving funds
+        let mut recipients = Vec::with_capacity(remaining_accounts.len() / 2);
+        let mut covered_weight: u64 = 0;
+
+        for pair in remaining_accounts.chunks(2) {
+            let record = Account::<DepositorRecord>::try_from(&pair[0])?;
+            let (expected_record, _) = Pubkey::find_program_address(
+                &[INVESTOR_RECORD_SEED, record.investor.as_ref()],
+                ctx.program_id,
+            );
+            require!(pair[0].key() == expected_record, ErrorCode::InvalidDepositorRecord);
+            require!(
+                recipients.iter().all(|(investor, _, _)| *investor != record.investor),
+                ErrorCode::InvalidDepositorRecord
+            );
+
+            let quote_account = Account::<TokenAccount>::try_from(&pair[1])?;
+            require!(
+                quote_account.owner == record.investor
+                    && quote_account.mint == ctx.accounts.distribution_config.quote_mint,
+                ErrorCode::InvalidInvestorQuoteAccount
+            );
+            require_undelegated_payout_account(&ctx.accounts.distribution_config, &quote_account)?;
+
+            let weight = record.weighted_usdc_balance()?;
+            covered_weight = add_u64(covered_weight, weight)?;
+            recipients.push((record.investor, weight, &pair[1]));
+        }
+
+        // Everyone with a stake must be present, otherwise the split is not pro-rata
+        let total_weight = ctx.accounts.vault_stats.current_total_weighted_usdc;
+        require!(
+            total_weight > 0 && covered_weight == total_weight,
+            ErrorCode::FlushCoverageIncomplete
+        );
+
+        let mut total_flushed: u64 = 0;
+
+        for (investor, weight, quote_account) in recipients.iter() {
+            let payout = mul_div_u64(carry_over, *weight, total_weight)?;
+            if payout == 0 {
+                continue;
+            }
+
+            anchor_spl::token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    anchor_spl::token::Transfer {
+                        from: ctx.accounts.program_quote_vault.to_account_info(),
+                        to: (*quote_account).clone(),
+                        authority: ctx.accounts.fee_collector.to_account_info(),
+                    },
+                    &[&[
+                        FEE_COLLECTOR_SEED,
+                        &[ctx.bumps.fee_collector]
+                    ]]
+                ),
+                payout,
+            )?;
+
+            total_flushed = add_u64(total_flushed, payout)?;
+            msg!("Flushed {} units to investor {}", payout, investor);
+        }
+
+        // Rounding leftovers are released to the next remainder routing
+        let crank_state = &mut ctx.accounts.crank_state;
+        crank_state.carry_over = 0;
+
+        msg!("Total flushed: {} of {} units", total_flushed, carry_over);
+
+        emit!(crate::events::CarryOverFlushed {
+            day: crank_state.current_day,
+            carry_over,
+            total_flushed,
+            investors_count: u32::try_from(recipients.len()).map_err(|_| ErrorCode::MathOverflow)?,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
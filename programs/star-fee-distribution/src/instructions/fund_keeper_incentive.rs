@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::constants::KEEPER_INCENTIVE_SEED;
+use crate::errors::ErrorCode;
+use crate::utils::is_plain_system_account;
+
+/// Permissionless instruction topping up the SOL balance crank keepers are rewarded from.
+/// The keeper incentive account is its own PDA, separate from the deposit vaults, so rewards
+/// can never be paid out of investor deposits
+#[derive(Accounts)]
+pub struct FundKeeperIncentive<'info> {
+    /// Account funding the keeper rewards
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// Keeper incentive SOL account
+    #[account(
+        mut,
+        seeds = [KEEPER_INCENTIVE_SEED],
+        bump,
+        constraint = is_plain_system_account(&keeper_incentive) @ ErrorCode::SolVaultNotSystemAccount
+    )]
+    pub keeper_incentive: SystemAccount<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct FundKeeperIncentiveParams {
+    /// Lamports to add to the keeper incentive balance
+    pub amount: u64,
+}
+
+impl<'info> FundKeeperIncentive<'info> {
+    pub fn handle(ctx: Context<FundKeeperIncentive>, params: FundKeeperIncentiveParams) -> Result<()> {
+        require!(params.amount > 0, ErrorCode::InvalidDepositAmount);
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.keeper_incentive.to_account_info(),
+                },
+            ),
+            params.amount,
+        )?;
+
+        let balance = ctx.accounts.keeper_incentive.lamports();
+
+        msg!("Funded keeper incentive with {} lamports", params.amount);
+        msg!("Keeper incentive balance: {} lamports", balance);
+
+        emit!(crate::events::KeeperIncentiveFunded {
+            funder: ctx.accounts.funder.key(),
+            amount: params.amount,
+            balance,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -1,7 +1,14 @@
 use anchor_lang::prelude::*;
 use crate::errors::ErrorCode;
-use crate::constants::{DISTRIBUTION_CONFIG_SEED, DEFAULT_MIN_PAYOUT_LAMPORTS};
-use crate::states::DistributionConfig;
+use crate::constants::{
+    DISTRIBUTION_CONFIG_SEED, DEFAULT_MIN_PAYOUT_LAMPORTS, DEFAULT_CREATOR_WALLET_TIMELOCK_SECONDS,
+    DEFAULT_DAY_MAX_DURATION_SECONDS, REMAINDER_POLICY_TREASURY, CARRY_OVER_POLICY_HOLD, CARRY_OVER_CAP_POLICY_TOP_INVESTOR,
+    ZERO_BALANCE_POLICY_REJECT, SECONDS_PER_DAY, MAX_ROUNDS_PER_DAY, MAX_AGE_BRACKETS, DEFAULT_FEE_SHARE_MULTIPLIER_BPS,
+    MAX_FEE_SHARE_MULTIPLIER_BPS,
+};
+use crate::states::{AgeBracket, DistributionConfig};
+use crate::utils::{quote_mint_decimals, is_allowed_deposit_mint, to_ui_amount};
+use super::crank_fee_distribution::{validate_f_locked_band, validate_creator_min_share};
 
 #[derive(Accounts)]
 pub struct InitializeDistributionConfig<'info> {
@@ -31,84 +38,509 @@ pub struct InitializeDistributionConfigParams {
     pub investor_fee_share_bps: u16,
     /// Minimum payout amount in lamports (dust threshold)
     pub min_payout_lamports: u64,
-    /// Daily distribution cap in lamports (0 = no cap)
+    /// Legacy daily distribution cap, only used while `daily_cap_quote_units` is 0
     pub daily_cap_lamports: u64,
     /// Creator wallet address for remainder routing
     pub creator_wallet: Pubkey,
     /// Quote mint address (for validation)
     pub quote_mint: Pubkey,
+    /// Route the creator remainder and close the day from the crank's final page
+    pub auto_close_on_final_page: bool,
+    /// External vesting program to read locked amounts from (default = use DepositorRecord)
+    pub vesting_program: Pubkey,
+    /// Share of the creator remainder paid to fully-vested investors in basis points (0 = disabled)
+    pub vested_investor_share_bps: u16,
+    /// Delay before a creator wallet update takes effect in seconds (0 = default)
+    pub creator_wallet_timelock_seconds: i64,
+    /// Where the creator remainder goes at day close (0 = creator, 1 = investors, 2 = treasury)
+    pub remainder_policy: u8,
+    /// Treasury wallet receiving the remainder (required for the treasury policy)
+    pub treasury_wallet: Pubkey,
+    /// Emit per-page and per-investor payout events (disable to save compute and log space)
+    pub emit_events: bool,
+    /// Days after the current distribution day before a new depositor becomes eligible (0 = immediately)
+    pub eligibility_delay_days: u32,
+    /// Merkle root of investors allowed to receive payouts (all zeros = no allowlist)
+    pub eligibility_root: [u8; 32],
+    /// Daily distribution cap in the quote mint's native units, e.g. 1_000_000 = 1 USDC (0 = use `daily_cap_lamports`)
+    pub daily_cap_quote_units: u64,
+    /// Seconds after a day begins before anyone can force-close it (0 = default 48h, otherwise at least 24h)
+    pub day_max_duration_seconds: i64,
+    /// Minimum time between an investor's deposits in seconds (0 = no limit)
+    pub min_deposit_interval_seconds: i64,
+    /// Whether carry-over joins the day's investor pool (0 = retain, 1 = include, 2 = hold until a day's claimed quote covers it)
+    pub carry_over_policy: u8,
+    /// Lower bound for f_locked in basis points (0 = none)
+    pub f_locked_floor_bps: u16,
+    /// Upper bound for f_locked in basis points (0 = none)
+    pub f_locked_ceiling_bps: u16,
+    /// Cross-check vault stats against the depositor records on page 0
+    pub verify_total_locked: bool,
+    /// Allowed drift between the summed records and vault stats (in quote native units)
+    pub total_locked_tolerance_units: u64,
+    /// Mint the creator remainder is paid in (Pubkey::default() = quote mint)
+    pub creator_payout_mint: Pubkey,
+    /// Deposits are accepted from this timestamp (0 = no start bound)
+    pub deposit_window_start_ts: i64,
+    /// Deposits are rejected from this timestamp (0 = no end bound)
+    pub deposit_window_end_ts: i64,
+    /// Carry-over retained past a day close in quote native units (0 = no cap)
+    pub max_carry_over: u64,
+    /// Where carry-over above the cap goes (0 = creator, 1 = largest-weight investor)
+    pub carry_over_cap_policy: u8,
+    /// How zero-balance depositor records are treated during distribution (0 = skip, 1 = reject)
+    pub zero_balance_policy: u8,
+    /// Distribution rounds per day, evenly spaced from the day start (1 = one round a day)
+    pub rounds_per_day: u8,
+    /// Locked total below which a day's fees skip the investor distribution (0 = no minimum)
+    pub min_total_locked_to_distribute: u64,
+    /// Weight external vesting payouts by locked amount times remaining lock time
+    pub weight_by_remaining_lock: bool,
+    /// Seconds without a creator payout after which the creator remainder goes to investors (0 = never)
+    pub creator_inactivity_seconds: i64,
+    /// Minimum share of claimed fees kept for the creator in basis points, whatever f_locked is (0 = none)
+    pub creator_min_share_bps: u16,
+    /// Stable token accepted for deposits, from the deposit mint registry
+    pub deposit_mint: Pubkey,
+    /// Scale the dust threshold as investor_fee_quote / (k * investor_count) (0 = fixed `min_payout_lamports`)
+    pub dust_threshold_k: u32,
+    /// Require non-final crank pages of exactly `DISTRIBUTION_BATCH_SIZE` investors
+    pub fixed_page_size: bool,
+    /// Program notified when route_creator_remainder closes a day (default = no callback)
+    pub callback_program: Pubkey,
+    /// Largest share of the vault's USDC a deposit may take an investor to, in bps (0 = no cap)
+    pub max_investor_share_bps: u16,
+    /// SOL paid from the keeper incentive account per crank page (0 = no reward)
+    pub keeper_reward_lamports: u64,
+    /// Timestamp until which compounded earnings cannot be withdrawn (0 = never locked)
+    pub earnings_unlock_ts: i64,
+    /// Deposit age brackets scaling investor weights, by ascending age (unused entries zeroed)
+    pub age_brackets: [AgeBracket; MAX_AGE_BRACKETS],
+    /// Share of the accumulated quote released each day, in basis points (0 = release everything)
+    pub distribution_rate_bps: u16,
+    /// Require a fee claim into the quote vault during the day before its first crank page
+    pub require_fresh_claim: bool,
+    /// Quote vault balance below which an `auto_claim` crank claims the position's fees first (0 = never)
+    pub min_crankable_quote: u64,
+    /// Largest total USDC one investor may deposit, in basis points of Y0 (0 = no cap)
+    pub max_investor_share_of_y0_bps: u16,
+    /// Delay after a config change before the crank uses the new fee share (0 = immediate)
+    pub config_change_cooloff_seconds: i64,
+    /// Largest quote amount a single position fee claim may bring in (0 = no maximum)
+    pub max_claim_per_tx: u64,
+    /// Wallet whose quote account receives the carry-over dust at day close (default = dust is retained)
+    pub dust_recipient: Pubkey,
+    /// Reject investor payouts into quote accounts with a delegate set
+    pub reject_delegated_accounts: bool,
 }
 
 impl<'info> InitializeDistributionConfig<'info> {
     pub fn handle(ctx: Context<InitializeDistributionConfig>, params: InitializeDistributionConfigParams) -> Result<()> {
-        msg!("Initializing distribution configuration");
-        
-        // Validate Y0 allocation
-        require!(
-            params.y0_allocation > 0,
-            ErrorCode::InvalidY0Allocation
-        );
-        
-        // Validate investor fee share (max 10000 bps = 100%)
-        require!(
-            params.investor_fee_share_bps <= 10000,
-            ErrorCode::InvalidDepositAmount
-        );
-        
-        // Validate creator wallet
-        require!(
-            params.creator_wallet != Pubkey::default(),
-            ErrorCode::CreatorWalletNotProvided
-        );
-        
-        // Validate quote mint
-        require!(
-            params.quote_mint != Pubkey::default(),
-            ErrorCode::InvalidPosition
-        );
-        
-        let config_key = ctx.accounts.distribution_config.key();
-        let distribution_config = &mut ctx.accounts.distribution_config;
-        
-        distribution_config.y0_allocation = params.y0_allocation;
-        distribution_config.investor_fee_share_bps = params.investor_fee_share_bps;
-        distribution_config.min_payout_lamports = if params.min_payout_lamports == 0 {
-            DEFAULT_MIN_PAYOUT_LAMPORTS
-        } else {
-            params.min_payout_lamports
-        };
-        distribution_config.daily_cap_lamports = params.daily_cap_lamports;
-        distribution_config.creator_wallet = params.creator_wallet;
-        distribution_config.quote_mint = params.quote_mint;
-        distribution_config.bump = ctx.bumps.distribution_config;
-        
-        msg!("Distribution configuration initialized successfully");
-        msg!("Y0 allocation: {} units", params.y0_allocation);
-        msg!("Investor fee share: {} bps", params.investor_fee_share_bps);
-        msg!("Min payout: {} lamports", distribution_config.min_payout_lamports);
-        msg!("Daily cap: {} lamports", params.daily_cap_lamports);
-        msg!("Creator wallet: {}", params.creator_wallet);
-        msg!("Quote mint: {}", params.quote_mint);
-        
-        // Emit event
-        let y0 = params.y0_allocation;
-        let fee_share = params.investor_fee_share_bps;
-        let min_payout = distribution_config.min_payout_lamports;
-        let daily_cap = params.daily_cap_lamports;
-        let creator = params.creator_wallet;
-        let quote = params.quote_mint;
-        
-        emit!(crate::events::DistributionConfigInitialized {
-            config: config_key,
-            y0_allocation: y0,
-            investor_fee_share_bps: fee_share,
-            min_payout_lamports: min_payout,
-            daily_cap_lamports: daily_cap,
-            creator_wallet: creator,
-            quote_mint: quote,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
-        
-        Ok(())
+        initialize_config(
+            &mut ctx.accounts.distribution_config,
+            ctx.accounts.admin.key(),
+            ctx.bumps.distribution_config,
+            &params,
+        )
     }
 }
+
+/// Checks the age brackets: used entries come first, with strictly ascending non-negative ages
+/// and multipliers from 1x up to `MAX_FEE_SHARE_MULTIPLIER_BPS`
+pub(crate) fn validate_age_brackets(age_brackets: &[AgeBracket]) -> Result<()> {
+    let used = age_brackets.iter().take_while(|bracket| bracket.multiplier_bps > 0).count();
+    require!(
+        age_brackets[used..].iter().all(|bracket| *bracket == AgeBracket::default()),
+        ErrorCode::InvalidAgeBrackets
+    );
+    require!(
+        age_brackets[..used].iter().all(|bracket| {
+            bracket.min_age_seconds >= 0
+                && (DEFAULT_FEE_SHARE_MULTIPLIER_BPS..=MAX_FEE_SHARE_MULTIPLIER_BPS).contains(&bracket.multiplier_bps)
+        }),
+        ErrorCode::InvalidAgeBrackets
+    );
+    require!(
+        age_brackets[..used].windows(2).all(|pair| pair[0].min_age_seconds < pair[1].min_age_seconds),
+        ErrorCode::InvalidAgeBrackets
+    );
+    Ok(())
+}
+
+/// Checks deposit window bounds (a zero bound is open-ended; both set must be ordered)
+pub(crate) fn validate_deposit_window(start_ts: i64, end_ts: i64) -> Result<()> {
+    require!(
+        start_ts >= 0 && end_ts >= 0 && (start_ts == 0 || end_ts == 0 || start_ts < end_ts),
+        ErrorCode::InvalidDepositWindow
+    );
+    Ok(())
+}
+
+/// Checks that a single minimum payout fits under a non-zero daily cap, otherwise every payout
+/// would exceed the cap and the whole investor pool would be routed away
+pub(crate) fn validate_min_payout_below_cap(min_payout_lamports: u64, daily_cap: u64) -> Result<()> {
+    require!(
+        daily_cap == 0 || min_payout_lamports < daily_cap,
+        ErrorCode::InvalidConfigCombination
+    );
+    Ok(())
+}
+
+/// Validates the params and writes a freshly created distribution config
+pub(crate) fn initialize_config(
+    distribution_config: &mut Account<DistributionConfig>,
+    admin: Pubkey,
+    bump: u8,
+    params: &InitializeDistributionConfigParams,
+) -> Result<()> {
+    msg!("Initializing distribution configuration");
+    
+    let quote_decimals = validate_config_params(params)?;
+    
+    let config_key = distribution_config.key();
+    let now = Clock::get()?.unix_timestamp;
+    
+    write_config_params(distribution_config, params, quote_decimals);
+    distribution_config.admin = admin;
+    distribution_config.pending_creator_wallet = Pubkey::default();
+    distribution_config.creator_update_effective_ts = 0;
+    distribution_config.last_creator_payout_ts = now;
+    distribution_config.bump = bump;
+    
+    msg!("Distribution configuration initialized successfully");
+    msg!("Y0 allocation: {} units", params.y0_allocation);
+    msg!("Investor fee share: {} bps", params.investor_fee_share_bps);
+    msg!("Min payout: {} lamports", distribution_config.min_payout_lamports);
+    msg!("Daily cap: {} units ({} tokens)",
+        distribution_config.daily_cap(),
+        to_ui_amount(distribution_config.daily_cap(), quote_decimals));
+    msg!("Creator wallet: {}", params.creator_wallet);
+    msg!("Quote mint: {}", params.quote_mint);
+    msg!("Auto-close on final page: {}", params.auto_close_on_final_page);
+    msg!("Vesting program: {}", params.vesting_program);
+    msg!("Vested investor share: {} bps", params.vested_investor_share_bps);
+    msg!("Admin: {}", distribution_config.admin);
+    msg!("Creator wallet timelock: {} seconds", distribution_config.creator_wallet_timelock_seconds);
+    msg!("Remainder policy: {}", params.remainder_policy);
+    msg!("Treasury wallet: {}", params.treasury_wallet);
+    msg!("Emit payout events: {}", params.emit_events);
+    msg!("Eligibility delay: {} days", params.eligibility_delay_days);
+    msg!("Day max duration: {} seconds", distribution_config.day_max_duration_seconds);
+    msg!("Min deposit interval: {} seconds", params.min_deposit_interval_seconds);
+    msg!("Carry-over policy: {}", params.carry_over_policy);
+    msg!("f_locked band: {}-{} bps", params.f_locked_floor_bps, params.f_locked_ceiling_bps);
+    msg!("Verify total locked: {} (tolerance: {} units)", params.verify_total_locked, params.total_locked_tolerance_units);
+    msg!("Creator payout mint: {}", params.creator_payout_mint);
+    msg!("Deposit window: {} - {}", params.deposit_window_start_ts, params.deposit_window_end_ts);
+    msg!("Max carry-over: {} units (cap policy {})", params.max_carry_over, params.carry_over_cap_policy);
+    msg!("Zero-balance policy: {}", params.zero_balance_policy);
+    msg!("Rounds per day: {}", params.rounds_per_day);
+    msg!("Min total locked to distribute: {} units", params.min_total_locked_to_distribute);
+    msg!("Weight by remaining lock: {}", params.weight_by_remaining_lock);
+    msg!("Creator inactivity: {} seconds", params.creator_inactivity_seconds);
+    msg!("Creator min share: {} bps", params.creator_min_share_bps);
+    msg!("Deposit mint: {}", params.deposit_mint);
+    msg!("Dust threshold k: {}", params.dust_threshold_k);
+    msg!("Fixed page size: {}", params.fixed_page_size);
+    msg!("Day close callback program: {}", params.callback_program);
+    msg!("Max investor share: {} bps", params.max_investor_share_bps);
+    msg!("Keeper reward: {} lamports per page", params.keeper_reward_lamports);
+    msg!("Earnings unlock: {}", params.earnings_unlock_ts);
+    msg!("Deposit age brackets: {:?}", params.age_brackets);
+    msg!("Distribution rate: {} bps", params.distribution_rate_bps);
+    msg!("Require fresh claim: {}", params.require_fresh_claim);
+    msg!("Min crankable quote: {} units", params.min_crankable_quote);
+    msg!("Max investor share of Y0: {} bps", params.max_investor_share_of_y0_bps);
+    msg!("Config change cool-off: {} seconds", params.config_change_cooloff_seconds);
+    msg!("Max claim per transaction: {} units", params.max_claim_per_tx);
+    msg!("Dust recipient: {}", params.dust_recipient);
+    msg!("Reject delegated accounts: {}", params.reject_delegated_accounts);
+    
+    // Emit event
+    let y0 = params.y0_allocation;
+    let fee_share = params.investor_fee_share_bps;
+    let min_payout = distribution_config.min_payout_lamports;
+    let daily_cap = params.daily_cap_lamports;
+    let creator = params.creator_wallet;
+    let quote = params.quote_mint;
+    
+    emit!(crate::events::DistributionConfigInitialized {
+        config: config_key,
+        y0_allocation: y0,
+        investor_fee_share_bps: fee_share,
+        min_payout_lamports: min_payout,
+        daily_cap_lamports: daily_cap,
+        creator_wallet: creator,
+        quote_mint: quote,
+        auto_close_on_final_page: params.auto_close_on_final_page,
+        vesting_program: params.vesting_program,
+        vested_investor_share_bps: params.vested_investor_share_bps,
+        admin: distribution_config.admin,
+        creator_wallet_timelock_seconds: distribution_config.creator_wallet_timelock_seconds,
+        remainder_policy: params.remainder_policy,
+        treasury_wallet: params.treasury_wallet,
+        emit_events: params.emit_events,
+        eligibility_delay_days: params.eligibility_delay_days,
+        eligibility_root: params.eligibility_root,
+        daily_cap_quote_units: params.daily_cap_quote_units,
+        quote_decimals,
+        day_max_duration_seconds: distribution_config.day_max_duration_seconds,
+        min_deposit_interval_seconds: params.min_deposit_interval_seconds,
+        carry_over_policy: params.carry_over_policy,
+        f_locked_floor_bps: params.f_locked_floor_bps,
+        f_locked_ceiling_bps: params.f_locked_ceiling_bps,
+        verify_total_locked: params.verify_total_locked,
+        total_locked_tolerance_units: params.total_locked_tolerance_units,
+        creator_payout_mint: params.creator_payout_mint,
+        deposit_window_start_ts: params.deposit_window_start_ts,
+        deposit_window_end_ts: params.deposit_window_end_ts,
+        max_carry_over: params.max_carry_over,
+        carry_over_cap_policy: params.carry_over_cap_policy,
+        zero_balance_policy: params.zero_balance_policy,
+        rounds_per_day: params.rounds_per_day,
+        min_total_locked_to_distribute: params.min_total_locked_to_distribute,
+        weight_by_remaining_lock: params.weight_by_remaining_lock,
+        creator_inactivity_seconds: params.creator_inactivity_seconds,
+        creator_min_share_bps: params.creator_min_share_bps,
+        deposit_mint: params.deposit_mint,
+        dust_threshold_k: params.dust_threshold_k,
+        fixed_page_size: params.fixed_page_size,
+        callback_program: params.callback_program,
+        max_investor_share_bps: params.max_investor_share_bps,
+        keeper_reward_lamports: params.keeper_reward_lamports,
+        earnings_unlock_ts: params.earnings_unlock_ts,
+        age_brackets: params.age_brackets,
+        distribution_rate_bps: params.distribution_rate_bps,
+        require_fresh_claim: params.require_fresh_claim,
+        min_crankable_quote: params.min_crankable_quote,
+        max_investor_share_of_y0_bps: params.max_investor_share_of_y0_bps,
+        config_change_cooloff_seconds: params.config_change_cooloff_seconds,
+        max_claim_per_tx: params.max_claim_per_tx,
+        dust_recipient: params.dust_recipient,
+        reject_delegated_accounts: params.reject_delegated_accounts,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
+
+/// Checks every param invariant as a set, returning the quote mint's decimals
+pub(crate) fn validate_config_params(params: &InitializeDistributionConfigParams) -> Result<u8> {
+    // Validate Y0 allocation
+    require!(
+        params.y0_allocation > 0,
+        ErrorCode::InvalidY0Allocation
+    );
+    
+    // Validate investor fee share (max 10000 bps = 100%)
+    require!(
+        params.investor_fee_share_bps <= 10000,
+        ErrorCode::InvalidDepositAmount
+    );
+    
+    // Validate vested investor share (max 10000 bps = 100%)
+    require!(
+        params.vested_investor_share_bps <= 10000,
+        ErrorCode::InvalidDepositAmount
+    );
+    
+    // Validate the concentration cap (max 10000 bps = 100%)
+    require!(
+        params.max_investor_share_bps <= 10000,
+        ErrorCode::InvalidDepositAmount
+    );
+    
+    // Validate the Y0-relative deposit cap (max 10000 bps = 100%)
+    require!(
+        params.max_investor_share_of_y0_bps <= 10000,
+        ErrorCode::InvalidDepositAmount
+    );
+    
+    // Validate the daily release rate (max 10000 bps = 100%)
+    require!(
+        params.distribution_rate_bps <= 10000,
+        ErrorCode::InvalidDepositAmount
+    );
+    
+    // Validate creator wallet timelock
+    require!(
+        params.creator_wallet_timelock_seconds >= 0,
+        ErrorCode::InvalidCreatorWalletTimelock
+    );
+    
+    // Validate the day's maximum duration (keepers get at least a full day)
+    require!(
+        params.day_max_duration_seconds == 0 || params.day_max_duration_seconds >= SECONDS_PER_DAY,
+        ErrorCode::InvalidDayMaxDuration
+    );
+    
+    // Validate the deposit interval
+    require!(
+        params.min_deposit_interval_seconds >= 0,
+        ErrorCode::InvalidDepositInterval
+    );
+    
+    // Validate the config change cool-off
+    require!(
+        params.config_change_cooloff_seconds >= 0,
+        ErrorCode::InvalidConfigCooloff
+    );
+    
+    // Validate the creator inactivity window
+    require!(
+        params.creator_inactivity_seconds >= 0,
+        ErrorCode::InvalidCreatorInactivity
+    );
+    
+    // Validate the deposit window
+    validate_deposit_window(params.deposit_window_start_ts, params.deposit_window_end_ts)?;
+    
+    // Validate the minimum payout against the daily cap, as written (defaults included)
+    let min_payout_lamports = if params.min_payout_lamports == 0 {
+        DEFAULT_MIN_PAYOUT_LAMPORTS
+    } else {
+        params.min_payout_lamports
+    };
+    let daily_cap = if params.daily_cap_quote_units > 0 {
+        params.daily_cap_quote_units
+    } else {
+        params.daily_cap_lamports
+    };
+    validate_min_payout_below_cap(min_payout_lamports, daily_cap)?;
+    
+    // Validate the deposit age brackets
+    validate_age_brackets(&params.age_brackets)?;
+    
+    // Validate the f_locked band
+    validate_f_locked_band(params.f_locked_floor_bps, params.f_locked_ceiling_bps)?;
+    
+    // Validate creator wallet
+    require!(
+        params.creator_wallet != Pubkey::default(),
+        ErrorCode::CreatorWalletNotProvided
+    );
+    
+    // Validate remainder policy
+    require!(
+        params.remainder_policy <= REMAINDER_POLICY_TREASURY,
+        ErrorCode::InvalidRemainderPolicy
+    );
+    
+    // Validate carry-over policy
+    require!(
+        params.carry_over_policy <= CARRY_OVER_POLICY_HOLD,
+        ErrorCode::InvalidCarryOverPolicy
+    );
+    require!(
+        params.carry_over_cap_policy <= CARRY_OVER_CAP_POLICY_TOP_INVESTOR,
+        ErrorCode::InvalidCarryOverCapPolicy
+    );
+    require!(
+        params.zero_balance_policy <= ZERO_BALANCE_POLICY_REJECT,
+        ErrorCode::InvalidZeroBalancePolicy
+    );
+    require!(
+        (1..=MAX_ROUNDS_PER_DAY).contains(&params.rounds_per_day),
+        ErrorCode::InvalidRoundsPerDay
+    );
+    
+    // Validate the creator's minimum share
+    validate_creator_min_share(params.creator_min_share_bps, params.investor_fee_share_bps, params.remainder_policy)?;
+    
+    // Validate treasury wallet
+    require!(
+        params.remainder_policy != REMAINDER_POLICY_TREASURY
+            || params.treasury_wallet != Pubkey::default(),
+        ErrorCode::TreasuryWalletNotProvided
+    );
+    
+    // Validate quote mint
+    require!(
+        params.quote_mint != Pubkey::default(),
+        ErrorCode::InvalidPosition
+    );
+    
+    // Only allow known quote mints (no transfer hooks)
+    let quote_decimals = quote_mint_decimals(&params.quote_mint)
+        .ok_or(ErrorCode::QuoteMintNotAllowed)?;
+    
+    // Only registered stable mints can be deposited
+    require!(
+        is_allowed_deposit_mint(&params.deposit_mint),
+        ErrorCode::WrongDepositMint
+    );
+    
+    Ok(quote_decimals)
+}
+
+/// Writes the param-derived fields of the config (admin, creator wallet schedule, program end,
+/// last creator payout and bump are left to the caller)
+pub(crate) fn write_config_params(
+    distribution_config: &mut DistributionConfig,
+    params: &InitializeDistributionConfigParams,
+    quote_decimals: u8,
+) {
+    distribution_config.y0_allocation = params.y0_allocation;
+    distribution_config.investor_fee_share_bps = params.investor_fee_share_bps;
+    distribution_config.min_payout_lamports = if params.min_payout_lamports == 0 {
+        DEFAULT_MIN_PAYOUT_LAMPORTS
+    } else {
+        params.min_payout_lamports
+    };
+    distribution_config.daily_cap_lamports = params.daily_cap_lamports;
+    distribution_config.creator_wallet = params.creator_wallet;
+    distribution_config.quote_mint = params.quote_mint;
+    distribution_config.auto_close_on_final_page = params.auto_close_on_final_page;
+    distribution_config.vesting_program = params.vesting_program;
+    distribution_config.vested_investor_share_bps = params.vested_investor_share_bps;
+    distribution_config.creator_wallet_timelock_seconds = if params.creator_wallet_timelock_seconds == 0 {
+        DEFAULT_CREATOR_WALLET_TIMELOCK_SECONDS
+    } else {
+        params.creator_wallet_timelock_seconds
+    };
+    distribution_config.remainder_policy = params.remainder_policy;
+    distribution_config.treasury_wallet = params.treasury_wallet;
+    distribution_config.emit_events = params.emit_events;
+    distribution_config.eligibility_delay_days = params.eligibility_delay_days;
+    distribution_config.eligibility_root = params.eligibility_root;
+    distribution_config.daily_cap_quote_units = params.daily_cap_quote_units;
+    distribution_config.quote_decimals = quote_decimals;
+    distribution_config.day_max_duration_seconds = if params.day_max_duration_seconds == 0 {
+        DEFAULT_DAY_MAX_DURATION_SECONDS
+    } else {
+        params.day_max_duration_seconds
+    };
+    distribution_config.min_deposit_interval_seconds = params.min_deposit_interval_seconds;
+    distribution_config.carry_over_policy = params.carry_over_policy;
+    distribution_config.f_locked_floor_bps = params.f_locked_floor_bps;
+    distribution_config.f_locked_ceiling_bps = params.f_locked_ceiling_bps;
+    distribution_config.verify_total_locked = params.verify_total_locked;
+    distribution_config.total_locked_tolerance_units = params.total_locked_tolerance_units;
+    distribution_config.creator_payout_mint = params.creator_payout_mint;
+    distribution_config.deposit_window_start_ts = params.deposit_window_start_ts;
+    distribution_config.deposit_window_end_ts = params.deposit_window_end_ts;
+    distribution_config.max_carry_over = params.max_carry_over;
+    distribution_config.carry_over_cap_policy = params.carry_over_cap_policy;
+    distribution_config.zero_balance_policy = params.zero_balance_policy;
+    distribution_config.rounds_per_day = params.rounds_per_day;
+    distribution_config.min_total_locked_to_distribute = params.min_total_locked_to_distribute;
+    distribution_config.weight_by_remaining_lock = params.weight_by_remaining_lock;
+    distribution_config.creator_inactivity_seconds = params.creator_inactivity_seconds;
+    distribution_config.creator_min_share_bps = params.creator_min_share_bps;
+    distribution_config.deposit_mint = params.deposit_mint;
+    distribution_config.dust_threshold_k = params.dust_threshold_k;
+    distribution_config.fixed_page_size = params.fixed_page_size;
+    distribution_config.callback_program = params.callback_program;
+    distribution_config.max_investor_share_bps = params.max_investor_share_bps;
+    distribution_config.keeper_reward_lamports = params.keeper_reward_lamports;
+    distribution_config.earnings_unlock_ts = params.earnings_unlock_ts;
+    distribution_config.age_brackets = params.age_brackets;
+    distribution_config.distribution_rate_bps = params.distribution_rate_bps;
+    distribution_config.require_fresh_claim = params.require_fresh_claim;
+    distribution_config.min_crankable_quote = params.min_crankable_quote;
+    distribution_config.max_investor_share_of_y0_bps = params.max_investor_share_of_y0_bps;
+    distribution_config.config_change_cooloff_seconds = params.config_change_cooloff_seconds;
+    distribution_config.max_claim_per_tx = params.max_claim_per_tx;
+    distribution_config.dust_recipient = params.dust_recipient;
+    distribution_config.reject_delegated_accounts = params.reject_delegated_accounts;
+}
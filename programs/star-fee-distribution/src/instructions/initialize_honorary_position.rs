@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
 use damm_v2::types::AddLiquidityParameters;
 
 use crate::errors::ErrorCode;
+use crate::constants::{POSITION_STATE_SEED, DAMM_V2_FEE_NUMERATOR_PER_FEE_TIER};
+use crate::states::PositionState;
+use crate::utils::{read_zero_copy, require_supported_amm_layout};
 
 #[derive(Accounts)]
 pub struct InitializeHonoraryPosition<'info> {
@@ -20,7 +24,17 @@ pub struct InitializeHonoraryPosition<'info> {
     #[account(mut)]
     pub position: UncheckedAccount<'info>,
     
-    /// CHECK: Position NFT mint
+    /// Guards against initializing the honorary position for this pool twice
+    #[account(
+        init,
+        payer = signer,
+        space = PositionState::DISCRIMINATOR.len() + PositionState::INIT_SPACE,
+        seeds = [POSITION_STATE_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub position_state: Account<'info, PositionState>,
+    
+    /// CHECK: Position NFT mint (created by DAMM v2, supply checked after create_position)
     #[account(mut)]
     pub position_nft_mint: UncheckedAccount<'info>,
     
@@ -76,8 +90,11 @@ pub struct HonoraryPositionConfig {
     pub lower_tick: i32,
     /// Upper tick for the position range
     pub upper_tick: i32,
-    /// Fee tier for the position
+    /// Fee tier for the position in hundredths of a basis point (must match the pool's base fee)
     pub fee_tier: u16,
+    /// Liquidity added to the position (0 for the honorary position; non-zero liquidity is only
+    /// allowed in a single-sided range entirely on the quote side of the pool's current price)
+    pub liquidity_delta: u128,
 }
 
 impl<'info> InitializeHonoraryPosition<'info> {
@@ -94,10 +111,24 @@ impl<'info> InitializeHonoraryPosition<'info> {
         msg!("Base mint: {}", base_mint);
         msg!("Quote mint: {}", quote_mint);
         
+        // A degenerate quote == base pool would make every fee a base fee
+        require!(base_mint != quote_mint, ErrorCode::InvalidPosition);
+        
         // Preflight validation: Ensure this configuration can only accrue quote fees
         // This is a deterministic validation step that rejects any config that could accrue base fees
         validate_quote_only_fee_configuration(&config)?;
         
+        // The pool reads and CPIs below assume the pool layout the bindings were built for
+        require_supported_amm_layout(&ctx.accounts.pool.to_account_info())?;
+        
+        // Reject a tier the pool does not charge before the create_position CPI
+        validate_pool_fee_tier(&config, &ctx.accounts.pool.to_account_info())?;
+        
+        // Liquidity spanning the current price would earn base fees as well
+        if config.liquidity_delta > 0 {
+            validate_quote_side_liquidity(&config, &ctx.accounts.pool.to_account_info())?;
+        }
+        
         // Create position using DAMM v2 CPI (owned by our PDA)
         damm_v2::cpi::create_position(
             CpiContext::new(
@@ -118,8 +149,24 @@ impl<'info> InitializeHonoraryPosition<'info> {
             ),
         )?;
 
+        // A mint that is not a single NFT would break the position's later fee claims
+        let position_nft_mint_info = ctx.accounts.position_nft_mint.to_account_info();
+        require!(
+            *position_nft_mint_info.owner == anchor_spl::token::ID
+                || *position_nft_mint_info.owner == anchor_spl::token_2022::ID,
+            ErrorCode::InvalidPositionNft
+        );
+        let position_nft_mint = Mint::try_deserialize(&mut &position_nft_mint_info.try_borrow_data()?[..])
+            .map_err(|_| ErrorCode::InvalidPositionNft)?;
+        validate_position_nft_supply(position_nft_mint.supply, position_nft_mint.decimals)?;
+
         // Add liquidity to the position (zero amounts for honorary position)
         // This creates an empty position that only accrues quote token fees
+        let base_balance_before = if config.liquidity_delta > 0 {
+            anchor_spl::token::accessor::amount(&ctx.accounts.user_token_a_account.to_account_info())?
+        } else {
+            0
+        };
         damm_v2::cpi::add_liquidity(
             CpiContext::new(
                 ctx.accounts.amm_program.to_account_info(),
@@ -141,12 +188,29 @@ impl<'info> InitializeHonoraryPosition<'info> {
                 },
             ),
             AddLiquidityParameters {
-                liquidity_delta: 0, // Zero liquidity for honorary position
+                liquidity_delta: config.liquidity_delta, // Zero liquidity for honorary position
                 token_a_amount_threshold: 0,
-                token_b_amount_threshold: 0,
+                token_b_amount_threshold: if config.liquidity_delta > 0 { u64::MAX } else { 0 },
             },
         )?;
+        
+        // Post-add check: single-sided quote liquidity must not have pulled in any base token
+        if config.liquidity_delta > 0 {
+            let base_balance_after = anchor_spl::token::accessor::amount(&ctx.accounts.user_token_a_account.to_account_info())?;
+            require!(base_balance_after == base_balance_before, ErrorCode::QuoteOnlyViolation);
+        }
 
+        // Record the canonical position for this pool
+        let position_state = &mut ctx.accounts.position_state;
+        position_state.pool = ctx.accounts.pool.key();
+        position_state.position = ctx.accounts.position.key();
+        position_state.position_nft_mint = ctx.accounts.position_nft_mint.key();
+        position_state.base_mint = base_mint;
+        position_state.quote_mint = quote_mint;
+        position_state.created_timestamp = Clock::get()?.unix_timestamp;
+        position_state.last_claim_day = 0;
+        position_state.bump = ctx.bumps.position_state;
+        
         msg!("Honorary quote-only fee position created successfully!");
         
         // Emit event
@@ -161,6 +225,7 @@ impl<'info> InitializeHonoraryPosition<'info> {
             lower_tick: config.lower_tick,
             upper_tick: config.upper_tick,
             fee_tier: config.fee_tier,
+            liquidity_delta: config.liquidity_delta,
             timestamp: Clock::get()?.unix_timestamp,
         });
         
@@ -184,19 +249,7 @@ fn validate_quote_only_fee_configuration(config: &HonoraryPositionConfig) -> Res
         ErrorCode::QuoteWeightMustBe10000
     );
     
-    // 2. Validate tick range is appropriate for quote-only fee collection
-    // For quote-only positions, we want a wide range to capture all fees
-    require!(
-        config.lower_tick <= -443636,
-        ErrorCode::LowerTickTooHigh
-    );
-    
-    require!(
-        config.upper_tick >= 443636,
-        ErrorCode::UpperTickTooLow
-    );
-    
-    // 3. Validate fee tier is appropriate
+    // 2. Validate fee tier is appropriate
     // Common fee tiers: 100, 500, 3000, 10000 (in basis points)
     require!(
         config.fee_tier == 100 || config.fee_tier == 500 || 
@@ -204,24 +257,149 @@ fn validate_quote_only_fee_configuration(config: &HonoraryPositionConfig) -> Res
         ErrorCode::InvalidFeeTier
     );
     
-    // 4. Additional validation: Ensure the position range is wide enough
-    // This prevents positions that could accidentally collect base token fees
-    let tick_range = config.upper_tick - config.lower_tick;
+    // Non-zero liquidity is checked against the pool's current price instead
+    // (validate_quote_side_liquidity), since a full range would earn base fees too
+    if config.liquidity_delta == 0 {
+        // 3. Validate tick range is appropriate for quote-only fee collection
+        // For quote-only positions, we want a wide range to capture all fees
+        require!(
+            config.lower_tick <= -443636,
+            ErrorCode::LowerTickTooHigh
+        );
+        
+        require!(
+            config.upper_tick >= 443636,
+            ErrorCode::UpperTickTooLow
+        );
+        
+        // 4. Additional validation: Ensure the position range is wide enough
+        // This prevents positions that could accidentally collect base token fees
+        let tick_range = config.upper_tick - config.lower_tick;
+        require!(
+            tick_range >= 887272, // Minimum range for quote-only positions
+            ErrorCode::PositionRangeTooNarrow
+        );
+        
+        // 5. Validate that the position is configured for maximum fee capture
+        // Quote-only positions should be set up to capture fees across the entire price range
+        require!(
+            config.lower_tick <= -443636 && config.upper_tick >= 443636,
+            ErrorCode::PositionMustSpanFullRange
+        );
+    }
+    
+    msg!("Quote-only fee configuration validated successfully");
+    msg!("Base weight: {} bps, Quote weight: {} bps", config.base_weight_bps, config.quote_weight_bps);
+    msg!("Tick range: {} to {}, Fee tier: {} bps", config.lower_tick, config.upper_tick, config.fee_tier);
+    
+    Ok(())
+}
+
+/// Tick of a Q64.64 sqrt price: floor(log_1.0001(price)) with price = (sqrt_price / 2^64)^2
+/// in token B (quote) per token A (base)
+pub fn tick_from_sqrt_price_x64(sqrt_price: u128) -> i32 {
+    let sqrt_price = sqrt_price as f64 / 2f64.powi(64);
+    (2.0 * sqrt_price.ln() / 1.0001f64.ln()).floor() as i32
+}
+
+/// Checks that a tick range lies entirely below `current_tick`, where a position holds only
+/// token B (quote), within the supported tick bounds
+pub fn is_single_sided_quote_range(lower_tick: i32, upper_tick: i32, current_tick: i32) -> bool {
+    lower_tick >= -443636 && lower_tick < upper_tick && upper_tick <= current_tick
+}
+
+/// Validates that non-zero liquidity stays on the quote side of the pool's current price,
+/// so the position cannot accrue base fees
+fn validate_quote_side_liquidity(config: &HonoraryPositionConfig, pool: &AccountInfo) -> Result<()> {
+    let pool = read_zero_copy::<damm_v2::accounts::Pool>(pool)?;
+    let current_tick = tick_from_sqrt_price_x64(pool.sqrt_price);
+    
+    msg!("Pool current tick: {}, liquidity delta: {}", current_tick, config.liquidity_delta);
+    
     require!(
-        tick_range >= 887272, // Minimum range for quote-only positions
-        ErrorCode::PositionRangeTooNarrow
+        is_single_sided_quote_range(config.lower_tick, config.upper_tick, current_tick),
+        ErrorCode::QuoteOnlyViolation
     );
     
-    // 5. Validate that the position is configured for maximum fee capture
-    // Quote-only positions should be set up to capture fees across the entire price range
+    Ok(())
+}
+
+/// Fee tier charged by a DAMM v2 pool with the given cliff fee numerator
+/// (None when the numerator is not a whole tier)
+pub fn fee_tier_from_cliff_fee_numerator(cliff_fee_numerator: u64) -> Option<u16> {
+    if !cliff_fee_numerator.is_multiple_of(DAMM_V2_FEE_NUMERATOR_PER_FEE_TIER) {
+        return None;
+    }
+    u16::try_from(cliff_fee_numerator / DAMM_V2_FEE_NUMERATOR_PER_FEE_TIER).ok()
+}
+
+/// Validates that the position NFT mint holds exactly one indivisible token
+pub fn validate_position_nft_supply(supply: u64, decimals: u8) -> Result<()> {
+    msg!("Position NFT supply: {}, decimals: {}", supply, decimals);
+    
     require!(
-        config.lower_tick <= -443636 && config.upper_tick >= 443636,
-        ErrorCode::PositionMustSpanFullRange
+        supply == 1 && decimals == 0,
+        ErrorCode::InvalidPositionNft
     );
     
-    msg!("Quote-only fee configuration validated successfully");
-    msg!("Base weight: {} bps, Quote weight: {} bps", config.base_weight_bps, config.quote_weight_bps);
-    msg!("Tick range: {} to {}, Fee tier: {} bps", config.lower_tick, config.upper_tick, config.fee_tier);
+    Ok(())
+}
+
+/// Validates that the configured fee tier is the one the pool's base fee charges
+fn validate_pool_fee_tier(config: &HonoraryPositionConfig, pool: &AccountInfo) -> Result<()> {
+    let pool = read_zero_copy::<damm_v2::accounts::Pool>(pool)?;
+    let cliff_fee_numerator = pool.pool_fees.base_fee.cliff_fee_numerator;
+    
+    msg!("Pool cliff fee numerator: {}", cliff_fee_numerator);
+    
+    require!(
+        fee_tier_from_cliff_fee_numerator(cliff_fee_numerator) == Some(config.fee_tier),
+        ErrorCode::FeeTierMismatch
+    );
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fee_tier_from_cliff_fee_numerator_matches_whole_tiers() {
+        assert_eq!(fee_tier_from_cliff_fee_numerator(100_000), Some(100));
+        assert_eq!(fee_tier_from_cliff_fee_numerator(3_000_000), Some(3000));
+        assert_eq!(fee_tier_from_cliff_fee_numerator(10_000_000), Some(10000));
+        assert_eq!(fee_tier_from_cliff_fee_numerator(2_500_500), None);
+        assert_eq!(fee_tier_from_cliff_fee_numerator(u64::MAX - u64::MAX % 1_000), None);
+    }
+
+    #[test]
+    fn tick_follows_the_pool_price() {
+        assert_eq!(tick_from_sqrt_price_x64(1u128 << 64), 0);
+        // sqrt(1.0001^100) = 1.0001^50
+        let sqrt_price = (1.0001f64.powi(50) * 2f64.powi(64)) as u128;
+        assert!((99..=100).contains(&tick_from_sqrt_price_x64(sqrt_price)));
+        assert!(tick_from_sqrt_price_x64(1u128 << 63) < 0);
+    }
+
+    #[test]
+    fn only_ranges_below_the_price_are_quote_only() {
+        // Full range and ranges straddling or above the price hold base as well
+        assert!(!is_single_sided_quote_range(-443636, 443636, 0));
+        assert!(!is_single_sided_quote_range(-1_000, 10, 0));
+        assert!(!is_single_sided_quote_range(10, 1_000, 0));
+        assert!(is_single_sided_quote_range(-1_000, -10, 0));
+        assert!(is_single_sided_quote_range(-1_000, 0, 0));
+        // Empty or out-of-bounds ranges are rejected
+        assert!(!is_single_sided_quote_range(-10, -10, 0));
+        assert!(!is_single_sided_quote_range(-443637, -10, 0));
+    }
+
+    #[test]
+    fn position_nft_must_be_a_single_indivisible_token() {
+        assert!(validate_position_nft_supply(1, 0).is_ok());
+        assert!(validate_position_nft_supply(2, 0).is_err());
+        assert!(validate_position_nft_supply(0, 0).is_err());
+        assert!(validate_position_nft_supply(1, 6).is_err());
+    }
+}
@@ -10,9 +10,15 @@ pub use distribute_fees::*;
 pub mod deposit;
 pub use deposit::*;
 
+pub mod deposit_with_proof;
+pub use deposit_with_proof::*;
+
 pub mod withdraw;
 pub use withdraw::*;
 
+pub mod close_depositor_record;
+pub use close_depositor_record::*;
+
 pub mod depositor_record;
 pub use depositor_record::*;
 
@@ -22,4 +28,75 @@ pub mod crank_fee_distribution;
 pub use crank_fee_distribution::*;
 
 pub mod initialize_distribution_config;
-pub use initialize_distribution_config::*;
\ No newline at end of file
+pub use initialize_distribution_config::*;
+
+pub mod schedule_creator_wallet_update;
+pub use schedule_creator_wallet_update::*;
+
+pub mod submit_payout_root;
+pub use submit_payout_root::*;
+
+pub mod set_investor_multiplier;
+pub use set_investor_multiplier::*;
+
+pub mod update_distribution_config;
+pub use update_distribution_config::*;
+
+pub mod replace_config;
+pub use replace_config::*;
+
+pub mod update_quote_mint;
+pub use update_quote_mint::*;
+pub mod flush_carry_over;
+pub use flush_carry_over::*;
+
+pub mod packed_investors;
+pub use packed_investors::*;
+
+pub mod begin_distribution_day;
+pub use begin_distribution_day::*;
+
+pub mod query_vault_concentration;
+pub use query_vault_concentration::*;
+
+pub mod bootstrap_program;
+pub use bootstrap_program::*;
+
+pub mod register_quote_account;
+pub use register_quote_account::*;
+
+pub mod query_global_stats;
+pub use query_global_stats::*;
+
+pub mod set_auto_compound;
+pub use set_auto_compound::*;
+
+pub mod reconcile_vault_stats;
+pub use reconcile_vault_stats::*;
+
+pub mod set_batch_withdraw_approval;
+pub use set_batch_withdraw_approval::*;
+
+pub mod batch_withdraw;
+pub use batch_withdraw::*;
+
+pub mod query_investor_weight;
+pub use query_investor_weight::*;
+
+pub mod rescue_tokens;
+pub use rescue_tokens::*;
+
+pub mod simulate_day;
+pub use simulate_day::*;
+
+pub mod fund_keeper_incentive;
+pub use fund_keeper_incentive::*;
+
+pub mod query_day_record;
+pub use query_day_record::*;
+
+pub mod set_opt_out;
+pub use set_opt_out::*;
+
+pub mod position_registry;
+pub use position_registry::*;
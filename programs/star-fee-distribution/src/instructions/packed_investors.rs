@@ -0,0 +1,520 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::errors::ErrorCode;
+use crate::constants::{
+    FEE_COLLECTOR_SEED, FEE_VAULT_SEED, DEPOSIT_VAULT_SEED, CRANK_STATE_SEED,
+    DISTRIBUTION_CONFIG_SEED, PACKED_INVESTORS_SEED, MAX_INVESTORS_PER_PAGE,
+};
+use crate::instructions::deposit::{
+    first_eligible_day, require_deposit_window_open, require_deposits_unlocked, require_deposit_mint,
+    require_program_active, require_withdrawals_unlocked, require_within_concentration_limit, require_within_y0_cap,
+};
+use crate::instructions::crank_fee_distribution::calculate_investor_payout;
+use crate::math::{add_u64, sub_u64};
+use crate::utils::{packed_member_hash, require_undelegated_payout_account};
+use crate::events::PagePayout;
+use crate::states::{CrankState, DistributionConfig, PackedInvestors, VaultStats};
+
+/// Create the packed investors account (admin only)
+#[derive(Accounts)]
+pub struct InitializePackedInvestors<'info> {
+    /// Admin who initialized the config
+    #[account(
+        mut,
+        constraint = admin.key() == distribution_config.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    /// Distribution configuration PDA
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    /// Packed investors PDA
+    #[account(
+        init,
+        payer = admin,
+        space = PackedInvestors::DISCRIMINATOR.len() + PackedInvestors::INIT_SPACE,
+        seeds = [PACKED_INVESTORS_SEED],
+        bump
+    )]
+    pub packed_investors: Box<Account<'info, PackedInvestors>>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializePackedInvestors<'info> {
+    pub fn handle(ctx: Context<InitializePackedInvestors>) -> Result<()> {
+        msg!("Initializing packed investors account");
+
+        let packed_investors = &mut ctx.accounts.packed_investors;
+        packed_investors.total_usdc_balance = 0;
+        packed_investors.active_count = 0;
+        packed_investors.distribution_day = 0;
+        packed_investors.distribution_round = 0;
+        packed_investors.next_index = 0;
+        packed_investors.entries = Vec::new();
+        packed_investors.bump = ctx.bumps.packed_investors;
+
+        Ok(())
+    }
+}
+
+/// Deposit or withdraw USDC against a packed investor slot
+#[derive(Accounts)]
+pub struct PackedBalanceChange<'info> {
+    /// The investor owning the slot
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    /// CHECK: Program authority (our program)
+    #[account(
+        seeds = [FEE_COLLECTOR_SEED],
+        bump
+    )]
+    pub fee_collector: UncheckedAccount<'info>,
+
+    /// Program's USDC vault for deposits (shared with DepositorRecord investors)
+    #[account(
+        init_if_needed,
+        payer = investor,
+        seeds = [DEPOSIT_VAULT_SEED, usdc_mint.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = fee_collector
+    )]
+    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: USDC mint
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    /// Investor's USDC token account
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = investor
+    )]
+    pub investor_usdc_account: Box<Account<'info, TokenAccount>>,
+
+    /// Packed investors PDA
+    #[account(
+        mut,
+        seeds = [PACKED_INVESTORS_SEED],
+        bump = packed_investors.bump
+    )]
+    pub packed_investors: Box<Account<'info, PackedInvestors>>,
+
+    /// Global vault statistics
+    #[account(
+        init_if_needed,
+        payer = investor,
+        space = VaultStats::DISCRIMINATOR.len() + VaultStats::INIT_SPACE,
+        seeds = [DEPOSIT_VAULT_SEED, b"stats"],
+        bump
+    )]
+    pub vault_stats: Box<Account<'info, VaultStats>>,
+
+    /// CHECK: Crank state PDA, read for the current day and deposit lock once a day has been begun
+    #[account(
+        seeds = [CRANK_STATE_SEED],
+        bump
+    )]
+    pub crank_state: UncheckedAccount<'info>,
+
+    /// CHECK: Distribution config PDA, read for the eligibility delay and deposit mint once initialized
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump
+    )]
+    pub distribution_config: UncheckedAccount<'info>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PackedBalanceChangeParams {
+    /// Amount of USDC (in smallest unit)
+    pub usdc_amount: u64,
+    /// Investor's slot index (None on the first deposit, which allocates a slot)
+    pub index: Option<u32>,
+}
+
+impl<'info> PackedBalanceChange<'info> {
+    pub fn handle_deposit(ctx: Context<PackedBalanceChange>, params: PackedBalanceChangeParams) -> Result<()> {
+        let investor = ctx.accounts.investor.key();
+        msg!("Processing packed deposit from investor: {}", investor);
+        msg!("USDC amount: {} units", params.usdc_amount);
+
+        require!(
+            params.usdc_amount >= 1_000 && params.usdc_amount <= 1_000_000_000_000, // 0.001 to 1M USDC
+            ErrorCode::InvalidDepositAmount
+        );
+        require_program_active(&ctx.accounts.distribution_config)?;
+        require_deposits_unlocked(&ctx.accounts.crank_state)?;
+        require_deposit_window_open(&ctx.accounts.distribution_config)?;
+        require_deposit_mint(&ctx.accounts.distribution_config, &ctx.accounts.usdc_mint.key())?;
+        let current_balance = match params.index {
+            Some(index) => ctx.accounts.packed_investors.entry(index, &investor)?.usdc_balance,
+            None => 0,
+        };
+        require_within_concentration_limit(
+            &ctx.accounts.distribution_config,
+            current_balance,
+            ctx.accounts.vault_stats.current_total_usdc,
+            params.usdc_amount,
+        )?;
+        // Slots keep no deposit history, so their balance stands in for the deposited total
+        require_within_y0_cap(&ctx.accounts.distribution_config, current_balance, params.usdc_amount)?;
+
+        anchor_spl::token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::Transfer {
+                    from: ctx.accounts.investor_usdc_account.to_account_info(),
+                    to: ctx.accounts.usdc_vault.to_account_info(),
+                    authority: ctx.accounts.investor.to_account_info(),
+                },
+            ),
+            params.usdc_amount,
+        )?;
+
+        let (index, previous_balance) = match params.index {
+            Some(index) => {
+                let previous_balance = ctx.accounts.packed_investors.entry(index, &investor)?.usdc_balance;
+                ctx.accounts.packed_investors.increase(index, &investor, params.usdc_amount)?;
+                (index, previous_balance)
+            }
+            None => {
+                let first_eligible_day = first_eligible_day(
+                    &ctx.accounts.crank_state,
+                    &ctx.accounts.distribution_config,
+                )?;
+                let index = ctx.accounts.packed_investors.add(
+                    investor,
+                    params.usdc_amount,
+                    first_eligible_day,
+                    Clock::get()?.unix_timestamp,
+                )?;
+                msg!("Allocated packed slot {} (first eligible day {})", index, first_eligible_day);
+                (index, 0)
+            }
+        };
+
+        let usdc_balance = ctx.accounts.packed_investors.entries[index as usize].usdc_balance;
+
+        // Packed balances count toward the same totals as DepositorRecord balances (1x multiplier)
+        let vault_stats = &mut ctx.accounts.vault_stats;
+        vault_stats.add_deposits(0, params.usdc_amount)?;
+        vault_stats.update_weighted_usdc(previous_balance, usdc_balance)?;
+
+        if previous_balance == 0 {
+            vault_stats.add_depositor(&packed_member_hash(&investor))?;
+        }
+
+        emit!(crate::events::PackedInvestorUpdated {
+            investor,
+            index,
+            usdc_balance,
+            active: true,
+            active_count: ctx.accounts.packed_investors.active_count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn handle_withdraw(ctx: Context<PackedBalanceChange>, params: PackedBalanceChangeParams) -> Result<()> {
+        let investor = ctx.accounts.investor.key();
+        msg!("Processing packed withdrawal for investor: {}", investor);
+        msg!("USDC amount: {} units", params.usdc_amount);
+
+        require!(params.usdc_amount > 0, ErrorCode::InvalidDepositAmount);
+        require_withdrawals_unlocked(&ctx.accounts.crank_state, &ctx.accounts.distribution_config)?;
+        require_deposit_mint(&ctx.accounts.distribution_config, &ctx.accounts.usdc_mint.key())?;
+        let index = params.index.ok_or(ErrorCode::PackedInvestorMismatch)?;
+
+        let previous_balance = ctx.accounts.packed_investors.entry(index, &investor)?.usdc_balance;
+        let removed = ctx.accounts.packed_investors.decrease(index, &investor, params.usdc_amount)?;
+        let usdc_balance = previous_balance - params.usdc_amount;
+
+        anchor_spl::token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::Transfer {
+                    from: ctx.accounts.usdc_vault.to_account_info(),
+                    to: ctx.accounts.investor_usdc_account.to_account_info(),
+                    authority: ctx.accounts.fee_collector.to_account_info(),
+                },
+                &[&[
+                    FEE_COLLECTOR_SEED,
+                    &[ctx.bumps.fee_collector]
+                ]]
+            ),
+            params.usdc_amount,
+        )?;
+
+        let vault_stats = &mut ctx.accounts.vault_stats;
+        vault_stats.add_withdrawals(0, params.usdc_amount)?;
+        vault_stats.update_weighted_usdc(previous_balance, usdc_balance)?;
+
+        if removed {
+            vault_stats.remove_depositor(&packed_member_hash(&investor))?;
+            msg!("Freed packed slot {}", index);
+        }
+
+        emit!(crate::events::PackedInvestorUpdated {
+            investor,
+            index,
+            usdc_balance,
+            active: !removed,
+            active_count: ctx.accounts.packed_investors.active_count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+/// Pay a contiguous slice of packed investors from the day's investor allocation
+///
+/// Remaining accounts hold one investor quote token account per active slot in the slice, in order.
+#[derive(Accounts)]
+pub struct DistributePackedPage<'info> {
+    /// CHECK: Program authority (our program)
+    #[account(
+        seeds = [FEE_COLLECTOR_SEED],
+        bump
+    )]
+    pub fee_collector: UncheckedAccount<'info>,
+
+    /// Program's quote token vault for fee collection (QUOTE ONLY)
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, distribution_config.quote_mint.as_ref()],
+        bump,
+        token::mint = distribution_config.quote_mint,
+        token::authority = fee_collector,
+        constraint = program_quote_vault.key() == crank_state.quote_vault @ ErrorCode::QuoteVaultMismatch
+    )]
+    pub program_quote_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Packed investors PDA
+    #[account(
+        mut,
+        seeds = [PACKED_INVESTORS_SEED],
+        bump = packed_investors.bump
+    )]
+    pub packed_investors: Box<Account<'info, PackedInvestors>>,
+
+    /// Global vault statistics
+    #[account(
+        seeds = [DEPOSIT_VAULT_SEED, b"stats"],
+        bump = vault_stats.bump
+    )]
+    pub vault_stats: Account<'info, VaultStats>,
+
+    /// Distribution configuration
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    /// Crank state for tracking
+    #[account(
+        mut,
+        seeds = [CRANK_STATE_SEED],
+        bump = crank_state.bump
+    )]
+    pub crank_state: Account<'info, CrankState>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DistributePackedPageParams {
+    /// First slot of the page (must equal the packed page cursor)
+    pub start_index: u32,
+    /// Number of slots in the page (at most MAX_INVESTORS_PER_PAGE)
+    pub count: u32,
+}
+
+impl<'info> DistributePackedPage<'info> {
+    pub fn handle(
+        ctx: Context<'_, '_, 'info, 'info, DistributePackedPage<'info>>,
+        params: DistributePackedPageParams,
+    ) -> Result<()> {
+        msg!("Distributing packed investors page: {} + {}", params.start_index, params.count);
+
+        let config = &ctx.accounts.distribution_config;
+        let crank_state = &mut ctx.accounts.crank_state;
+        let packed_investors = &mut ctx.accounts.packed_investors;
+
+        require!(
+            crank_state.is_day_in_progress(),
+            ErrorCode::DistributionNotStarted
+        );
+        crank_state.require_not_stale(config.day_max_duration_seconds)?;
+
+        // Packed slots have no vesting account to read locked amounts from
+        require!(
+            config.vesting_program == Pubkey::default(),
+            ErrorCode::VestingAccountInvalid
+        );
+
+        // Bounds the page's payouts event
+        require!(params.count <= MAX_INVESTORS_PER_PAGE, ErrorCode::PageTooLarge);
+
+        // Each slot is paid once per round, in order
+        packed_investors.sync_cursor(crank_state.current_day, crank_state.current_round);
+        require!(
+            params.start_index == packed_investors.next_index,
+            ErrorCode::InvalidPaginationCursor
+        );
+
+        let total_locked = crank_state.day_total_weighted_usdc;
+        let total_investor_fee = crank_state.round_investor_fee_quote;
+        let min_payout = config.min_payout(total_investor_fee, crank_state.expected_investors);
+        let daily_cap = config.daily_cap();
+        let mut quote_accounts = ctx.remaining_accounts.iter();
+        // The account's in-memory amount is not refreshed by the transfers below, so the page
+        // tracks what is left in the vault itself
+        let mut vault_balance = ctx.accounts.program_quote_vault.amount;
+        let mut investors_paid: u32 = 0;
+        let mut page_distributed: u64 = 0;
+        let mut page_dust: u64 = 0;
+        let mut payouts: Vec<PagePayout> = Vec::with_capacity(params.count as usize);
+
+        for entry in packed_investors.page(params.start_index, params.count)? {
+            if !entry.is_active() {
+                continue;
+            }
+
+            let quote_account_info = quote_accounts
+                .next()
+                .ok_or(ErrorCode::InvalidInvestorQuoteAccount)?;
+            let quote_account = Account::<TokenAccount>::try_from(quote_account_info)?;
+            require!(
+                quote_account.owner == entry.investor && quote_account.mint == config.quote_mint,
+                ErrorCode::InvalidInvestorQuoteAccount
+            );
+            require_undelegated_payout_account(config, &quote_account)?;
+
+            let (payout, dust) = calculate_investor_payout(
+                entry.usdc_balance,
+                total_locked,
+                total_investor_fee,
+                min_payout,
+            )?;
+
+            // Packed slots cannot prove allowlist inclusion, so an allowlist keeps them unpaid
+            let eligible = entry.first_eligible_day <= crank_state.current_day
+                && !config.requires_eligibility_proof();
+            let (payout, dust) = if eligible {
+                (payout, dust)
+            } else {
+                (0, add_u64(payout, dust)?)
+            };
+
+            if payout > 0 {
+                require!(
+                    daily_cap == 0 || add_u64(crank_state.investor_distributed_today(), payout)? <= daily_cap,
+                    ErrorCode::DailyCapExceeded
+                );
+                let new_total = add_u64(crank_state.daily_distributed, payout)?;
+                require!(
+                    new_total <= crank_state.day_investor_fee_quote,
+                    ErrorCode::InvestorPoolExhausted
+                );
+                // Carry-over dust must stay backed by the vault
+                require!(
+                    payout <= vault_balance.saturating_sub(crank_state.carry_over),
+                    ErrorCode::InsufficientTokenBalance
+                );
+
+                anchor_spl::token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        anchor_spl::token::Transfer {
+                            from: ctx.accounts.program_quote_vault.to_account_info(),
+                            to: quote_account_info.clone(),
+                            authority: ctx.accounts.fee_collector.to_account_info(),
+                        },
+                        &[&[
+                            FEE_COLLECTOR_SEED,
+                            &[ctx.bumps.fee_collector]
+                        ]]
+                    ),
+                    payout,
+                )?;
+
+                crank_state.daily_distributed = new_total;
+                crank_state.record_top_investor(entry.usdc_balance, quote_account_info.key());
+                vault_balance = sub_u64(vault_balance, payout)?;
+                page_distributed = add_u64(page_distributed, payout)?;
+                payouts.push(PagePayout {
+                    investor: entry.investor,
+                    amount: payout,
+                });
+            }
+
+            page_dust = add_u64(page_dust, dust)?;
+            investors_paid = investors_paid.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+            // The cursor pays each slot once per round; slots allocated after the day began are not in its snapshot
+            if entry.was_active_at(crank_state.last_distribution_timestamp) {
+                crank_state.record_snapshot_payout(&packed_member_hash(&entry.investor))?;
+            }
+        }
+
+        require!(
+            quote_accounts.next().is_none(),
+            ErrorCode::InvalidInvestorQuoteAccount
+        );
+
+        crank_state.carry_over = add_u64(crank_state.carry_over, page_dust)?;
+        crank_state.investors_paid_today = crank_state.investors_paid_today
+            .checked_add(investors_paid)
+            .ok_or(ErrorCode::MathOverflow)?;
+        packed_investors.next_index = params.start_index
+            .checked_add(params.count)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        msg!("Packed investors paid: {}", investors_paid);
+        msg!("Page distributed: {} units", page_distributed);
+        msg!("Page dust carried over: {} units", page_dust);
+
+        let now = Clock::get()?.unix_timestamp;
+        if config.emit_events {
+            emit!(crate::events::PackedInvestorPagePaid {
+                day: crank_state.current_day,
+                round_index: crank_state.current_round,
+                start_index: params.start_index,
+                count: params.count,
+                investors_paid,
+                page_distributed,
+                dust: page_dust,
+                timestamp: now,
+            });
+        }
+
+        // Always emitted so indexers can ingest the page's transfers from a single event
+        emit!(crate::events::PackedPagePayouts {
+            day: crank_state.current_day,
+            round_index: crank_state.current_round,
+            start_index: params.start_index,
+            payouts,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::constants::{DISTRIBUTION_CONFIG_SEED, POSITION_REGISTRY_SEED};
+use crate::states::{DistributionConfig, PositionRegistry};
+
+/// Authorize claims from an honorary position into the shared fee vaults (admin only),
+/// creating the registry on first use
+#[derive(Accounts)]
+pub struct AddPosition<'info> {
+    /// Admin who initialized the config
+    #[account(
+        mut,
+        constraint = admin.key() == distribution_config.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    /// Distribution configuration PDA
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    /// Position registry PDA
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = PositionRegistry::DISCRIMINATOR.len() + PositionRegistry::INIT_SPACE,
+        seeds = [POSITION_REGISTRY_SEED],
+        bump
+    )]
+    pub position_registry: Box<Account<'info, PositionRegistry>>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Revoke claims from a registered position (admin only)
+#[derive(Accounts)]
+pub struct RemovePosition<'info> {
+    /// Admin who initialized the config
+    #[account(
+        constraint = admin.key() == distribution_config.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    /// Distribution configuration PDA
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    /// Position registry PDA
+    #[account(
+        mut,
+        seeds = [POSITION_REGISTRY_SEED],
+        bump = position_registry.bump
+    )]
+    pub position_registry: Box<Account<'info, PositionRegistry>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PositionRegistryParams {
+    /// DAMM v2 position account
+    pub position: Pubkey,
+}
+
+impl<'info> AddPosition<'info> {
+    pub fn handle(ctx: Context<AddPosition>, params: PositionRegistryParams) -> Result<()> {
+        let position_registry = &mut ctx.accounts.position_registry;
+        if position_registry.config == Pubkey::default() {
+            position_registry.config = ctx.accounts.distribution_config.key();
+            position_registry.bump = ctx.bumps.position_registry;
+        }
+
+        position_registry.add(params.position)?;
+
+        msg!("Registered position: {}", params.position);
+        msg!("Registered positions: {}", position_registry.positions.len());
+
+        emit!(crate::events::PositionRegistryChanged {
+            position: params.position,
+            registered: true,
+            position_count: position_registry.positions.len() as u8,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> RemovePosition<'info> {
+    pub fn handle(ctx: Context<RemovePosition>, params: PositionRegistryParams) -> Result<()> {
+        let position_registry = &mut ctx.accounts.position_registry;
+        position_registry.remove(&params.position)?;
+
+        msg!("Removed position: {}", params.position);
+        msg!("Registered positions: {}", position_registry.positions.len());
+
+        emit!(crate::events::PositionRegistryChanged {
+            position: params.position,
+            registered: false,
+            position_count: position_registry.positions.len() as u8,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::constants::DAY_RECORD_SEED;
+use crate::errors::ErrorCode;
+use crate::states::DayRecord;
+use crate::utils::load_if_initialized;
+
+/// Read-only instruction returning a past day's record, derived from the day number
+#[derive(Accounts)]
+#[instruction(params: QueryDayRecordParams)]
+pub struct QueryDayRecord<'info> {
+    /// CHECK: Day record PDA for the queried day, which may not exist yet
+    #[account(
+        seeds = [DAY_RECORD_SEED, params.day.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub day_record: UncheckedAccount<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct QueryDayRecordParams {
+    /// Distribution day to look up
+    pub day: u32,
+}
+
+impl<'info> QueryDayRecord<'info> {
+    pub fn handle(ctx: Context<QueryDayRecord>, params: QueryDayRecordParams) -> Result<DayRecord> {
+        let day_record = load_if_initialized::<DayRecord>(&ctx.accounts.day_record)?
+            .ok_or(ErrorCode::DayRecordNotFound)?;
+        
+        msg!("Day record for day {}:", params.day);
+        msg!("Total distributed: {} units over {} leaves", day_record.total_distributed, day_record.leaf_count);
+        msg!("Root submitted at: {}", day_record.submitted_timestamp);
+        
+        Ok(day_record)
+    }
+}
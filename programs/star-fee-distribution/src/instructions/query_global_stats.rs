@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::constants::DEPOSIT_VAULT_SEED;
+use crate::states::VaultStats;
+
+/// Read-only instruction returning lifetime distribution totals across all closed days
+#[derive(Accounts)]
+pub struct QueryGlobalStats<'info> {
+    /// Global vault statistics holding the lifetime totals
+    #[account(
+        seeds = [DEPOSIT_VAULT_SEED, b"stats"],
+        bump = vault_stats.bump
+    )]
+    pub vault_stats: Account<'info, VaultStats>,
+}
+
+/// Response structure for lifetime distribution totals
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct GlobalStats {
+    /// Freshly claimed quote (excluding carry-over) across all closed days
+    pub lifetime_quote_claimed: u64,
+    /// Quote paid to investors across all closed days
+    pub lifetime_distributed_to_investors: u64,
+    /// Quote routed to the creator or treasury across all closed days
+    pub lifetime_to_creator: u64,
+    /// Number of distribution days closed
+    pub total_days_distributed: u32,
+}
+
+impl<'info> QueryGlobalStats<'info> {
+    pub fn handle(ctx: Context<QueryGlobalStats>) -> Result<GlobalStats> {
+        let vault_stats = &ctx.accounts.vault_stats;
+
+        let stats = GlobalStats {
+            lifetime_quote_claimed: vault_stats.lifetime_quote_claimed,
+            lifetime_distributed_to_investors: vault_stats.lifetime_distributed_to_investors,
+            lifetime_to_creator: vault_stats.lifetime_to_creator,
+            total_days_distributed: vault_stats.total_days_distributed,
+        };
+
+        msg!("Global stats over {} closed days:", stats.total_days_distributed);
+        msg!("Lifetime quote claimed: {} units", stats.lifetime_quote_claimed);
+        msg!("Lifetime distributed to investors: {} units", stats.lifetime_distributed_to_investors);
+        msg!("Lifetime to creator: {} units", stats.lifetime_to_creator);
+
+        Ok(stats)
+    }
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::constants::{INVESTOR_RECORD_SEED, DEPOSIT_VAULT_SEED};
+use crate::states::{DepositorRecord, VaultStats};
+use super::crank_fee_distribution::calculate_weight_bps;
+
+/// Read-only instruction returning an investor's live payout weight
+#[derive(Accounts)]
+pub struct QueryInvestorWeight<'info> {
+    /// CHECK: Investor whose weight is queried (any investor can be queried)
+    pub investor: UncheckedAccount<'info>,
+    
+    /// Depositor record for this investor
+    #[account(
+        seeds = [INVESTOR_RECORD_SEED, investor.key().as_ref()],
+        bump = depositor_record.bump,
+        has_one = investor
+    )]
+    pub depositor_record: Account<'info, DepositorRecord>,
+    
+    /// Global vault statistics
+    #[account(
+        seeds = [DEPOSIT_VAULT_SEED, b"stats"],
+        bump = vault_stats.bump
+    )]
+    pub vault_stats: Account<'info, VaultStats>,
+}
+
+/// Response structure for an investor's projected weight
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct InvestorWeight {
+    /// Investor public key
+    pub investor: Pubkey,
+    /// Investor's current USDC balance
+    pub current_usdc_balance: u64,
+    /// Investor's balance scaled by their fee share multiplier
+    pub weighted_usdc_balance: u64,
+    /// Sum of all multiplier-scaled balances
+    pub total_weighted_usdc: u64,
+    /// Share of the next investor fee pool in basis points (0 when nothing is locked)
+    pub weight_bps: u64,
+}
+
+impl<'info> QueryInvestorWeight<'info> {
+    pub fn handle(ctx: Context<QueryInvestorWeight>) -> Result<InvestorWeight> {
+        let depositor_record = &ctx.accounts.depositor_record;
+        let vault_stats = &ctx.accounts.vault_stats;
+        
+        // Same basis as distribute_to_investor: with 1x multipliers this is
+        // current_usdc_balance * 10000 / current_total_usdc
+        let weighted_usdc_balance = depositor_record.weighted_usdc_balance()?;
+        let weight_bps = calculate_weight_bps(weighted_usdc_balance, vault_stats.current_total_weighted_usdc)?;
+        
+        let weight = InvestorWeight {
+            investor: depositor_record.investor,
+            current_usdc_balance: depositor_record.current_usdc_balance,
+            weighted_usdc_balance,
+            total_weighted_usdc: vault_stats.current_total_weighted_usdc,
+            weight_bps,
+        };
+        
+        msg!("Weight for investor: {}", weight.investor);
+        msg!("Weighted USDC balance: {} of {} units", weight.weighted_usdc_balance, weight.total_weighted_usdc);
+        msg!("Weight: {} bps", weight.weight_bps);
+        
+        Ok(weight)
+    }
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use crate::constants::{INVESTOR_RECORD_SEED, DEPOSIT_VAULT_SEED};
+use crate::errors::ErrorCode;
+use crate::math::{add_u64, mul_div_u64};
+use crate::states::{DepositorRecord, VaultStats};
+
+/// Number of largest depositors summed for the top-N share
+pub const CONCENTRATION_TOP_N: usize = 5;
+
+/// Read-only instruction measuring how concentrated the vault's USDC is.
+/// Depositor records to consider are passed in `remaining_accounts`
+#[derive(Accounts)]
+pub struct QueryVaultConcentration<'info> {
+    /// Global vault statistics
+    #[account(
+        seeds = [DEPOSIT_VAULT_SEED, b"stats"],
+        bump = vault_stats.bump
+    )]
+    pub vault_stats: Account<'info, VaultStats>,
+}
+
+/// Response structure for vault concentration query
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct VaultConcentration {
+    /// Current total USDC balance in vault (in smallest unit)
+    pub current_total_usdc: u64,
+    /// Number of depositor records considered
+    pub records_count: u32,
+    /// Investor with the largest USDC balance (default if none)
+    pub top_investor: Pubkey,
+    /// Share of the largest depositor (in basis points)
+    pub top_1_share_bps: u64,
+    /// Combined share of the five largest depositors (in basis points)
+    pub top_5_share_bps: u64,
+}
+
+/// Share of `total` held by the `n` largest balances, in basis points.
+/// `balances` must be sorted in descending order
+pub fn top_share_bps(balances: &[u64], n: usize, total: u64) -> Result<u64> {
+    if total == 0 {
+        return Ok(0);
+    }
+
+    let mut sum: u64 = 0;
+    for balance in balances.iter().take(n) {
+        sum = add_u64(sum, *balance)?;
+    }
+
+    mul_div_u64(sum, 10000, total)
+}
+
+impl<'info> QueryVaultConcentration<'info> {
+    pub fn handle(ctx: Context<'_, '_, 'info, 'info, QueryVaultConcentration<'info>>) -> Result<VaultConcentration> {
+        let current_total_usdc = ctx.accounts.vault_stats.current_total_usdc;
+
+        let mut holders: Vec<(Pubkey, u64)> = Vec::with_capacity(ctx.remaining_accounts.len());
+        for account in ctx.remaining_accounts.iter() {
+            let record = Account::<DepositorRecord>::try_from(account)?;
+            let (expected_record, _) = Pubkey::find_program_address(
+                &[INVESTOR_RECORD_SEED, record.investor.as_ref()],
+                ctx.program_id,
+            );
+            require!(account.key() == expected_record, ErrorCode::InvalidDepositorRecord);
+            require!(
+                holders.iter().all(|(investor, _)| *investor != record.investor),
+                ErrorCode::InvalidDepositorRecord
+            );
+
+            holders.push((record.investor, record.current_usdc_balance));
+        }
+
+        // Largest balances first
+        holders.sort_by_key(|(_, balance)| std::cmp::Reverse(*balance));
+        let balances: Vec<u64> = holders.iter().map(|(_, balance)| *balance).collect();
+
+        let concentration = VaultConcentration {
+            current_total_usdc,
+            records_count: u32::try_from(holders.len()).map_err(|_| ErrorCode::MathOverflow)?,
+            top_investor: holders.first().map(|(investor, _)| *investor).unwrap_or_default(),
+            top_1_share_bps: top_share_bps(&balances, 1, current_total_usdc)?,
+            top_5_share_bps: top_share_bps(&balances, CONCENTRATION_TOP_N, current_total_usdc)?,
+        };
+
+        msg!("Vault concentration over {} records:", concentration.records_count);
+        msg!("Current total USDC: {} units", concentration.current_total_usdc);
+        msg!("Top investor: {}", concentration.top_investor);
+        msg!("Top-1 share: {} bps", concentration.top_1_share_bps);
+        msg!("Top-5 share: {} bps", concentration.top_5_share_bps);
+
+        Ok(concentration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_share_bps_on_skewed_distribution() {
+        let balances = [7_000, 1_000, 1_000, 500, 300, 200];
+        assert_eq!(top_share_bps(&balances, 1, 10_000).unwrap(), 7_000);
+        assert_eq!(top_share_bps(&balances, CONCENTRATION_TOP_N, 10_000).unwrap(), 9_800);
+        assert_eq!(top_share_bps(&balances, 10, 10_000).unwrap(), 10_000);
+    }
+
+    #[test]
+    fn top_share_bps_handles_empty_vault() {
+        assert_eq!(top_share_bps(&[], 1, 0).unwrap(), 0);
+        assert_eq!(top_share_bps(&[], 1, 1_000).unwrap(), 0);
+        assert_eq!(top_share_bps(&[5], 1, 0).unwrap(), 0);
+    }
+}
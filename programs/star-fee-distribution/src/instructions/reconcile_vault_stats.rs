@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::constants::{
+    FEE_COLLECTOR_SEED, DEPOSIT_VAULT_SEED, INVESTOR_RECORD_SEED, CRANK_STATE_SEED,
+    DISTRIBUTION_CONFIG_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::math::add_u64;
+use crate::states::{CrankState, DepositorRecord, DistributionConfig, VaultStats};
+use crate::utils::is_plain_system_account;
+
+/// Admin instruction correcting drift in `vault_stats.current_total_sol` / `current_total_usdc`.
+/// With `from_records` the totals are summed from the depositor records in `remaining_accounts`,
+/// otherwise they are read from the deposit vault balances
+#[derive(Accounts)]
+pub struct ReconcileVaultStats<'info> {
+    /// Admin who initialized the config
+    #[account(
+        constraint = admin.key() == distribution_config.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    /// Distribution configuration PDA
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Box<Account<'info, DistributionConfig>>,
+
+    /// Global vault statistics to correct
+    #[account(
+        mut,
+        seeds = [DEPOSIT_VAULT_SEED, b"stats"],
+        bump = vault_stats.bump
+    )]
+    pub vault_stats: Account<'info, VaultStats>,
+
+    /// Crank state, read to refuse corrections while a day is in progress
+    #[account(
+        seeds = [CRANK_STATE_SEED],
+        bump = crank_state.bump
+    )]
+    pub crank_state: Box<Account<'info, CrankState>>,
+
+    /// CHECK: Program authority (our program)
+    #[account(
+        seeds = [FEE_COLLECTOR_SEED],
+        bump
+    )]
+    pub fee_collector: UncheckedAccount<'info>,
+
+    /// Program's SOL vault for deposits
+    #[account(
+        seeds = [DEPOSIT_VAULT_SEED, b"sol"],
+        bump,
+        constraint = is_plain_system_account(&sol_vault) @ ErrorCode::SolVaultNotSystemAccount
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    /// Program's USDC vault for deposits
+    #[account(
+        seeds = [DEPOSIT_VAULT_SEED, usdc_mint.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = fee_collector
+    )]
+    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Deposit mint, only used to derive the USDC vault (must be the configured deposit mint)
+    #[account(
+        constraint = usdc_mint.key() == distribution_config.deposit_mint @ ErrorCode::WrongDepositMint
+    )]
+    pub usdc_mint: UncheckedAccount<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ReconcileVaultStatsParams {
+    /// Sum the depositor records in `remaining_accounts` instead of reading the vault balances
+    pub from_records: bool,
+}
+
+/// Sums `(current_sol_balance, current_usdc_balance)` across the depositor records in
+/// `accounts`, rejecting accounts that are not depositor record PDAs and duplicates
+pub fn sum_record_totals<'info>(accounts: &'info [AccountInfo<'info>], program_id: &Pubkey) -> Result<(u64, u64)> {
+    let mut investors: Vec<Pubkey> = Vec::with_capacity(accounts.len());
+    let mut total_sol: u64 = 0;
+    let mut total_usdc: u64 = 0;
+    for account in accounts.iter() {
+        let record = Account::<DepositorRecord>::try_from(account)?;
+        let (expected_record, _) = Pubkey::find_program_address(
+            &[INVESTOR_RECORD_SEED, record.investor.as_ref()],
+            program_id,
+        );
+        require!(account.key() == expected_record, ErrorCode::InvalidDepositorRecord);
+        require!(!investors.contains(&record.investor), ErrorCode::InvalidDepositorRecord);
+
+        investors.push(record.investor);
+        total_sol = add_u64(total_sol, record.current_sol_balance)?;
+        total_usdc = add_u64(total_usdc, record.current_usdc_balance)?;
+    }
+
+    Ok((total_sol, total_usdc))
+}
+
+impl<'info> ReconcileVaultStats<'info> {
+    pub fn handle(
+        ctx: Context<'_, '_, 'info, 'info, ReconcileVaultStats<'info>>,
+        params: ReconcileVaultStatsParams,
+    ) -> Result<()> {
+        msg!("Reconciling vault stats");
+
+        // The day's snapshot and payouts are computed against the current totals
+        require!(
+            !ctx.accounts.crank_state.is_day_in_progress(),
+            ErrorCode::DistributionInProgress
+        );
+
+        let (new_total_sol, new_total_usdc) = if params.from_records {
+            sum_record_totals(ctx.remaining_accounts, ctx.program_id)?
+        } else {
+            (ctx.accounts.sol_vault.lamports(), ctx.accounts.usdc_vault.amount)
+        };
+
+        let vault_stats = &mut ctx.accounts.vault_stats;
+        let old_total_sol = vault_stats.current_total_sol;
+        let old_total_usdc = vault_stats.current_total_usdc;
+
+        let now = Clock::get()?.unix_timestamp;
+        vault_stats.current_total_sol = new_total_sol;
+        vault_stats.current_total_usdc = new_total_usdc;
+        vault_stats.last_update_timestamp = now;
+
+        msg!("Current total SOL: {} -> {} lamports", old_total_sol, new_total_sol);
+        msg!("Current total USDC: {} -> {} units", old_total_usdc, new_total_usdc);
+
+        emit!(crate::events::VaultStatsCorrected {
+            admin: ctx.accounts.admin.key(),
+            from_records: params.from_records,
+            old_total_sol,
+            new_total_sol,
+            old_total_usdc,
+            new_total_usdc,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
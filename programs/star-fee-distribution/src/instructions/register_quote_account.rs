@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::constants::{DISTRIBUTION_CONFIG_SEED, INVESTOR_RECORD_SEED};
+use crate::states::{DepositorRecord, DistributionConfig};
+
+#[derive(Accounts)]
+pub struct RegisterQuoteAccount<'info> {
+    /// Investor registering where their payouts are sent
+    pub investor: Signer<'info>,
+    
+    /// Investor's depositor record
+    #[account(
+        mut,
+        seeds = [INVESTOR_RECORD_SEED, investor.key().as_ref()],
+        bump = depositor_record.bump,
+        has_one = investor
+    )]
+    pub depositor_record: Account<'info, DepositorRecord>,
+    
+    /// Distribution configuration
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+    
+    /// Quote token account owned by the investor that will receive their payouts
+    #[account(
+        token::mint = distribution_config.quote_mint,
+        token::authority = investor
+    )]
+    pub quote_account: Account<'info, TokenAccount>,
+}
+
+impl<'info> RegisterQuoteAccount<'info> {
+    pub fn handle(ctx: Context<RegisterQuoteAccount>) -> Result<()> {
+        let depositor_record = &mut ctx.accounts.depositor_record;
+        let previous_quote_account = depositor_record.registered_quote_account;
+        
+        depositor_record.registered_quote_account = ctx.accounts.quote_account.key();
+        
+        msg!("Registered quote account for investor: {}", ctx.accounts.investor.key());
+        msg!("Quote account: {} -> {}", previous_quote_account, depositor_record.registered_quote_account);
+        
+        emit!(crate::events::QuoteAccountRegistered {
+            investor: ctx.accounts.investor.key(),
+            previous_quote_account,
+            quote_account: depositor_record.registered_quote_account,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::constants::{CRANK_STATE_SEED, DISTRIBUTION_CONFIG_SEED};
+use crate::states::{CrankState, DistributionConfig};
+use super::initialize_distribution_config::{
+    validate_config_params, write_config_params, InitializeDistributionConfigParams,
+};
+use super::update_distribution_config::emit_config_updated;
+
+/// Admin instruction replacing every config parameter at once. The new set is validated as a
+/// whole before anything is written, so a single invalid field rejects the entire replacement
+#[derive(Accounts)]
+pub struct ReplaceConfig<'info> {
+    /// Admin who initialized the config
+    #[account(
+        constraint = admin.key() == distribution_config.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    /// Distribution configuration PDA (address and bump are kept)
+    #[account(
+        mut,
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    /// Crank state (absent if the crank has never run)
+    #[account(
+        seeds = [CRANK_STATE_SEED],
+        bump = crank_state.bump
+    )]
+    pub crank_state: Option<Account<'info, CrankState>>,
+}
+
+impl<'info> ReplaceConfig<'info> {
+    pub fn handle(ctx: Context<ReplaceConfig>, params: InitializeDistributionConfigParams) -> Result<()> {
+        msg!("Replacing distribution configuration");
+
+        // A replacement always touches the distribution math
+        if let Some(crank_state) = ctx.accounts.crank_state.as_ref() {
+            require!(
+                !crank_state.is_day_in_progress(),
+                ErrorCode::ConfigLockedDuringDistribution
+            );
+        }
+
+        let quote_decimals = validate_config_params(&params)?;
+
+        // These have their own instructions (creator wallet timelock, quote sweep, deposit vault)
+        let distribution_config = &mut ctx.accounts.distribution_config;
+        require!(
+            params.creator_wallet == distribution_config.creator_wallet
+                && params.quote_mint == distribution_config.quote_mint
+                && params.deposit_mint == distribution_config.deposit_mint,
+            ErrorCode::ConfigFieldNotReplaceable
+        );
+
+        // A replacement is a config change like any update, subject to the current cool-off
+        let now = Clock::get()?.unix_timestamp;
+        let cooloff_seconds = distribution_config.config_change_cooloff_seconds;
+        distribution_config.snapshot_fee_share(now);
+
+        write_config_params(distribution_config, &params, quote_decimals);
+        distribution_config.start_config_cooloff(now, cooloff_seconds)?;
+
+        msg!("Distribution configuration replaced");
+        msg!("Y0 allocation: {} units", distribution_config.y0_allocation);
+        msg!("Investor fee share: {} bps", distribution_config.investor_fee_share_bps);
+        msg!("Vested investor share: {} bps", distribution_config.vested_investor_share_bps);
+        msg!("Remainder policy: {}", distribution_config.remainder_policy);
+
+        emit_config_updated(distribution_config)
+    }
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::constants::{FEE_COLLECTOR_SEED, DISTRIBUTION_CONFIG_SEED};
+use crate::errors::ErrorCode;
+use crate::states::DistributionConfig;
+use crate::utils::is_canonical_vault;
+
+/// Admin instruction returning tokens sent by mistake to a `fee_collector`-owned token account
+/// that is not one of the program's fee or deposit vaults
+#[derive(Accounts)]
+pub struct RescueTokens<'info> {
+    /// Admin who initialized the config
+    #[account(
+        constraint = admin.key() == distribution_config.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    /// Distribution configuration PDA
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Box<Account<'info, DistributionConfig>>,
+
+    /// CHECK: Program authority (our program)
+    #[account(
+        seeds = [FEE_COLLECTOR_SEED],
+        bump
+    )]
+    pub fee_collector: UncheckedAccount<'info>,
+
+    /// Stray token account owned by the fee_collector PDA (never a canonical vault)
+    #[account(
+        mut,
+        token::authority = fee_collector,
+        constraint = !is_canonical_vault(&stray_token_account.key(), &stray_token_account.mint, &crate::ID)
+            @ ErrorCode::CanonicalVaultProtected
+    )]
+    pub stray_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Admin's token account for the same mint
+    #[account(
+        mut,
+        token::mint = stray_token_account.mint,
+        token::authority = admin
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> RescueTokens<'info> {
+    pub fn handle(ctx: Context<RescueTokens>) -> Result<()> {
+        msg!("Rescuing tokens from {}", ctx.accounts.stray_token_account.key());
+
+        let amount = ctx.accounts.stray_token_account.amount;
+        require!(amount > 0, ErrorCode::InsufficientBalance);
+
+        anchor_spl::token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::Transfer {
+                    from: ctx.accounts.stray_token_account.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.fee_collector.to_account_info(),
+                },
+                &[&[
+                    FEE_COLLECTOR_SEED,
+                    &[ctx.bumps.fee_collector]
+                ]]
+            ),
+            amount,
+        )?;
+
+        msg!("Rescued {} units of {}", amount, ctx.accounts.stray_token_account.mint);
+
+        emit!(crate::events::TokensRescued {
+            admin: ctx.accounts.admin.key(),
+            source: ctx.accounts.stray_token_account.key(),
+            destination: ctx.accounts.destination.key(),
+            mint: ctx.accounts.stray_token_account.mint,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::constants::DISTRIBUTION_CONFIG_SEED;
+use crate::states::DistributionConfig;
+
+#[derive(Accounts)]
+pub struct ScheduleCreatorWalletUpdate<'info> {
+    /// Admin who initialized the config
+    #[account(
+        constraint = admin.key() == distribution_config.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    /// Distribution configuration PDA
+    #[account(
+        mut,
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ScheduleCreatorWalletUpdateParams {
+    /// Creator wallet to adopt once the timelock has elapsed
+    pub new_creator_wallet: Pubkey,
+}
+
+impl<'info> ScheduleCreatorWalletUpdate<'info> {
+    pub fn handle(ctx: Context<ScheduleCreatorWalletUpdate>, params: ScheduleCreatorWalletUpdateParams) -> Result<()> {
+        msg!("Scheduling creator wallet update");
+
+        // Validate new creator wallet
+        require!(
+            params.new_creator_wallet != Pubkey::default(),
+            ErrorCode::CreatorWalletNotProvided
+        );
+
+        let distribution_config = &mut ctx.accounts.distribution_config;
+        let now = Clock::get()?.unix_timestamp;
+
+        // Scheduling again replaces any pending update and restarts the timelock
+        let effective_ts = now
+            .checked_add(distribution_config.creator_wallet_timelock_seconds)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        distribution_config.pending_creator_wallet = params.new_creator_wallet;
+        distribution_config.creator_update_effective_ts = effective_ts;
+
+        msg!("Current creator wallet: {}", distribution_config.creator_wallet);
+        msg!("Pending creator wallet: {}", params.new_creator_wallet);
+        msg!("Effective at: {}", effective_ts);
+
+        emit!(crate::events::CreatorWalletUpdateScheduled {
+            current_creator_wallet: distribution_config.creator_wallet,
+            pending_creator_wallet: params.new_creator_wallet,
+            effective_timestamp: effective_ts,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::constants::INVESTOR_RECORD_SEED;
+use crate::states::DepositorRecord;
+
+#[derive(Accounts)]
+pub struct SetAutoCompound<'info> {
+    /// Investor choosing how their payouts are delivered
+    pub investor: Signer<'info>,
+    
+    /// Investor's depositor record
+    #[account(
+        mut,
+        seeds = [INVESTOR_RECORD_SEED, investor.key().as_ref()],
+        bump = depositor_record.bump,
+        has_one = investor
+    )]
+    pub depositor_record: Account<'info, DepositorRecord>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SetAutoCompoundParams {
+    /// Add payouts to the locked USDC balance instead of sending them to the investor's wallet
+    pub auto_compound: bool,
+}
+
+impl<'info> SetAutoCompound<'info> {
+    pub fn handle(ctx: Context<SetAutoCompound>, params: SetAutoCompoundParams) -> Result<()> {
+        let depositor_record = &mut ctx.accounts.depositor_record;
+        depositor_record.auto_compound = params.auto_compound;
+        
+        msg!("Auto-compound for investor {}: {}", ctx.accounts.investor.key(), params.auto_compound);
+        
+        emit!(crate::events::AutoCompoundSet {
+            investor: ctx.accounts.investor.key(),
+            auto_compound: params.auto_compound,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
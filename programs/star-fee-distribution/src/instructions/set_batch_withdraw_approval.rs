@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::constants::INVESTOR_RECORD_SEED;
+use crate::states::DepositorRecord;
+
+#[derive(Accounts)]
+pub struct SetBatchWithdrawApproval<'info> {
+    /// Investor approving or revoking an admin-assisted withdrawal
+    pub investor: Signer<'info>,
+    
+    /// Investor's depositor record
+    #[account(
+        mut,
+        seeds = [INVESTOR_RECORD_SEED, investor.key().as_ref()],
+        bump = depositor_record.bump,
+        has_one = investor
+    )]
+    pub depositor_record: Account<'info, DepositorRecord>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SetBatchWithdrawApprovalParams {
+    /// Allow the admin to withdraw the full USDC balance to the registered quote account
+    pub approved: bool,
+}
+
+impl<'info> SetBatchWithdrawApproval<'info> {
+    pub fn handle(ctx: Context<SetBatchWithdrawApproval>, params: SetBatchWithdrawApprovalParams) -> Result<()> {
+        let depositor_record = &mut ctx.accounts.depositor_record;
+        depositor_record.batch_withdraw_approved = params.approved;
+        
+        msg!("Batch withdrawal approval for investor {}: {}", ctx.accounts.investor.key(), params.approved);
+        
+        emit!(crate::events::BatchWithdrawApprovalSet {
+            investor: ctx.accounts.investor.key(),
+            approved: params.approved,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
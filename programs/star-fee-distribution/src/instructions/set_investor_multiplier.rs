@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::constants::{DISTRIBUTION_CONFIG_SEED, INVESTOR_RECORD_SEED, DEPOSIT_VAULT_SEED, CRANK_STATE_SEED, MAX_FEE_SHARE_MULTIPLIER_BPS};
+use crate::states::{DistributionConfig, DepositorRecord, VaultStats};
+use super::deposit::require_deposits_unlocked;
+
+#[derive(Accounts)]
+#[instruction(params: SetInvestorMultiplierParams)]
+pub struct SetInvestorMultiplier<'info> {
+    /// Admin who initialized the config
+    #[account(
+        constraint = admin.key() == distribution_config.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+    
+    /// Distribution configuration
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+    
+    /// Investor's depositor record
+    #[account(
+        mut,
+        seeds = [INVESTOR_RECORD_SEED, params.investor.as_ref()],
+        bump = depositor_record.bump
+    )]
+    pub depositor_record: Account<'info, DepositorRecord>,
+    
+    /// Global vault statistics
+    #[account(
+        mut,
+        seeds = [DEPOSIT_VAULT_SEED, b"stats"],
+        bump = vault_stats.bump
+    )]
+    pub vault_stats: Account<'info, VaultStats>,
+    
+    /// CHECK: Crank state PDA, read for the deposit lock once a day has been begun
+    #[account(
+        seeds = [CRANK_STATE_SEED],
+        bump
+    )]
+    pub crank_state: UncheckedAccount<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SetInvestorMultiplierParams {
+    /// Investor whose multiplier is being set
+    pub investor: Pubkey,
+    /// Fee share multiplier in basis points (10000 = 1x)
+    pub fee_share_multiplier_bps: u16,
+}
+
+impl<'info> SetInvestorMultiplier<'info> {
+    pub fn handle(ctx: Context<SetInvestorMultiplier>, params: SetInvestorMultiplierParams) -> Result<()> {
+        msg!("Setting fee share multiplier for investor: {}", params.investor);
+        
+        // Validate multiplier (max 30000 bps = 3x)
+        require!(
+            params.fee_share_multiplier_bps > 0
+                && params.fee_share_multiplier_bps <= MAX_FEE_SHARE_MULTIPLIER_BPS,
+            ErrorCode::InvalidFeeShareMultiplier
+        );
+        
+        // Payouts are weighted against the weighted total, which cannot move while a day pays out
+        require_deposits_unlocked(&ctx.accounts.crank_state)?;
+        
+        let depositor_record = &mut ctx.accounts.depositor_record;
+        let vault_stats = &mut ctx.accounts.vault_stats;
+        
+        let previous_multiplier_bps = depositor_record.fee_share_multiplier_bps;
+        let previous_weighted_usdc = depositor_record.weighted_usdc_balance()?;
+        
+        depositor_record.fee_share_multiplier_bps = params.fee_share_multiplier_bps;
+        
+        // Keep the weighted total in sync so payouts still sum to the pool
+        vault_stats.update_weighted_usdc(
+            previous_weighted_usdc,
+            depositor_record.weighted_usdc_balance()?,
+        )?;
+        
+        msg!("Multiplier: {} -> {} bps", previous_multiplier_bps, params.fee_share_multiplier_bps);
+        msg!("Total weighted USDC: {} units", vault_stats.current_total_weighted_usdc);
+        
+        emit!(crate::events::InvestorMultiplierSet {
+            investor: params.investor,
+            previous_multiplier_bps,
+            fee_share_multiplier_bps: params.fee_share_multiplier_bps,
+            total_weighted_usdc: vault_stats.current_total_weighted_usdc,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use crate::constants::{INVESTOR_RECORD_SEED, DEPOSIT_VAULT_SEED, CRANK_STATE_SEED};
+use crate::states::{DepositorRecord, VaultStats};
+use super::deposit::require_deposits_unlocked;
+
+#[derive(Accounts)]
+pub struct SetOptOut<'info> {
+    /// Investor choosing whether to receive fee distributions
+    pub investor: Signer<'info>,
+    
+    /// Investor's depositor record
+    #[account(
+        mut,
+        seeds = [INVESTOR_RECORD_SEED, investor.key().as_ref()],
+        bump = depositor_record.bump,
+        has_one = investor
+    )]
+    pub depositor_record: Account<'info, DepositorRecord>,
+    
+    /// Global vault statistics
+    #[account(
+        mut,
+        seeds = [DEPOSIT_VAULT_SEED, b"stats"],
+        bump = vault_stats.bump
+    )]
+    pub vault_stats: Account<'info, VaultStats>,
+    
+    /// CHECK: Crank state PDA, read for the deposit lock once a day has been begun
+    #[account(
+        seeds = [CRANK_STATE_SEED],
+        bump
+    )]
+    pub crank_state: UncheckedAccount<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SetOptOutParams {
+    /// Forgo fee distributions, leaving the investor's share to the other investors
+    pub opted_out: bool,
+}
+
+impl<'info> SetOptOut<'info> {
+    pub fn handle(ctx: Context<SetOptOut>, params: SetOptOutParams) -> Result<()> {
+        // Payouts are weighted against the weighted total, which cannot move while a day pays out
+        require_deposits_unlocked(&ctx.accounts.crank_state)?;
+        
+        let depositor_record = &mut ctx.accounts.depositor_record;
+        let vault_stats = &mut ctx.accounts.vault_stats;
+        
+        let previous_weighted_usdc = depositor_record.weighted_usdc_balance()?;
+        depositor_record.opted_out = params.opted_out;
+        
+        // An opted-out record carries no weight, so the others' shares are renormalized over the rest
+        vault_stats.update_weighted_usdc(
+            previous_weighted_usdc,
+            depositor_record.weighted_usdc_balance()?,
+        )?;
+        
+        msg!("Opt-out for investor {}: {}", ctx.accounts.investor.key(), params.opted_out);
+        msg!("Total weighted USDC: {} units", vault_stats.current_total_weighted_usdc);
+        
+        emit!(crate::events::OptOutSet {
+            investor: ctx.accounts.investor.key(),
+            opted_out: params.opted_out,
+            total_weighted_usdc: vault_stats.current_total_weighted_usdc,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+}
@@ -0,0 +1,300 @@
+use anchor_lang::prelude::*;
+use crate::constants::{
+    INVESTOR_RECORD_SEED, DEPOSIT_VAULT_SEED, CRANK_STATE_SEED, DISTRIBUTION_CONFIG_SEED,
+    REMAINDER_POLICY_INVESTORS, PPM_PER_BPS,
+};
+use crate::errors::ErrorCode;
+use crate::math::add_u64;
+use crate::states::{CrankState, DepositorRecord, DistributionConfig, VaultStats};
+use crate::utils::load_if_initialized;
+use super::crank_fee_distribution::{
+    apply_age_multiplier, calculate_investor_payout, effective_eligible_share_ppm, investor_fee_quote_from_ppm,
+    smooth_round_quote,
+};
+
+/// Most depositor records one simulation can return payouts for (the return data limit is 1024 bytes)
+pub const MAX_SIMULATED_INVESTORS: usize = 20;
+
+/// Read-only instruction computing the next day's payouts for a hypothetical claimed quote
+/// amount, without moving funds or touching state. Depositor records to simulate are passed
+/// in `remaining_accounts`
+#[derive(Accounts)]
+pub struct SimulateDay<'info> {
+    /// Distribution configuration PDA
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    /// Global vault statistics
+    #[account(
+        seeds = [DEPOSIT_VAULT_SEED, b"stats"],
+        bump = vault_stats.bump
+    )]
+    pub vault_stats: Account<'info, VaultStats>,
+
+    /// CHECK: Crank state PDA, read for the carry-over and day number once initialized
+    #[account(
+        seeds = [CRANK_STATE_SEED],
+        bump
+    )]
+    pub crank_state: UncheckedAccount<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SimulateDayParams {
+    /// Quote claimed for the day, excluding the carry-over and buffered quote already held in
+    /// the vault (the buffer is added and `distribution_rate_bps` applied like the crank does)
+    pub claimed_quote: u64,
+}
+
+/// Payout one investor would receive
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct SimulatedPayout {
+    /// Investor public key
+    pub investor: Pubkey,
+    /// Quote transferred to the investor (0 below the dust threshold or when not yet eligible)
+    pub payout: u64,
+}
+
+/// Response structure for a simulated distribution day
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct SimulatedDay {
+    /// Day the simulation applies to (the day in progress, or the next one)
+    pub day: u32,
+    /// Eligible investor share in basis points (floored, the allocation uses parts per million)
+    pub eligible_investor_share_bps: u16,
+    /// Investor pool, including the carry-over when the carry-over policy adds it
+    pub investor_fee_quote: u64,
+    /// Payouts for the records passed, in the same order
+    pub payouts: Vec<SimulatedPayout>,
+    /// Sum of the payouts
+    pub total_to_investors: u64,
+    /// Shares retained as carry-over (below the dust threshold, not yet eligible or withheld by age bracket)
+    pub total_dust: u64,
+    /// Quote routed to the creator or treasury when the day closes (0 under the investor policy)
+    pub creator_remainder: u64,
+}
+
+/// Depositor record inputs to `simulate_payouts`
+pub struct SimulatedRecord {
+    pub investor: Pubkey,
+    pub weighted_usdc_balance: u64,
+    pub age_multiplier_bps: u16,
+    pub eligible: bool,
+}
+
+/// Computes a day's payouts the way `crank_fee_distribution` and `distribute_to_investor` do for
+/// DepositorRecord investors: the pool is the eligible share of `claimed_quote` (plus carry-over
+/// under the carry-over policy), each record is paid pro rata by multiplier- and age-scaled balance, and
+/// payouts below the dust threshold or to records not yet eligible stay as carry-over. The daily
+/// cap, vested payouts and external vesting are not simulated
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_payouts(
+    config: &DistributionConfig,
+    day: u32,
+    claimed_quote: u64,
+    carry_over: u64,
+    locked_total: u64,
+    total_weighted_usdc: u64,
+    expected_investors: u32,
+    records: &[SimulatedRecord],
+) -> Result<SimulatedDay> {
+    let eligible_share_ppm = effective_eligible_share_ppm(config, locked_total)?;
+    let eligible_investor_share_bps = u16::try_from(eligible_share_ppm / PPM_PER_BPS)
+        .map_err(|_| ErrorCode::MathOverflow)?;
+
+    let round_investor_fee = if config.remainder_policy == REMAINDER_POLICY_INVESTORS {
+        claimed_quote
+    } else {
+        investor_fee_quote_from_ppm(claimed_quote, eligible_share_ppm)?
+    };
+    let included_carry_over = if config.includes_carry_over(claimed_quote, carry_over) {
+        carry_over
+    } else {
+        0
+    };
+    let investor_fee_quote = add_u64(round_investor_fee, included_carry_over)?;
+
+    let min_payout = config.min_payout(investor_fee_quote, expected_investors);
+    let mut payouts = Vec::with_capacity(records.len());
+    let mut total_to_investors: u64 = 0;
+    let mut total_dust: u64 = 0;
+    for record in records.iter() {
+        let (weight, age_withheld) = if config.has_age_brackets() {
+            apply_age_multiplier(
+                record.weighted_usdc_balance,
+                total_weighted_usdc,
+                investor_fee_quote,
+                record.age_multiplier_bps,
+                config.max_age_multiplier_bps(),
+            )?
+        } else {
+            (record.weighted_usdc_balance, 0)
+        };
+        let (payout, dust) = calculate_investor_payout(
+            weight,
+            total_weighted_usdc,
+            investor_fee_quote,
+            min_payout,
+        )?;
+        let dust = add_u64(dust, age_withheld)?;
+        let (payout, dust) = if record.eligible {
+            (payout, dust)
+        } else {
+            (0, add_u64(payout, dust)?)
+        };
+
+        total_to_investors = add_u64(total_to_investors, payout)?;
+        total_dust = add_u64(total_dust, dust)?;
+        payouts.push(SimulatedPayout { investor: record.investor, payout });
+    }
+
+    // Everything fresh that was neither paid nor kept as dust is routed at close, unless
+    // investors receive the remainder
+    let creator_remainder = if config.remainder_policy == REMAINDER_POLICY_INVESTORS {
+        0
+    } else {
+        add_u64(claimed_quote, included_carry_over)?
+            .saturating_sub(total_to_investors)
+            .saturating_sub(total_dust)
+    };
+
+    Ok(SimulatedDay {
+        day,
+        eligible_investor_share_bps,
+        investor_fee_quote,
+        payouts,
+        total_to_investors,
+        total_dust,
+        creator_remainder,
+    })
+}
+
+impl<'info> SimulateDay<'info> {
+    pub fn handle(
+        ctx: Context<'_, '_, 'info, 'info, SimulateDay<'info>>,
+        params: SimulateDayParams,
+    ) -> Result<SimulatedDay> {
+        require!(
+            ctx.remaining_accounts.len() <= MAX_SIMULATED_INVESTORS,
+            ErrorCode::TooManySimulatedRecords
+        );
+
+        let vault_stats = &ctx.accounts.vault_stats;
+        let crank_state = load_if_initialized::<CrankState>(&ctx.accounts.crank_state)?;
+        let now = Clock::get()?.unix_timestamp;
+        
+        // Use the fee share the crank will: as of the day in progress's start, or of a day begun now
+        let day_start_ts = match crank_state.as_ref() {
+            Some(crank_state) if crank_state.is_day_in_progress() => crank_state.last_distribution_timestamp,
+            _ => now,
+        };
+        let config = &*ctx.accounts.distribution_config.effective_at(day_start_ts);
+        let (day, carry_over) = match crank_state.as_ref() {
+            Some(crank_state) if crank_state.is_day_in_progress() => (crank_state.current_day, crank_state.carry_over),
+            Some(crank_state) => (
+                crank_state.current_day.checked_add(1).ok_or(ErrorCode::MathOverflow)?,
+                crank_state.carry_over,
+            ),
+            None => (1, 0),
+        };
+        
+        // A day in progress takes the claim as a later round, which releases no buffer
+        let first_round = crank_state.as_ref().is_none_or(|crank_state| !crank_state.is_day_in_progress());
+        let buffered_quote = crank_state.as_ref().map_or(0, |crank_state| crank_state.buffered_quote);
+        let (claimed_quote, _) = smooth_round_quote(config, params.claimed_quote, buffered_quote, first_round)?;
+
+        let mut records: Vec<SimulatedRecord> = Vec::with_capacity(ctx.remaining_accounts.len());
+        for account in ctx.remaining_accounts.iter() {
+            let record = Account::<DepositorRecord>::try_from(account)?;
+            let (expected_record, _) = Pubkey::find_program_address(
+                &[INVESTOR_RECORD_SEED, record.investor.as_ref()],
+                ctx.program_id,
+            );
+            require!(account.key() == expected_record, ErrorCode::InvalidDepositorRecord);
+            require!(
+                records.iter().all(|simulated| simulated.investor != record.investor),
+                ErrorCode::InvalidDepositorRecord
+            );
+
+            records.push(SimulatedRecord {
+                investor: record.investor,
+                weighted_usdc_balance: record.weighted_usdc_balance()?,
+                age_multiplier_bps: config.age_multiplier_bps(now.saturating_sub(record.first_deposit_timestamp)),
+                eligible: record.first_eligible_day <= day
+                    && (!config.requires_eligibility_proof() || record.eligible),
+            });
+        }
+
+        let simulated = simulate_payouts(
+            config,
+            day,
+            claimed_quote,
+            carry_over,
+            vault_stats.current_total_usdc,
+            vault_stats.current_total_weighted_usdc,
+            vault_stats.depositor_count,
+            &records,
+        )?;
+
+        msg!("Simulated day {} over {} records:", simulated.day, simulated.payouts.len());
+        msg!("Investor pool: {} units ({} bps)", simulated.investor_fee_quote, simulated.eligible_investor_share_bps);
+        msg!("Total to investors: {} units", simulated.total_to_investors);
+        msg!("Total dust: {} units", simulated.total_dust);
+        msg!("Creator remainder: {} units", simulated.creator_remainder);
+
+        Ok(simulated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::DEFAULT_FEE_SHARE_MULTIPLIER_BPS;
+
+    fn record(weighted_usdc_balance: u64, eligible: bool) -> SimulatedRecord {
+        SimulatedRecord {
+            investor: Pubkey::new_unique(),
+            weighted_usdc_balance,
+            age_multiplier_bps: DEFAULT_FEE_SHARE_MULTIPLIER_BPS,
+            eligible,
+        }
+    }
+
+    #[test]
+    fn simulation_splits_the_claim_between_investors_dust_and_creator() {
+        let config = DistributionConfig {
+            y0_allocation: 1_000,
+            investor_fee_share_bps: 5_000,
+            min_payout_lamports: 100,
+            ..Default::default()
+        };
+        // Fully locked: half of 10_000 goes to investors weighted 6:3:1, the smallest below dust
+        let records = [record(600, true), record(300, true), record(50, true), record(50, false)];
+        let simulated = simulate_payouts(&config, 1, 10_000, 0, 1_000, 1_000, 4, &records).unwrap();
+
+        assert_eq!(simulated.eligible_investor_share_bps, 5_000);
+        assert_eq!(simulated.investor_fee_quote, 5_000);
+        let payouts: Vec<u64> = simulated.payouts.iter().map(|payout| payout.payout).collect();
+        assert_eq!(payouts, vec![3_000, 1_500, 250, 0]);
+        assert_eq!(simulated.total_to_investors, 4_750);
+        assert_eq!(simulated.total_dust, 250);
+        assert_eq!(simulated.creator_remainder, 5_000);
+    }
+
+    #[test]
+    fn simulation_leaves_no_remainder_under_the_investor_policy() {
+        let config = DistributionConfig {
+            y0_allocation: 1_000,
+            investor_fee_share_bps: 5_000,
+            remainder_policy: REMAINDER_POLICY_INVESTORS,
+            ..Default::default()
+        };
+        let simulated = simulate_payouts(&config, 1, 10_000, 0, 1_000, 1_000, 1, &[record(1_000, true)]).unwrap();
+
+        assert_eq!(simulated.total_to_investors, 10_000);
+        assert_eq!(simulated.creator_remainder, 0);
+    }
+}
@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::constants::{DISTRIBUTION_CONFIG_SEED, CRANK_STATE_SEED, DAY_RECORD_SEED};
+use crate::states::{DistributionConfig, CrankState, DayRecord};
+use crate::utils::{payout_leaf_hash, merkle_root};
+
+#[derive(Accounts)]
+pub struct SubmitPayoutRoot<'info> {
+    /// Config admin or creator wallet submitting the root
+    #[account(
+        mut,
+        constraint = submitter.key() == distribution_config.admin
+            || submitter.key() == distribution_config.creator_wallet
+            @ ErrorCode::UnauthorizedPayoutRootSubmitter
+    )]
+    pub submitter: Signer<'info>,
+    
+    /// Distribution configuration
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+    
+    /// Crank state holding the closed day's totals
+    #[account(
+        seeds = [CRANK_STATE_SEED],
+        bump = crank_state.bump
+    )]
+    pub crank_state: Account<'info, CrankState>,
+    
+    /// Day record for the closed day (one root per day). Already created by whichever of
+    /// `route_creator_remainder`, `force_close_day` or the crank closed the day
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = DayRecord::DISCRIMINATOR.len() + DayRecord::INIT_SPACE,
+        seeds = [DAY_RECORD_SEED, crank_state.current_day.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub day_record: Account<'info, DayRecord>,
+    
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PayoutLeaf {
+    /// Investor wallet that received the payout
+    pub investor: Pubkey,
+    /// Amount paid to the investor for the day
+    pub amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SubmitPayoutRootParams {
+    /// Payout leaves the root was built from
+    pub leaves: Vec<PayoutLeaf>,
+    /// Merkle root computed off-chain
+    pub payout_root: [u8; 32],
+}
+
+impl<'info> SubmitPayoutRoot<'info> {
+    pub fn handle(ctx: Context<SubmitPayoutRoot>, params: SubmitPayoutRootParams) -> Result<()> {
+        let crank_state = &ctx.accounts.crank_state;
+        
+        msg!("Submitting payout root for day {}", crank_state.current_day);
+        
+        // Payouts are only final once the day is closed
+        require!(
+            crank_state.is_day_closed(),
+            ErrorCode::DayNotClosed
+        );
+        
+        require!(
+            ctx.accounts.day_record.submitted_timestamp == 0,
+            ErrorCode::PayoutRootAlreadySubmitted
+        );
+        
+        // Leaves must account for exactly what was distributed to investors
+        let mut total_distributed: u64 = 0;
+        for leaf in params.leaves.iter() {
+            total_distributed = total_distributed
+                .checked_add(leaf.amount)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+        
+        msg!("Leaves total: {} units", total_distributed);
+        msg!("Daily distributed: {} units", crank_state.daily_distributed);
+        
+        require!(
+            total_distributed == crank_state.daily_distributed,
+            ErrorCode::PayoutRootMismatch
+        );
+        
+        // Recompute the root from the leaves
+        let leaf_hashes = params.leaves
+            .iter()
+            .map(|leaf| payout_leaf_hash(&leaf.investor, leaf.amount))
+            .collect();
+        let computed_root = merkle_root(leaf_hashes);
+        
+        require!(
+            computed_root == params.payout_root,
+            ErrorCode::PayoutRootMismatch
+        );
+        
+        let leaf_count = u32::try_from(params.leaves.len()).map_err(|_| ErrorCode::MathOverflow)?;
+        let now = Clock::get()?.unix_timestamp;
+        
+        let day_record = &mut ctx.accounts.day_record;
+        day_record.day = crank_state.current_day;
+        day_record.payout_root = computed_root;
+        day_record.total_distributed = total_distributed;
+        day_record.leaf_count = leaf_count;
+        day_record.submitted_by = ctx.accounts.submitter.key();
+        day_record.submitted_timestamp = now;
+        day_record.bump = ctx.bumps.day_record;
+        
+        msg!("Payout root stored for day {} ({} leaves)", day_record.day, leaf_count);
+        
+        emit!(crate::events::PayoutRootSubmitted {
+            day: day_record.day,
+            payout_root: computed_root,
+            total_distributed,
+            leaf_count,
+            submitted_by: day_record.submitted_by,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+}
@@ -0,0 +1,475 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::constants::{
+    CRANK_STATE_SEED, DISTRIBUTION_CONFIG_SEED, DEFAULT_DAY_MAX_DURATION_SECONDS, CARRY_OVER_POLICY_HOLD,
+    CARRY_OVER_CAP_POLICY_TOP_INVESTOR, ZERO_BALANCE_POLICY_REJECT, SECONDS_PER_DAY, MAX_ROUNDS_PER_DAY, MAX_AGE_BRACKETS,
+};
+use crate::states::{AgeBracket, CrankState, DistributionConfig};
+use crate::utils::to_ui_amount;
+use super::crank_fee_distribution::{validate_f_locked_band, validate_creator_min_share};
+use super::initialize_distribution_config::{validate_age_brackets, validate_deposit_window, validate_min_payout_below_cap};
+
+#[derive(Accounts)]
+pub struct UpdateDistributionConfig<'info> {
+    /// Admin who initialized the config
+    #[account(
+        constraint = admin.key() == distribution_config.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    /// Distribution configuration PDA
+    #[account(
+        mut,
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    /// Crank state (absent if the crank has never run)
+    #[account(
+        seeds = [CRANK_STATE_SEED],
+        bump = crank_state.bump
+    )]
+    pub crank_state: Option<Account<'info, CrankState>>,
+}
+
+/// Fields left as None are unchanged
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UpdateDistributionConfigParams {
+    /// Total investor allocation at TGE (Y0), locked while a day is in progress
+    pub y0_allocation: Option<u64>,
+    /// Investor fee share in basis points, locked while a day is in progress
+    pub investor_fee_share_bps: Option<u16>,
+    /// External vesting program, locked while a day is in progress
+    pub vesting_program: Option<Pubkey>,
+    /// Share of the creator remainder paid to fully-vested investors, locked while a day is in progress
+    pub vested_investor_share_bps: Option<u16>,
+    /// Minimum payout amount in lamports (dust threshold)
+    pub min_payout_lamports: Option<u64>,
+    /// Legacy daily distribution cap, only used while `daily_cap_quote_units` is 0
+    pub daily_cap_lamports: Option<u64>,
+    /// Route the creator remainder and close the day from the crank's final page
+    pub auto_close_on_final_page: Option<bool>,
+    /// Emit per-page and per-investor payout events
+    pub emit_events: Option<bool>,
+    /// Merkle root of investors allowed to receive payouts (all zeros = no allowlist), locked while a day is in progress
+    pub eligibility_root: Option<[u8; 32]>,
+    /// Daily distribution cap in the quote mint's native units (0 = use `daily_cap_lamports`)
+    pub daily_cap_quote_units: Option<u64>,
+    /// Seconds after a day begins before anyone can force-close it (0 = default 48h, otherwise at
+    /// least 24h), locked while a day is in progress
+    pub day_max_duration_seconds: Option<i64>,
+    /// Minimum time between an investor's deposits in seconds (0 = no limit)
+    pub min_deposit_interval_seconds: Option<i64>,
+    /// Whether carry-over joins the day's investor pool (0 = retain, 1 = include, 2 = hold until covered)
+    pub carry_over_policy: Option<u8>,
+    /// Lower bound for f_locked in basis points (0 = none), locked while a day is in progress
+    pub f_locked_floor_bps: Option<u16>,
+    /// Upper bound for f_locked in basis points (0 = none), locked while a day is in progress
+    pub f_locked_ceiling_bps: Option<u16>,
+    /// Cross-check vault stats against the depositor records on page 0
+    pub verify_total_locked: Option<bool>,
+    /// Allowed drift between the summed records and vault stats (in quote native units)
+    pub total_locked_tolerance_units: Option<u64>,
+    /// Mint the creator remainder is paid in (Pubkey::default() = quote mint)
+    pub creator_payout_mint: Option<Pubkey>,
+    /// Deposits are accepted from this timestamp (0 = no start bound)
+    pub deposit_window_start_ts: Option<i64>,
+    /// Deposits are rejected from this timestamp (0 = no end bound)
+    pub deposit_window_end_ts: Option<i64>,
+    /// Carry-over retained past a day close in quote native units (0 = no cap)
+    pub max_carry_over: Option<u64>,
+    /// Where carry-over above the cap goes (0 = creator, 1 = largest-weight investor)
+    pub carry_over_cap_policy: Option<u8>,
+    /// How zero-balance depositor records are treated during distribution (0 = skip, 1 = reject)
+    pub zero_balance_policy: Option<u8>,
+    /// Distribution rounds per day, evenly spaced from the day start (1 = one round a day)
+    pub rounds_per_day: Option<u8>,
+    /// Locked total below which a day's fees skip the investor distribution (0 = no minimum)
+    pub min_total_locked_to_distribute: Option<u64>,
+    /// Weight external vesting payouts by locked amount times remaining lock time
+    pub weight_by_remaining_lock: Option<bool>,
+    /// Seconds without a creator payout after which the creator remainder goes to investors (0 = never)
+    pub creator_inactivity_seconds: Option<i64>,
+    /// Minimum share of claimed fees kept for the creator in basis points, whatever f_locked is (0 = none)
+    pub creator_min_share_bps: Option<u16>,
+    /// Scale the dust threshold as investor_fee_quote / (k * investor_count) (0 = fixed `min_payout_lamports`)
+    pub dust_threshold_k: Option<u32>,
+    /// Require non-final crank pages of exactly `DISTRIBUTION_BATCH_SIZE` investors
+    pub fixed_page_size: Option<bool>,
+    /// End the program: reject deposits and let withdrawals skip the day's lock (one-way)
+    pub program_ended: Option<bool>,
+    /// Program notified when route_creator_remainder closes a day (default = no callback)
+    pub callback_program: Option<Pubkey>,
+    /// Largest share of the vault's USDC a deposit may take an investor to, in bps (0 = no cap)
+    pub max_investor_share_bps: Option<u16>,
+    /// SOL paid from the keeper incentive account per crank page (0 = no reward)
+    pub keeper_reward_lamports: Option<u64>,
+    /// Timestamp until which compounded earnings cannot be withdrawn (0 = never locked)
+    pub earnings_unlock_ts: Option<i64>,
+    /// Deposit age brackets scaling investor weights, by ascending age (unused entries zeroed)
+    pub age_brackets: Option<[AgeBracket; MAX_AGE_BRACKETS]>,
+    /// Share of the accumulated quote released each day, in basis points (0 = release everything)
+    pub distribution_rate_bps: Option<u16>,
+    /// Require a fee claim into the quote vault during the day before its first crank page
+    pub require_fresh_claim: Option<bool>,
+    /// Quote vault balance below which an `auto_claim` crank claims the position's fees first (0 = never)
+    pub min_crankable_quote: Option<u64>,
+    /// Stop new days from starting while letting the day in progress finish
+    pub new_days_paused: Option<bool>,
+    /// Largest total USDC one investor may deposit, in basis points of Y0 (0 = no cap)
+    pub max_investor_share_of_y0_bps: Option<u16>,
+    /// Delay after a config change before the crank uses the new fee share (0 = immediate)
+    pub config_change_cooloff_seconds: Option<i64>,
+    /// Largest quote amount a single position fee claim may bring in (0 = no maximum)
+    pub max_claim_per_tx: Option<u64>,
+    /// Wallet whose quote account receives the carry-over dust at day close (default = dust is retained)
+    pub dust_recipient: Option<Pubkey>,
+    /// Reject investor payouts into quote accounts with a delegate set
+    pub reject_delegated_accounts: Option<bool>,
+}
+
+impl UpdateDistributionConfigParams {
+    /// Checks if the update touches parameters used by in-flight distribution math
+    pub fn touches_distribution_math(&self) -> bool {
+        self.y0_allocation.is_some()
+            || self.investor_fee_share_bps.is_some()
+            || self.vesting_program.is_some()
+            || self.vested_investor_share_bps.is_some()
+            || self.eligibility_root.is_some()
+            || self.f_locked_floor_bps.is_some()
+            || self.f_locked_ceiling_bps.is_some()
+            || self.rounds_per_day.is_some()
+            || self.weight_by_remaining_lock.is_some()
+            || self.creator_min_share_bps.is_some()
+            || self.age_brackets.is_some()
+            || self.distribution_rate_bps.is_some()
+    }
+}
+
+impl<'info> UpdateDistributionConfig<'info> {
+    pub fn handle(ctx: Context<UpdateDistributionConfig>, params: UpdateDistributionConfigParams) -> Result<()> {
+        msg!("Updating distribution configuration");
+
+        // The day's allocation was computed from these parameters, so they cannot move mid-day.
+        // Nor can the day's deadline, which its deposit lock runs to
+        if params.touches_distribution_math() || params.day_max_duration_seconds.is_some() {
+            if let Some(crank_state) = ctx.accounts.crank_state.as_ref() {
+                require!(
+                    !crank_state.is_day_in_progress(),
+                    ErrorCode::ConfigLockedDuringDistribution
+                );
+            }
+        }
+
+        let distribution_config = &mut ctx.accounts.distribution_config;
+
+        // Days begun during the cool-off keep the fee share in effect before this change
+        let now = Clock::get()?.unix_timestamp;
+        let cooloff_seconds = distribution_config.config_change_cooloff_seconds;
+        distribution_config.snapshot_fee_share(now);
+
+        if let Some(y0_allocation) = params.y0_allocation {
+            require!(y0_allocation > 0, ErrorCode::InvalidY0Allocation);
+            distribution_config.y0_allocation = y0_allocation;
+        }
+
+        if let Some(investor_fee_share_bps) = params.investor_fee_share_bps {
+            require!(investor_fee_share_bps <= 10000, ErrorCode::InvalidDepositAmount);
+            distribution_config.investor_fee_share_bps = investor_fee_share_bps;
+        }
+
+        if params.creator_min_share_bps.is_some() || params.investor_fee_share_bps.is_some() {
+            let creator_min_share_bps = params.creator_min_share_bps.unwrap_or(distribution_config.creator_min_share_bps);
+            validate_creator_min_share(
+                creator_min_share_bps,
+                distribution_config.investor_fee_share_bps,
+                distribution_config.remainder_policy,
+            )?;
+            distribution_config.creator_min_share_bps = creator_min_share_bps;
+        }
+
+        if let Some(vesting_program) = params.vesting_program {
+            distribution_config.vesting_program = vesting_program;
+        }
+
+        if let Some(vested_investor_share_bps) = params.vested_investor_share_bps {
+            require!(vested_investor_share_bps <= 10000, ErrorCode::InvalidDepositAmount);
+            distribution_config.vested_investor_share_bps = vested_investor_share_bps;
+        }
+
+        if params.f_locked_floor_bps.is_some() || params.f_locked_ceiling_bps.is_some() {
+            let floor_bps = params.f_locked_floor_bps.unwrap_or(distribution_config.f_locked_floor_bps);
+            let ceiling_bps = params.f_locked_ceiling_bps.unwrap_or(distribution_config.f_locked_ceiling_bps);
+            validate_f_locked_band(floor_bps, ceiling_bps)?;
+            distribution_config.f_locked_floor_bps = floor_bps;
+            distribution_config.f_locked_ceiling_bps = ceiling_bps;
+        }
+
+        if let Some(min_payout_lamports) = params.min_payout_lamports {
+            distribution_config.min_payout_lamports = min_payout_lamports;
+        }
+
+        if let Some(dust_threshold_k) = params.dust_threshold_k {
+            distribution_config.dust_threshold_k = dust_threshold_k;
+        }
+
+        if let Some(fixed_page_size) = params.fixed_page_size {
+            distribution_config.fixed_page_size = fixed_page_size;
+        }
+
+        if let Some(program_ended) = params.program_ended {
+            require!(
+                program_ended || !distribution_config.program_ended,
+                ErrorCode::ProgramEnded
+            );
+            if program_ended && !distribution_config.program_ended {
+                msg!("Program ended: deposits disabled, withdrawals unlocked");
+            }
+            distribution_config.program_ended = program_ended;
+        }
+
+        if let Some(callback_program) = params.callback_program {
+            distribution_config.callback_program = callback_program;
+        }
+
+        if let Some(max_investor_share_bps) = params.max_investor_share_bps {
+            require!(max_investor_share_bps <= 10000, ErrorCode::InvalidDepositAmount);
+            distribution_config.max_investor_share_bps = max_investor_share_bps;
+        }
+
+        if let Some(max_investor_share_of_y0_bps) = params.max_investor_share_of_y0_bps {
+            require!(max_investor_share_of_y0_bps <= 10000, ErrorCode::InvalidDepositAmount);
+            distribution_config.max_investor_share_of_y0_bps = max_investor_share_of_y0_bps;
+        }
+
+        if let Some(config_change_cooloff_seconds) = params.config_change_cooloff_seconds {
+            require!(config_change_cooloff_seconds >= 0, ErrorCode::InvalidConfigCooloff);
+            distribution_config.config_change_cooloff_seconds = config_change_cooloff_seconds;
+        }
+
+        if let Some(max_claim_per_tx) = params.max_claim_per_tx {
+            distribution_config.max_claim_per_tx = max_claim_per_tx;
+        }
+
+        if let Some(dust_recipient) = params.dust_recipient {
+            distribution_config.dust_recipient = dust_recipient;
+        }
+
+        if let Some(reject_delegated_accounts) = params.reject_delegated_accounts {
+            distribution_config.reject_delegated_accounts = reject_delegated_accounts;
+        }
+
+        if let Some(keeper_reward_lamports) = params.keeper_reward_lamports {
+            distribution_config.keeper_reward_lamports = keeper_reward_lamports;
+        }
+
+        if let Some(earnings_unlock_ts) = params.earnings_unlock_ts {
+            distribution_config.earnings_unlock_ts = earnings_unlock_ts;
+        }
+
+        if let Some(age_brackets) = params.age_brackets {
+            validate_age_brackets(&age_brackets)?;
+            distribution_config.age_brackets = age_brackets;
+        }
+
+        if let Some(distribution_rate_bps) = params.distribution_rate_bps {
+            require!(distribution_rate_bps <= 10000, ErrorCode::InvalidDepositAmount);
+            distribution_config.distribution_rate_bps = distribution_rate_bps;
+        }
+
+        if let Some(require_fresh_claim) = params.require_fresh_claim {
+            distribution_config.require_fresh_claim = require_fresh_claim;
+        }
+
+        if let Some(min_crankable_quote) = params.min_crankable_quote {
+            distribution_config.min_crankable_quote = min_crankable_quote;
+        }
+
+        if let Some(new_days_paused) = params.new_days_paused {
+            if new_days_paused && !distribution_config.new_days_paused {
+                msg!("New days paused: the day in progress can still finish");
+            }
+            distribution_config.new_days_paused = new_days_paused;
+        }
+
+        if let Some(daily_cap_lamports) = params.daily_cap_lamports {
+            distribution_config.daily_cap_lamports = daily_cap_lamports;
+        }
+
+        if let Some(auto_close_on_final_page) = params.auto_close_on_final_page {
+            distribution_config.auto_close_on_final_page = auto_close_on_final_page;
+        }
+
+        if let Some(daily_cap_quote_units) = params.daily_cap_quote_units {
+            distribution_config.daily_cap_quote_units = daily_cap_quote_units;
+        }
+
+        if let Some(day_max_duration_seconds) = params.day_max_duration_seconds {
+            require!(
+                day_max_duration_seconds == 0 || day_max_duration_seconds >= SECONDS_PER_DAY,
+                ErrorCode::InvalidDayMaxDuration
+            );
+            distribution_config.day_max_duration_seconds = if day_max_duration_seconds == 0 {
+                DEFAULT_DAY_MAX_DURATION_SECONDS
+            } else {
+                day_max_duration_seconds
+            };
+        }
+
+        if let Some(min_deposit_interval_seconds) = params.min_deposit_interval_seconds {
+            require!(min_deposit_interval_seconds >= 0, ErrorCode::InvalidDepositInterval);
+            distribution_config.min_deposit_interval_seconds = min_deposit_interval_seconds;
+        }
+
+        if let Some(carry_over_policy) = params.carry_over_policy {
+            require!(carry_over_policy <= CARRY_OVER_POLICY_HOLD, ErrorCode::InvalidCarryOverPolicy);
+            distribution_config.carry_over_policy = carry_over_policy;
+        }
+
+        if let Some(verify_total_locked) = params.verify_total_locked {
+            distribution_config.verify_total_locked = verify_total_locked;
+        }
+
+        if let Some(total_locked_tolerance_units) = params.total_locked_tolerance_units {
+            distribution_config.total_locked_tolerance_units = total_locked_tolerance_units;
+        }
+
+        if let Some(creator_payout_mint) = params.creator_payout_mint {
+            distribution_config.creator_payout_mint = creator_payout_mint;
+        }
+
+        if params.deposit_window_start_ts.is_some() || params.deposit_window_end_ts.is_some() {
+            let start_ts = params.deposit_window_start_ts.unwrap_or(distribution_config.deposit_window_start_ts);
+            let end_ts = params.deposit_window_end_ts.unwrap_or(distribution_config.deposit_window_end_ts);
+            validate_deposit_window(start_ts, end_ts)?;
+            distribution_config.deposit_window_start_ts = start_ts;
+            distribution_config.deposit_window_end_ts = end_ts;
+        }
+
+        if let Some(max_carry_over) = params.max_carry_over {
+            distribution_config.max_carry_over = max_carry_over;
+        }
+
+        if let Some(carry_over_cap_policy) = params.carry_over_cap_policy {
+            require!(
+                carry_over_cap_policy <= CARRY_OVER_CAP_POLICY_TOP_INVESTOR,
+                ErrorCode::InvalidCarryOverCapPolicy
+            );
+            distribution_config.carry_over_cap_policy = carry_over_cap_policy;
+        }
+
+        if let Some(zero_balance_policy) = params.zero_balance_policy {
+            require!(
+                zero_balance_policy <= ZERO_BALANCE_POLICY_REJECT,
+                ErrorCode::InvalidZeroBalancePolicy
+            );
+            distribution_config.zero_balance_policy = zero_balance_policy;
+        }
+
+        if let Some(rounds_per_day) = params.rounds_per_day {
+            require!(
+                (1..=MAX_ROUNDS_PER_DAY).contains(&rounds_per_day),
+                ErrorCode::InvalidRoundsPerDay
+            );
+            distribution_config.rounds_per_day = rounds_per_day;
+        }
+
+        if let Some(min_total_locked_to_distribute) = params.min_total_locked_to_distribute {
+            distribution_config.min_total_locked_to_distribute = min_total_locked_to_distribute;
+        }
+
+        if let Some(weight_by_remaining_lock) = params.weight_by_remaining_lock {
+            distribution_config.weight_by_remaining_lock = weight_by_remaining_lock;
+        }
+
+        if let Some(creator_inactivity_seconds) = params.creator_inactivity_seconds {
+            require!(creator_inactivity_seconds >= 0, ErrorCode::InvalidCreatorInactivity);
+            distribution_config.creator_inactivity_seconds = creator_inactivity_seconds;
+        }
+
+        if let Some(emit_events) = params.emit_events {
+            distribution_config.emit_events = emit_events;
+        }
+
+        if let Some(eligibility_root) = params.eligibility_root {
+            distribution_config.eligibility_root = eligibility_root;
+        }
+
+        if params.min_payout_lamports.is_some()
+            || params.daily_cap_lamports.is_some()
+            || params.daily_cap_quote_units.is_some()
+        {
+            validate_min_payout_below_cap(distribution_config.min_payout_lamports, distribution_config.daily_cap())?;
+        }
+
+        distribution_config.start_config_cooloff(now, cooloff_seconds)?;
+
+        msg!("Y0 allocation: {} units", distribution_config.y0_allocation);
+        msg!("Investor fee share: {} bps", distribution_config.investor_fee_share_bps);
+        msg!("Min payout: {} lamports", distribution_config.min_payout_lamports);
+        msg!("Daily cap: {} units ({} tokens)",
+            distribution_config.daily_cap(),
+            to_ui_amount(distribution_config.daily_cap(), distribution_config.quote_decimals));
+        msg!("Vesting program: {}", distribution_config.vesting_program);
+        msg!("Vested investor share: {} bps", distribution_config.vested_investor_share_bps);
+        msg!("Crank uses the new fee share from: {}", distribution_config.config_effective_ts);
+
+        emit_config_updated(distribution_config)
+    }
+}
+
+/// Emits `DistributionConfigUpdated` with the config as it now stands
+pub(crate) fn emit_config_updated(distribution_config: &DistributionConfig) -> Result<()> {
+    emit!(crate::events::DistributionConfigUpdated {
+        y0_allocation: distribution_config.y0_allocation,
+        investor_fee_share_bps: distribution_config.investor_fee_share_bps,
+        min_payout_lamports: distribution_config.min_payout_lamports,
+        daily_cap_lamports: distribution_config.daily_cap_lamports,
+        auto_close_on_final_page: distribution_config.auto_close_on_final_page,
+        vesting_program: distribution_config.vesting_program,
+        vested_investor_share_bps: distribution_config.vested_investor_share_bps,
+        emit_events: distribution_config.emit_events,
+        eligibility_root: distribution_config.eligibility_root,
+        daily_cap_quote_units: distribution_config.daily_cap_quote_units,
+        day_max_duration_seconds: distribution_config.day_max_duration_seconds,
+        min_deposit_interval_seconds: distribution_config.min_deposit_interval_seconds,
+        carry_over_policy: distribution_config.carry_over_policy,
+        f_locked_floor_bps: distribution_config.f_locked_floor_bps,
+        f_locked_ceiling_bps: distribution_config.f_locked_ceiling_bps,
+        verify_total_locked: distribution_config.verify_total_locked,
+        total_locked_tolerance_units: distribution_config.total_locked_tolerance_units,
+        creator_payout_mint: distribution_config.creator_payout_mint,
+        deposit_window_start_ts: distribution_config.deposit_window_start_ts,
+        deposit_window_end_ts: distribution_config.deposit_window_end_ts,
+        max_carry_over: distribution_config.max_carry_over,
+        carry_over_cap_policy: distribution_config.carry_over_cap_policy,
+        zero_balance_policy: distribution_config.zero_balance_policy,
+        rounds_per_day: distribution_config.rounds_per_day,
+        min_total_locked_to_distribute: distribution_config.min_total_locked_to_distribute,
+        weight_by_remaining_lock: distribution_config.weight_by_remaining_lock,
+        creator_inactivity_seconds: distribution_config.creator_inactivity_seconds,
+        creator_min_share_bps: distribution_config.creator_min_share_bps,
+        dust_threshold_k: distribution_config.dust_threshold_k,
+        fixed_page_size: distribution_config.fixed_page_size,
+        program_ended: distribution_config.program_ended,
+        callback_program: distribution_config.callback_program,
+        max_investor_share_bps: distribution_config.max_investor_share_bps,
+        keeper_reward_lamports: distribution_config.keeper_reward_lamports,
+        earnings_unlock_ts: distribution_config.earnings_unlock_ts,
+        age_brackets: distribution_config.age_brackets,
+        distribution_rate_bps: distribution_config.distribution_rate_bps,
+        require_fresh_claim: distribution_config.require_fresh_claim,
+        min_crankable_quote: distribution_config.min_crankable_quote,
+        new_days_paused: distribution_config.new_days_paused,
+        max_investor_share_of_y0_bps: distribution_config.max_investor_share_of_y0_bps,
+        config_change_cooloff_seconds: distribution_config.config_change_cooloff_seconds,
+        config_effective_ts: distribution_config.config_effective_ts,
+        max_claim_per_tx: distribution_config.max_claim_per_tx,
+        dust_recipient: distribution_config.dust_recipient,
+        reject_delegated_accounts: distribution_config.reject_delegated_accounts,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
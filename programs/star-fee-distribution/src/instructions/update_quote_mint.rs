@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::errors::ErrorCode;
+use crate::constants::{FEE_COLLECTOR_SEED, CRANK_STATE_SEED, DISTRIBUTION_CONFIG_SEED};
+use crate::states::{CrankState, DistributionConfig};
+use crate::utils::quote_mint_decimals;
+
+#[derive(Accounts)]
+pub struct UpdateQuoteMint<'info> {
+    /// Admin who initialized the config
+    #[account(
+        constraint = admin.key() == distribution_config.admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Program authority (our program)
+    #[account(
+        seeds = [FEE_COLLECTOR_SEED],
+        bump
+    )]
+    pub fee_collector: UncheckedAccount<'info>,
+
+    /// Distribution configuration PDA
+    #[account(
+        mut,
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump = distribution_config.bump
+    )]
+    pub distribution_config: Account<'info, DistributionConfig>,
+
+    /// Crank state (absent if the crank has never run)
+    #[account(
+        mut,
+        seeds = [CRANK_STATE_SEED],
+        bump = crank_state.bump
+    )]
+    pub crank_state: Option<Account<'info, CrankState>>,
+
+    /// Quote vault recorded by the crank, holding carry-over in the old quote mint
+    #[account(
+        mut,
+        token::authority = fee_collector
+    )]
+    pub old_quote_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Old-quote token account of the remainder recipient receiving the swept carry-over
+    #[account(mut)]
+    pub sweep_destination: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UpdateQuoteMintParams {
+    /// Quote mint to distribute from now on (must be in ALLOWED_QUOTE_MINTS)
+    pub new_quote_mint: Pubkey,
+}
+
+impl<'info> UpdateQuoteMint<'info> {
+    pub fn handle(ctx: Context<UpdateQuoteMint>, params: UpdateQuoteMintParams) -> Result<()> {
+        msg!("Updating quote mint");
+
+        let previous_quote_mint = ctx.accounts.distribution_config.quote_mint;
+
+        require!(
+            params.new_quote_mint != previous_quote_mint,
+            ErrorCode::QuoteMintUnchanged
+        );
+
+        // Only allow known quote mints (no transfer hooks)
+        let quote_decimals = quote_mint_decimals(&params.new_quote_mint)
+            .ok_or(ErrorCode::QuoteMintNotAllowed)?;
+
+        let mut swept_carry_over = 0;
+        let mut sweep_destination = Pubkey::default();
+
+        if let Some(crank_state) = ctx.accounts.crank_state.as_mut() {
+            // Payouts for the current day are denominated in the old quote
+            require!(
+                !crank_state.is_day_in_progress(),
+                ErrorCode::ConfigLockedDuringDistribution
+            );
+
+            // Sweep the old-quote carry-over dust, unrouted creator remainder and buffered
+            // quote before they lose their meaning
+            let retained = crank_state.carry_over
+                .checked_add(crank_state.unrouted_creator_remainder)
+                .and_then(|retained| retained.checked_add(crank_state.buffered_quote))
+                .ok_or(ErrorCode::MathOverflow)?;
+            if retained > 0 {
+                let old_quote_vault = ctx.accounts.old_quote_vault
+                    .as_ref()
+                    .ok_or(ErrorCode::QuoteVaultMismatch)?;
+                require!(
+                    old_quote_vault.key() == crank_state.quote_vault,
+                    ErrorCode::QuoteVaultMismatch
+                );
+
+                let destination = ctx.accounts.sweep_destination
+                    .as_ref()
+                    .ok_or(ErrorCode::CreatorQuoteAccountRequired)?;
+                let config = &ctx.accounts.distribution_config;
+                let recipient = config.remainder_recipient().unwrap_or(config.creator_wallet);
+                require!(
+                    destination.owner == recipient && destination.mint == old_quote_vault.mint,
+                    ErrorCode::CreatorQuoteAccountMismatch
+                );
+
+                swept_carry_over = std::cmp::min(retained, old_quote_vault.amount);
+                sweep_destination = destination.key();
+
+                if swept_carry_over > 0 {
+                    anchor_spl::token::transfer(
+                        CpiContext::new_with_signer(
+                            ctx.accounts.token_program.to_account_info(),
+                            anchor_spl::token::Transfer {
+                                from: old_quote_vault.to_account_info(),
+                                to: destination.to_account_info(),
+                                authority: ctx.accounts.fee_collector.to_account_info(),
+                            },
+                            &[&[
+                                FEE_COLLECTOR_SEED,
+                                &[ctx.bumps.fee_collector]
+                            ]]
+                        ),
+                        swept_carry_over,
+                    )?;
+                }
+
+                msg!("Swept {} units of old-quote carry-over", swept_carry_over);
+            }
+
+            crank_state.reset_for_quote_mint_change();
+        }
+
+        // The cap stays in native units, so operators must rescale it for a mint with other decimals
+        let distribution_config = &mut ctx.accounts.distribution_config;
+        distribution_config.quote_mint = params.new_quote_mint;
+        distribution_config.quote_decimals = quote_decimals;
+
+        msg!("Previous quote mint: {}", previous_quote_mint);
+        msg!("New quote mint: {}", params.new_quote_mint);
+
+        emit!(crate::events::QuoteMintUpdated {
+            previous_quote_mint,
+            new_quote_mint: params.new_quote_mint,
+            swept_carry_over,
+            sweep_destination,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
 use crate::errors::ErrorCode;
-use crate::constants::{FEE_COLLECTOR_SEED, DEPOSIT_VAULT_SEED, INVESTOR_RECORD_SEED};
-use crate::states::{DepositorRecord, VaultStats};
+use crate::constants::{
+    FEE_COLLECTOR_SEED, DEPOSIT_VAULT_SEED, INVESTOR_RECORD_SEED, CRANK_STATE_SEED,
+    DISTRIBUTION_CONFIG_SEED,
+};
+use crate::utils::{depositor_member_hash, is_plain_system_account, load_if_initialized};
+use crate::states::{DepositorRecord, DistributionConfig, VaultStats};
+use super::deposit::{require_withdrawals_unlocked, require_deposit_mint};
 
 /// Withdrawal instruction for investors to withdraw SOL/USDC from vaults
 #[derive(Accounts)]
@@ -23,7 +28,8 @@ pub struct Withdraw<'info> {
     #[account(
         mut,
         seeds = [DEPOSIT_VAULT_SEED, b"sol"],
-        bump
+        bump,
+        constraint = is_plain_system_account(&sol_vault) @ ErrorCode::SolVaultNotSystemAccount
     )]
     pub sol_vault: SystemAccount<'info>,
     
@@ -72,6 +78,20 @@ pub struct Withdraw<'info> {
         bump = vault_stats.bump
     )]
     pub vault_stats: Account<'info, VaultStats>,
+    
+    /// CHECK: Crank state PDA, read for the deposit lock once a day has been begun
+    #[account(
+        seeds = [CRANK_STATE_SEED],
+        bump
+    )]
+    pub crank_state: UncheckedAccount<'info>,
+    
+    /// CHECK: Distribution config PDA, read for the deposit mint and program end once initialized
+    #[account(
+        seeds = [DISTRIBUTION_CONFIG_SEED],
+        bump
+    )]
+    pub distribution_config: UncheckedAccount<'info>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -82,14 +102,30 @@ pub struct WithdrawParams {
     pub usdc_amount: u64,
 }
 
+/// Response structure for a withdrawal, set as return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct WithdrawResult {
+    /// SOL withdrawn by this instruction (in lamports)
+    pub sol_withdrawn: u64,
+    /// USDC withdrawn by this instruction (in smallest unit)
+    pub usdc_withdrawn: u64,
+    /// SOL balance left in the depositor record
+    pub remaining_sol: u64,
+    /// USDC balance left in the depositor record
+    pub remaining_usdc: u64,
+}
+
 impl<'info> Withdraw<'info> {
-    pub fn handle(mut ctx: Context<Withdraw>, params: WithdrawParams) -> Result<()> {
+    pub fn handle(mut ctx: Context<Withdraw>, params: WithdrawParams) -> Result<WithdrawResult> {
         msg!("Processing withdrawal for investor: {}", ctx.accounts.investor.key());
         msg!("SOL amount: {} lamports", params.sol_amount);
         msg!("USDC amount: {} units", params.usdc_amount);
         
         // Validate withdrawal amounts
         validate_withdrawal_amounts(&ctx, &params)?;
+        require_withdrawals_unlocked(&ctx.accounts.crank_state, &ctx.accounts.distribution_config)?;
+        require_deposit_mint(&ctx.accounts.distribution_config, &ctx.accounts.usdc_mint.key())?;
+        require_earnings_unlocked(&ctx, params.usdc_amount)?;
         
         // Process SOL withdrawal if amount > 0
         if params.sol_amount > 0 {
@@ -102,10 +138,12 @@ impl<'info> Withdraw<'info> {
         }
         
         // Update depositor record
+        let was_active = ctx.accounts.depositor_record.is_active();
+        let previous_weighted_usdc = ctx.accounts.depositor_record.weighted_usdc_balance()?;
         update_depositor_record_withdrawal(&mut ctx, params.sol_amount, params.usdc_amount)?;
         
         // Update vault stats
-        update_vault_stats_withdrawal(&mut ctx, params.sol_amount, params.usdc_amount)?;
+        update_vault_stats_withdrawal(&mut ctx, params.sol_amount, params.usdc_amount, was_active, previous_weighted_usdc)?;
         
         msg!("Withdrawal completed successfully!");
         
@@ -123,7 +161,12 @@ impl<'info> Withdraw<'info> {
             timestamp: Clock::get()?.unix_timestamp,
         });
         
-        Ok(())
+        Ok(WithdrawResult {
+            sol_withdrawn: params.sol_amount,
+            usdc_withdrawn: params.usdc_amount,
+            remaining_sol: depositor_record.current_sol_balance,
+            remaining_usdc: depositor_record.current_usdc_balance,
+        })
     }
 }
 
@@ -165,6 +208,27 @@ fn validate_withdrawal_amounts(ctx: &Context<Withdraw>, params: &WithdrawParams)
     Ok(())
 }
 
+/// Limits a USDC withdrawal to the principal balance until the config's earnings unlock
+/// (lifted once the program has ended)
+fn require_earnings_unlocked(ctx: &Context<Withdraw>, usdc_amount: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let locked = load_if_initialized::<DistributionConfig>(&ctx.accounts.distribution_config)?
+        .is_some_and(|config| !config.program_ended && config.are_earnings_locked(now));
+    if !locked {
+        return Ok(());
+    }
+    
+    let depositor_record = &ctx.accounts.depositor_record;
+    msg!("Earnings locked: {} principal, {} earnings units",
+        depositor_record.principal_balance, depositor_record.earnings_balance);
+    
+    require!(
+        usdc_amount <= depositor_record.principal_balance,
+        ErrorCode::EarningsLocked
+    );
+    Ok(())
+}
+
 /// Processes SOL withdrawal by transferring from vault to investor
 fn process_sol_withdrawal(ctx: &Context<Withdraw>, amount: u64) -> Result<()> {
     msg!("Processing SOL withdrawal of {} lamports", amount);
@@ -231,11 +295,33 @@ fn update_depositor_record_withdrawal(ctx: &mut Context<Withdraw>, sol_amount: u
 }
 
 /// Updates the global vault statistics with withdrawal information
-fn update_vault_stats_withdrawal(ctx: &mut Context<Withdraw>, sol_amount: u64, usdc_amount: u64) -> Result<()> {
+fn update_vault_stats_withdrawal(
+    ctx: &mut Context<Withdraw>,
+    sol_amount: u64,
+    usdc_amount: u64,
+    was_active: bool,
+    previous_weighted_usdc: u64,
+) -> Result<()> {
     let vault_stats = &mut ctx.accounts.vault_stats;
     
     // Add withdrawals to stats
     vault_stats.add_withdrawals(sol_amount, usdc_amount)?;
+    vault_stats.update_weighted_usdc(
+        previous_weighted_usdc,
+        ctx.accounts.depositor_record.weighted_usdc_balance()?,
+    )?;
+    
+    // Full withdrawal: the investor no longer counts as a depositor
+    if was_active && !ctx.accounts.depositor_record.is_active() {
+        vault_stats.remove_depositor(&depositor_member_hash(&ctx.accounts.investor.key()))?;
+        
+        emit!(crate::events::DepositorCountChanged {
+            investor: ctx.accounts.investor.key(),
+            depositor_count: vault_stats.depositor_count,
+            increased: false,
+            timestamp: vault_stats.last_update_timestamp,
+        });
+    }
     
     msg!("Updated vault stats:");
     msg!("Total SOL withdrawn: {} lamports", vault_stats.total_sol_withdrawn);
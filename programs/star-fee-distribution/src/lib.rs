@@ -7,54 +7,221 @@ pub mod errors;
 pub mod constants;
 pub mod states;
 pub mod events;
+pub mod utils;
+pub mod math;
 
 pub use instructions::*;
 pub use events::*;
 
-#[program]
-pub mod star_fee_distribution {
+// The IDL account handlers #[program] generates next to the program module still call the
+// deprecated `AccountInfo::realloc`, so the allow is scoped to the module wrapping it
+#[allow(deprecated)]
+mod processor {
     use super::*;
-    
-    pub fn deposit(ctx: Context<Deposit>, params: DepositParams) -> Result<()> {
-        Deposit::handle(ctx, params)
-    }
-    pub fn initialize_honorary_position(
-        ctx: Context<InitializeHonoraryPosition>, 
-        config: HonoraryPositionConfig
-    ) -> Result<()> {
-        InitializeHonoraryPosition::handle(ctx, config)
-    }
-    pub fn claim_fees_to_pda(ctx: Context<ClaimFeesToPDA>) -> Result<()> {
-        ClaimFeesToPDA::handle(ctx)
-    }
 
-    pub fn distribute_fees(ctx: Context<DistributeFees>, params: FeeDistributionParams) -> Result<()> {
-        DistributeFees::handle(ctx, params)
-    }
+    #[program]
+    pub mod star_fee_distribution {
+        use super::*;
+        
+        pub fn deposit(ctx: Context<Deposit>, params: DepositParams) -> Result<()> {
+            Deposit::handle(ctx, params)
+        }
 
+        pub fn deposit_with_proof(ctx: Context<Deposit>, params: DepositWithProofParams) -> Result<()> {
+            Deposit::handle_with_proof(ctx, params)
+        }
+        pub fn initialize_honorary_position(
+            ctx: Context<InitializeHonoraryPosition>, 
+            config: HonoraryPositionConfig
+        ) -> Result<()> {
+            InitializeHonoraryPosition::handle(ctx, config)
+        }
+        pub fn claim_fees_to_pda(ctx: Context<ClaimFeesToPDA>) -> Result<()> {
+            ClaimFeesToPDA::handle(ctx)
+        }
 
-    pub fn withdraw(ctx: Context<Withdraw>, params: WithdrawParams) -> Result<()> {
-        Withdraw::handle(ctx, params)
-    }
+        pub fn distribute_fees(ctx: Context<DistributeFees>, params: FeeDistributionParams) -> Result<()> {
+            DistributeFees::handle(ctx, params)
+        }
 
-    pub fn crank_fee_distribution(ctx: Context<CrankFeeDistribution>, params: DistributionParams) -> Result<()> {
-        CrankFeeDistribution::handle(ctx, params)
-    }
 
-    pub fn distribute_to_investor(ctx: Context<DistributeToInvestor>, params: InvestorDistributionParams) -> Result<()> {
-        DistributeToInvestor::handle(ctx, params)
-    }
+        pub fn withdraw(ctx: Context<Withdraw>, params: WithdrawParams) -> Result<WithdrawResult> {
+            Withdraw::handle(ctx, params)
+        }
 
-    pub fn route_creator_remainder(ctx: Context<RouteCreatorRemainder>) -> Result<()> {
-        RouteCreatorRemainder::handle(ctx)
-    }
+        pub fn close_depositor_record(ctx: Context<CloseDepositorRecord>) -> Result<()> {
+            CloseDepositorRecord::handle(ctx)
+        }
 
-    pub fn initialize_distribution_config(
-        ctx: Context<InitializeDistributionConfig>,
-        params: InitializeDistributionConfigParams
-    ) -> Result<()> {
-        InitializeDistributionConfig::handle(ctx, params)
-    }
+        pub fn crank_fee_distribution<'info>(ctx: Context<'_, '_, 'info, 'info, CrankFeeDistribution<'info>>, params: DistributionParams) -> Result<()> {
+            CrankFeeDistribution::handle(ctx, params)
+        }
+
+        pub fn distribute_to_investor(ctx: Context<DistributeToInvestor>, params: InvestorDistributionParams) -> Result<()> {
+            DistributeToInvestor::handle(ctx, params)
+        }
+
+        pub fn route_creator_remainder(ctx: Context<RouteCreatorRemainder>, params: RouteCreatorRemainderParams) -> Result<()> {
+            RouteCreatorRemainder::handle(ctx, params)
+        }
+
+        pub fn force_close_day(ctx: Context<ForceCloseDay>) -> Result<()> {
+            ForceCloseDay::handle(ctx)
+        }
+
+        pub fn creator_partial_claim(ctx: Context<CreatorPartialClaim>, params: CreatorPartialClaimParams) -> Result<()> {
+            CreatorPartialClaim::handle(ctx, params)
+        }
+
+        pub fn initialize_distribution_config(
+            ctx: Context<InitializeDistributionConfig>,
+            params: InitializeDistributionConfigParams
+        ) -> Result<()> {
+            InitializeDistributionConfig::handle(ctx, params)
+        }
+
+        pub fn schedule_creator_wallet_update(
+            ctx: Context<ScheduleCreatorWalletUpdate>,
+            params: ScheduleCreatorWalletUpdateParams
+        ) -> Result<()> {
+            ScheduleCreatorWalletUpdate::handle(ctx, params)
+        }
+
+        pub fn submit_payout_root(ctx: Context<SubmitPayoutRoot>, params: SubmitPayoutRootParams) -> Result<()> {
+            SubmitPayoutRoot::handle(ctx, params)
+        }
+
+        pub fn set_investor_multiplier(ctx: Context<SetInvestorMultiplier>, params: SetInvestorMultiplierParams) -> Result<()> {
+            SetInvestorMultiplier::handle(ctx, params)
+        }
+
+        pub fn update_distribution_config(
+            ctx: Context<UpdateDistributionConfig>,
+            params: UpdateDistributionConfigParams
+        ) -> Result<()> {
+            UpdateDistributionConfig::handle(ctx, params)
+        }
+
+        pub fn replace_config(
+            ctx: Context<ReplaceConfig>,
+            params: InitializeDistributionConfigParams
+        ) -> Result<()> {
+            ReplaceConfig::handle(ctx, params)
+        }
+
+        pub fn update_quote_mint(ctx: Context<UpdateQuoteMint>, params: UpdateQuoteMintParams) -> Result<()> {
+            UpdateQuoteMint::handle(ctx, params)
+        }
+
+        pub fn flush_carry_over<'info>(ctx: Context<'_, '_, 'info, 'info, FlushCarryOver<'info>>) -> Result<()> {
+            FlushCarryOver::handle(ctx)
+        }
+
+        pub fn initialize_packed_investors(ctx: Context<InitializePackedInvestors>) -> Result<()> {
+            InitializePackedInvestors::handle(ctx)
+        }
 
+        pub fn packed_deposit(ctx: Context<PackedBalanceChange>, params: PackedBalanceChangeParams) -> Result<()> {
+            PackedBalanceChange::handle_deposit(ctx, params)
+        }
+
+        pub fn packed_withdraw(ctx: Context<PackedBalanceChange>, params: PackedBalanceChangeParams) -> Result<()> {
+            PackedBalanceChange::handle_withdraw(ctx, params)
+        }
+
+        pub fn distribute_packed_page<'info>(
+            ctx: Context<'_, '_, 'info, 'info, DistributePackedPage<'info>>,
+            params: DistributePackedPageParams
+        ) -> Result<()> {
+            DistributePackedPage::handle(ctx, params)
+        }
+
+        pub fn begin_distribution_day(ctx: Context<BeginDistributionDay>) -> Result<()> {
+            BeginDistributionDay::handle(ctx)
+        }
+
+        pub fn query_vault_concentration<'info>(
+            ctx: Context<'_, '_, 'info, 'info, QueryVaultConcentration<'info>>
+        ) -> Result<VaultConcentration> {
+            QueryVaultConcentration::handle(ctx)
+        }
+
+        pub fn bootstrap_program(
+            ctx: Context<BootstrapProgram>,
+            params: InitializeDistributionConfigParams
+        ) -> Result<()> {
+            BootstrapProgram::handle(ctx, params)
+        }
+
+        pub fn register_quote_account(ctx: Context<RegisterQuoteAccount>) -> Result<()> {
+            RegisterQuoteAccount::handle(ctx)
+        }
+
+        pub fn query_global_stats(ctx: Context<QueryGlobalStats>) -> Result<GlobalStats> {
+            QueryGlobalStats::handle(ctx)
+        }
+
+        pub fn set_auto_compound(ctx: Context<SetAutoCompound>, params: SetAutoCompoundParams) -> Result<()> {
+            SetAutoCompound::handle(ctx, params)
+        }
+
+        pub fn reconcile_vault_stats<'info>(
+            ctx: Context<'_, '_, 'info, 'info, ReconcileVaultStats<'info>>,
+            params: ReconcileVaultStatsParams
+        ) -> Result<()> {
+            ReconcileVaultStats::handle(ctx, params)
+        }
+
+        pub fn set_batch_withdraw_approval(
+            ctx: Context<SetBatchWithdrawApproval>,
+            params: SetBatchWithdrawApprovalParams
+        ) -> Result<()> {
+            SetBatchWithdrawApproval::handle(ctx, params)
+        }
+
+        pub fn batch_withdraw<'info>(ctx: Context<'_, '_, 'info, 'info, BatchWithdraw<'info>>) -> Result<()> {
+            BatchWithdraw::handle(ctx)
+        }
+
+        pub fn query_investor_weight(ctx: Context<QueryInvestorWeight>) -> Result<InvestorWeight> {
+            QueryInvestorWeight::handle(ctx)
+        }
+
+        pub fn rescue_tokens(ctx: Context<RescueTokens>) -> Result<()> {
+            RescueTokens::handle(ctx)
+        }
+
+        pub fn simulate_day<'info>(
+            ctx: Context<'_, '_, 'info, 'info, SimulateDay<'info>>,
+            params: SimulateDayParams
+        ) -> Result<SimulatedDay> {
+            SimulateDay::handle(ctx, params)
+        }
+
+        pub fn fund_keeper_incentive(
+            ctx: Context<FundKeeperIncentive>,
+            params: FundKeeperIncentiveParams
+        ) -> Result<()> {
+            FundKeeperIncentive::handle(ctx, params)
+        }
+
+        pub fn query_day_record(ctx: Context<QueryDayRecord>, params: QueryDayRecordParams) -> Result<states::DayRecord> {
+            QueryDayRecord::handle(ctx, params)
+        }
+
+        pub fn set_opt_out(ctx: Context<SetOptOut>, params: SetOptOutParams) -> Result<()> {
+            SetOptOut::handle(ctx, params)
+        }
+
+        pub fn add_position(ctx: Context<AddPosition>, params: PositionRegistryParams) -> Result<()> {
+            AddPosition::handle(ctx, params)
+        }
+
+        pub fn remove_position(ctx: Context<RemovePosition>, params: PositionRegistryParams) -> Result<()> {
+            RemovePosition::handle(ctx, params)
+        }
+
+    }
 }
 
+pub use processor::*;
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Adds two u64 values, failing on overflow
+#[inline]
+pub fn add_u64(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b).ok_or(ErrorCode::MathOverflow.into())
+}
+
+/// Subtracts b from a, failing on underflow
+#[inline]
+pub fn sub_u64(a: u64, b: u64) -> Result<u64> {
+    a.checked_sub(b).ok_or(ErrorCode::MathOverflow.into())
+}
+
+/// Computes floor(a * b / c) with a u128 intermediate, failing if c is zero
+/// or the result does not fit in a u64
+#[inline]
+pub fn mul_div_u64(a: u64, b: u64, c: u64) -> Result<u64> {
+    require!(c > 0, ErrorCode::DivisionByZero);
+    
+    u64::try_from((a as u128 * b as u128) / c as u128)
+        .map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_u64_handles_boundaries() {
+        assert_eq!(add_u64(0, 0).unwrap(), 0);
+        assert_eq!(add_u64(u64::MAX - 1, 1).unwrap(), u64::MAX);
+        assert_eq!(add_u64(u64::MAX, 1).unwrap_err(), ErrorCode::MathOverflow.into());
+        assert_eq!(add_u64(u64::MAX, u64::MAX).unwrap_err(), ErrorCode::MathOverflow.into());
+    }
+
+    #[test]
+    fn sub_u64_handles_boundaries() {
+        assert_eq!(sub_u64(1, 1).unwrap(), 0);
+        assert_eq!(sub_u64(u64::MAX, u64::MAX).unwrap(), 0);
+        assert_eq!(sub_u64(u64::MAX, 0).unwrap(), u64::MAX);
+        assert_eq!(sub_u64(0, 1).unwrap_err(), ErrorCode::MathOverflow.into());
+    }
+
+    #[test]
+    fn mul_div_u64_uses_wide_intermediate() {
+        assert_eq!(mul_div_u64(500_000, 5000, 10000).unwrap(), 250_000);
+        assert_eq!(mul_div_u64(u64::MAX, u64::MAX, u64::MAX).unwrap(), u64::MAX);
+        assert_eq!(mul_div_u64(u64::MAX, 10000, 10000).unwrap(), u64::MAX);
+        assert_eq!(mul_div_u64(0, u64::MAX, 1).unwrap(), 0);
+        // Floors the result
+        assert_eq!(mul_div_u64(1, 9999, 10000).unwrap(), 0);
+    }
+
+    #[test]
+    fn mul_div_u64_rejects_overflow_and_zero_divisor() {
+        assert_eq!(mul_div_u64(u64::MAX, 2, 1).unwrap_err(), ErrorCode::MathOverflow.into());
+        assert_eq!(mul_div_u64(u64::MAX, 10000, 9999).unwrap_err(), ErrorCode::MathOverflow.into());
+        assert_eq!(mul_div_u64(1, 1, 0).unwrap_err(), ErrorCode::DivisionByZero.into());
+    }
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::errors::ErrorCode;
+use crate::utils::toggle_commitment;
 
 /// Crank state to track the last distribution time
 #[account]
@@ -15,12 +16,66 @@ pub struct CrankState {
     pub pagination_cursor: u32,
     /// Total investors processed in current day
     pub investors_processed_today: u32,
+    /// Total investor payouts executed in current day
+    pub investors_paid_today: u32,
     /// Amount distributed in current day
     pub daily_distributed: u64,
     /// Carry-over from previous distribution (dust)
     pub carry_over: u64,
+    /// Portion of the creator remainder reserved for fully-vested investors today
+    pub vested_pool: u64,
+    /// Amount distributed to fully-vested investors today
+    pub vested_distributed_today: u64,
+    /// Investor allocation snapshotted on the day's first page
+    pub day_investor_fee_quote: u64,
+    /// Freshly claimed quote (excluding carry-over) snapshotted on the day's first page
+    pub day_claimed_quote: u64,
+    /// Amount the creator has claimed before the day closed
+    pub creator_claimed_today: u64,
     /// Day state: 0=not started, 1=in progress, 2=closed
     pub day_state: u8,
+    /// Quote vault the current day's distribution was computed from
+    pub quote_vault: Pubkey,
+    /// Total locked USDC snapshotted when the day was begun
+    pub day_total_locked: u64,
+    /// Total weighted USDC snapshotted when the day was begun; the day's payouts are weighted against it
+    pub day_total_weighted_usdc: u64,
+    /// Depositor count snapshotted when the day was begun
+    pub expected_investors: u32,
+    /// Depositor set commitment snapshotted when the day was begun
+    pub day_investor_commitment: [u8; 32],
+    /// XOR of the member hashes of snapshotted investors paid today
+    pub day_paid_commitment: [u8; 32],
+    /// Snapshotted investors paid today (each counted once)
+    pub snapshot_investors_paid: u32,
+    /// Deposits and withdrawals are rejected until this timestamp (the day's force-close deadline)
+    /// while the day is in progress
+    pub deposits_locked_until: i64,
+    /// Slot at which the current day was started
+    pub last_distribution_slot: u64,
+    /// Largest payout weight among investors paid today
+    pub day_top_investor_weight: u64,
+    /// Quote account of today's largest-weight paid investor (receives capped carry-over)
+    pub day_top_investor_quote_account: Pubkey,
+    /// Distribution round in progress within the current day (0 = first round)
+    pub current_round: u8,
+    /// Investor allocation snapshotted on the current round's first page
+    pub round_investor_fee_quote: u64,
+    /// Quote vault balance when the current round's first page was processed
+    pub round_vault_baseline: u64,
+    /// Quote paid out of the vault today as of the current round's first page
+    pub round_outflow_baseline: u64,
+    /// Investor allocation the crank computed for the current page, which must be passed
+    /// as `total_investor_fee` to distribute_to_investor
+    pub current_page_investor_fee: u64,
+    /// Creator remainder left in the vault by a day closed without routing it; excluded from
+    /// later days' claimed quote and routed at the next close that reaches the recipient
+    pub unrouted_creator_remainder: u64,
+    /// Accumulated quote held back by `distribution_rate_bps`; excluded from the remainder at
+    /// close and released into later days' claimed quote
+    pub buffered_quote: u64,
+    /// Quote fee vault balance when the current day was begun (a fresh claim must exceed it)
+    pub day_start_quote_balance: u64,
     /// Bump seed for the PDA
     pub bump: u8,
 }
@@ -34,38 +89,149 @@ impl CrankState {
             distribution_count: 0,
             pagination_cursor: 0,
             investors_processed_today: 0,
+            investors_paid_today: 0,
             daily_distributed: 0,
             carry_over: 0,
+            vested_pool: 0,
+            vested_distributed_today: 0,
+            day_investor_fee_quote: 0,
+            day_claimed_quote: 0,
+            creator_claimed_today: 0,
             day_state: 0, // not started
+            quote_vault: Pubkey::default(),
+            day_total_locked: 0,
+            day_total_weighted_usdc: 0,
+            expected_investors: 0,
+            day_investor_commitment: [0u8; 32],
+            day_paid_commitment: [0u8; 32],
+            snapshot_investors_paid: 0,
+            deposits_locked_until: 0,
+            last_distribution_slot: 0,
+            day_top_investor_weight: 0,
+            day_top_investor_quote_account: Pubkey::default(),
+            current_round: 0,
+            round_investor_fee_quote: 0,
+            round_vault_baseline: 0,
+            round_outflow_baseline: 0,
+            current_page_investor_fee: 0,
+            unrouted_creator_remainder: 0,
+            buffered_quote: 0,
+            day_start_quote_balance: 0,
             bump,
         }
     }
 
+    /// Current timestamp, rejecting a clock behind the last day start. Validators can skew
+    /// `unix_timestamp`, but slots only move forward, so a slot behind the recorded one
+    /// is rejected as well
+    pub fn checked_now(&self) -> Result<i64> {
+        let clock = Clock::get()?;
+        self.check_clock(clock.unix_timestamp, clock.slot)?;
+        Ok(clock.unix_timestamp)
+    }
+
+    /// Fails with `ClockWentBackward` when `now` or `slot` is behind the last day start
+    fn check_clock(&self, now: i64, slot: u64) -> Result<()> {
+        require!(
+            now >= self.last_distribution_timestamp && slot >= self.last_distribution_slot,
+            ErrorCode::ClockWentBackward
+        );
+        Ok(())
+    }
+
     /// Checks if 24 hours have passed since last distribution
     pub fn can_start_new_day(&self) -> Result<bool> {
-        let now = Clock::get()?.unix_timestamp;
-        let time_since_last = now - self.last_distribution_timestamp;
-        
+        let now = self.checked_now()?;
+        Ok(self.can_start_new_day_at(now))
+    }
+
+    /// Checks if 24 hours have passed between the last distribution and `now`
+    fn can_start_new_day_at(&self, now: i64) -> bool {
         // 24 hours = 86400 seconds
-        Ok(time_since_last >= 86400 || self.last_distribution_timestamp == 0)
+        now - self.last_distribution_timestamp >= 86400 || self.last_distribution_timestamp == 0
     }
 
     /// Starts a new distribution day
     pub fn start_new_day(&mut self) -> Result<()> {
-        require!(self.can_start_new_day()?, ErrorCode::DistributionTooFrequent);
+        let clock = Clock::get()?;
+        self.start_new_day_at(clock.unix_timestamp, clock.slot)
+    }
+
+    /// Starts a new distribution day at `now` and `slot`. Every per-day counter is reset here,
+    /// including the pagination cursor, so a crank state reused across many closed and
+    /// reopened days never carries a cursor over
+    pub fn start_new_day_at(&mut self, now: i64, slot: u64) -> Result<()> {
+        self.check_clock(now, slot)?;
+        require!(self.can_start_new_day_at(now), ErrorCode::DistributionTooFrequent);
         
-        let now = Clock::get()?.unix_timestamp;
         self.last_distribution_timestamp = now;
+        self.last_distribution_slot = slot;
         self.current_day = self.current_day.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
         self.pagination_cursor = 0;
         self.investors_processed_today = 0;
+        self.investors_paid_today = 0;
         self.daily_distributed = 0;
+        self.vested_pool = 0;
+        self.vested_distributed_today = 0;
+        self.day_investor_fee_quote = 0;
+        self.day_claimed_quote = 0;
+        self.creator_claimed_today = 0;
+        self.day_paid_commitment = [0u8; 32];
+        self.snapshot_investors_paid = 0;
+        self.day_top_investor_weight = 0;
+        self.day_top_investor_quote_account = Pubkey::default();
+        self.current_round = 0;
+        self.round_investor_fee_quote = 0;
+        self.round_vault_baseline = 0;
+        self.round_outflow_baseline = 0;
+        self.current_page_investor_fee = 0;
         self.day_state = 1; // in progress
         
         msg!("Started new distribution day: {}", self.current_day);
         Ok(())
     }
 
+    /// Starts the day's next distribution round with its own pagination cursor and
+    /// reconciliation; the day's snapshot, daily cap and totals are shared
+    pub fn start_next_round(&mut self) -> Result<()> {
+        self.current_round = self.current_round.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        self.pagination_cursor = 0;
+        self.round_investor_fee_quote = 0;
+        self.current_page_investor_fee = 0;
+        self.day_paid_commitment = [0u8; 32];
+        self.snapshot_investors_paid = 0;
+        
+        msg!("Started distribution round {} of day {}", self.current_round, self.current_day);
+        Ok(())
+    }
+
+    /// Quote paid to investors today, regular and vested payouts alike, as counted against the daily cap
+    pub fn investor_distributed_today(&self) -> u64 {
+        self.daily_distributed.saturating_add(self.vested_distributed_today)
+    }
+
+    /// Quote paid out of the vault today (investor and vested payouts plus creator claims)
+    pub fn day_outflows(&self) -> u64 {
+        self.daily_distributed
+            .saturating_add(self.vested_distributed_today)
+            .saturating_add(self.creator_claimed_today)
+    }
+
+    /// Freshly claimed quote in a vault holding `vault_amount`: everything but the carry-over,
+    /// unrouted creator remainder and buffered quote in the first round, and only what arrived
+    /// since the last round snapshot in later rounds
+    pub fn fresh_round_quote(&self, vault_amount: u64) -> u64 {
+        if self.current_round == 0 {
+            return vault_amount
+                .saturating_sub(self.carry_over)
+                .saturating_sub(self.unrouted_creator_remainder)
+                .saturating_sub(self.buffered_quote);
+        }
+        
+        let paid_since_snapshot = self.day_outflows().saturating_sub(self.round_outflow_baseline);
+        vault_amount.saturating_sub(self.round_vault_baseline.saturating_sub(paid_since_snapshot))
+    }
+
     /// Advances pagination cursor
     pub fn advance_cursor(&mut self, investors_processed: u32) -> Result<()> {
         self.pagination_cursor = self.pagination_cursor.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
@@ -75,9 +241,65 @@ impl CrankState {
         Ok(())
     }
 
+    /// Counts the first payout of the day to an investor from the day's snapshot
+    pub fn record_snapshot_payout(&mut self, member: &[u8; 32]) -> Result<()> {
+        self.snapshot_investors_paid = self.snapshot_investors_paid
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        toggle_commitment(&mut self.day_paid_commitment, member);
+        Ok(())
+    }
+
+    /// Records a paid investor as today's largest-weight investor if they outweigh the current one
+    pub fn record_top_investor(&mut self, weight: u64, quote_account: Pubkey) {
+        if weight > self.day_top_investor_weight {
+            self.day_top_investor_weight = weight;
+            self.day_top_investor_quote_account = quote_account;
+        }
+    }
+
+    /// Checks if exactly the investors snapshotted when the day was begun have been paid,
+    /// regardless of depositors who joined or left afterwards
+    pub fn is_reconciled(&self) -> bool {
+        self.snapshot_investors_paid == self.expected_investors
+            && self.day_paid_commitment == self.day_investor_commitment
+    }
+
+    /// Quote still owed to investors (today's regular and vested pools plus carry-over dust)
+    pub fn reserved_for_investors(&self) -> u64 {
+        let investor_reserved = self.day_investor_fee_quote.saturating_sub(self.daily_distributed);
+        let vested_reserved = self.vested_pool.saturating_sub(self.vested_distributed_today);
+        investor_reserved
+            .saturating_add(vested_reserved)
+            .saturating_add(self.carry_over)
+    }
+
+    /// Clears quote-denominated state so nothing carries across a quote mint change
+    pub fn reset_for_quote_mint_change(&mut self) {
+        self.carry_over = 0;
+        self.unrouted_creator_remainder = 0;
+        self.buffered_quote = 0;
+        self.day_start_quote_balance = 0;
+        self.vested_pool = 0;
+        self.vested_distributed_today = 0;
+        self.day_investor_fee_quote = 0;
+        self.day_claimed_quote = 0;
+        self.creator_claimed_today = 0;
+        self.daily_distributed = 0;
+        self.pagination_cursor = 0;
+        self.quote_vault = Pubkey::default();
+        self.day_top_investor_weight = 0;
+        self.day_top_investor_quote_account = Pubkey::default();
+        self.round_investor_fee_quote = 0;
+        self.round_vault_baseline = 0;
+        self.round_outflow_baseline = 0;
+        self.current_page_investor_fee = 0;
+    }
+
     /// Closes the current day
     pub fn close_day(&mut self) -> Result<()> {
         self.day_state = 2; // closed
+        self.deposits_locked_until = 0;
         self.distribution_count = self.distribution_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
         msg!("Closed distribution day: {}", self.current_day);
         Ok(())
@@ -88,8 +310,102 @@ impl CrankState {
         self.day_state == 1
     }
 
+    /// Checks if deposits and withdrawals are locked for the day in progress
+    pub fn are_deposits_locked(&self, now: i64) -> bool {
+        self.is_day_in_progress() && now < self.deposits_locked_until
+    }
+
+    /// Checks if at least one page of the current day has been processed
+    pub fn has_processed_pages(&self) -> bool {
+        self.pagination_cursor > 0 || self.investors_processed_today > 0
+    }
+
+    /// Time from which the day in progress can be force-closed, `max_duration_seconds` after it began
+    pub fn day_deadline(&self, max_duration_seconds: i64) -> i64 {
+        self.last_distribution_timestamp.saturating_add(max_duration_seconds)
+    }
+
+    /// Locks deposits and withdrawals for as long as the day can be cranked, up to its deadline
+    pub fn lock_deposits_until_deadline(&mut self, max_duration_seconds: i64) {
+        self.deposits_locked_until = self.day_deadline(max_duration_seconds);
+    }
+
+    /// Checks if the day in progress has outlived `max_duration_seconds` since it began
+    pub fn is_day_expired(&self, now: i64, max_duration_seconds: i64) -> bool {
+        self.is_day_in_progress() && now >= self.day_deadline(max_duration_seconds)
+    }
+
+    /// Fails with `PriorDayNotClosed` when the day in progress has outlived `max_duration_seconds`,
+    /// so a stale day is force-closed instead of taking further pages or payouts
+    pub fn require_not_stale(&self, max_duration_seconds: i64) -> Result<()> {
+        if !self.is_day_in_progress() {
+            return Ok(());
+        }
+        
+        let now = self.checked_now()?;
+        require!(
+            !self.is_day_expired(now, max_duration_seconds),
+            ErrorCode::PriorDayNotClosed
+        );
+        Ok(())
+    }
+
     /// Checks if day is closed
     pub fn is_day_closed(&self) -> bool {
         self.day_state == 2
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pagination_cursor_resets_on_every_reopened_day() {
+        let mut crank_state = CrankState::new(255);
+        let mut now = 1_000_000;
+        for day in 1..=5_000u32 {
+            crank_state.start_new_day_at(now, u64::from(day)).unwrap();
+            assert_eq!(crank_state.current_day, day);
+            assert_eq!(crank_state.pagination_cursor, 0);
+            assert_eq!(crank_state.investors_processed_today, 0);
+
+            for _ in 0..3 {
+                crank_state.advance_cursor(25).unwrap();
+            }
+            assert_eq!(crank_state.pagination_cursor, 3);
+            crank_state.close_day().unwrap();
+            now += 86_400;
+        }
+
+        assert_eq!(crank_state.distribution_count, 5_000);
+        crank_state.start_new_day_at(now, 5_001).unwrap();
+        assert_eq!(crank_state.pagination_cursor, 0);
+    }
+
+    #[test]
+    fn deposits_stay_locked_until_the_day_can_be_force_closed() {
+        let mut crank_state = CrankState::new(255);
+        crank_state.start_new_day_at(1_000_000, 10).unwrap();
+        crank_state.lock_deposits_until_deadline(172_800);
+
+        for now in [1_000_000, 1_000_000 + 86_401, 1_000_000 + 172_799] {
+            assert!(crank_state.are_deposits_locked(now));
+            assert!(!crank_state.is_day_expired(now, 172_800));
+        }
+        assert!(!crank_state.are_deposits_locked(1_000_000 + 172_800));
+        assert!(crank_state.is_day_expired(1_000_000 + 172_800, 172_800));
+
+        crank_state.close_day().unwrap();
+        assert!(!crank_state.are_deposits_locked(1_000_000 + 86_401));
+    }
+
+    #[test]
+    fn a_new_day_needs_24_hours_and_a_forward_clock() {
+        let mut crank_state = CrankState::new(255);
+        crank_state.start_new_day_at(1_000_000, 10).unwrap();
+        assert!(crank_state.start_new_day_at(1_000_000 + 86_399, 11).is_err());
+        assert!(crank_state.start_new_day_at(1_000_000 + 86_400, 9).is_err());
+        assert!(crank_state.start_new_day_at(1_000_000 + 86_400, 11).is_ok());
+    }
+}
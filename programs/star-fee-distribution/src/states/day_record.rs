@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// Per-day record of a closed distribution day
+#[account]
+#[derive(InitSpace)]
+pub struct DayRecord {
+    /// Distribution day this record belongs to
+    pub day: u32,
+    /// Merkle root of the day's (investor, amount) payout leaves
+    pub payout_root: [u8; 32],
+    /// Sum of all payout leaves (matches the day's daily_distributed)
+    pub total_distributed: u64,
+    /// Number of payout leaves in the tree
+    pub leaf_count: u32,
+    /// Signer who submitted the root
+    pub submitted_by: Pubkey,
+    /// Timestamp the root was submitted
+    pub submitted_timestamp: i64,
+    /// Bump seed for the PDA
+    pub bump: u8,
+}
@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
 use crate::errors::ErrorCode;
+use crate::math::{add_u64, sub_u64, mul_div_u64};
+use crate::constants::DEFAULT_FEE_SHARE_MULTIPLIER_BPS;
 
 /// Depositor record to track individual investor's deposits and shares
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Default)]
 pub struct DepositorRecord {
     /// The investor's public key
     pub investor: Pubkey,
@@ -13,8 +15,12 @@ pub struct DepositorRecord {
     pub total_usdc_deposited: u64,
     /// Current SOL balance (in lamports)
     pub current_sol_balance: u64,
-    /// Current USDC balance (in smallest unit)
+    /// Current USDC balance (in smallest unit), `principal_balance + earnings_balance`
     pub current_usdc_balance: u64,
+    /// Part of the USDC balance that was deposited
+    pub principal_balance: u64,
+    /// Part of the USDC balance credited by auto-compounded fee payouts
+    pub earnings_balance: u64,
     /// Total SOL withdrawn (in lamports)
     pub total_sol_withdrawn: u64,
     /// Total USDC withdrawn (in smallest unit)
@@ -27,6 +33,32 @@ pub struct DepositorRecord {
     pub deposit_count: u32,
     /// Number of withdrawals made
     pub withdrawal_count: u32,
+    /// Fee share multiplier applied to the investor's weight in basis points (10000 = 1x)
+    pub fee_share_multiplier_bps: u16,
+    /// First distribution day the investor is paid on (earlier days roll into carry-over)
+    pub first_eligible_day: u32,
+    /// Inclusion in the config's eligibility root has been proven
+    pub eligible: bool,
+    /// Timestamp the balance last became non-zero
+    pub activated_at: i64,
+    /// Timestamp the balance last reached zero (0 while active)
+    pub deactivated_at: i64,
+    /// Last distribution day the investor was paid in
+    pub last_paid_day: u32,
+    /// Distribution round of `last_paid_day` the investor was paid in
+    pub last_paid_round: u8,
+    /// Quote token account payouts must be sent to (set on the first deposit or by register_quote_account)
+    pub registered_quote_account: Pubkey,
+    /// Fee payouts are added to the USDC balance instead of being sent to the registered quote account
+    pub auto_compound: bool,
+    /// The admin may withdraw the full USDC balance to the registered quote account (cleared once used)
+    pub batch_withdraw_approved: bool,
+    /// The investor forgoes fee distributions; their weight is excluded so their share goes to the others
+    pub opted_out: bool,
+    /// Distribution day `compounded_today` was credited on
+    pub compounded_day: u32,
+    /// USDC compounded into the balance on `compounded_day`, left out of that day's payout weight
+    pub compounded_today: u64,
     /// Bump seed for the PDA
     pub bump: u8,
 }
@@ -41,12 +73,27 @@ impl DepositorRecord {
             total_usdc_deposited: 0,
             current_sol_balance: 0,
             current_usdc_balance: 0,
+            principal_balance: 0,
+            earnings_balance: 0,
             total_sol_withdrawn: 0,
             total_usdc_withdrawn: 0,
             first_deposit_timestamp: now,
             last_activity_timestamp: now,
             deposit_count: 0,
             withdrawal_count: 0,
+            fee_share_multiplier_bps: DEFAULT_FEE_SHARE_MULTIPLIER_BPS,
+            first_eligible_day: 0,
+            eligible: false,
+            activated_at: 0,
+            deactivated_at: 0,
+            last_paid_day: 0,
+            last_paid_round: 0,
+            registered_quote_account: Pubkey::default(),
+            auto_compound: false,
+            batch_withdraw_approved: false,
+            opted_out: false,
+            compounded_day: 0,
+            compounded_today: 0,
             bump,
         }
     }
@@ -54,30 +101,28 @@ impl DepositorRecord {
     /// Updates the record with a new deposit
     pub fn add_deposit(&mut self, sol_amount: u64, usdc_amount: u64) -> Result<()> {
         let now = Clock::get().unwrap().unix_timestamp;
+        let was_active = self.is_active();
         
         // Update totals
-        self.total_sol_deposited = self.total_sol_deposited
-            .checked_add(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_sol_deposited = add_u64(self.total_sol_deposited, sol_amount)?;
         
-        self.total_usdc_deposited = self.total_usdc_deposited
-            .checked_add(usdc_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_usdc_deposited = add_u64(self.total_usdc_deposited, usdc_amount)?;
         
         // Update current balances
-        self.current_sol_balance = self.current_sol_balance
-            .checked_add(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        self.current_sol_balance = add_u64(self.current_sol_balance, sol_amount)?;
         
-        self.current_usdc_balance = self.current_usdc_balance
-            .checked_add(usdc_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        self.current_usdc_balance = add_u64(self.current_usdc_balance, usdc_amount)?;
+        self.principal_balance = add_u64(self.principal_balance, usdc_amount)?;
         
         // Update timestamps
         self.last_activity_timestamp = now;
         if self.deposit_count == 0 {
             self.first_deposit_timestamp = now;
         }
+        if !was_active && self.is_active() {
+            self.activated_at = now;
+            self.deactivated_at = 0;
+        }
         
         // Increment deposit count
         self.deposit_count = self.deposit_count
@@ -102,25 +147,23 @@ impl DepositorRecord {
         );
         
         // Update totals
-        self.total_sol_withdrawn = self.total_sol_withdrawn
-            .checked_add(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_sol_withdrawn = add_u64(self.total_sol_withdrawn, sol_amount)?;
         
-        self.total_usdc_withdrawn = self.total_usdc_withdrawn
-            .checked_add(usdc_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_usdc_withdrawn = add_u64(self.total_usdc_withdrawn, usdc_amount)?;
         
         // Update current balances
-        self.current_sol_balance = self.current_sol_balance
-            .checked_sub(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        self.current_sol_balance = sub_u64(self.current_sol_balance, sol_amount)?;
         
-        self.current_usdc_balance = self.current_usdc_balance
-            .checked_sub(usdc_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        self.current_usdc_balance = sub_u64(self.current_usdc_balance, usdc_amount)?;
+        let (from_principal, from_earnings) = self.usdc_withdrawal_split(usdc_amount);
+        self.principal_balance = sub_u64(self.principal_balance, from_principal)?;
+        self.earnings_balance = sub_u64(self.earnings_balance, from_earnings)?;
         
         // Update timestamp
         self.last_activity_timestamp = now;
+        if !self.is_active() {
+            self.deactivated_at = now;
+        }
         
         // Increment withdrawal count
         self.withdrawal_count = self.withdrawal_count
@@ -130,6 +173,36 @@ impl DepositorRecord {
         Ok(())
     }
 
+    /// Adds a compounded fee payout made on distribution `day` to the USDC balance without
+    /// counting it as a deposit
+    pub fn add_compounded_usdc(&mut self, usdc_amount: u64, day: u32) -> Result<()> {
+        let now = Clock::get().unwrap().unix_timestamp;
+        let was_active = self.is_active();
+        
+        self.current_usdc_balance = add_u64(self.current_usdc_balance, usdc_amount)?;
+        self.earnings_balance = add_u64(self.earnings_balance, usdc_amount)?;
+        
+        if self.compounded_day != day {
+            self.compounded_day = day;
+            self.compounded_today = 0;
+        }
+        self.compounded_today = add_u64(self.compounded_today, usdc_amount)?;
+        
+        if !was_active && self.is_active() {
+            self.activated_at = now;
+            self.deactivated_at = 0;
+        }
+        
+        Ok(())
+    }
+
+    /// Splits a USDC withdrawal into the parts taken from principal and from earnings;
+    /// principal is drawn first so locked earnings stay in place
+    pub fn usdc_withdrawal_split(&self, usdc_amount: u64) -> (u64, u64) {
+        let from_principal = usdc_amount.min(self.principal_balance);
+        (from_principal, usdc_amount - from_principal)
+    }
+
     /// Calculates the investor's share percentage based on their deposits
     pub fn calculate_share_percentage(&self, total_sol: u64, total_usdc: u64) -> Result<u16> {
         if total_sol == 0 && total_usdc == 0 {
@@ -156,6 +229,37 @@ impl DepositorRecord {
         Ok(share_percentage.min(10000) as u16)
     }
 
+    /// USDC balance scaled by the investor's fee share multiplier (0 once opted out)
+    pub fn weighted_usdc_balance(&self) -> Result<u64> {
+        self.weighted(self.current_usdc_balance)
+    }
+
+    /// Weighted USDC balance as of the start of distribution `day`, before the payouts
+    /// compounded on that day
+    pub fn day_start_weighted_usdc_balance(&self, day: u32) -> Result<u64> {
+        let compounded = if self.compounded_day == day { self.compounded_today } else { 0 };
+        self.weighted(self.current_usdc_balance.saturating_sub(compounded))
+    }
+
+    fn weighted(&self, usdc_balance: u64) -> Result<u64> {
+        if self.opted_out {
+            return Ok(0);
+        }
+        mul_div_u64(usdc_balance, self.fee_share_multiplier_bps as u64, 10000)
+    }
+
+    /// Checks if the investor currently has a non-zero balance
+    pub fn is_active(&self) -> bool {
+        self.current_sol_balance > 0 || self.current_usdc_balance > 0
+    }
+
+    /// Checks if the investor held a balance at `timestamp` since their last activation
+    pub fn was_active_at(&self, timestamp: i64) -> bool {
+        self.activated_at > 0
+            && self.activated_at <= timestamp
+            && (self.deactivated_at == 0 || self.deactivated_at > timestamp)
+    }
+
     /// Checks if the investor has any deposits
     pub fn has_deposits(&self) -> bool {
         self.total_sol_deposited > 0 || self.total_usdc_deposited > 0
@@ -168,3 +272,37 @@ impl DepositorRecord {
         self.total_sol_deposited
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn withdrawals_draw_principal_before_earnings() {
+        let record = DepositorRecord {
+            current_usdc_balance: 1_500,
+            principal_balance: 1_000,
+            earnings_balance: 500,
+            ..Default::default()
+        };
+
+        assert_eq!(record.usdc_withdrawal_split(600), (600, 0));
+        assert_eq!(record.usdc_withdrawal_split(1_000), (1_000, 0));
+        assert_eq!(record.usdc_withdrawal_split(1_200), (1_000, 200));
+    }
+
+    #[test]
+    fn compounded_payouts_only_weigh_from_the_next_day() {
+        let record = DepositorRecord {
+            current_usdc_balance: 1_500,
+            fee_share_multiplier_bps: 20_000,
+            compounded_day: 3,
+            compounded_today: 500,
+            ..Default::default()
+        };
+
+        assert_eq!(record.day_start_weighted_usdc_balance(3).unwrap(), 2_000);
+        assert_eq!(record.day_start_weighted_usdc_balance(4).unwrap(), 3_000);
+        assert_eq!(record.weighted_usdc_balance().unwrap(), 3_000);
+    }
+}
@@ -1,7 +1,32 @@
 use anchor_lang::prelude::*;
+use crate::constants::{
+    REMAINDER_POLICY_CREATOR, REMAINDER_POLICY_TREASURY, CARRY_OVER_POLICY_INCLUDE, CARRY_OVER_POLICY_HOLD,
+    SECONDS_PER_DAY, MAX_AGE_BRACKETS, DEFAULT_FEE_SHARE_MULTIPLIER_BPS,
+};
+use crate::errors::ErrorCode;
+
+/// Weight multiplier for investors whose first deposit is at least `min_age_seconds` old
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct AgeBracket {
+    /// Deposit age the bracket starts at, in seconds
+    pub min_age_seconds: i64,
+    /// Weight multiplier in basis points (0 for an unused entry)
+    pub multiplier_bps: u16,
+}
+
+/// Config values setting the investors' fee share, kept from before a config change until its
+/// cool-off has passed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct FeeShareSnapshot {
+    pub y0_allocation: u64,
+    pub investor_fee_share_bps: u16,
+    pub f_locked_floor_bps: u16,
+    pub f_locked_ceiling_bps: u16,
+    pub creator_min_share_bps: u16,
+}
 
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Default)]
 pub struct DistributionConfig {
     /// Total investor allocation at TGE (Y0)
     pub y0_allocation: u64,
@@ -9,12 +34,324 @@ pub struct DistributionConfig {
     pub investor_fee_share_bps: u16,
     /// Minimum payout amount in lamports (dust threshold)
     pub min_payout_lamports: u64,
-    /// Daily distribution cap in lamports (0 = no cap)
+    /// Legacy daily distribution cap, only used while `daily_cap_quote_units` is 0
+    /// (applied to quote payouts despite the name)
     pub daily_cap_lamports: u64,
     /// Creator wallet address for remainder routing
     pub creator_wallet: Pubkey,
     /// Quote mint address (for validation)
     pub quote_mint: Pubkey,
+    /// Route the creator remainder and close the day from the crank's final page
+    pub auto_close_on_final_page: bool,
+    /// External vesting program to read locked amounts from (default = use DepositorRecord)
+    pub vesting_program: Pubkey,
+    /// Share of the creator remainder paid to fully-vested investors in basis points (0 = disabled)
+    pub vested_investor_share_bps: u16,
+    /// Admin allowed to schedule config changes
+    pub admin: Pubkey,
+    /// Creator wallet scheduled to replace the current one (default = none pending)
+    pub pending_creator_wallet: Pubkey,
+    /// Timestamp after which the pending creator wallet takes effect
+    pub creator_update_effective_ts: i64,
+    /// Delay between scheduling and applying a creator wallet update (in seconds)
+    pub creator_wallet_timelock_seconds: i64,
+    /// Where the creator remainder goes at day close (0 = creator, 1 = investors, 2 = treasury)
+    pub remainder_policy: u8,
+    /// Treasury wallet receiving the remainder under the treasury policy
+    pub treasury_wallet: Pubkey,
+    /// Emit per-page and per-investor payout events (day close events are always emitted)
+    pub emit_events: bool,
+    /// Days after the current distribution day before a new depositor becomes eligible
+    pub eligibility_delay_days: u32,
+    /// Merkle root of investors allowed to receive payouts (all zeros = no allowlist)
+    pub eligibility_root: [u8; 32],
+    /// Daily distribution cap in the quote mint's native units (0 = fall back to `daily_cap_lamports`)
+    pub daily_cap_quote_units: u64,
+    /// Decimals of the quote mint, for displaying quote amounts
+    pub quote_decimals: u8,
+    /// Time after a day begins from which anyone can force-close it (in seconds)
+    pub day_max_duration_seconds: i64,
+    /// Minimum time between an investor's deposits (in seconds, 0 = no limit)
+    pub min_deposit_interval_seconds: i64,
+    /// Whether carry-over joins the day's investor pool (0 = retain, 1 = include, 2 = hold until covered)
+    pub carry_over_policy: u8,
+    /// Lower bound applied to f_locked before the min with the fee share (in basis points)
+    pub f_locked_floor_bps: u16,
+    /// Upper bound applied to f_locked before the min with the fee share (in basis points, 0 = none)
+    pub f_locked_ceiling_bps: u16,
+    /// Require page 0 to sum the depositor records in remaining_accounts and match vault stats
+    pub verify_total_locked: bool,
+    /// Allowed difference between the summed records and vault stats (in quote native units)
+    pub total_locked_tolerance_units: u64,
+    /// Mint the creator remainder is paid in (default or quote_mint = no swap)
+    pub creator_payout_mint: Pubkey,
+    /// Deposits are accepted from this timestamp (0 = no start bound)
+    pub deposit_window_start_ts: i64,
+    /// Deposits are rejected from this timestamp (0 = no end bound)
+    pub deposit_window_end_ts: i64,
+    /// Carry-over retained past a day close (in quote native units, 0 = no cap)
+    pub max_carry_over: u64,
+    /// Where carry-over above `max_carry_over` goes at day close (0 = creator, 1 = largest-weight investor)
+    pub carry_over_cap_policy: u8,
+    /// How distribute_to_investor treats records with a zero balance (0 = skip, 1 = reject)
+    pub zero_balance_policy: u8,
+    /// Distribution rounds per day, evenly spaced from the day start (1 = one round a day)
+    pub rounds_per_day: u8,
+    /// Locked total below which a day's fees skip the investor distribution (0 = no minimum)
+    pub min_total_locked_to_distribute: u64,
+    /// Weight external vesting payouts by locked amount times remaining lock time
+    pub weight_by_remaining_lock: bool,
+    /// Seconds without a creator payout after which the creator remainder goes to investors (0 = never)
+    pub creator_inactivity_seconds: i64,
+    /// Timestamp of the last remainder routed to or claimed by the creator (config creation at first)
+    pub last_creator_payout_ts: i64,
+    /// Minimum share of claimed fees kept for the creator in basis points, whatever f_locked is (0 = none)
+    pub creator_min_share_bps: u16,
+    /// Stable token accepted for deposits (the `usdc_*` balances are denominated in it)
+    pub deposit_mint: Pubkey,
+    /// Scales the dust threshold with the investor count: investor_fee_quote / (k * investor_count)
+    /// replaces `min_payout_lamports` (0 = fixed `min_payout_lamports`)
+    pub dust_threshold_k: u32,
+    /// Require every non-final crank page to process exactly `DISTRIBUTION_BATCH_SIZE` investors
+    pub fixed_page_size: bool,
+    /// The program is winding down for good: deposits are rejected and withdrawals skip the
+    /// day's lock (set once by the admin, cannot be cleared)
+    pub program_ended: bool,
+    /// Program notified by CPI when route_creator_remainder closes a day (default = no callback)
+    pub callback_program: Pubkey,
+    /// Largest share of the vault's USDC a single investor may reach by depositing, in basis
+    /// points (0 = no cap)
+    pub max_investor_share_bps: u16,
+    /// SOL paid from the keeper incentive account to the payer of each crank page (0 = no reward)
+    pub keeper_reward_lamports: u64,
+    /// Timestamp until which compounded earnings cannot be withdrawn, only principal (0 = never locked)
+    pub earnings_unlock_ts: i64,
+    /// Deposit age brackets scaling investor weights, by ascending age (unused entries are zeroed)
+    pub age_brackets: [AgeBracket; MAX_AGE_BRACKETS],
+    /// Share of the accumulated quote released as each day's claimed quote, in basis points;
+    /// the rest stays buffered for later days (0 = release everything)
+    pub distribution_rate_bps: u16,
+    /// Require quote fees to be claimed into the vault during the day before its first page
+    pub require_fresh_claim: bool,
+    /// Quote vault balance below which a crank page 0 run with `auto_claim` claims the position's fees first
+    pub min_crankable_quote: u64,
+    /// Stop `begin_distribution_day` from starting new days; a day in progress still finishes
+    pub new_days_paused: bool,
+    /// Largest total USDC one investor may deposit, as a share of `y0_allocation` in basis
+    /// points (0 = no cap)
+    pub max_investor_share_of_y0_bps: u16,
+    /// Delay after a config change before the crank uses the new fee share (in seconds, 0 = immediate)
+    pub config_change_cooloff_seconds: i64,
+    /// Timestamp from which the last config change applies to the crank
+    pub config_effective_ts: i64,
+    /// Fee share in effect before the last config change, used by days begun before `config_effective_ts`
+    pub previous_fee_share: FeeShareSnapshot,
+    /// Largest quote amount a single position fee claim may bring in (0 = no maximum)
+    pub max_claim_per_tx: u64,
+    /// Wallet whose quote account receives the carry-over dust at day close (default = dust is retained)
+    pub dust_recipient: Pubkey,
+    /// Reject investor payouts into quote accounts with a delegate set
+    pub reject_delegated_accounts: bool,
     /// Bump seed for the PDA
     pub bump: u8,
-}
\ No newline at end of file
+}
+
+impl DistributionConfig {
+    /// Checks if a creator wallet update is pending
+    pub fn has_pending_creator_wallet(&self) -> bool {
+        self.pending_creator_wallet != Pubkey::default()
+    }
+    
+    /// Adopts the pending creator wallet once its timelock has elapsed
+    pub fn apply_pending_creator_wallet(&mut self, now: i64) -> bool {
+        if !self.has_pending_creator_wallet() || now < self.creator_update_effective_ts {
+            return false;
+        }
+        
+        self.creator_wallet = self.pending_creator_wallet;
+        self.pending_creator_wallet = Pubkey::default();
+        self.creator_update_effective_ts = 0;
+        true
+    }
+    
+    /// Checks if investors must prove inclusion in the eligibility root to be paid
+    pub fn requires_eligibility_proof(&self) -> bool {
+        self.eligibility_root != [0u8; 32]
+    }
+    
+    /// Daily cap in quote native units (0 = no cap), preferring `daily_cap_quote_units` over the legacy field
+    pub fn daily_cap(&self) -> u64 {
+        if self.daily_cap_quote_units > 0 {
+            self.daily_cap_quote_units
+        } else {
+            self.daily_cap_lamports
+        }
+    }
+    
+    /// Cap left for the day once `daily_distributed` has been paid (0 when uncapped)
+    pub fn remaining_daily_cap(&self, daily_distributed: u64) -> u64 {
+        self.daily_cap().saturating_sub(daily_distributed)
+    }
+    
+    /// Dust threshold for payouts out of `investor_fee_quote` shared by `investor_count` investors:
+    /// `min_payout_lamports`, or investor_fee_quote / (k * investor_count) when `dust_threshold_k` is set
+    pub fn min_payout(&self, investor_fee_quote: u64, investor_count: u32) -> u64 {
+        if self.dust_threshold_k == 0 {
+            return self.min_payout_lamports;
+        }
+        
+        let recipients = self.dust_threshold_k as u128 * investor_count.max(1) as u128;
+        (investor_fee_quote as u128 / recipients) as u64
+    }
+    
+    /// Checks if the creator has gone `creator_inactivity_seconds` without a payout, so the
+    /// creator remainder is routed to investors instead
+    pub fn is_creator_inactive(&self, now: i64) -> bool {
+        self.remainder_policy == REMAINDER_POLICY_CREATOR
+            && self.creator_inactivity_seconds > 0
+            && now >= self.last_creator_payout_ts.saturating_add(self.creator_inactivity_seconds)
+    }
+    
+    /// Wallet that receives the remainder at day close (None when investors receive it)
+    pub fn remainder_recipient(&self) -> Option<Pubkey> {
+        match self.remainder_policy {
+            REMAINDER_POLICY_CREATOR => Some(self.creator_wallet),
+            REMAINDER_POLICY_TREASURY => Some(self.treasury_wallet),
+            _ => None,
+        }
+    }
+    
+    /// Wallet that receives the day's carry-over dust at day close (None when it is retained)
+    pub fn dust_recipient(&self) -> Option<Pubkey> {
+        (self.dust_recipient != Pubkey::default()).then_some(self.dust_recipient)
+    }
+    
+    /// Mint the creator remainder is swapped into before payout (None when it is paid in quote)
+    pub fn creator_payout_swap_mint(&self) -> Option<Pubkey> {
+        let swaps = self.remainder_policy == REMAINDER_POLICY_CREATOR
+            && self.creator_payout_mint != Pubkey::default()
+            && self.creator_payout_mint != self.quote_mint;
+        swaps.then_some(self.creator_payout_mint)
+    }
+    
+    /// Checks if deposits are accepted at `now`; a zero bound is open-ended, so 0/0 is always open
+    pub fn is_deposit_window_open(&self, now: i64) -> bool {
+        (self.deposit_window_start_ts == 0 || now >= self.deposit_window_start_ts)
+            && (self.deposit_window_end_ts == 0 || now < self.deposit_window_end_ts)
+    }
+    
+    /// Checks if withdrawals are limited to principal at `now` because earnings are still locked
+    pub fn are_earnings_locked(&self, now: i64) -> bool {
+        now < self.earnings_unlock_ts
+    }
+    
+    /// Checks if deposit age brackets scale investor weights
+    pub fn has_age_brackets(&self) -> bool {
+        self.age_brackets[0].multiplier_bps > 0
+    }
+    
+    /// Weight multiplier of the oldest bracket a deposit `age_seconds` old has reached
+    /// (1x below the first bracket)
+    pub fn age_multiplier_bps(&self, age_seconds: i64) -> u16 {
+        self.age_brackets
+            .iter()
+            .filter(|bracket| bracket.multiplier_bps > 0 && age_seconds >= bracket.min_age_seconds)
+            .map(|bracket| bracket.multiplier_bps)
+            .next_back()
+            .unwrap_or(DEFAULT_FEE_SHARE_MULTIPLIER_BPS)
+    }
+    
+    /// Largest age multiplier any investor can reach, which age-scaled weights are divided by
+    pub fn max_age_multiplier_bps(&self) -> u16 {
+        self.age_brackets
+            .iter()
+            .map(|bracket| bracket.multiplier_bps)
+            .fold(DEFAULT_FEE_SHARE_MULTIPLIER_BPS, u16::max)
+    }
+    
+    /// Quote released from `accumulated_quote` as a day's claimed quote:
+    /// min(accumulated_quote * distribution_rate_bps / 10000, accumulated_quote)
+    pub fn smoothed_claim(&self, accumulated_quote: u64) -> u64 {
+        if self.distribution_rate_bps == 0 {
+            return accumulated_quote;
+        }
+        let released = (accumulated_quote as u128 * self.distribution_rate_bps as u128) / 10000;
+        std::cmp::min(released as u64, accumulated_quote)
+    }
+    
+    /// Checks if the carry-over joins a day's investor pool given that day's freshly claimed quote
+    pub fn includes_carry_over(&self, claimed_quote: u64, carry_over: u64) -> bool {
+        match self.carry_over_policy {
+            CARRY_OVER_POLICY_INCLUDE => carry_over > 0,
+            CARRY_OVER_POLICY_HOLD => carry_over > 0 && claimed_quote >= carry_over,
+            _ => false,
+        }
+    }
+    
+    /// Carry-over above `max_carry_over` that must leave the vault at day close (0 when uncapped)
+    pub fn carry_over_excess(&self, carry_over: u64) -> u64 {
+        if self.max_carry_over == 0 {
+            0
+        } else {
+            carry_over.saturating_sub(self.max_carry_over)
+        }
+    }
+    
+    /// Seconds after the day start at which `round` opens
+    pub fn round_start_offset(&self, round: u8) -> i64 {
+        SECONDS_PER_DAY / i64::from(self.rounds_per_day.max(1)) * i64::from(round)
+    }
+    
+    /// Checks if `round` is the day's last round, whose final page may close the day
+    pub fn is_last_round(&self, round: u8) -> bool {
+        round.saturating_add(1) >= self.rounds_per_day
+    }
+    
+    /// Fee share values currently written to the config
+    pub fn fee_share(&self) -> FeeShareSnapshot {
+        FeeShareSnapshot {
+            y0_allocation: self.y0_allocation,
+            investor_fee_share_bps: self.investor_fee_share_bps,
+            f_locked_floor_bps: self.f_locked_floor_bps,
+            f_locked_ceiling_bps: self.f_locked_ceiling_bps,
+            creator_min_share_bps: self.creator_min_share_bps,
+        }
+    }
+    
+    /// Checks if the last config change is still cooling off at `now`
+    pub fn is_config_cooling_off(&self, now: i64) -> bool {
+        now < self.config_effective_ts
+    }
+    
+    /// Snapshots the fee share in effect before a config change made at `now`. A change made
+    /// while an earlier one is still cooling off keeps the earlier snapshot
+    pub fn snapshot_fee_share(&mut self, now: i64) {
+        if !self.is_config_cooling_off(now) {
+            self.previous_fee_share = self.fee_share();
+        }
+    }
+    
+    /// Starts the cool-off of a config change made at `now`, under the cool-off in place before
+    /// the change so it cannot shorten its own delay
+    pub fn start_config_cooloff(&mut self, now: i64, cooloff_seconds: i64) -> Result<()> {
+        self.config_effective_ts = now
+            .checked_add(cooloff_seconds)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+    
+    /// Config the crank uses for a day begun at `day_start_ts`: the previous fee share while the
+    /// last change was still cooling off at the day start, the current values otherwise
+    pub fn effective_at(&self, day_start_ts: i64) -> Box<DistributionConfig> {
+        let mut config = Box::new(self.clone());
+        if self.is_config_cooling_off(day_start_ts) {
+            let previous = self.previous_fee_share;
+            config.y0_allocation = previous.y0_allocation;
+            config.investor_fee_share_bps = previous.investor_fee_share_bps;
+            config.f_locked_floor_bps = previous.f_locked_floor_bps;
+            config.f_locked_ceiling_bps = previous.f_locked_ceiling_bps;
+            config.creator_min_share_bps = previous.creator_min_share_bps;
+        }
+        config
+    }
+}
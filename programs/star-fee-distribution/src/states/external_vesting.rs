@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::math::{sub_u64, mul_div_u64};
+
+/// Read-only view of an investor's stream in an external vesting program
+///
+/// The account must be owned by `DistributionConfig::vesting_program` and its data
+/// must be the Borsh encoding of this struct with no discriminator:
+/// `recipient (32) | mint (32) | deposited_amount (u64) | start_time (i64) | end_time (i64)`.
+/// Tokens unlock linearly between `start_time` and `end_time`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ExternalVesting {
+    /// The investor receiving the vested tokens
+    pub recipient: Pubkey,
+    /// Mint of the vested token
+    pub mint: Pubkey,
+    /// Total amount deposited into the stream
+    pub deposited_amount: u64,
+    /// Timestamp when unlocking starts
+    pub start_time: i64,
+    /// Timestamp when the stream is fully unlocked
+    pub end_time: i64,
+}
+
+impl ExternalVesting {
+    /// Deserializes and validates a vesting account for the given investor
+    pub fn load(account: &AccountInfo, vesting_program: &Pubkey, investor: &Pubkey) -> Result<Self> {
+        require!(
+            *vesting_program != Pubkey::default() && account.owner == vesting_program,
+            ErrorCode::VestingAccountInvalid
+        );
+        
+        let data = account.try_borrow_data()?;
+        let vesting = Self::deserialize(&mut &data[..])
+            .map_err(|_| ErrorCode::VestingAccountInvalid)?;
+        
+        require!(
+            vesting.recipient == *investor && vesting.start_time <= vesting.end_time,
+            ErrorCode::VestingAccountInvalid
+        );
+        
+        Ok(vesting)
+    }
+
+    /// Amount still locked at the given timestamp
+    pub fn locked_amount(&self, now: i64) -> Result<u64> {
+        if now <= self.start_time {
+            return Ok(self.deposited_amount);
+        }
+        if now >= self.end_time {
+            return Ok(0);
+        }
+        
+        let elapsed = (now - self.start_time) as u64;
+        let duration = (self.end_time - self.start_time) as u64;
+        let unlocked = mul_div_u64(self.deposited_amount, elapsed, duration)?;
+        
+        sub_u64(self.deposited_amount, unlocked)
+    }
+
+    /// Seconds until the stream is fully unlocked at the given timestamp
+    pub fn remaining_lock_seconds(&self, now: i64) -> u64 {
+        u64::try_from(self.end_time.saturating_sub(now)).unwrap_or(0)
+    }
+}
@@ -2,8 +2,18 @@ pub mod distribution_config;
 pub mod depositor_record;
 pub mod vault_stats;
 pub mod crank_state;
+pub mod position_state;
+pub mod external_vesting;
+pub mod day_record;
+pub mod packed_investors;
+pub mod position_registry;
 
 pub use distribution_config::*;
 pub use depositor_record::*;
 pub use vault_stats::*;
-pub use crank_state::*;
\ No newline at end of file
+pub use crank_state::*;
+pub use position_state::*;
+pub use external_vesting::*;
+pub use day_record::*;
+pub use packed_investors::*;
+pub use position_registry::*;
\ No newline at end of file
@@ -0,0 +1,242 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::constants::{MAX_PACKED_INVESTORS, PACKED_INVESTOR_FLAG_ACTIVE};
+use crate::math::{add_u64, sub_u64};
+
+/// One investor slot in the packed investors account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct PackedInvestorEntry {
+    /// Investor wallet (default for a free slot)
+    pub investor: Pubkey,
+    /// Current USDC balance
+    pub usdc_balance: u64,
+    /// Bit flags (PACKED_INVESTOR_FLAG_*)
+    pub flags: u8,
+    /// First distribution day this investor is paid
+    pub first_eligible_day: u32,
+    /// Timestamp the slot was allocated to this investor
+    pub activated_at: i64,
+}
+
+impl PackedInvestorEntry {
+    /// Checks if the slot holds an investor with a balance
+    pub fn is_active(&self) -> bool {
+        self.flags & PACKED_INVESTOR_FLAG_ACTIVE != 0
+    }
+
+    /// Checks if the slot already held this investor at `timestamp`
+    pub fn was_active_at(&self, timestamp: i64) -> bool {
+        self.is_active() && self.activated_at <= timestamp
+    }
+}
+
+/// Bounded array of small investors, the dense alternative to one DepositorRecord per investor
+#[account]
+#[derive(InitSpace)]
+pub struct PackedInvestors {
+    /// Sum of all entry balances
+    pub total_usdc_balance: u64,
+    /// Number of active entries
+    pub active_count: u32,
+    /// Distribution day the page cursor belongs to
+    pub distribution_day: u32,
+    /// Distribution round of `distribution_day` the page cursor belongs to
+    pub distribution_round: u8,
+    /// Next entry index to pay in `distribution_round` of `distribution_day`
+    pub next_index: u32,
+    /// Investor slots; removed investors leave a free slot for reuse
+    #[max_len(MAX_PACKED_INVESTORS)]
+    pub entries: Vec<PackedInvestorEntry>,
+    /// Bump seed for the PDA
+    pub bump: u8,
+}
+
+impl PackedInvestors {
+    /// Finds the slot of an active investor
+    pub fn find(&self, investor: &Pubkey) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|entry| entry.is_active() && entry.investor == *investor)
+    }
+
+    /// Adds a new investor to the first free slot and returns its index
+    pub fn add(&mut self, investor: Pubkey, usdc_balance: u64, first_eligible_day: u32, activated_at: i64) -> Result<u32> {
+        require!(self.find(&investor).is_none(), ErrorCode::PackedInvestorAlreadyExists);
+        require!(usdc_balance > 0, ErrorCode::InvalidDepositAmount);
+
+        let entry = PackedInvestorEntry {
+            investor,
+            usdc_balance,
+            flags: PACKED_INVESTOR_FLAG_ACTIVE,
+            first_eligible_day,
+            activated_at,
+        };
+
+        let index = match self.entries.iter().position(|entry| !entry.is_active()) {
+            Some(index) => {
+                self.entries[index] = entry;
+                index
+            }
+            None => {
+                require!(self.entries.len() < MAX_PACKED_INVESTORS, ErrorCode::PackedInvestorsFull);
+                self.entries.push(entry);
+                self.entries.len() - 1
+            }
+        };
+
+        self.total_usdc_balance = add_u64(self.total_usdc_balance, usdc_balance)?;
+        self.active_count = self.active_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+        u32::try_from(index).map_err(|_| ErrorCode::MathOverflow.into())
+    }
+
+    /// Returns the active entry at `index`, which must belong to `investor`
+    pub fn entry(&self, index: u32, investor: &Pubkey) -> Result<&PackedInvestorEntry> {
+        let entry = self.entries
+            .get(index as usize)
+            .ok_or(ErrorCode::PackedInvestorMismatch)?;
+        require!(
+            entry.is_active() && entry.investor == *investor,
+            ErrorCode::PackedInvestorMismatch
+        );
+        Ok(entry)
+    }
+
+    /// Adds to the balance of the investor at `index`
+    pub fn increase(&mut self, index: u32, investor: &Pubkey, amount: u64) -> Result<()> {
+        let balance = add_u64(self.entry(index, investor)?.usdc_balance, amount)?;
+        self.entries[index as usize].usdc_balance = balance;
+        self.total_usdc_balance = add_u64(self.total_usdc_balance, amount)?;
+        Ok(())
+    }
+
+    /// Subtracts from the balance of the investor at `index`, freeing the slot at zero.
+    /// Returns true when the investor was removed
+    pub fn decrease(&mut self, index: u32, investor: &Pubkey, amount: u64) -> Result<bool> {
+        let current = self.entry(index, investor)?.usdc_balance;
+        require!(amount <= current, ErrorCode::InsufficientBalance);
+
+        let balance = sub_u64(current, amount)?;
+        self.total_usdc_balance = sub_u64(self.total_usdc_balance, amount)?;
+
+        if balance > 0 {
+            self.entries[index as usize].usdc_balance = balance;
+            return Ok(false);
+        }
+
+        self.entries[index as usize] = PackedInvestorEntry::default();
+        self.active_count = self.active_count.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+        Ok(true)
+    }
+
+    /// Returns `count` slots starting at `start`, including free ones
+    pub fn page(&self, start: u32, count: u32) -> Result<&[PackedInvestorEntry]> {
+        let start = start as usize;
+        let end = start.checked_add(count as usize).ok_or(ErrorCode::MathOverflow)?;
+        self.entries
+            .get(start..end)
+            .ok_or(ErrorCode::InvalidPaginationCursor.into())
+    }
+
+    /// Moves the page cursor to `round` of `day`, restarting from the first slot on a new round
+    pub fn sync_cursor(&mut self, day: u32, round: u8) {
+        if (self.distribution_day, self.distribution_round) != (day, round) {
+            self.distribution_day = day;
+            self.distribution_round = round;
+            self.next_index = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty() -> PackedInvestors {
+        PackedInvestors {
+            total_usdc_balance: 0,
+            active_count: 0,
+            distribution_day: 0,
+            distribution_round: 0,
+            next_index: 0,
+            entries: Vec::new(),
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn add_update_and_remove_entries() {
+        let mut packed = empty();
+        let (alice, bob, carol) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert_eq!(packed.add(alice, 1_000, 1, 10).unwrap(), 0);
+        assert_eq!(packed.add(bob, 3_000, 1, 10).unwrap(), 1);
+        assert!(packed.add(alice, 1, 1, 10).is_err());
+
+        packed.increase(0, &alice, 500).unwrap();
+        assert!(packed.increase(1, &alice, 500).is_err());
+        assert_eq!(packed.entries[0].usdc_balance, 1_500);
+        assert_eq!(packed.total_usdc_balance, 4_500);
+
+        assert!(!packed.decrease(1, &bob, 1_000).unwrap());
+        assert!(packed.decrease(1, &bob, 2_001).is_err());
+        assert!(packed.decrease(1, &bob, 2_000).unwrap());
+        assert_eq!(packed.entries[1], PackedInvestorEntry::default());
+        assert_eq!(packed.active_count, 1);
+        assert_eq!(packed.total_usdc_balance, 1_500);
+
+        // The freed slot is reused before the array grows
+        assert_eq!(packed.add(carol, 2_000, 2, 20).unwrap(), 1);
+        assert_eq!(packed.entries.len(), 2);
+        assert_eq!(packed.find(&carol), Some(1));
+        assert_eq!(packed.find(&bob), None);
+    }
+
+    #[test]
+    fn add_rejects_when_full() {
+        let mut packed = empty();
+        for _ in 0..MAX_PACKED_INVESTORS {
+            packed.add(Pubkey::new_unique(), 1_000, 0, 0).unwrap();
+        }
+        assert!(packed.add(Pubkey::new_unique(), 1_000, 0, 0).is_err());
+    }
+
+    #[test]
+    fn page_returns_slices_within_bounds() {
+        let mut packed = empty();
+        let investors: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        for investor in &investors {
+            packed.add(*investor, 1_000, 0, 0).unwrap();
+        }
+        packed.decrease(2, &investors[2], 1_000).unwrap();
+
+        let page = packed.page(1, 3).unwrap();
+        assert_eq!(page.len(), 3);
+        assert_eq!(page[0].investor, investors[1]);
+        assert!(!page[1].is_active());
+        assert_eq!(page[2].investor, investors[3]);
+
+        assert_eq!(packed.page(5, 0).unwrap().len(), 0);
+        assert!(packed.page(4, 2).is_err());
+    }
+
+    #[test]
+    fn sync_cursor_restarts_on_a_new_day() {
+        let mut packed = empty();
+        packed.sync_cursor(3, 0);
+        packed.next_index = 4;
+        packed.sync_cursor(3, 0);
+        assert_eq!(packed.next_index, 4);
+        packed.sync_cursor(4, 0);
+        assert_eq!((packed.distribution_day, packed.next_index), (4, 0));
+    }
+
+    #[test]
+    fn sync_cursor_restarts_on_a_new_round() {
+        let mut packed = empty();
+        packed.sync_cursor(3, 0);
+        packed.next_index = 4;
+        packed.sync_cursor(3, 1);
+        assert_eq!((packed.distribution_day, packed.distribution_round, packed.next_index), (3, 1, 0));
+    }
+}
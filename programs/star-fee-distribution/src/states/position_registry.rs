@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::constants::MAX_REGISTERED_POSITIONS;
+
+/// Honorary positions whose fees may be claimed into the config's shared fee vaults
+#[account]
+#[derive(InitSpace)]
+pub struct PositionRegistry {
+    /// Distribution config the positions feed
+    pub config: Pubkey,
+    /// Authorized position accounts
+    #[max_len(MAX_REGISTERED_POSITIONS)]
+    pub positions: Vec<Pubkey>,
+    /// Bump seed for the PDA
+    pub bump: u8,
+}
+
+impl PositionRegistry {
+    /// Checks if fees may be claimed from `position`
+    pub fn contains(&self, position: &Pubkey) -> bool {
+        self.positions.contains(position)
+    }
+
+    /// Authorizes claims from `position`
+    pub fn add(&mut self, position: Pubkey) -> Result<()> {
+        require!(!self.contains(&position), ErrorCode::PositionAlreadyRegistered);
+        require!(self.positions.len() < MAX_REGISTERED_POSITIONS, ErrorCode::PositionRegistryFull);
+        self.positions.push(position);
+        Ok(())
+    }
+
+    /// Stops claims from `position`
+    pub fn remove(&mut self, position: &Pubkey) -> Result<()> {
+        let index = self.positions
+            .iter()
+            .position(|registered| registered == position)
+            .ok_or(ErrorCode::PositionNotRegistered)?;
+        self.positions.swap_remove(index);
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_remove_positions() {
+        let mut registry = PositionRegistry { config: Pubkey::new_unique(), positions: Vec::new(), bump: 0 };
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        registry.add(first).unwrap();
+        registry.add(second).unwrap();
+        assert!(registry.add(first).is_err());
+        assert!(registry.contains(&first) && registry.contains(&second));
+
+        registry.remove(&first).unwrap();
+        assert!(!registry.contains(&first));
+        assert!(registry.remove(&first).is_err());
+        assert_eq!(registry.positions, vec![second]);
+
+        while registry.positions.len() < MAX_REGISTERED_POSITIONS {
+            registry.add(Pubkey::new_unique()).unwrap();
+        }
+        assert!(registry.add(Pubkey::new_unique()).is_err());
+    }
+}
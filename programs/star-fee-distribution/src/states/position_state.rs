@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+/// Tracks the canonical honorary position created for a pool
+#[account]
+#[derive(InitSpace)]
+pub struct PositionState {
+    /// The pool the honorary position belongs to
+    pub pool: Pubkey,
+    /// The canonical honorary position address
+    pub position: Pubkey,
+    /// The position NFT mint
+    pub position_nft_mint: Pubkey,
+    /// Base token mint
+    pub base_mint: Pubkey,
+    /// Quote token mint
+    pub quote_mint: Pubkey,
+    /// Timestamp of initialization
+    pub created_timestamp: i64,
+    /// Distribution day in progress when quote fees were last claimed into the quote fee vault
+    pub last_claim_day: u32,
+    /// Bump seed for the PDA
+    pub bump: u8,
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::errors::ErrorCode;
+use crate::math::{add_u64, sub_u64};
+use crate::utils::toggle_commitment;
 
 /// Global vault statistics to track total deposits across all investors
 #[account]
@@ -17,10 +19,22 @@ pub struct VaultStats {
     pub total_sol_withdrawn: u64,
     /// Total USDC withdrawn across all investors (in smallest unit)
     pub total_usdc_withdrawn: u64,
-    /// Number of unique depositors
+    /// Sum of depositor USDC balances scaled by their fee share multipliers
+    pub current_total_weighted_usdc: u64,
+    /// Number of depositors with a non-zero balance
     pub depositor_count: u32,
+    /// XOR of the member hashes of the depositors counted in `depositor_count`
+    pub depositor_set_commitment: [u8; 32],
     /// Timestamp of last update
     pub last_update_timestamp: i64,
+    /// Freshly claimed quote (excluding carry-over) across all closed days
+    pub lifetime_quote_claimed: u64,
+    /// Quote paid to investors across all closed days (regular, vested and capped carry-over payouts)
+    pub lifetime_distributed_to_investors: u64,
+    /// Quote routed to the creator or treasury across all closed days (partial claims included)
+    pub lifetime_to_creator: u64,
+    /// Number of distribution days closed
+    pub total_days_distributed: u32,
     /// Bump seed for the PDA
     pub bump: u8,
 }
@@ -35,8 +49,14 @@ impl VaultStats {
             current_total_usdc: 0,
             total_sol_withdrawn: 0,
             total_usdc_withdrawn: 0,
+            current_total_weighted_usdc: 0,
             depositor_count: 0,
+            depositor_set_commitment: [0u8; 32],
             last_update_timestamp: 0,
+            lifetime_quote_claimed: 0,
+            lifetime_distributed_to_investors: 0,
+            lifetime_to_creator: 0,
+            total_days_distributed: 0,
             bump,
         }
     }
@@ -46,22 +66,14 @@ impl VaultStats {
         let now = Clock::get().unwrap().unix_timestamp;
         
         // Update totals
-        self.total_sol_deposited = self.total_sol_deposited
-            .checked_add(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_sol_deposited = add_u64(self.total_sol_deposited, sol_amount)?;
         
-        self.total_usdc_deposited = self.total_usdc_deposited
-            .checked_add(usdc_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_usdc_deposited = add_u64(self.total_usdc_deposited, usdc_amount)?;
         
         // Update current balances
-        self.current_total_sol = self.current_total_sol
-            .checked_add(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        self.current_total_sol = add_u64(self.current_total_sol, sol_amount)?;
         
-        self.current_total_usdc = self.current_total_usdc
-            .checked_add(usdc_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        self.current_total_usdc = add_u64(self.current_total_usdc, usdc_amount)?;
         
         // Update timestamp
         self.last_update_timestamp = now;
@@ -74,22 +86,14 @@ impl VaultStats {
         let now = Clock::get().unwrap().unix_timestamp;
         
         // Update totals
-        self.total_sol_withdrawn = self.total_sol_withdrawn
-            .checked_add(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_sol_withdrawn = add_u64(self.total_sol_withdrawn, sol_amount)?;
         
-        self.total_usdc_withdrawn = self.total_usdc_withdrawn
-            .checked_add(usdc_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_usdc_withdrawn = add_u64(self.total_usdc_withdrawn, usdc_amount)?;
         
         // Update current balances
-        self.current_total_sol = self.current_total_sol
-            .checked_sub(sol_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        self.current_total_sol = sub_u64(self.current_total_sol, sol_amount)?;
         
-        self.current_total_usdc = self.current_total_usdc
-            .checked_sub(usdc_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        self.current_total_usdc = sub_u64(self.current_total_usdc, usdc_amount)?;
         
         // Update timestamp
         self.last_update_timestamp = now;
@@ -97,6 +101,56 @@ impl VaultStats {
         Ok(())
     }
 
+    /// Adds a compounded fee payout to the current USDC balance
+    pub fn add_compounded_usdc(&mut self, usdc_amount: u64) -> Result<()> {
+        self.current_total_usdc = add_u64(self.current_total_usdc, usdc_amount)?;
+        self.last_update_timestamp = Clock::get().unwrap().unix_timestamp;
+        
+        Ok(())
+    }
+
+    /// Replaces a depositor's previous weighted USDC balance with their new one
+    pub fn update_weighted_usdc(&mut self, previous: u64, current: u64) -> Result<()> {
+        self.current_total_weighted_usdc = add_u64(
+            sub_u64(self.current_total_weighted_usdc, previous)?,
+            current,
+        )?;
+        
+        Ok(())
+    }
+
+    /// Counts a depositor whose balance became non-zero
+    pub fn add_depositor(&mut self, member: &[u8; 32]) -> Result<()> {
+        self.depositor_count = self.depositor_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        toggle_commitment(&mut self.depositor_set_commitment, member);
+        
+        Ok(())
+    }
+
+    /// Stops counting a depositor whose balance reached zero
+    pub fn remove_depositor(&mut self, member: &[u8; 32]) -> Result<()> {
+        self.depositor_count = self.depositor_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        toggle_commitment(&mut self.depositor_set_commitment, member);
+        
+        Ok(())
+    }
+
+    /// Adds a closed day's quote flows to the lifetime totals
+    pub fn record_day_close(&mut self, quote_claimed: u64, to_investors: u64, to_creator: u64) -> Result<()> {
+        self.lifetime_quote_claimed = add_u64(self.lifetime_quote_claimed, quote_claimed)?;
+        self.lifetime_distributed_to_investors = add_u64(self.lifetime_distributed_to_investors, to_investors)?;
+        self.lifetime_to_creator = add_u64(self.lifetime_to_creator, to_creator)?;
+        self.total_days_distributed = self.total_days_distributed
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        Ok(())
+    }
+
     /// Gets the current SOL balance in the vault
     pub fn get_current_sol_balance(&self) -> u64 {
         self.current_total_sol
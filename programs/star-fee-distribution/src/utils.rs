@@ -0,0 +1,366 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_spl::token::TokenAccount;
+use crate::constants::{
+    ALLOWED_QUOTE_MINTS, ALLOWED_QUOTE_MINT_DECIMALS, ALLOWED_DEPOSIT_MINTS, FEE_VAULT_SEED, DEPOSIT_VAULT_SEED,
+    SUPPORTED_DAMM_V2_POOL_LEN,
+};
+use crate::errors::ErrorCode;
+use crate::states::DistributionConfig;
+
+/// Converts a raw token amount into its UI representation
+#[inline]
+pub fn to_ui_amount(raw: u64, decimals: u8) -> f64 {
+    raw as f64 / 10f64.powi(decimals as i32)
+}
+
+/// Converts a UI token amount into its raw representation, rounding to the nearest unit
+/// Negative, NaN and infinite inputs map to 0; values above u64::MAX saturate
+#[inline]
+pub fn from_ui_amount(ui: f64, decimals: u8) -> u64 {
+    if !ui.is_finite() || ui <= 0.0 {
+        return 0;
+    }
+    
+    (ui * 10f64.powi(decimals as i32)).round() as u64
+}
+
+/// Hashes a payout leaf as sha256(0x00 || investor || amount_le)
+pub fn payout_leaf_hash(investor: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[&[0u8], investor.as_ref(), &amount.to_le_bytes()]).to_bytes()
+}
+
+/// Hashes an eligibility leaf as sha256(0x02 || investor)
+pub fn eligibility_leaf_hash(investor: &Pubkey) -> [u8; 32] {
+    hashv(&[&[2u8], investor.as_ref()]).to_bytes()
+}
+
+/// Hashes a DepositorRecord depositor as sha256(0x03 || investor) for the depositor set commitment
+pub fn depositor_member_hash(investor: &Pubkey) -> [u8; 32] {
+    hashv(&[&[3u8], investor.as_ref()]).to_bytes()
+}
+
+/// Hashes a packed investor as sha256(0x04 || investor) for the depositor set commitment
+pub fn packed_member_hash(investor: &Pubkey) -> [u8; 32] {
+    hashv(&[&[4u8], investor.as_ref()]).to_bytes()
+}
+
+/// Adds or removes a member of an order-independent set commitment (XOR of member hashes)
+pub fn toggle_commitment(commitment: &mut [u8; 32], member: &[u8; 32]) {
+    for (byte, member_byte) in commitment.iter_mut().zip(member.iter()) {
+        *byte ^= member_byte;
+    }
+}
+
+/// Hashes an internal Merkle node as sha256(0x01 || min(a, b) || max(a, b))
+fn merkle_node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[&[1u8], lo, hi]).to_bytes()
+}
+
+/// Computes a Merkle root over leaf hashes
+/// Internal nodes are sha256(0x01 || min(a, b) || max(a, b)); an odd node is promoted as-is
+pub fn merkle_root(mut nodes: Vec<[u8; 32]>) -> [u8; 32] {
+    if nodes.is_empty() {
+        return [0u8; 32];
+    }
+    
+    while nodes.len() > 1 {
+        nodes = nodes
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => merkle_node_hash(a, b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    
+    nodes[0]
+}
+
+/// Verifies a Merkle proof against a root built by `merkle_root`
+/// Levels where the node was promoted without a sibling are omitted from the proof
+pub fn verify_merkle_proof(leaf: [u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| merkle_node_hash(&node, sibling));
+    computed == *root
+}
+
+/// Looks up the decimals of an allowlisted quote mint
+pub fn quote_mint_decimals(mint: &Pubkey) -> Option<u8> {
+    ALLOWED_QUOTE_MINTS
+        .iter()
+        .position(|allowed| allowed == mint)
+        .map(|index| ALLOWED_QUOTE_MINT_DECIMALS[index])
+}
+
+/// Checks that a mint is in the stable deposit mint registry
+pub fn is_allowed_deposit_mint(mint: &Pubkey) -> bool {
+    ALLOWED_DEPOSIT_MINTS.contains(mint)
+}
+
+/// Checks if a token account of `mint` is one of the program's fee or deposit vaults for that mint
+pub fn is_canonical_vault(account: &Pubkey, mint: &Pubkey, program_id: &Pubkey) -> bool {
+    [FEE_VAULT_SEED, DEPOSIT_VAULT_SEED].iter().any(|seed| {
+        Pubkey::find_program_address(&[seed, mint.as_ref()], program_id).0 == *account
+    })
+}
+
+/// Checks if a token account of `mint` is the program's deposit vault for that mint
+pub fn is_deposit_vault(account: &Pubkey, mint: &Pubkey, program_id: &Pubkey) -> bool {
+    Pubkey::find_program_address(&[DEPOSIT_VAULT_SEED, mint.as_ref()], program_id).0 == *account
+}
+
+/// Checks that an account is owned by the system program and holds no data,
+/// so it cannot be a token account or other program state created under the same address
+pub fn is_plain_system_account(account: &AccountInfo) -> bool {
+    account.owner == &anchor_lang::system_program::ID && account.data_is_empty()
+}
+
+/// Rejects paying into a quote account with a delegate under `reject_delegated_accounts`,
+/// since the delegate could move the payout out before the investor does
+pub fn require_undelegated_payout_account(config: &DistributionConfig, account: &TokenAccount) -> Result<()> {
+    require!(
+        !config.reject_delegated_accounts || account.delegate.is_none(),
+        ErrorCode::DelegatedPayoutAccount
+    );
+    Ok(())
+}
+
+/// Deserializes a program account that may not have been created yet
+pub fn load_if_initialized<T: AccountDeserialize + Owner>(account: &AccountInfo) -> Result<Option<T>> {
+    if account.owner != &T::owner() || account.data_is_empty() {
+        return Ok(None);
+    }
+    
+    let data = account.try_borrow_data()?;
+    Ok(Some(T::try_deserialize(&mut &data[..])?))
+}
+
+/// Copies a zero-copy account owned by another program out of its data,
+/// so the read does not depend on the account buffer's alignment
+pub fn read_zero_copy<T: anchor_lang::ZeroCopy + Owner>(account: &AccountInfo) -> Result<T> {
+    if account.owner != &T::owner() {
+        return Err(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+
+    let data = account.try_borrow_data()?;
+    let discriminator = T::DISCRIMINATOR;
+    if data.len() < discriminator.len() || &data[..discriminator.len()] != discriminator {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+
+    let body = data
+        .get(discriminator.len()..discriminator.len() + std::mem::size_of::<T>())
+        .ok_or(anchor_lang::error::ErrorCode::AccountDidNotDeserialize)?;
+    Ok(bytemuck::pod_read_unaligned(body))
+}
+
+/// Checks a DAMM v2 pool's account size against the layout the CPI bindings expect
+pub fn is_supported_amm_pool(data_len: usize) -> bool {
+    data_len == SUPPORTED_DAMM_V2_POOL_LEN
+}
+
+/// Rejects a DAMM v2 pool from an upgraded program whose accounts may no longer match the CPI bindings
+pub fn require_supported_amm_layout(pool: &AccountInfo) -> Result<()> {
+    read_zero_copy::<damm_v2::accounts::Pool>(pool)?;
+
+    msg!("DAMM v2 pool size: {} bytes", pool.data_len());
+
+    require!(
+        is_supported_amm_pool(pool.data_len()),
+        ErrorCode::UnsupportedAmmVersion
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{SOL_DECIMALS, USDC_DECIMALS};
+
+    #[test]
+    fn to_ui_amount_converts_usdc_and_sol() {
+        assert_eq!(to_ui_amount(1_000_000, USDC_DECIMALS), 1.0);
+        assert_eq!(to_ui_amount(1_500_000, USDC_DECIMALS), 1.5);
+        assert_eq!(to_ui_amount(1, USDC_DECIMALS), 0.000001);
+        assert_eq!(to_ui_amount(1_000_000_000, SOL_DECIMALS), 1.0);
+        assert_eq!(to_ui_amount(1_000_000, SOL_DECIMALS), 0.001);
+        assert_eq!(to_ui_amount(0, SOL_DECIMALS), 0.0);
+        assert_eq!(to_ui_amount(42, 0), 42.0);
+    }
+
+    #[test]
+    fn from_ui_amount_converts_usdc_and_sol() {
+        assert_eq!(from_ui_amount(1.0, USDC_DECIMALS), 1_000_000);
+        assert_eq!(from_ui_amount(100.25, USDC_DECIMALS), 100_250_000);
+        assert_eq!(from_ui_amount(0.001, USDC_DECIMALS), 1_000);
+        assert_eq!(from_ui_amount(1.0, SOL_DECIMALS), 1_000_000_000);
+        assert_eq!(from_ui_amount(0.001, SOL_DECIMALS), 1_000_000);
+        assert_eq!(from_ui_amount(42.0, 0), 42);
+    }
+
+    #[test]
+    fn from_ui_amount_rounds_to_nearest_unit() {
+        // 0.1 and 0.7 are not exactly representable in binary
+        assert_eq!(from_ui_amount(0.1, SOL_DECIMALS), 100_000_000);
+        assert_eq!(from_ui_amount(0.7, USDC_DECIMALS), 700_000);
+        assert_eq!(from_ui_amount(0.0000004, USDC_DECIMALS), 0);
+        assert_eq!(from_ui_amount(0.0000005, USDC_DECIMALS), 1);
+        assert_eq!(from_ui_amount(0.0000015, USDC_DECIMALS), 2);
+        assert_eq!(from_ui_amount(1.2345675, USDC_DECIMALS), 1_234_568);
+    }
+
+    #[test]
+    fn from_ui_amount_handles_invalid_input() {
+        assert_eq!(from_ui_amount(-1.0, USDC_DECIMALS), 0);
+        assert_eq!(from_ui_amount(f64::NAN, USDC_DECIMALS), 0);
+        assert_eq!(from_ui_amount(f64::INFINITY, USDC_DECIMALS), 0);
+        assert_eq!(from_ui_amount(1e30, SOL_DECIMALS), u64::MAX);
+    }
+
+    #[test]
+    fn round_trips_raw_amounts() {
+        for raw in [0u64, 1, 999, 1_000, 123_456_789, 1_000_000_000_000] {
+            assert_eq!(from_ui_amount(to_ui_amount(raw, USDC_DECIMALS), USDC_DECIMALS), raw);
+            assert_eq!(from_ui_amount(to_ui_amount(raw, SOL_DECIMALS), SOL_DECIMALS), raw);
+        }
+    }
+
+    #[test]
+    fn merkle_root_is_order_independent_per_pair() {
+        let a = payout_leaf_hash(&Pubkey::new_unique(), 10);
+        let b = payout_leaf_hash(&Pubkey::new_unique(), 20);
+        assert_eq!(merkle_root(vec![a, b]), merkle_root(vec![b, a]));
+        assert_eq!(merkle_root(vec![a]), a);
+        assert_eq!(merkle_root(vec![]), [0u8; 32]);
+    }
+
+    #[test]
+    fn merkle_root_changes_with_amounts() {
+        let investor = Pubkey::new_unique();
+        let other = payout_leaf_hash(&Pubkey::new_unique(), 5);
+        let root = merkle_root(vec![payout_leaf_hash(&investor, 10), other, other]);
+        let tampered = merkle_root(vec![payout_leaf_hash(&investor, 11), other, other]);
+        assert_ne!(root, tampered);
+    }
+
+    #[test]
+    fn set_commitment_is_order_independent_and_reversible() {
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (a, b) = (depositor_member_hash(&alice), depositor_member_hash(&bob));
+
+        let (mut forward, mut backward) = ([0u8; 32], [0u8; 32]);
+        toggle_commitment(&mut forward, &a);
+        toggle_commitment(&mut forward, &b);
+        toggle_commitment(&mut backward, &b);
+        toggle_commitment(&mut backward, &a);
+        assert_eq!(forward, backward);
+
+        toggle_commitment(&mut forward, &b);
+        assert_eq!(forward, a);
+        toggle_commitment(&mut forward, &a);
+        assert_eq!(forward, [0u8; 32]);
+
+        // Packed slots and records of the same investor are distinct members
+        assert_ne!(packed_member_hash(&alice), a);
+    }
+
+    #[test]
+    fn deposit_mint_registry_accepts_only_listed_stables() {
+        for mint in crate::constants::ALLOWED_DEPOSIT_MINTS.iter() {
+            assert!(is_allowed_deposit_mint(mint));
+        }
+        assert!(!is_allowed_deposit_mint(&anchor_lang::solana_program::system_program::ID));
+        assert!(!is_allowed_deposit_mint(&Pubkey::default()));
+        assert!(!is_allowed_deposit_mint(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn canonical_vaults_are_the_fee_and_deposit_vault_pdas() {
+        let program_id = crate::ID;
+        let mint = Pubkey::new_unique();
+        let fee_vault = Pubkey::find_program_address(&[FEE_VAULT_SEED, mint.as_ref()], &program_id).0;
+        let deposit_vault = Pubkey::find_program_address(&[DEPOSIT_VAULT_SEED, mint.as_ref()], &program_id).0;
+        assert!(is_canonical_vault(&fee_vault, &mint, &program_id));
+        assert!(is_canonical_vault(&deposit_vault, &mint, &program_id));
+        // Another mint's vault or any other address is not canonical for this mint
+        assert!(!is_canonical_vault(&fee_vault, &Pubkey::new_unique(), &program_id));
+        assert!(!is_canonical_vault(&Pubkey::new_unique(), &mint, &program_id));
+        assert!(is_deposit_vault(&deposit_vault, &mint, &program_id));
+        assert!(!is_deposit_vault(&fee_vault, &mint, &program_id));
+    }
+
+    #[test]
+    fn plain_system_account_rejects_foreign_owner_and_data() {
+        let key = Pubkey::new_unique();
+        let system_owner = anchor_lang::system_program::ID;
+        let token_owner = anchor_spl::token::ID;
+        let (mut lamports_a, mut lamports_b, mut lamports_c) = (1_000_000u64, 1_000_000u64, 1_000_000u64);
+        let (mut empty_a, mut empty_b, mut token_data) = ([0u8; 0], [0u8; 0], [0u8; 165]);
+
+        let plain = AccountInfo::new(&key, false, true, &mut lamports_a, &mut empty_a, &system_owner, false, 0);
+        let foreign = AccountInfo::new(&key, false, true, &mut lamports_b, &mut empty_b, &token_owner, false, 0);
+        let with_data = AccountInfo::new(&key, false, true, &mut lamports_c, &mut token_data, &system_owner, false, 0);
+
+        assert!(is_plain_system_account(&plain));
+        assert!(!is_plain_system_account(&foreign));
+        assert!(!is_plain_system_account(&with_data));
+    }
+
+    #[test]
+    fn verify_merkle_proof_accepts_members_and_rejects_others() {
+        let leaves: Vec<[u8; 32]> = (0..3)
+            .map(|_| eligibility_leaf_hash(&Pubkey::new_unique()))
+            .collect();
+        let root = merkle_root(leaves.clone());
+        let first_pair = merkle_node_hash(&leaves[0], &leaves[1]);
+
+        assert!(verify_merkle_proof(leaves[0], &[leaves[1], leaves[2]], &root));
+        assert!(verify_merkle_proof(leaves[1], &[leaves[0], leaves[2]], &root));
+        // The odd leaf was promoted, so its proof skips the first level
+        assert!(verify_merkle_proof(leaves[2], &[first_pair], &root));
+
+        let outsider = eligibility_leaf_hash(&Pubkey::new_unique());
+        assert!(!verify_merkle_proof(outsider, &[leaves[1], leaves[2]], &root));
+        assert!(!verify_merkle_proof(leaves[0], &[leaves[2]], &root));
+    }
+
+    #[test]
+    fn read_zero_copy_checks_owner_and_discriminator() {
+        use damm_v2::accounts::Position;
+
+        let key = Pubkey::new_unique();
+        let damm_owner = damm_v2::ID;
+        let token_owner = anchor_spl::token::ID;
+        let mut position: Position = bytemuck::Zeroable::zeroed();
+        position.fee_a_pending = 42;
+
+        // Prefix one byte so the copied body is misaligned
+        let mut data = vec![0u8];
+        data.extend_from_slice(Position::DISCRIMINATOR);
+        data.extend_from_slice(bytemuck::bytes_of(&position));
+        let mut wrong_discriminator = data.clone();
+        wrong_discriminator[1] ^= 0xff;
+
+        let (mut lamports_a, mut lamports_b, mut lamports_c) = (1_000_000u64, 1_000_000u64, 1_000_000u64);
+        let mut foreign_data = data.clone();
+        let valid = AccountInfo::new(&key, false, false, &mut lamports_a, &mut data[1..], &damm_owner, false, 0);
+        let foreign = AccountInfo::new(&key, false, false, &mut lamports_b, &mut foreign_data[1..], &token_owner, false, 0);
+        let forged = AccountInfo::new(&key, false, false, &mut lamports_c, &mut wrong_discriminator[1..], &damm_owner, false, 0);
+
+        assert_eq!(read_zero_copy::<Position>(&valid).unwrap().fee_a_pending, 42);
+        assert!(read_zero_copy::<Position>(&foreign).is_err());
+        assert!(read_zero_copy::<Position>(&forged).is_err());
+    }
+
+    #[test]
+    fn only_the_bound_damm_v2_pool_layout_is_supported() {
+        // The layout the bindings were generated against: 1104-byte body after the discriminator
+        assert!(is_supported_amm_pool(8 + 1104));
+        assert!(!is_supported_amm_pool(8 + 1104 + 64));
+        assert!(!is_supported_amm_pool(8 + 1104 - 8));
+    }
+}
@@ -39,6 +39,7 @@ describe("Complete Integration Tests (Bankrun)", () => {
   let usdcVaultPDA: PublicKey;
   let vaultStatsPDA: PublicKey;
   let distributionConfigPDA: PublicKey;
+  let crankStatePDA: PublicKey;
 
   before(async () => {
     context = await startTest();
@@ -94,6 +95,11 @@ describe("Complete Integration Tests (Bankrun)", () => {
       [Buffer.from("distribution_config")],
       program.programId
     );
+
+    [crankStatePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("crank_state")],
+      program.programId
+    );
   });
 
   describe("Complete Flow: Setup to Distribution", () => {
@@ -106,6 +112,50 @@ describe("Complete Integration Tests (Bankrun)", () => {
           dailyCapLamports: new BN(1_000_000_000_000), // 1000 SOL
           creatorWallet: creatorWallet.publicKey,
           quoteMint: USDC_MINT,
+          autoCloseOnFinalPage: false,
+          vestingProgram: PublicKey.default,
+          vestedInvestorShareBps: 0,
+          creatorWalletTimelockSeconds: new BN(0),
+          remainderPolicy: 0,
+          treasuryWallet: PublicKey.default,
+          emitEvents: true,
+          eligibilityDelayDays: 1,
+          eligibilityRoot: Array(32).fill(0),
+          dailyCapQuoteUnits: new BN(0),
+          dayMaxDurationSeconds: new BN(0),
+          minDepositIntervalSeconds: new BN(0),
+          carryOverPolicy: 0,
+          fLockedFloorBps: 0,
+          fLockedCeilingBps: 0,
+          verifyTotalLocked: false,
+          totalLockedToleranceUnits: new BN(0),
+          creatorPayoutMint: PublicKey.default,
+          depositWindowStartTs: new BN(0),
+          depositWindowEndTs: new BN(0),
+          maxCarryOver: new BN(0),
+          carryOverCapPolicy: 0,
+          zeroBalancePolicy: 0,
+          roundsPerDay: 1,
+          minTotalLockedToDistribute: new BN(0),
+          weightByRemainingLock: false,
+          creatorInactivitySeconds: new BN(0),
+          creatorMinShareBps: 0,
+          depositMint: USDC_MINT,
+          dustThresholdK: 0,
+          fixedPageSize: false,
+          callbackProgram: PublicKey.default,
+          maxInvestorShareBps: 0,
+          keeperRewardLamports: new BN(0),
+          earningsUnlockTs: new BN(0),
+          ageBrackets: Array(4).fill({ minAgeSeconds: new BN(0), multiplierBps: 0 }),
+          distributionRateBps: 0,
+          requireFreshClaim: false,
+          minCrankableQuote: new BN(0),
+          maxInvestorShareOfY0Bps: 0,
+          configChangeCooloffSeconds: new BN(0),
+          maxClaimPerTx: new BN(0),
+          dustRecipient: PublicKey.default,
+          rejectDelegatedAccounts: false,
         })
         .accountsStrict({
           admin: admin.publicKey,
@@ -159,6 +209,8 @@ describe("Complete Integration Tests (Bankrun)", () => {
             program.programId
           )[0],
           vaultStats: vaultStatsPDA,
+          crankState: crankStatePDA,
+          distributionConfig: distributionConfigPDA,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -192,6 +244,8 @@ describe("Complete Integration Tests (Bankrun)", () => {
             program.programId
           )[0],
           vaultStats: vaultStatsPDA,
+          crankState: crankStatePDA,
+          distributionConfig: distributionConfigPDA,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -225,6 +279,8 @@ describe("Complete Integration Tests (Bankrun)", () => {
             program.programId
           )[0],
           vaultStats: vaultStatsPDA,
+          crankState: crankStatePDA,
+          distributionConfig: distributionConfigPDA,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -0,0 +1,4832 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, Transaction } from "@solana/web3.js";
+import {
+  createApproveInstruction,
+  createRevokeInstruction,
+  NATIVE_MINT,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect, assert } from "chai";
+import {
+  createMintData,
+  fetchAccount,
+  fundSol,
+  fundUsdc,
+  getBalance,
+  getOrCreateAta,
+  getTokenAccount,
+  MOCK_DAY_CALLBACK_PROGRAM_ID,
+  setTokenAccount,
+  USDC_MINT,
+  warpTimeBy,
+} from "./utils/bankrun";
+import {
+  addPosition,
+  computeEligibilityTree,
+  computePayoutRoot,
+  bootstrapProgram,
+  claimFeesToPda,
+  closeDepositorRecord,
+  CrankEnv,
+  createCrankEnv,
+  createInvestorWithDeposit,
+  batchWithdraw,
+  beginDistributionDay,
+  crankPage,
+  creatorPartialClaim,
+  depositUsdc,
+  depositUsdcWithProof,
+  distributeFees,
+  distributeToInvestor,
+  flushCarryOver,
+  forceCloseDay,
+  fundKeeperIncentive,
+  getCreatorQuoteAccount,
+  getDayRecordPDA,
+  getDepositorRecordPDA,
+  getKeeperIncentivePDA,
+  getPositionRegistryPDA,
+  parseEvents,
+  queryDayRecord,
+  queryGlobalStats,
+  queryInvestorWeight,
+  queryVaultConcentration,
+  reconcileVaultStats,
+  registerQuoteAccount,
+  removePosition,
+  rescueTokens,
+  routeCreatorRemainder,
+  scheduleCreatorWalletUpdate,
+  sendTx,
+  setAutoCompound,
+  setOptOut,
+  setBatchWithdrawApproval,
+  setInvestorMultiplier,
+  submitPayoutRoot,
+  seedFeeVaults,
+  setupCrankEnv,
+  setupMockClaimPosition,
+  simulateDay,
+  updateDistributionConfig,
+  updateQuoteMint,
+  withdrawUsdc,
+} from "./utils/crank";
+import { DAMM_V2_PROGRAM_ID } from "./utils/damm_v2_integration";
+import IDL from "../target/idl/star_fee_distribution.json";
+
+describe("Crank Lifecycle Tests (Bankrun)", () => {
+  describe("Auto-close on final page", () => {
+    let env: CrankEnv;
+
+    before(async () => {
+      env = await setupCrankEnv({ autoCloseOnFinalPage: true });
+    });
+
+    it("Should route the creator remainder and close the day on the final page", async () => {
+      // Y0 fully locked, so the investor share is the full 50% cap
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+      const creatorBefore = await getTokenAccount(
+        env.context.banksClient,
+        creatorQuoteAccount
+      );
+
+      await beginDistributionDay(env);
+
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: false });
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+      await crankPage(
+        env,
+        { pageIndex: 1, investorsCount: 0, isFinalPage: true },
+        creatorQuoteAccount
+      );
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.dayState).to.equal(2);
+      expect(crankState!.distributionCount).to.equal(1);
+
+      const creatorAfter = await getTokenAccount(
+        env.context.banksClient,
+        creatorQuoteAccount
+      );
+      expect(
+        (creatorAfter!.amount - creatorBefore!.amount).toString()
+      ).to.equal("500000");
+
+      const quoteVault = await getTokenAccount(
+        env.context.banksClient,
+        env.quoteFeeVaultPDA
+      );
+      expect(quoteVault!.amount.toString()).to.equal("0");
+    });
+  });
+
+  describe("External vesting mode", () => {
+    const vestingProgram = Keypair.generate().publicKey;
+    let env: CrankEnv;
+
+    // Borsh layout of ExternalVesting: recipient | mint | deposited | start | end
+    const encodeVesting = (
+      recipient: PublicKey,
+      depositedAmount: BN,
+      startTime: BN,
+      endTime: BN
+    ) =>
+      Buffer.concat([
+        recipient.toBuffer(),
+        USDC_MINT.toBuffer(),
+        depositedAmount.toArrayLike(Buffer, "le", 8),
+        startTime.toTwos(64).toArrayLike(Buffer, "le", 8),
+        endTime.toTwos(64).toArrayLike(Buffer, "le", 8),
+      ]);
+
+    before(async () => {
+      env = await setupCrankEnv({ vestingProgram });
+    });
+
+    it("Should weight payouts by the externally locked amount", async () => {
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      // Half of Y0 still locked: unlocking starts in the far future
+      const vestingAccount = Keypair.generate().publicKey;
+      env.context.setAccount(vestingAccount, {
+        executable: false,
+        owner: vestingProgram,
+        lamports: 1_000_000_000,
+        data: encodeVesting(
+          investor.publicKey,
+          new BN(500_000_000),
+          new BN(4_000_000_000),
+          new BN(4_100_000_000)
+        ),
+      });
+
+      const balanceBefore = await getTokenAccount(env.context.banksClient, usdcAccount);
+
+      await beginDistributionDay(env);
+
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      await distributeToInvestor(
+        env,
+        investor,
+        usdcAccount,
+        new BN(500_000),
+        vestingAccount
+      );
+
+      const balanceAfter = await getTokenAccount(env.context.banksClient, usdcAccount);
+      expect((balanceAfter!.amount - balanceBefore!.amount).toString()).to.equal("250000");
+    });
+
+    it("Should favour the longer remaining lock when weighting by remaining lock", async () => {
+      const weightedEnv = await setupCrankEnv({ vestingProgram, weightByRemainingLock: true });
+      const short = await createInvestorWithDeposit(weightedEnv, new BN(1_000_000));
+      const long = await createInvestorWithDeposit(weightedEnv, new BN(1_000_000));
+      seedFeeVaults(weightedEnv, BigInt(1_000_000));
+
+      // Equal still-locked amounts, unlocking one and three years from now
+      const now = (await weightedEnv.context.banksClient.getClock()).unixTimestamp;
+      const year = BigInt(365 * 86400);
+      const cliffVesting = (investor: Keypair, unlockTime: bigint) => {
+        const vestingAccount = Keypair.generate().publicKey;
+        weightedEnv.context.setAccount(vestingAccount, {
+          executable: false,
+          owner: vestingProgram,
+          lamports: 1_000_000_000,
+          data: encodeVesting(
+            investor.publicKey,
+            new BN(250_000_000),
+            new BN(unlockTime.toString()),
+            new BN(unlockTime.toString())
+          ),
+        });
+        return vestingAccount;
+      };
+      const vestingAccounts = [
+        cliffVesting(short.investor, now + year),
+        cliffVesting(long.investor, now + BigInt(3) * year),
+      ];
+
+      // 2 USDC locked of Y0 = 20 bps: investor_fee = 2000
+      await beginDistributionDay(weightedEnv);
+      await crankPage(weightedEnv, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+
+      const payouts: bigint[] = [];
+      for (const [index, { investor, usdcAccount }] of [short, long].entries()) {
+        const before = await getTokenAccount(weightedEnv.context.banksClient, usdcAccount);
+        await distributeToInvestor(
+          weightedEnv,
+          investor,
+          usdcAccount,
+          new BN(2_000),
+          vestingAccounts[index]
+        );
+        const after = await getTokenAccount(weightedEnv.context.banksClient, usdcAccount);
+        payouts.push(after!.amount - before!.amount);
+      }
+
+      expect(payouts[0] > BigInt(0)).to.be.true;
+      expect(payouts[1]).to.equal(payouts[0] * BigInt(3));
+    });
+
+    it("Should reject a vesting account owned by another program", async () => {
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000)
+      );
+
+      const forgedAccount = Keypair.generate().publicKey;
+      env.context.setAccount(forgedAccount, {
+        executable: false,
+        owner: Keypair.generate().publicKey,
+        lamports: 1_000_000_000,
+        data: encodeVesting(
+          investor.publicKey,
+          new BN(1_000_000_000),
+          new BN(4_000_000_000),
+          new BN(4_100_000_000)
+        ),
+      });
+
+      try {
+        await distributeToInvestor(
+          env,
+          investor,
+          usdcAccount,
+          new BN(500_000),
+          forgedAccount
+        );
+        assert.fail("Should have rejected the forged vesting account");
+      } catch (error) {
+        expect(error.toString()).to.not.include(
+          "Should have rejected the forged vesting account"
+        );
+      }
+    });
+  });
+
+  describe("Vested investor share", () => {
+    let env: CrankEnv;
+
+    before(async () => {
+      env = await setupCrankEnv({ vestedInvestorShareBps: 2000 });
+    });
+
+    it("Should pay a fully-vested investor the reduced share from the creator remainder", async () => {
+      const alumni = await createInvestorWithDeposit(env, new BN(500_000_000));
+      await withdrawUsdc(env, alumni.investor, alumni.usdcAccount, new BN(500_000_000));
+      const locked = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      const alumniBefore = await getTokenAccount(
+        env.context.banksClient,
+        alumni.usdcAccount
+      );
+
+      // investor_fee = 500_000, creator remainder = 500_000, vested pool = 20%
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.vestedPool.toString()).to.equal("100000");
+
+      await distributeToInvestor(env, locked.investor, locked.usdcAccount, new BN(500_000));
+      await distributeToInvestor(env, alumni.investor, alumni.usdcAccount, new BN(500_000));
+
+      // Alumni weight = 500 / 1500 USDC of historical deposits = 3333 bps
+      const alumniAfter = await getTokenAccount(
+        env.context.banksClient,
+        alumni.usdcAccount
+      );
+      expect(
+        (alumniAfter!.amount - alumniBefore!.amount).toString()
+      ).to.equal("33330");
+
+      const crankStateAfter = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankStateAfter!.vestedDistributedToday.toString()).to.equal("33330");
+      expect(crankStateAfter!.dailyDistributed.toString()).to.equal("500000");
+    });
+  });
+
+  describe("Overflow-safe weight math", () => {
+    let env: CrankEnv;
+
+    before(async () => {
+      env = await setupCrankEnv();
+    });
+
+    // Re-encodes a program account with the given field overrides
+    const corruptAccount = async (
+      name: "DepositorRecord" | "CrankState",
+      address: PublicKey,
+      overrides: Record<string, any>
+    ) => {
+      const current = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        name,
+        address
+      );
+      const raw = await env.context.banksClient.getAccount(address);
+      const data = await env.program.coder.accounts.encode(
+        name.charAt(0).toLowerCase() + name.slice(1),
+        { ...current, ...overrides }
+      );
+      env.context.setAccount(address, {
+        executable: false,
+        owner: env.program.programId,
+        lamports: raw!.lamports,
+        data,
+      });
+    };
+
+    it("Should return MathOverflow when the weight product exceeds u64", async () => {
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+
+      // balance * 10000 / total_locked no longer fits in a u64
+      await corruptAccount("DepositorRecord", getDepositorRecordPDA(env, investor.publicKey), {
+        currentUsdcBalance: new BN("18446744073709551615"),
+      });
+      await corruptAccount("CrankState", env.crankStatePDA, {
+        dayTotalWeightedUsdc: new BN(1),
+      });
+
+      const balanceBefore = await getTokenAccount(env.context.banksClient, usdcAccount);
+
+      try {
+        await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+        assert.fail("Should have failed with MathOverflow");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have failed with MathOverflow");
+        // MathOverflow = 6013
+        expect(error.toString()).to.include("0x177d");
+      }
+
+      const balanceAfter = await getTokenAccount(env.context.banksClient, usdcAccount);
+      expect(balanceAfter!.amount.toString()).to.equal(balanceBefore!.amount.toString());
+    });
+  });
+
+  describe("Creator wallet timelock", () => {
+    const timelockSeconds = 3600;
+
+    // Opens a day with 1 USDC of fees and schedules a new creator wallet
+    const setupScheduledUpdate = async () => {
+      const env = await setupCrankEnv({
+        creatorWalletTimelockSeconds: new BN(timelockSeconds),
+      });
+      const newCreatorWallet = Keypair.generate();
+
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+
+      await scheduleCreatorWalletUpdate(env, newCreatorWallet.publicKey);
+
+      const newCreatorQuoteAccount = await getOrCreateAta(
+        env.context.banksClient,
+        env.admin,
+        USDC_MINT,
+        newCreatorWallet.publicKey
+      );
+
+      return { env, newCreatorWallet, newCreatorQuoteAccount };
+    };
+
+    it("Should reject scheduling from a non-admin signer", async () => {
+      const env = await setupCrankEnv();
+      const attacker = Keypair.generate();
+      await fundSol(env.context.banksClient, env.admin, [attacker.publicKey]);
+
+      try {
+        await scheduleCreatorWalletUpdate(env, attacker.publicKey, attacker);
+        assert.fail("Should have rejected the non-admin signer");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the non-admin signer");
+      }
+    });
+
+    it("Should keep routing to the current wallet before the effective time", async () => {
+      const { env, newCreatorWallet, newCreatorQuoteAccount } =
+        await setupScheduledUpdate();
+
+      const config = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DistributionConfig",
+        env.distributionConfigPDA
+      );
+      expect(config!.pendingCreatorWallet.toString()).to.equal(
+        newCreatorWallet.publicKey.toString()
+      );
+
+      try {
+        await routeCreatorRemainder(env, newCreatorQuoteAccount);
+        assert.fail("Should have rejected the pending creator wallet");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the pending creator wallet");
+      }
+
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+      await routeCreatorRemainder(env, creatorQuoteAccount);
+
+      const creatorAccount = await getTokenAccount(
+        env.context.banksClient,
+        creatorQuoteAccount
+      );
+      expect(creatorAccount!.amount.toString()).to.equal("1000000");
+
+      const configAfter = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DistributionConfig",
+        env.distributionConfigPDA
+      );
+      expect(configAfter!.creatorWallet.toString()).to.equal(
+        env.creatorWallet.publicKey.toString()
+      );
+    });
+
+    it("Should adopt the pending wallet after the effective time", async () => {
+      const { env, newCreatorWallet, newCreatorQuoteAccount } =
+        await setupScheduledUpdate();
+
+      await warpTimeBy(env.context, timelockSeconds);
+
+      // The old wallet is no longer accepted once the update takes effect
+      const oldCreatorQuoteAccount = await getCreatorQuoteAccount(env);
+      try {
+        await routeCreatorRemainder(env, oldCreatorQuoteAccount);
+        assert.fail("Should have rejected the previous creator wallet");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the previous creator wallet");
+      }
+
+      await routeCreatorRemainder(env, newCreatorQuoteAccount);
+
+      const newCreatorAccount = await getTokenAccount(
+        env.context.banksClient,
+        newCreatorQuoteAccount
+      );
+      expect(newCreatorAccount!.amount.toString()).to.equal("1000000");
+
+      const config = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DistributionConfig",
+        env.distributionConfigPDA
+      );
+      expect(config!.creatorWallet.toString()).to.equal(
+        newCreatorWallet.publicKey.toString()
+      );
+      expect(config!.pendingCreatorWallet.toString()).to.equal(
+        PublicKey.default.toString()
+      );
+    });
+  });
+
+  describe("Daily payout root", () => {
+    let env: CrankEnv;
+    let investor: Keypair;
+
+    before(async () => {
+      env = await setupCrankEnv();
+      const deposit = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      investor = deposit.investor;
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      await distributeToInvestor(env, investor, deposit.usdcAccount, new BN(500_000));
+      await routeCreatorRemainder(env, await getCreatorQuoteAccount(env));
+    });
+
+    it("Should reject a root that does not match the leaves", async () => {
+      const leaves = [{ investor: investor.publicKey, amount: new BN(500_000) }];
+      const wrongRoot = computePayoutRoot([
+        { investor: investor.publicKey, amount: new BN(499_999) },
+      ]);
+
+      try {
+        await submitPayoutRoot(env, 1, leaves, wrongRoot);
+        assert.fail("Should have rejected the mismatched root");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the mismatched root");
+        // PayoutRootMismatch = 6037
+        expect(error.toString()).to.include("0x1795");
+      }
+    });
+
+    it("Should reject leaves that do not sum to the day's distributed total", async () => {
+      const leaves = [{ investor: investor.publicKey, amount: new BN(400_000) }];
+
+      try {
+        await submitPayoutRoot(env, 1, leaves, computePayoutRoot(leaves));
+        assert.fail("Should have rejected the short leaves");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the short leaves");
+        expect(error.toString()).to.include("0x1795");
+      }
+    });
+
+    it("Should store a root that matches the leaves and daily total", async () => {
+      const leaves = [{ investor: investor.publicKey, amount: new BN(500_000) }];
+      const payoutRoot = computePayoutRoot(leaves);
+
+      await submitPayoutRoot(env, 1, leaves, payoutRoot);
+
+      const dayRecord = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DayRecord",
+        getDayRecordPDA(env, 1)
+      );
+      expect(dayRecord!.day).to.equal(1);
+      expect(Array.from(dayRecord!.payoutRoot)).to.deep.equal(payoutRoot);
+      expect(dayRecord!.totalDistributed.toString()).to.equal("500000");
+      expect(dayRecord!.leafCount).to.equal(1);
+    });
+
+    it("Should reject a second root for the same day", async () => {
+      const leaves = [{ investor: investor.publicKey, amount: new BN(500_000) }];
+
+      try {
+        await submitPayoutRoot(env, 1, leaves, computePayoutRoot(leaves));
+        assert.fail("Should have rejected the second root");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the second root");
+        // PayoutRootAlreadySubmitted = 6105
+        expect(error.toString()).to.include("0x17d9");
+      }
+    });
+
+    it("Should reject closing the same day twice", async () => {
+      // A different min_out (ignored without a swap) keeps the retry from being deduplicated
+      try {
+        await routeCreatorRemainder(env, await getCreatorQuoteAccount(env), null, new BN(1));
+        assert.fail("Should have rejected the second close");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the second close");
+        // The day record's init fails because the account is already in use
+        expect(error.toString()).to.include("0x0");
+      }
+    });
+  });
+
+  describe("Creator partial claim", () => {
+    let env: CrankEnv;
+
+    before(async () => {
+      env = await setupCrankEnv();
+    });
+
+    it("Should let the creator claim the non-reserved portion without starving investors", async () => {
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+
+      // Y0 fully locked: 500_000 of the 1_000_000 is reserved for investors
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: false });
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.dayInvestorFeeQuote.toString()).to.equal("500000");
+
+      // Asking for more than is claimable is capped at the non-reserved balance
+      await creatorPartialClaim(env, creatorQuoteAccount, new BN(1_000_000));
+
+      const creatorAccount = await getTokenAccount(
+        env.context.banksClient,
+        creatorQuoteAccount
+      );
+      expect(creatorAccount!.amount.toString()).to.equal("500000");
+
+      // Nothing left to claim until investors are paid
+      try {
+        await creatorPartialClaim(env, creatorQuoteAccount, new BN(1));
+        assert.fail("Should have rejected claiming the reserved balance");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected claiming the reserved balance");
+      }
+
+      const balanceBefore = await getTokenAccount(env.context.banksClient, usdcAccount);
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+      const balanceAfter = await getTokenAccount(env.context.banksClient, usdcAccount);
+      expect((balanceAfter!.amount - balanceBefore!.amount).toString()).to.equal("500000");
+
+      const crankStateAfter = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankStateAfter!.creatorClaimedToday.toString()).to.equal("500000");
+    });
+  });
+
+  describe("Carry-over retention", () => {
+    let env: CrankEnv;
+
+    before(async () => {
+      // Minimum payout above the investor share, so the whole payout becomes dust
+      env = await setupCrankEnv({ minPayoutLamports: new BN(1_000_000) });
+    });
+
+    it("Should retain exactly carry_over in the vault after close", async () => {
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+
+      await beginDistributionDay(env);
+
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+      await routeCreatorRemainder(env, creatorQuoteAccount);
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.carryOver.toString()).to.equal("500000");
+
+      const quoteVault = await getTokenAccount(
+        env.context.banksClient,
+        env.quoteFeeVaultPDA
+      );
+      expect(quoteVault!.amount.toString()).to.equal(crankState!.carryOver.toString());
+
+      const creatorAccount = await getTokenAccount(
+        env.context.banksClient,
+        creatorQuoteAccount
+      );
+      expect(creatorAccount!.amount.toString()).to.equal("500000");
+    });
+  });
+
+  describe("Depositor count events", () => {
+    let env: CrankEnv;
+
+    before(async () => {
+      env = await setupCrankEnv();
+    });
+
+    it("Should emit DepositorCountChanged on a first deposit and a full withdrawal", async () => {
+      const { investor, usdcAccount, meta } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000)
+      );
+
+      const depositEvent = parseEvents(env, meta).find(
+        (event) => event.name === "depositorCountChanged"
+      );
+      expect(depositEvent).to.exist;
+      expect(depositEvent!.data.investor.toString()).to.equal(investor.publicKey.toString());
+      expect(depositEvent!.data.depositorCount).to.equal(1);
+      expect(depositEvent!.data.increased).to.equal(true);
+
+      // A partial withdrawal leaves the count unchanged
+      const partialMeta = await withdrawUsdc(env, investor, usdcAccount, new BN(500_000));
+      expect(
+        parseEvents(env, partialMeta).some((event) => event.name === "depositorCountChanged")
+      ).to.equal(false);
+
+      const fullMeta = await withdrawUsdc(env, investor, usdcAccount, new BN(500_000));
+      const withdrawEvent = parseEvents(env, fullMeta).find(
+        (event) => event.name === "depositorCountChanged"
+      );
+      expect(withdrawEvent).to.exist;
+      expect(withdrawEvent!.data.investor.toString()).to.equal(investor.publicKey.toString());
+      expect(withdrawEvent!.data.depositorCount).to.equal(0);
+      expect(withdrawEvent!.data.increased).to.equal(false);
+    });
+  });
+
+  describe("Depositor record re-activation", () => {
+    let env: CrankEnv;
+
+    before(async () => {
+      env = await setupCrankEnv();
+    });
+
+    it("Should reject closing a record that still holds a balance", async () => {
+      const { investor } = await createInvestorWithDeposit(env, new BN(1_000_000));
+
+      try {
+        await closeDepositorRecord(env, investor);
+        assert.fail("Should have rejected closing a funded record");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected closing a funded record");
+      }
+    });
+
+    it("Should start from a fresh record after close and re-deposit", async () => {
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(2_000_000)
+      );
+      const recordPDA = getDepositorRecordPDA(env, investor.publicKey);
+
+      await withdrawUsdc(env, investor, usdcAccount, new BN(2_000_000));
+      await closeDepositorRecord(env, investor);
+      expect(await env.context.banksClient.getAccount(recordPDA)).to.be.null;
+
+      const statsBefore = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "VaultStats",
+        env.vaultStatsPDA
+      );
+
+      await depositUsdc(env, investor, usdcAccount, new BN(3_000_000));
+
+      const record = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DepositorRecord",
+        recordPDA
+      );
+      expect(record!.investor.toString()).to.equal(investor.publicKey.toString());
+      expect(record!.depositCount).to.equal(1);
+      expect(record!.withdrawalCount).to.equal(0);
+      expect(record!.totalUsdcDeposited.toString()).to.equal("3000000");
+      expect(record!.totalUsdcWithdrawn.toString()).to.equal("0");
+      expect(record!.currentUsdcBalance.toString()).to.equal("3000000");
+
+      const statsAfter = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "VaultStats",
+        env.vaultStatsPDA
+      );
+      expect(statsAfter!.depositorCount).to.equal(statsBefore!.depositorCount + 1);
+    });
+  });
+
+  describe("Carry-over backing invariant", () => {
+    let env: CrankEnv;
+
+    before(async () => {
+      env = await setupCrankEnv();
+    });
+
+    it("Should reject a carry-over that is not backed by vault funds", async () => {
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      // A finished previous day whose recorded dust exceeds the vault balance
+      const [, crankStateBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("crank_state")],
+        env.program.programId
+      );
+      const staleState = await env.program.coder.accounts.encode("crankState", {
+        lastDistributionTimestamp: new BN(1),
+        currentDay: 1,
+        distributionCount: 1,
+        paginationCursor: 0,
+        investorsProcessedToday: 0,
+        investorsPaidToday: 0,
+        dailyDistributed: new BN(0),
+        carryOver: new BN(5_000_000),
+        vestedPool: new BN(0),
+        vestedDistributedToday: new BN(0),
+        dayInvestorFeeQuote: new BN(0),
+        dayClaimedQuote: new BN(0),
+        creatorClaimedToday: new BN(0),
+        dayState: 0,
+        quoteVault: PublicKey.default,
+        dayTotalLocked: new BN(0),
+        expectedInvestors: 0,
+        dayInvestorCommitment: Array(32).fill(0),
+        dayPaidCommitment: Array(32).fill(0),
+        snapshotInvestorsPaid: 0,
+        depositsLockedUntil: new BN(0),
+        lastDistributionSlot: new BN(0),
+        dayTopInvestorWeight: new BN(0),
+        dayTopInvestorQuoteAccount: PublicKey.default,
+        currentRound: 0,
+        roundInvestorFeeQuote: new BN(0),
+        roundVaultBaseline: new BN(0),
+        roundOutflowBaseline: new BN(0),
+        currentPageInvestorFee: new BN(0),
+        unroutedCreatorRemainder: new BN(0),
+        bufferedQuote: new BN(0),
+        dayStartQuoteBalance: new BN(0),
+        bump: crankStateBump,
+      });
+      env.context.setAccount(env.crankStatePDA, {
+        executable: false,
+        owner: env.program.programId,
+        lamports: 1_000_000_000,
+        data: staleState,
+      });
+
+      await beginDistributionDay(env);
+      try {
+        await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+        assert.fail("Should have rejected the unbacked carry-over");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the unbacked carry-over");
+        // CarryOverNotBacked = 6041
+        expect(error.toString()).to.include("0x1799");
+      }
+    });
+  });
+
+  describe("Per-investor fee share multiplier", () => {
+    let env: CrankEnv;
+
+    before(async () => {
+      env = await setupCrankEnv();
+    });
+
+    it("Should give a boosted investor a larger renormalized share of the pool", async () => {
+      const boosted = await createInvestorWithDeposit(env, new BN(500_000_000));
+      const regular = await createInvestorWithDeposit(env, new BN(500_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      // 1.5x: weighted balances are 750 and 500 USDC
+      await setInvestorMultiplier(env, boosted.investor.publicKey, 15000);
+
+      const vaultStats = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "VaultStats",
+        env.vaultStatsPDA
+      );
+      expect(vaultStats!.currentTotalWeightedUsdc.toString()).to.equal("1250000000");
+
+      const boostedBefore = await getTokenAccount(env.context.banksClient, boosted.usdcAccount);
+      const regularBefore = await getTokenAccount(env.context.banksClient, regular.usdcAccount);
+
+      await beginDistributionDay(env);
+
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+      await distributeToInvestor(env, boosted.investor, boosted.usdcAccount, new BN(500_000));
+      await distributeToInvestor(env, regular.investor, regular.usdcAccount, new BN(500_000));
+
+      const boostedAfter = await getTokenAccount(env.context.banksClient, boosted.usdcAccount);
+      const regularAfter = await getTokenAccount(env.context.banksClient, regular.usdcAccount);
+      const boostedPayout = boostedAfter!.amount - boostedBefore!.amount;
+      const regularPayout = regularAfter!.amount - regularBefore!.amount;
+
+      // 60% / 40% of the 500_000 investor pool
+      expect(boostedPayout.toString()).to.equal("300000");
+      expect(regularPayout.toString()).to.equal("200000");
+      expect((boostedPayout + regularPayout).toString()).to.equal("500000");
+    });
+
+    it("Should reject a multiplier set by a non-admin", async () => {
+      const { investor } = await createInvestorWithDeposit(env, new BN(1_000_000));
+      const attacker = Keypair.generate();
+      await fundSol(env.context.banksClient, env.admin, [attacker.publicKey]);
+
+      try {
+        await setInvestorMultiplier(env, investor.publicKey, 30000, attacker);
+        assert.fail("Should have rejected the non-admin signer");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the non-admin signer");
+      }
+    });
+
+    it("Should refuse a multiplier change while a day is in progress", async () => {
+      const lockedEnv = await setupCrankEnv();
+      const { investor } = await createInvestorWithDeposit(lockedEnv, new BN(1_000_000_000));
+      seedFeeVaults(lockedEnv, BigInt(1_000_000));
+      await beginDistributionDay(lockedEnv);
+
+      try {
+        await setInvestorMultiplier(lockedEnv, investor.publicKey, 20000);
+        assert.fail("Should have refused the multiplier change");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have refused the multiplier change");
+        // DepositsLocked = 6061
+        expect(error.toString()).to.include("0x17ad");
+      }
+    });
+  });
+
+  describe("Remainder policy", () => {
+    it("Should distribute the whole claim to investors by weight under the investor policy", async () => {
+      const env = await setupCrankEnv({ remainderPolicy: 1 });
+      const first = await createInvestorWithDeposit(env, new BN(600_000_000));
+      const second = await createInvestorWithDeposit(env, new BN(400_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      // The creator remainder is folded into the investor allocation
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.dayInvestorFeeQuote.toString()).to.equal("1000000");
+      expect(crankState!.vestedPool.toString()).to.equal("0");
+
+      // The creator cannot claim a remainder it does not own
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+      try {
+        await creatorPartialClaim(env, creatorQuoteAccount, new BN(1));
+        assert.fail("Should have rejected the creator claim");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the creator claim");
+      }
+
+      const firstBefore = await getTokenAccount(env.context.banksClient, first.usdcAccount);
+      const secondBefore = await getTokenAccount(env.context.banksClient, second.usdcAccount);
+      for (const { investor, usdcAccount } of [first, second]) {
+        await distributeToInvestor(env, investor, usdcAccount, new BN(1_000_000));
+      }
+
+      // No recipient account is needed to close the day
+      await routeCreatorRemainder(env, null);
+
+      const firstAccount = await getTokenAccount(env.context.banksClient, first.usdcAccount);
+      const secondAccount = await getTokenAccount(env.context.banksClient, second.usdcAccount);
+      const creatorAccount = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+      const vault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      expect((firstAccount!.amount - firstBefore!.amount).toString()).to.equal("600000");
+      expect((secondAccount!.amount - secondBefore!.amount).toString()).to.equal("400000");
+      expect(creatorAccount!.amount.toString()).to.equal("0");
+      expect(vault!.amount.toString()).to.equal("0");
+
+      const crankStateAfter = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankStateAfter!.dailyDistributed.toString()).to.equal("1000000");
+      expect(crankStateAfter!.carryOver.toString()).to.equal("0");
+    });
+
+    it("Should route the remainder to the treasury under the treasury policy", async () => {
+      const treasury = Keypair.generate();
+      const env = await setupCrankEnv({
+        remainderPolicy: 2,
+        treasuryWallet: treasury.publicKey,
+      });
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+
+      // The creator's account is not owned by the treasury
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+      try {
+        await routeCreatorRemainder(env, creatorQuoteAccount);
+        assert.fail("Should have rejected the creator account");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the creator account");
+      }
+
+      const treasuryQuoteAccount = await getOrCreateAta(
+        env.context.banksClient,
+        env.admin,
+        USDC_MINT,
+        treasury.publicKey
+      );
+      await routeCreatorRemainder(env, treasuryQuoteAccount);
+
+      const treasuryAccount = await getTokenAccount(
+        env.context.banksClient,
+        treasuryQuoteAccount
+      );
+      expect(treasuryAccount!.amount.toString()).to.equal("500000");
+    });
+  });
+
+  describe("Quote mint change", () => {
+    const WSOL_MINT = new PublicKey("So11111111111111111111111111111111111111112");
+
+    it("Should sweep old-quote carry-over and zero it when the quote mint changes", async () => {
+      const env = await setupCrankEnv();
+      seedFeeVaults(env, BigInt(1_234));
+
+      // A closed previous day that left 1_234 units of USDC dust in the vault
+      const [, crankStateBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("crank_state")],
+        env.program.programId
+      );
+      const closedState = await env.program.coder.accounts.encode("crankState", {
+        lastDistributionTimestamp: new BN(1),
+        currentDay: 1,
+        distributionCount: 1,
+        paginationCursor: 1,
+        investorsProcessedToday: 1,
+        investorsPaidToday: 1,
+        dailyDistributed: new BN(500_000),
+        carryOver: new BN(1_234),
+        vestedPool: new BN(0),
+        vestedDistributedToday: new BN(0),
+        dayInvestorFeeQuote: new BN(500_000),
+        dayClaimedQuote: new BN(0),
+        creatorClaimedToday: new BN(0),
+        dayState: 2,
+        quoteVault: env.quoteFeeVaultPDA,
+        dayTotalLocked: new BN(0),
+        expectedInvestors: 0,
+        dayInvestorCommitment: Array(32).fill(0),
+        dayPaidCommitment: Array(32).fill(0),
+        snapshotInvestorsPaid: 0,
+        depositsLockedUntil: new BN(0),
+        lastDistributionSlot: new BN(0),
+        dayTopInvestorWeight: new BN(0),
+        dayTopInvestorQuoteAccount: PublicKey.default,
+        currentRound: 0,
+        roundInvestorFeeQuote: new BN(0),
+        roundVaultBaseline: new BN(0),
+        roundOutflowBaseline: new BN(0),
+        currentPageInvestorFee: new BN(0),
+        unroutedCreatorRemainder: new BN(0),
+        bufferedQuote: new BN(0),
+        dayStartQuoteBalance: new BN(0),
+        bump: crankStateBump,
+      });
+      env.context.setAccount(env.crankStatePDA, {
+        executable: false,
+        owner: env.program.programId,
+        lamports: 1_000_000_000,
+        data: closedState,
+      });
+
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+      await updateQuoteMint(env, WSOL_MINT, env.quoteFeeVaultPDA, creatorQuoteAccount);
+
+      const creatorAccount = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+      const vault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      expect(creatorAccount!.amount.toString()).to.equal("1234");
+      expect(vault!.amount.toString()).to.equal("0");
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.carryOver.toString()).to.equal("0");
+      expect(crankState!.dayInvestorFeeQuote.toString()).to.equal("0");
+      expect(crankState!.quoteVault.toString()).to.equal(PublicKey.default.toString());
+
+      const config = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DistributionConfig",
+        env.distributionConfigPDA
+      );
+      expect(config!.quoteMint.toString()).to.equal(WSOL_MINT.toString());
+    });
+  });
+
+  describe("Event emission toggle", () => {
+    it("Should pay investors without emitting InvestorPayout when events are disabled", async () => {
+      const env = await setupCrankEnv({ emitEvents: false });
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      const balanceBefore = await getTokenAccount(env.context.banksClient, usdcAccount);
+      const meta = await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+
+      const balanceAfter = await getTokenAccount(env.context.banksClient, usdcAccount);
+      expect((balanceAfter!.amount - balanceBefore!.amount).toString()).to.equal("500000");
+      expect(
+        parseEvents(env, meta).some((event) => event.name === "investorPayout")
+      ).to.equal(false);
+    });
+  });
+
+  describe("Config lock during distribution", () => {
+    it("Should reject distribution math updates mid-day and accept them after close", async () => {
+      const env = await setupCrankEnv();
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+
+      for (const updates of [
+        { y0Allocation: new BN(2_000_000_000) },
+        { investorFeeShareBps: 8000 },
+        { vestingProgram: Keypair.generate().publicKey },
+        { vestedInvestorShareBps: 1000 },
+      ]) {
+        try {
+          await updateDistributionConfig(env, updates);
+          assert.fail("Should have rejected the mid-day update");
+        } catch (error) {
+          expect(error.toString()).to.not.include("Should have rejected the mid-day update");
+          // ConfigLockedDuringDistribution = 6047
+          expect(error.toString()).to.include("0x179f");
+        }
+      }
+
+      // Operational parameters stay adjustable mid-day
+      await updateDistributionConfig(env, { dailyCapLamports: new BN(10_000_000) });
+
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+      await routeCreatorRemainder(env, await getCreatorQuoteAccount(env));
+
+      await updateDistributionConfig(env, {
+        y0Allocation: new BN(2_000_000_000),
+        investorFeeShareBps: 8000,
+      });
+
+      const config = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DistributionConfig",
+        env.distributionConfigPDA
+      );
+      expect(config!.y0Allocation.toString()).to.equal("2000000000");
+      expect(config!.investorFeeShareBps).to.equal(8000);
+      expect(config!.dailyCapLamports.toString()).to.equal("10000000");
+    });
+  });
+
+  describe("First eligible day", () => {
+    it("Should only pay a depositor who joined after day 1 began from day 2", async () => {
+      const env = await setupCrankEnv();
+      const early = await createInvestorWithDeposit(env, new BN(500_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      await distributeToInvestor(env, early.investor, early.usdcAccount, new BN(500_000));
+      await routeCreatorRemainder(env, await getCreatorQuoteAccount(env));
+
+      // Joins once day 1 has closed, before day 2 is begun
+      const late = await createInvestorWithDeposit(env, new BN(500_000_000));
+      const lateRecord = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DepositorRecord",
+        getDepositorRecordPDA(env, late.investor.publicKey)
+      );
+      expect(lateRecord!.firstEligibleDay).to.equal(2);
+
+      // Day 2: 1 USDC of new fees split by weight
+      await warpTimeBy(env.context, 86_400);
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+
+      const day2State = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(day2State!.currentDay).to.equal(2);
+
+      const lateBefore = await getTokenAccount(env.context.banksClient, late.usdcAccount);
+      await distributeToInvestor(env, late.investor, late.usdcAccount, new BN(500_000));
+      const lateAfter = await getTokenAccount(env.context.banksClient, late.usdcAccount);
+      expect((lateAfter!.amount - lateBefore!.amount).toString()).to.equal("250000");
+    });
+  });
+
+  describe("Deposit with eligibility proof", () => {
+    it("Should mark a proven investor eligible and reject an invalid proof", async () => {
+      const allowed = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+      const { root, proofs } = computeEligibilityTree(
+        allowed.map((investor) => investor.publicKey)
+      );
+      const env = await setupCrankEnv({ eligibilityRoot: root });
+
+      const fundInvestor = async (investor: Keypair) => {
+        await fundSol(env.context.banksClient, env.admin, [investor.publicKey]);
+        await fundUsdc(env.context.banksClient, [investor.publicKey]);
+        return getOrCreateAta(env.context.banksClient, env.admin, USDC_MINT, investor.publicKey);
+      };
+
+      // The odd investor was promoted a level, so its proof is shorter
+      const proven = allowed[2];
+      const provenUsdcAccount = await fundInvestor(proven);
+      await depositUsdcWithProof(env, proven, provenUsdcAccount, new BN(500_000_000), proofs[2]);
+
+      const record = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DepositorRecord",
+        getDepositorRecordPDA(env, proven.publicKey)
+      );
+      expect(record!.eligible).to.equal(true);
+      expect(record!.currentUsdcBalance.toString()).to.equal("500000000");
+
+      // An outsider cannot reuse an allowed investor's proof
+      const outsider = Keypair.generate();
+      const outsiderUsdcAccount = await fundInvestor(outsider);
+      try {
+        await depositUsdcWithProof(env, outsider, outsiderUsdcAccount, new BN(500_000_000), proofs[0]);
+        assert.fail("Should have rejected the invalid proof");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the invalid proof");
+      }
+
+      // A plain deposit is accepted but never paid while the allowlist is active
+      await depositUsdc(env, outsider, outsiderUsdcAccount, new BN(500_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+
+      const outsiderBefore = await getTokenAccount(env.context.banksClient, outsiderUsdcAccount);
+      const provenBefore = await getTokenAccount(env.context.banksClient, provenUsdcAccount);
+      await distributeToInvestor(env, outsider, outsiderUsdcAccount, new BN(500_000));
+      await distributeToInvestor(env, proven, provenUsdcAccount, new BN(500_000));
+      const outsiderAfter = await getTokenAccount(env.context.banksClient, outsiderUsdcAccount);
+      const provenAfter = await getTokenAccount(env.context.banksClient, provenUsdcAccount);
+      expect((outsiderAfter!.amount - outsiderBefore!.amount).toString()).to.equal("0");
+      expect((provenAfter!.amount - provenBefore!.amount).toString()).to.equal("250000");
+    });
+  });
+
+  describe("Carry-over flush", () => {
+    it("Should distribute accumulated carry-over pro-rata and zero it", async () => {
+      const env = await setupCrankEnv();
+      const small = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      const large = await createInvestorWithDeposit(env, new BN(3_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      // A closed previous day that left 1_000_000 units of carry-over in the vault
+      const [, crankStateBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("crank_state")],
+        env.program.programId
+      );
+      const closedState = await env.program.coder.accounts.encode("crankState", {
+        lastDistributionTimestamp: new BN(1),
+        currentDay: 1,
+        distributionCount: 1,
+        paginationCursor: 2,
+        investorsProcessedToday: 2,
+        investorsPaidToday: 0,
+        dailyDistributed: new BN(0),
+        carryOver: new BN(1_000_000),
+        vestedPool: new BN(0),
+        vestedDistributedToday: new BN(0),
+        dayInvestorFeeQuote: new BN(0),
+        dayClaimedQuote: new BN(0),
+        creatorClaimedToday: new BN(0),
+        dayState: 2,
+        quoteVault: env.quoteFeeVaultPDA,
+        dayTotalLocked: new BN(0),
+        expectedInvestors: 0,
+        dayInvestorCommitment: Array(32).fill(0),
+        dayPaidCommitment: Array(32).fill(0),
+        snapshotInvestorsPaid: 0,
+        depositsLockedUntil: new BN(0),
+        lastDistributionSlot: new BN(0),
+        dayTopInvestorWeight: new BN(0),
+        dayTopInvestorQuoteAccount: PublicKey.default,
+        currentRound: 0,
+        roundInvestorFeeQuote: new BN(0),
+        roundVaultBaseline: new BN(0),
+        roundOutflowBaseline: new BN(0),
+        currentPageInvestorFee: new BN(0),
+        unroutedCreatorRemainder: new BN(0),
+        bufferedQuote: new BN(0),
+        dayStartQuoteBalance: new BN(0),
+        bump: crankStateBump,
+      });
+      env.context.setAccount(env.crankStatePDA, {
+        executable: false,
+        owner: env.program.programId,
+        lamports: 1_000_000_000,
+        data: closedState,
+      });
+
+      // Leaving an investor out would skew the split
+      try {
+        await flushCarryOver(env, [
+          { investor: large.investor.publicKey, quoteAccount: large.usdcAccount },
+        ]);
+        assert.fail("Should have required every weighted investor");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have required every weighted investor");
+      }
+
+      const smallBefore = await getTokenAccount(env.context.banksClient, small.usdcAccount);
+      const largeBefore = await getTokenAccount(env.context.banksClient, large.usdcAccount);
+      await flushCarryOver(env, [
+        { investor: small.investor.publicKey, quoteAccount: small.usdcAccount },
+        { investor: large.investor.publicKey, quoteAccount: large.usdcAccount },
+      ]);
+      const smallAfter = await getTokenAccount(env.context.banksClient, small.usdcAccount);
+      const largeAfter = await getTokenAccount(env.context.banksClient, large.usdcAccount);
+      expect((smallAfter!.amount - smallBefore!.amount).toString()).to.equal("250000");
+      expect((largeAfter!.amount - largeBefore!.amount).toString()).to.equal("750000");
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.carryOver.toString()).to.equal("0");
+
+      const vault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      expect(vault!.amount.toString()).to.equal("0");
+    });
+  });
+
+  describe("Daily cap in quote units", () => {
+    it("Should cap payouts at the configured USDC amount and fall back to the legacy cap", async () => {
+      // 0.3 USDC cap; the legacy field is large enough to never trigger
+      const env = await setupCrankEnv({
+        dailyCapQuoteUnits: new BN(300_000),
+        dailyCapLamports: new BN(1_000_000_000),
+      });
+      const first = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      const second = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+
+      const config = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DistributionConfig",
+        env.distributionConfigPDA
+      );
+      expect(config!.quoteDecimals).to.equal(6);
+
+      // 0.25 USDC fits under the cap, a second 0.25 USDC payout does not
+      const firstBefore = await getTokenAccount(env.context.banksClient, first.usdcAccount);
+      await distributeToInvestor(env, first.investor, first.usdcAccount, new BN(500_000));
+      const firstAfter = await getTokenAccount(env.context.banksClient, first.usdcAccount);
+      expect((firstAfter!.amount - firstBefore!.amount).toString()).to.equal("250000");
+
+      try {
+        await distributeToInvestor(env, second.investor, second.usdcAccount, new BN(500_000));
+        assert.fail("Should have hit the daily cap");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have hit the daily cap");
+        // DailyCapExceeded = 6020
+        expect(error.toString()).to.include("0x1784");
+      }
+
+      // Clearing the quote-unit cap falls back to the legacy field
+      await updateDistributionConfig(env, { dailyCapQuoteUnits: new BN(0) });
+      const secondBefore = await getTokenAccount(env.context.banksClient, second.usdcAccount);
+      await distributeToInvestor(env, second.investor, second.usdcAccount, new BN(500_000));
+      const secondAfter = await getTokenAccount(env.context.banksClient, second.usdcAccount);
+      expect((secondAfter!.amount - secondBefore!.amount).toString()).to.equal("250000");
+    });
+  });
+
+  describe("Remainder routing before any page", () => {
+    it("Should reject creator routing while no page has been processed", async () => {
+      const env = await setupCrankEnv();
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      // A day that was started but never paged
+      const [, crankStateBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("crank_state")],
+        env.program.programId
+      );
+      const unpagedState = await env.program.coder.accounts.encode("crankState", {
+        lastDistributionTimestamp: new BN(1),
+        currentDay: 1,
+        distributionCount: 0,
+        paginationCursor: 0,
+        investorsProcessedToday: 0,
+        investorsPaidToday: 0,
+        dailyDistributed: new BN(0),
+        carryOver: new BN(0),
+        vestedPool: new BN(0),
+        vestedDistributedToday: new BN(0),
+        dayInvestorFeeQuote: new BN(0),
+        dayClaimedQuote: new BN(0),
+        creatorClaimedToday: new BN(0),
+        dayState: 1,
+        quoteVault: env.quoteFeeVaultPDA,
+        dayTotalLocked: new BN(0),
+        expectedInvestors: 0,
+        dayInvestorCommitment: Array(32).fill(0),
+        dayPaidCommitment: Array(32).fill(0),
+        snapshotInvestorsPaid: 0,
+        depositsLockedUntil: new BN(0),
+        lastDistributionSlot: new BN(0),
+        dayTopInvestorWeight: new BN(0),
+        dayTopInvestorQuoteAccount: PublicKey.default,
+        currentRound: 0,
+        roundInvestorFeeQuote: new BN(0),
+        roundVaultBaseline: new BN(0),
+        roundOutflowBaseline: new BN(0),
+        currentPageInvestorFee: new BN(0),
+        unroutedCreatorRemainder: new BN(0),
+        bufferedQuote: new BN(0),
+        dayStartQuoteBalance: new BN(0),
+        bump: crankStateBump,
+      });
+      env.context.setAccount(env.crankStatePDA, {
+        executable: false,
+        owner: env.program.programId,
+        lamports: 1_000_000_000,
+        data: unpagedState,
+      });
+
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+      try {
+        await routeCreatorRemainder(env, creatorQuoteAccount);
+        assert.fail("Should have rejected routing before any page");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected routing before any page");
+        // NoPagesProcessed = 6056
+        expect(error.toString()).to.include("0x17a8");
+      }
+
+      const creatorAccount = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+      expect(creatorAccount!.amount.toString()).to.equal("0");
+    });
+  });
+
+  describe("Begin distribution day", () => {
+    it("Should snapshot the day and lock deposits and withdrawals until close", async () => {
+      const env = await setupCrankEnv();
+      const first = await createInvestorWithDeposit(env, new BN(600_000_000));
+      await createInvestorWithDeposit(env, new BN(400_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      // The crank refuses to run before the day has been begun
+      try {
+        await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+        assert.fail("Should have rejected a crank before the day was begun");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected a crank before the day was begun");
+      }
+
+      const meta = await beginDistributionDay(env);
+      const begun = parseEvents(env, meta).find((event) => event.name === "distributionDayBegun");
+      expect(begun).to.exist;
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.dayState).to.equal(1);
+      expect(crankState!.currentDay).to.equal(1);
+      expect(crankState!.dayTotalLocked.toString()).to.equal("1000000000");
+      expect(crankState!.expectedInvestors).to.equal(2);
+      expect(crankState!.quoteVault.toString()).to.equal(env.quoteFeeVaultPDA.toString());
+      // Locked until the default 48h force-close deadline
+      expect(crankState!.depositsLockedUntil.toNumber()).to.equal(
+        begun!.data.timestamp.toNumber() + 172_800
+      );
+
+      // A second begin while the day is in progress is rejected
+      try {
+        await beginDistributionDay(env);
+        assert.fail("Should have rejected a second begin");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected a second begin");
+        // DistributionInProgress = 6051
+        expect(error.toString()).to.include("0x17a3");
+      }
+
+      for (const [label, action] of [
+        ["deposit", () => depositUsdc(env, first.investor, first.usdcAccount, new BN(1_000_000))],
+        ["withdraw", () => withdrawUsdc(env, first.investor, first.usdcAccount, new BN(1_000_000))],
+      ] as [string, () => Promise<unknown>][]) {
+        try {
+          await action();
+          assert.fail(`Should have rejected the ${label} while the day is in progress`);
+        } catch (error) {
+          expect(error.toString()).to.not.include(`Should have rejected the ${label}`);
+          // DepositsLocked = 6061
+          expect(error.toString()).to.include("0x17ad");
+        }
+      }
+
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+      await routeCreatorRemainder(env, await getCreatorQuoteAccount(env));
+
+      // Closing the day releases the lock
+      await withdrawUsdc(env, first.investor, first.usdcAccount, new BN(1_000_000));
+      const closedState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(closedState!.dayState).to.equal(2);
+      expect(closedState!.depositsLockedUntil.toString()).to.equal("0");
+    });
+
+    it("Should keep deposits locked while the day stays open past 24 hours", async () => {
+      const env = await setupCrankEnv();
+      const first = await createInvestorWithDeposit(env, new BN(600_000_000));
+      await createInvestorWithDeposit(env, new BN(400_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: false });
+
+      // The day can still take pages and payouts until its 48h deadline
+      await warpTimeBy(env.context, 86_401);
+      try {
+        await depositUsdc(env, first.investor, first.usdcAccount, new BN(100_000_000));
+        assert.fail("Should have rejected the deposit on the open day");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the deposit on the open day");
+        // DepositsLocked = 6061
+        expect(error.toString()).to.include("0x17ad");
+      }
+
+      // The payout is still weighted by the balance the day began with (60% of 500000)
+      const before = await getTokenAccount(env.context.banksClient, first.usdcAccount);
+      await distributeToInvestor(env, first.investor, first.usdcAccount, new BN(500_000));
+      const after = await getTokenAccount(env.context.banksClient, first.usdcAccount);
+      expect((after!.amount - before!.amount).toString()).to.equal("300000");
+    });
+  });
+
+  describe("Force-close expired day", () => {
+    it("Should reject a force close before the deadline and allow it after", async () => {
+      const env = await setupCrankEnv();
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      // The keeper begins the day and processes a page but never pays or closes it
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: false });
+
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+      try {
+        await forceCloseDay(env, creatorQuoteAccount);
+        assert.fail("Should have rejected the force close before the deadline");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the force close before the deadline");
+        // DayNotExpired = 6063
+        expect(error.toString()).to.include("0x17af");
+      }
+
+      // Default maximum duration is 48h after the day began
+      await warpTimeBy(env.context, 172_800);
+      const meta = await forceCloseDay(env, creatorQuoteAccount);
+
+      const events = parseEvents(env, meta);
+      const forceClosed = events.find((event) => event.name === "dayForceClosed");
+      expect(forceClosed).to.exist;
+      expect(forceClosed!.data.day).to.equal(1);
+      expect(forceClosed!.data.pagesProcessed).to.equal(1);
+      expect(forceClosed!.data.investorsPaid).to.equal(0);
+      expect(events.some((event) => event.name === "creatorPayoutDayClosed")).to.be.true;
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.dayState).to.equal(2);
+
+      // The unpaid allocation is routed to the creator with the remainder
+      const creatorAccount = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+      expect(creatorAccount!.amount.toString()).to.equal("1000000");
+    });
+
+    it("Should reject pages and a new day on a stale day until it is force-closed", async () => {
+      const env = await setupCrankEnv();
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      // The day is left in progress with pages still to process
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: false });
+      await warpTimeBy(env.context, 172_800);
+
+      const stale = [
+        ["the next page", () => crankPage(env, { pageIndex: 1, investorsCount: 1, isFinalPage: true })],
+        ["the payout", () => distributeToInvestor(env, investor, usdcAccount, new BN(500_000))],
+        ["a new day", () => beginDistributionDay(env)],
+      ] as const;
+      for (const [label, attempt] of stale) {
+        try {
+          await attempt();
+          assert.fail(`Should have rejected ${label} on the stale day`);
+        } catch (error) {
+          expect(error.toString()).to.not.include(`Should have rejected ${label} on the stale day`);
+          // PriorDayNotClosed = 6087
+          expect(error.toString()).to.include("0x17c7");
+        }
+      }
+
+      await forceCloseDay(env, await getCreatorQuoteAccount(env));
+      await beginDistributionDay(env);
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.currentDay).to.equal(2);
+      expect(crankState!.paginationCursor).to.equal(0);
+    });
+
+    it("Should keep the deadline and the deposit lock fixed while the day is open", async () => {
+      const env = await setupCrankEnv();
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      const meta = await beginDistributionDay(env);
+      const begun = parseEvents(env, meta).find((event) => event.name === "distributionDayBegun");
+      expect(begun!.data.depositsLockedUntil.toNumber()).to.equal(
+        begun!.data.timestamp.toNumber() + 172_800
+      );
+
+      // Shortening the deadline would release the lock while pages can still run
+      try {
+        await updateDistributionConfig(env, { dayMaxDurationSeconds: new BN(86_400) });
+        assert.fail("Should have rejected moving the deadline mid-day");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected moving the deadline mid-day");
+        // ConfigLockedDuringDistribution = 6047
+        expect(error.toString()).to.include("0x179f");
+      }
+
+      await warpTimeBy(env.context, 172_800);
+      await forceCloseDay(env, await getCreatorQuoteAccount(env));
+      await updateDistributionConfig(env, { dayMaxDurationSeconds: new BN(86_400) });
+
+      const config = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DistributionConfig",
+        env.distributionConfigPDA
+      );
+      expect(config!.dayMaxDurationSeconds.toString()).to.equal("86400");
+    });
+  });
+
+  describe("Fee distribution recipients", () => {
+    it("Should reject recipient token accounts with the wrong mints", async () => {
+      const env = await setupCrankEnv();
+      seedFeeVaults(env, BigInt(1_000_000), BigInt(1_000));
+
+      const recipient = Keypair.generate().publicKey;
+      const baseAccount = Keypair.generate().publicKey;
+      const quoteAccount = Keypair.generate().publicKey;
+      setTokenAccount(env.context, baseAccount, env.baseMint, recipient, BigInt(0));
+      setTokenAccount(env.context, quoteAccount, USDC_MINT, recipient, BigInt(0));
+
+      // Swapped recipients: each account holds the other side's mint
+      try {
+        await distributeFees(env, quoteAccount, baseAccount);
+        assert.fail("Should have rejected the wrong-mint recipients");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the wrong-mint recipients");
+        // Anchor ConstraintTokenMint = 2014
+        expect(error.toString()).to.include("0x7de");
+      }
+
+      await distributeFees(env, baseAccount, quoteAccount);
+      const quoteRecipient = await getTokenAccount(env.context.banksClient, quoteAccount);
+      expect(quoteRecipient!.amount.toString()).to.equal("1000000");
+    });
+  });
+
+  describe("Minimum deposit interval", () => {
+    it("Should reject rapid repeat deposits and accept them after the interval", async () => {
+      const env = await setupCrankEnv({ minDepositIntervalSeconds: new BN(3600) });
+
+      // The first deposit is exempt
+      const { investor, usdcAccount } = await createInvestorWithDeposit(env, new BN(1_000_000));
+
+      try {
+        await depositUsdc(env, investor, usdcAccount, new BN(2_000_000));
+        assert.fail("Should have rejected the repeat deposit");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the repeat deposit");
+        // DepositTooFrequent = 6065
+        expect(error.toString()).to.include("0x17b1");
+      }
+
+      await warpTimeBy(env.context, 3600);
+      await depositUsdc(env, investor, usdcAccount, new BN(3_000_000));
+
+      const record = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DepositorRecord",
+        getDepositorRecordPDA(env, investor.publicKey)
+      );
+      expect(record!.depositCount).to.equal(2);
+      expect(record!.currentUsdcBalance.toString()).to.equal("4000000");
+    });
+  });
+
+  describe("Vault concentration", () => {
+    it("Should report the top-1 and top-5 shares of a skewed vault", async () => {
+      const env = await setupCrankEnv();
+
+      const investors: PublicKey[] = [];
+      for (const amount of [7_000_000, 1_000_000, 1_000_000, 500_000, 300_000, 200_000]) {
+        const { investor } = await createInvestorWithDeposit(env, new BN(amount));
+        investors.push(investor.publicKey);
+      }
+
+      const concentration = await queryVaultConcentration(env, investors);
+      expect(concentration.currentTotalUsdc.toString()).to.equal("10000000");
+      expect(concentration.recordsCount).to.equal(6);
+      expect(concentration.topInvestor.toString()).to.equal(investors[0].toString());
+      expect(concentration.top1ShareBps.toString()).to.equal("7000");
+      expect(concentration.top5ShareBps.toString()).to.equal("9800");
+
+      // Passing the same record twice is rejected
+      try {
+        await queryVaultConcentration(env, [investors[1], investors[1]]);
+        assert.fail("Should have rejected the duplicate record");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the duplicate record");
+        // InvalidDepositorRecord = 6053
+        expect(error.toString()).to.include("0x17a5");
+      }
+    });
+  });
+
+  describe("Carry-over policy", () => {
+    // Runs page 0 of a low-fee day (100_000 fresh quote) after a day that left 1_000_000 carry-over
+    async function crankLowFeeDay(carryOverPolicy: number) {
+      const env = await setupCrankEnv({ carryOverPolicy });
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_100_000));
+
+      const [, crankStateBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("crank_state")],
+        env.program.programId
+      );
+      const previousDay = await env.program.coder.accounts.encode("crankState", {
+        lastDistributionTimestamp: new BN(1),
+        currentDay: 1,
+        distributionCount: 1,
+        paginationCursor: 0,
+        investorsProcessedToday: 0,
+        investorsPaidToday: 0,
+        dailyDistributed: new BN(0),
+        carryOver: new BN(1_000_000),
+        vestedPool: new BN(0),
+        vestedDistributedToday: new BN(0),
+        dayInvestorFeeQuote: new BN(0),
+        dayClaimedQuote: new BN(0),
+        creatorClaimedToday: new BN(0),
+        dayState: 2,
+        quoteVault: env.quoteFeeVaultPDA,
+        dayTotalLocked: new BN(0),
+        expectedInvestors: 0,
+        dayInvestorCommitment: Array(32).fill(0),
+        dayPaidCommitment: Array(32).fill(0),
+        snapshotInvestorsPaid: 0,
+        depositsLockedUntil: new BN(0),
+        lastDistributionSlot: new BN(0),
+        dayTopInvestorWeight: new BN(0),
+        dayTopInvestorQuoteAccount: PublicKey.default,
+        currentRound: 0,
+        roundInvestorFeeQuote: new BN(0),
+        roundVaultBaseline: new BN(0),
+        roundOutflowBaseline: new BN(0),
+        currentPageInvestorFee: new BN(0),
+        unroutedCreatorRemainder: new BN(0),
+        bufferedQuote: new BN(0),
+        dayStartQuoteBalance: new BN(0),
+        bump: crankStateBump,
+      });
+      env.context.setAccount(env.crankStatePDA, {
+        executable: false,
+        owner: env.program.programId,
+        lamports: 1_000_000_000,
+        data: previousDay,
+      });
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: false });
+
+      return fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+    }
+
+    it("Should add the carry-over to a low-fee day's pool under the include policy", async () => {
+      const crankState = await crankLowFeeDay(1);
+      // 50% of the fresh 100_000 plus the whole carry-over
+      expect(crankState!.dayInvestorFeeQuote.toString()).to.equal("1050000");
+      expect(crankState!.carryOver.toString()).to.equal("0");
+    });
+
+    it("Should hold the carry-over on a low-fee day under the hold policy", async () => {
+      const crankState = await crankLowFeeDay(2);
+      // Only the fresh claim is distributed; the carry-over waits for a day that covers it
+      expect(crankState!.dayInvestorFeeQuote.toString()).to.equal("50000");
+      expect(crankState!.carryOver.toString()).to.equal("1000000");
+    });
+
+    it("Should reject an unknown carry-over policy", async () => {
+      const env = await setupCrankEnv();
+      try {
+        await updateDistributionConfig(env, { carryOverPolicy: 3 });
+        assert.fail("Should have rejected the policy");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the policy");
+        // InvalidCarryOverPolicy = 6066
+        expect(error.toString()).to.include("0x17b2");
+      }
+    });
+  });
+
+  describe("Bootstrap program", () => {
+    it("Should create every singleton so a deposit and crank need no further init", async () => {
+      const env = await createCrankEnv();
+      await bootstrapProgram(env);
+
+      const vaultStats = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "VaultStats",
+        env.vaultStatsPDA
+      );
+      const [, vaultStatsBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("deposit_vault"), Buffer.from("stats")],
+        env.program.programId
+      );
+      expect(vaultStats!.bump).to.equal(vaultStatsBump);
+
+      const quoteVault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      expect(quoteVault!.owner.toString()).to.equal(env.feeCollectorPDA.toString());
+      expect(quoteVault!.mint.toString()).to.equal(USDC_MINT.toString());
+
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.currentDay).to.equal(1);
+      expect(crankState!.dayInvestorFeeQuote.toString()).to.equal("500000");
+
+      // The singletons can only be created once
+      try {
+        await bootstrapProgram(env, { minDepositIntervalSeconds: new BN(1) });
+        assert.fail("Should have rejected a second bootstrap");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected a second bootstrap");
+      }
+    });
+  });
+
+  describe("Snapshot reconciliation", () => {
+    it("Should reconcile the final page against the day-start investor set", async () => {
+      const env = await setupCrankEnv({ autoCloseOnFinalPage: true });
+      const early = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: false });
+
+      // The deposit lock lapses while the day is still open and a new investor joins
+      await warpTimeBy(env.context, 86_400);
+      const late = await createInvestorWithDeposit(env, new BN(500_000_000));
+
+      // Paying only the post-snapshot investor does not reconcile the day
+      await distributeToInvestor(env, late.investor, late.usdcAccount, new BN(500_000));
+      await crankPage(
+        env,
+        { pageIndex: 1, investorsCount: 1, isFinalPage: true },
+        creatorQuoteAccount
+      );
+      let crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.dayState).to.equal(1);
+      expect(crankState!.expectedInvestors).to.equal(1);
+      expect(crankState!.snapshotInvestorsPaid).to.equal(0);
+
+      // Paying the snapshotted investor closes the day despite the changed depositor count
+      await distributeToInvestor(env, early.investor, early.usdcAccount, new BN(500_000));
+      await crankPage(
+        env,
+        { pageIndex: 2, investorsCount: 0, isFinalPage: true },
+        creatorQuoteAccount
+      );
+      crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.dayState).to.equal(2);
+      expect(crankState!.snapshotInvestorsPaid).to.equal(1);
+      expect(Buffer.from(crankState!.dayPaidCommitment).toString("hex")).to.equal(
+        Buffer.from(crankState!.dayInvestorCommitment).toString("hex")
+      );
+    });
+  });
+
+  describe("f_locked band", () => {
+    it("Should raise f_locked to the floor when little is locked", async () => {
+      // 1_000 of 10_000 USDC locked gives f_locked = 1000 bps, lifted to the 3000 bps floor
+      const env = await setupCrankEnv({
+        y0Allocation: new BN(10_000_000_000),
+        fLockedFloorBps: 3000,
+        fLockedCeilingBps: 8000,
+      });
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: false });
+
+      const crankState = await fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+      expect(crankState!.dayInvestorFeeQuote.toString()).to.equal("300000");
+    });
+
+    it("Should cap f_locked at the ceiling before the min with the fee share", async () => {
+      // Everything is locked (f_locked = 10000 bps), capped at the 2000 bps ceiling
+      const env = await setupCrankEnv({ fLockedCeilingBps: 2000 });
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: false });
+
+      const crankState = await fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+      expect(crankState!.dayInvestorFeeQuote.toString()).to.equal("200000");
+    });
+
+    it("Should reject a floor above the ceiling", async () => {
+      const env = await setupCrankEnv({ fLockedCeilingBps: 5000 });
+      try {
+        await updateDistributionConfig(env, { fLockedFloorBps: 5001 });
+        assert.fail("Should have rejected the band");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the band");
+        // InvalidFLockedBand = 6067
+        expect(error.toString()).to.include("0x17b3");
+      }
+    });
+  });
+
+  describe("Total-locked verification", () => {
+    async function offsetVaultStatsTotal(env: CrankEnv, offset: number) {
+      const account = await env.context.banksClient.getAccount(env.vaultStatsPDA);
+      const vaultStats = env.program.coder.accounts.decode("vaultStats", Buffer.from(account!.data));
+      vaultStats.currentTotalUsdc = vaultStats.currentTotalUsdc.add(new BN(offset));
+      env.context.setAccount(env.vaultStatsPDA, {
+        ...account!,
+        data: await env.program.coder.accounts.encode("vaultStats", vaultStats),
+      });
+    }
+
+    it("Should pass when the records match vault stats within the tolerance", async () => {
+      const env = await setupCrankEnv({
+        verifyTotalLocked: true,
+        totalLockedToleranceUnits: new BN(10),
+      });
+      const first = await createInvestorWithDeposit(env, new BN(600_000_000));
+      const second = await createInvestorWithDeposit(env, new BN(400_000_000));
+      await offsetVaultStatsTotal(env, 10);
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: false }, null, [
+        first.investor.publicKey,
+        second.investor.publicKey,
+      ]);
+
+      const crankState = await fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+      expect(crankState!.paginationCursor).to.equal(1);
+    });
+
+    it("Should detect vault stats drift beyond the tolerance", async () => {
+      const env = await setupCrankEnv({
+        verifyTotalLocked: true,
+        totalLockedToleranceUnits: new BN(10),
+      });
+      const first = await createInvestorWithDeposit(env, new BN(600_000_000));
+      const second = await createInvestorWithDeposit(env, new BN(400_000_000));
+      await offsetVaultStatsTotal(env, 11);
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+
+      try {
+        await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: false }, null, [
+          first.investor.publicKey,
+          second.investor.publicKey,
+        ]);
+        assert.fail("Should have detected the drift");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have detected the drift");
+        // VaultStatsDrift = 6068
+        expect(error.toString()).to.include("0x17b4");
+      }
+
+      // A record passed twice cannot paper over the drift
+      try {
+        await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: false }, null, [
+          first.investor.publicKey,
+          first.investor.publicKey,
+        ]);
+        assert.fail("Should have rejected the duplicate record");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the duplicate record");
+        // InvalidDepositorRecord = 6053
+        expect(error.toString()).to.include("0x17a5");
+      }
+    });
+  });
+
+  describe("Creator payout mint", () => {
+    // Closes out a day with a 500_000 quote creator remainder, leaving it for route_creator_remainder
+    async function setupRemainderDay(creatorPayoutMint: PublicKey) {
+      const env = await setupCrankEnv({ creatorPayoutMint });
+      const deposit = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      await distributeToInvestor(env, deposit.investor, deposit.usdcAccount, new BN(500_000));
+      return env;
+    }
+
+    it("Should pay the remainder in quote without a swap when the payout mint is the quote mint", async () => {
+      const env = await setupRemainderDay(USDC_MINT);
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+
+      await routeCreatorRemainder(env, creatorQuoteAccount);
+
+      const creatorAccount = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+      expect(creatorAccount!.amount.toString()).to.equal("500000");
+      const crankState = await fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+      expect(crankState!.dayState).to.equal(2);
+    });
+
+    it("Should swap the remainder through DAMM v2 when the payout mint differs", async () => {
+      const env = await setupRemainderDay(NATIVE_MINT);
+
+      // Mock DAMM v2 pool trading USDC (token A) against wrapped SOL (token B)
+      const POOL_DISCRIMINATOR = [241, 154, 109, 4, 17, 177, 109, 188];
+      const POOL_SIZE = 8 + 1104;
+      const swap = {
+        ammProgram: DAMM_V2_PROGRAM_ID,
+        pool: Keypair.generate().publicKey,
+        poolAuthority: Keypair.generate().publicKey,
+        poolTokenAVault: Keypair.generate().publicKey,
+        poolTokenBVault: Keypair.generate().publicKey,
+        poolTokenAMint: USDC_MINT,
+        poolTokenBMint: NATIVE_MINT,
+        eventAuthority: Keypair.generate().publicKey,
+      };
+      const poolData = Buffer.alloc(POOL_SIZE);
+      Buffer.from(POOL_DISCRIMINATOR).copy(poolData, 0);
+      USDC_MINT.toBuffer().copy(poolData, 8 + 160);
+      NATIVE_MINT.toBuffer().copy(poolData, 8 + 192);
+      swap.poolTokenAVault.toBuffer().copy(poolData, 8 + 224);
+      swap.poolTokenBVault.toBuffer().copy(poolData, 8 + 256);
+      env.context.setAccount(swap.pool, {
+        executable: false,
+        owner: DAMM_V2_PROGRAM_ID,
+        lamports: 1_000_000_000,
+        data: poolData,
+      });
+
+      const creatorPayoutAccount = Keypair.generate().publicKey;
+      setTokenAccount(env.context, creatorPayoutAccount, NATIVE_MINT, env.creatorWallet.publicKey, BigInt(0));
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+
+      const expectFailure = async (
+        run: () => Promise<void>,
+        label: string,
+        code: string | null
+      ) => {
+        try {
+          await run();
+          assert.fail(label);
+        } catch (error) {
+          expect(error.toString()).to.not.include(label);
+          if (code) {
+            expect(error.toString()).to.include(code);
+          }
+        }
+      };
+
+      // CreatorPayoutSwapAccountsRequired = 6069
+      await expectFailure(
+        () => routeCreatorRemainder(env, creatorPayoutAccount, null, new BN(1)),
+        "Should have required the swap accounts",
+        "0x17b5"
+      );
+      // CreatorPayoutMinOutRequired = 6070
+      await expectFailure(
+        () => routeCreatorRemainder(env, creatorPayoutAccount, swap, new BN(0)),
+        "Should have required a slippage guard",
+        "0x17b6"
+      );
+      // InvalidPosition = 6008
+      await expectFailure(
+        () =>
+          routeCreatorRemainder(
+            env,
+            creatorPayoutAccount,
+            { ...swap, poolTokenAVault: Keypair.generate().publicKey },
+            new BN(1)
+          ),
+        "Should have rejected a vault the pool does not use",
+        "0x1778"
+      );
+      // CreatorQuoteAccountMismatch = 6034
+      await expectFailure(
+        () => routeCreatorRemainder(env, creatorQuoteAccount, swap, new BN(1)),
+        "Should have rejected a quote-mint payout account",
+        "0x1792"
+      );
+
+      // With valid accounts the remainder reaches the swap CPI; the DAMM v2 mock loaded in
+      // bankrun has no swap, so the CPI itself fails and the day stays open
+      await expectFailure(
+        () => routeCreatorRemainder(env, creatorPayoutAccount, swap, new BN(1)),
+        "Should have reached the swap CPI",
+        null
+      );
+      const crankState = await fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+      expect(crankState!.dayState).to.equal(1);
+      const quoteVault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      expect(quoteVault!.amount.toString()).to.equal("500000");
+    });
+
+    it("Should leave the remainder unrouted instead of paying quote on auto-close", async () => {
+      const env = await setupCrankEnv({ creatorPayoutMint: NATIVE_MINT, autoCloseOnFinalPage: true });
+      const { investor, usdcAccount } = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+      const creatorBefore = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: false });
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+      const meta = await crankPage(
+        env,
+        { pageIndex: 1, investorsCount: 0, isFinalPage: true },
+        creatorQuoteAccount
+      );
+
+      // The day closes, but only route_creator_remainder can swap into the payout mint
+      const deferred = parseEvents(env, meta).find((event) => event.name === "creatorRemainderDeferred");
+      expect(deferred!.data.unroutedCreatorRemainder.toString()).to.equal("500000");
+      const crankState = await fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+      expect(crankState!.dayState).to.equal(2);
+      expect(crankState!.unroutedCreatorRemainder.toString()).to.equal("500000");
+
+      const creatorAfter = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+      expect(creatorAfter!.amount.toString()).to.equal(creatorBefore!.amount.toString());
+      const quoteVault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      expect(quoteVault!.amount.toString()).to.equal("500000");
+    });
+  });
+
+  describe("Clock monotonicity", () => {
+    // Runs and closes day 1 so the next begin_distribution_day checks the clock against it
+    async function closeFirstDay() {
+      const env = await setupCrankEnv();
+      const deposit = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      await distributeToInvestor(env, deposit.investor, deposit.usdcAccount, new BN(500_000));
+      await routeCreatorRemainder(env, await getCreatorQuoteAccount(env));
+      return env;
+    }
+
+    it("Should reject a new day when the timestamp moved behind the last day start", async () => {
+      const env = await closeFirstDay();
+      await warpTimeBy(env.context, -3600);
+
+      try {
+        await beginDistributionDay(env);
+        assert.fail("Should have rejected the backward clock");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the backward clock");
+        // ClockWentBackward = 6072
+        expect(error.toString()).to.include("0x17b8");
+      }
+
+      const crankState = await fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+      expect(crankState!.currentDay).to.equal(1);
+      expect(crankState!.dayState).to.equal(2);
+    });
+
+    it("Should reject a new day when the slot is behind the last day start", async () => {
+      const env = await closeFirstDay();
+
+      // A skewed timestamp a day ahead, but on a slot before the recorded day start
+      const account = await env.context.banksClient.getAccount(env.crankStatePDA);
+      const crankState = env.program.coder.accounts.decode("crankState", Buffer.from(account!.data));
+      crankState.lastDistributionSlot = crankState.lastDistributionSlot.add(new BN(1_000_000));
+      env.context.setAccount(env.crankStatePDA, {
+        ...account!,
+        data: await env.program.coder.accounts.encode("crankState", crankState),
+      });
+      await warpTimeBy(env.context, 86_400);
+
+      try {
+        await beginDistributionDay(env);
+        assert.fail("Should have rejected the stale slot");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the stale slot");
+        // ClockWentBackward = 6072
+        expect(error.toString()).to.include("0x17b8");
+      }
+    });
+  });
+
+  describe("Deposit window", () => {
+    it("Should only accept deposits inside the window and always allow withdrawals", async () => {
+      const env = await setupCrankEnv();
+      const early = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+
+      const now = Number((await env.context.banksClient.getClock()).unixTimestamp);
+      await updateDistributionConfig(env, {
+        depositWindowStartTs: new BN(now + 1_000),
+        depositWindowEndTs: new BN(now + 2_000),
+      });
+
+      // Before the window
+      try {
+        await createInvestorWithDeposit(env, new BN(1_000_000_000));
+        assert.fail("Should have rejected a deposit before the window");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected a deposit before the window");
+        // DepositWindowClosed = 6074
+        expect(error.toString()).to.include("0x17ba");
+      }
+
+      // During the window
+      await warpTimeBy(env.context, 1_500);
+      const during = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+
+      // After the window (the end is exclusive)
+      await warpTimeBy(env.context, 500);
+      try {
+        await createInvestorWithDeposit(env, new BN(1_000_000_000));
+        assert.fail("Should have rejected a deposit after the window");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected a deposit after the window");
+        expect(error.toString()).to.include("0x17ba");
+      }
+
+      // Withdrawals stay open outside the window
+      await withdrawUsdc(env, early.investor, early.usdcAccount, new BN(1_000_000_000));
+      await withdrawUsdc(env, during.investor, during.usdcAccount, new BN(500_000_000));
+      const vaultStats = await fetchAccount(env.context.banksClient, env.program, "VaultStats", env.vaultStatsPDA);
+      expect(vaultStats!.currentTotalUsdc.toString()).to.equal("500000000");
+
+      // An end before the start is rejected
+      try {
+        await updateDistributionConfig(env, { depositWindowEndTs: new BN(now + 500) });
+        assert.fail("Should have rejected an inverted window");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected an inverted window");
+        // InvalidDepositWindow = 6075
+        expect(error.toString()).to.include("0x17bb");
+      }
+    });
+  });
+
+  describe("Registered quote account", () => {
+    it("Should only pay the quote account registered in the depositor record", async () => {
+      const env = await setupCrankEnv();
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      const record = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DepositorRecord",
+        getDepositorRecordPDA(env, investor.publicKey)
+      );
+      expect(record!.registeredQuoteAccount.toString()).to.equal(usdcAccount.toString());
+
+      // A second USDC account owned by the investor, not yet registered
+      const otherAccount = Keypair.generate().publicKey;
+      setTokenAccount(env.context, otherAccount, USDC_MINT, investor.publicKey, BigInt(0));
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+
+      try {
+        await distributeToInvestor(env, investor, otherAccount, new BN(500_000));
+        assert.fail("Should have rejected the unregistered quote account");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the unregistered quote account");
+        // QuoteAccountNotRegistered = 6076
+        expect(error.toString()).to.include("0x17bc");
+      }
+
+      const meta = await registerQuoteAccount(env, investor, otherAccount);
+      const registered = parseEvents(env, meta).find((e) => e.name === "quoteAccountRegistered");
+      expect(registered!.data.previousQuoteAccount.toString()).to.equal(usdcAccount.toString());
+      expect(registered!.data.quoteAccount.toString()).to.equal(otherAccount.toString());
+
+      await distributeToInvestor(env, investor, otherAccount, new BN(500_000));
+      const other = await getTokenAccount(env.context.banksClient, otherAccount);
+      expect(other!.amount.toString()).to.equal("500000");
+    });
+
+    it("Should reject the program quote vault as the investor quote account", async () => {
+      const env = await setupCrankEnv();
+      const { investor } = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: false });
+
+      try {
+        await distributeToInvestor(env, investor, env.quoteFeeVaultPDA, new BN(500_000));
+        assert.fail("Should have rejected the self-transfer");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the self-transfer");
+        // InvalidQuoteAccount = 6083
+        expect(error.toString()).to.include("0x17c3");
+      }
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.dailyDistributed.toString()).to.equal("0");
+      expect(crankState!.investorsPaidToday).to.equal(0);
+    });
+  });
+
+  describe("Carry-over cap", () => {
+    it("Should sweep carry-over above the cap to the creator", async () => {
+      // Minimum payout above the investor share, so the whole payout becomes dust
+      const env = await setupCrankEnv({
+        minPayoutLamports: new BN(1_000_000),
+        maxCarryOver: new BN(200_000),
+      });
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+      await routeCreatorRemainder(env, creatorQuoteAccount);
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.carryOver.toString()).to.equal("200000");
+
+      const vault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      const creatorAccount = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+      expect(vault!.amount.toString()).to.equal("200000");
+      expect(creatorAccount!.amount.toString()).to.equal("800000");
+    });
+
+    it("Should pay carry-over above the cap to the largest-weight investor", async () => {
+      const env = await setupCrankEnv({
+        minPayoutLamports: new BN(250_000),
+        maxCarryOver: new BN(50_000),
+        carryOverCapPolicy: 1,
+      });
+      const large = await createInvestorWithDeposit(env, new BN(600_000_000));
+      const small = await createInvestorWithDeposit(env, new BN(400_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+
+      // The smaller investor's 200000 share is below the minimum payout and becomes carry-over
+      const largeBefore = await getTokenAccount(env.context.banksClient, large.usdcAccount);
+      await distributeToInvestor(env, large.investor, large.usdcAccount, new BN(500_000));
+      await distributeToInvestor(env, small.investor, small.usdcAccount, new BN(500_000));
+
+      for (const topAccount of [null, small.usdcAccount]) {
+        try {
+          await routeCreatorRemainder(env, creatorQuoteAccount, null, new BN(0), topAccount);
+          assert.fail("Should have rejected the top investor account");
+        } catch (error) {
+          expect(error.toString()).to.not.include("Should have rejected the top investor account");
+          // TopInvestorQuoteAccountMismatch = 6078
+          expect(error.toString()).to.include("0x17be");
+        }
+      }
+
+      await routeCreatorRemainder(env, creatorQuoteAccount, null, new BN(0), large.usdcAccount);
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.carryOver.toString()).to.equal("50000");
+      expect(crankState!.dayTopInvestorQuoteAccount.toString()).to.equal(large.usdcAccount.toString());
+
+      const largeAfter = await getTokenAccount(env.context.banksClient, large.usdcAccount);
+      const creatorAccount = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+      const vault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      expect((largeAfter!.amount - largeBefore!.amount).toString()).to.equal("450000");
+      expect(creatorAccount!.amount.toString()).to.equal("500000");
+      expect(vault!.amount.toString()).to.equal("50000");
+    });
+  });
+
+  describe("Lifetime global stats", () => {
+    it("Should accumulate two closed days into the lifetime totals", async () => {
+      const env = await setupCrankEnv();
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+
+      for (const claimed of [1_000_000, 600_000]) {
+        seedFeeVaults(env, BigInt(claimed));
+        await beginDistributionDay(env);
+        await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+        await distributeToInvestor(env, investor, usdcAccount, new BN(claimed / 2));
+        await routeCreatorRemainder(env, creatorQuoteAccount);
+        await warpTimeBy(env.context, 86_400);
+      }
+
+      const stats = await queryGlobalStats(env);
+      expect(stats.totalDaysDistributed).to.equal(2);
+      expect(stats.lifetimeQuoteClaimed.toString()).to.equal("1600000");
+      expect(stats.lifetimeDistributedToInvestors.toString()).to.equal("800000");
+      expect(stats.lifetimeToCreator.toString()).to.equal("800000");
+
+      const creatorAccount = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+      expect(creatorAccount!.amount.toString()).to.equal(stats.lifetimeToCreator.toString());
+    });
+  });
+
+  describe("Zero-balance records", () => {
+    it("Should skip a fully withdrawn record without affecting reconciliation", async () => {
+      const env = await setupCrankEnv({ autoCloseOnFinalPage: true });
+      const active = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      const withdrawn = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      await withdrawUsdc(env, withdrawn.investor, withdrawn.usdcAccount, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: false });
+      await distributeToInvestor(env, withdrawn.investor, withdrawn.usdcAccount, new BN(500_000));
+
+      const afterSkip = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(afterSkip!.investorsPaidToday).to.equal(0);
+      expect(afterSkip!.snapshotInvestorsPaid).to.equal(0);
+      expect(afterSkip!.carryOver.toString()).to.equal("0");
+
+      await distributeToInvestor(env, active.investor, active.usdcAccount, new BN(500_000));
+      await crankPage(
+        env,
+        { pageIndex: 1, investorsCount: 0, isFinalPage: true },
+        creatorQuoteAccount
+      );
+
+      const closed = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(closed!.dayState).to.equal(2);
+      expect(closed!.investorsPaidToday).to.equal(1);
+
+      // Under the reject policy the keeper is told to drop the record
+      await updateDistributionConfig(env, { zeroBalancePolicy: 1 });
+      await warpTimeBy(env.context, 86_400);
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: false });
+      try {
+        await distributeToInvestor(env, withdrawn.investor, withdrawn.usdcAccount, new BN(500_000));
+        assert.fail("Should have rejected the zero-balance record");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the zero-balance record");
+        // ZeroBalanceRecord = 6081
+        expect(error.toString()).to.include("0x17c1");
+      }
+    });
+  });
+
+  describe("Auto-compound", () => {
+    it("Should add a compounding investor's payout to their locked balance", async () => {
+      const env = await setupCrankEnv();
+      const compounding = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      const regular = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      await setAutoCompound(env, compounding.investor, true);
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: false });
+
+      // The deposit vault is required to compound
+      try {
+        await distributeToInvestor(env, compounding.investor, compounding.usdcAccount, new BN(500_000));
+        assert.fail("Should have required the deposit vault");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have required the deposit vault");
+        // DepositVaultRequired = 6082
+        expect(error.toString()).to.include("0x17c2");
+      }
+
+      const walletBefore = await getTokenAccount(env.context.banksClient, compounding.usdcAccount);
+      const vaultBefore = await getTokenAccount(env.context.banksClient, env.usdcVaultPDA);
+      const regularBefore = await getTokenAccount(env.context.banksClient, regular.usdcAccount);
+      await distributeToInvestor(
+        env,
+        compounding.investor,
+        compounding.usdcAccount,
+        new BN(500_000),
+        null,
+        env.usdcVaultPDA
+      );
+      await distributeToInvestor(env, regular.investor, regular.usdcAccount, new BN(500_000));
+
+      // The compounded weight only counts from the next day, so the later payout is not diluted
+      const regularAfter = await getTokenAccount(env.context.banksClient, regular.usdcAccount);
+      expect((regularAfter!.amount - regularBefore!.amount).toString()).to.equal("250000");
+
+      const record = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DepositorRecord",
+        getDepositorRecordPDA(env, compounding.investor.publicKey)
+      );
+      expect(record!.autoCompound).to.be.true;
+      expect(record!.currentUsdcBalance.toString()).to.equal("1000250000");
+      expect(record!.totalUsdcDeposited.toString()).to.equal("1000000000");
+
+      const vaultStats = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "VaultStats",
+        env.vaultStatsPDA
+      );
+      expect(vaultStats!.currentTotalUsdc.toString()).to.equal("2000250000");
+
+      const walletAfter = await getTokenAccount(env.context.banksClient, compounding.usdcAccount);
+      const vaultAfter = await getTokenAccount(env.context.banksClient, env.usdcVaultPDA);
+      expect(walletAfter!.amount).to.equal(walletBefore!.amount);
+      expect(vaultAfter!.amount - vaultBefore!.amount).to.equal(BigInt(250_000));
+    });
+  });
+
+  describe("Distribution rounds", () => {
+    it("Should run two rounds in one day that share the daily cap", async () => {
+      const env = await setupCrankEnv({
+        roundsPerDay: 2,
+        dailyCapQuoteUnits: new BN(700_000),
+      });
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+      await crankPage(env, { roundIndex: 0, pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+
+      // The second round opens half a day after the day start
+      try {
+        await crankPage(env, { roundIndex: 1, pageIndex: 0, investorsCount: 1, isFinalPage: true });
+        assert.fail("Should have rejected the early round");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the early round");
+        // RoundNotOpen = 6086
+        expect(error.toString()).to.include("0x17c6");
+      }
+
+      await warpTimeBy(env.context, 43_200);
+      try {
+        await crankPage(env, { roundIndex: 2, pageIndex: 0, investorsCount: 1, isFinalPage: true });
+        assert.fail("Should have rejected the round index");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the round index");
+        // InvalidRoundIndex = 6085
+        expect(error.toString()).to.include("0x17c5");
+      }
+
+      // 500_000 of fresh fees arrive on top of the 500_000 retained from the first round
+      seedFeeVaults(env, BigInt(1_000_000));
+      await crankPage(env, { roundIndex: 1, pageIndex: 0, investorsCount: 1, isFinalPage: true });
+
+      const secondRound = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(secondRound!.currentRound).to.equal(1);
+      expect(secondRound!.paginationCursor).to.equal(1);
+      expect(secondRound!.dayClaimedQuote.toString()).to.equal("1500000");
+      expect(secondRound!.roundInvestorFeeQuote.toString()).to.equal("250000");
+      expect(secondRound!.currentPageInvestorFee.toString()).to.equal("250000");
+      expect(secondRound!.dailyDistributed.toString()).to.equal("500000");
+
+      try {
+        await distributeToInvestor(env, investor, usdcAccount, new BN(250_000));
+        assert.fail("Should have hit the daily cap");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have hit the daily cap");
+        // DailyCapExceeded = 6020
+        expect(error.toString()).to.include("0x1784");
+      }
+
+      // The cap is operational and can be raised mid-day
+      await updateDistributionConfig(env, { dailyCapQuoteUnits: new BN(750_000) });
+      const meta = await distributeToInvestor(env, investor, usdcAccount, new BN(250_000));
+      const payout = parseEvents(env, meta).find((e) => e.name === "investorPayout");
+      expect(payout!.data.roundIndex).to.equal(1);
+
+      const capped = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(capped!.dailyDistributed.toString()).to.equal("750000");
+      expect(capped!.snapshotInvestorsPaid).to.equal(1);
+    });
+  });
+
+  describe("Minimum total locked", () => {
+    it("Should route the fees to the creator while the locked total is below the minimum", async () => {
+      const env = await setupCrankEnv({ minTotalLockedToDistribute: new BN(2_000_000_000) });
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+
+      await beginDistributionDay(env);
+      const meta = await crankPage(
+        env,
+        { pageIndex: 0, investorsCount: 1, isFinalPage: false },
+        creatorQuoteAccount
+      );
+
+      const events = parseEvents(env, meta);
+      const belowMin = events.find((event) => event.name === "belowMinLocked");
+      expect(belowMin!.data.totalLocked.toString()).to.equal("1000000000");
+      expect(belowMin!.data.minTotalLockedToDistribute.toString()).to.equal("2000000000");
+      expect(events.some((event) => event.name === "investorPayoutPage")).to.be.false;
+      expect(events.some((event) => event.name === "creatorPayoutDayClosed")).to.be.true;
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.dayState).to.equal(2);
+      const creatorAccount = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+      expect(creatorAccount!.amount.toString()).to.equal("1000000");
+    });
+
+    it("Should run the distribution once the locked total reaches the minimum", async () => {
+      const env = await setupCrankEnv({ minTotalLockedToDistribute: new BN(1_000_000_000) });
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+      const meta = await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      expect(parseEvents(env, meta).some((event) => event.name === "belowMinLocked")).to.be.false;
+
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.dayState).to.equal(1);
+      expect(crankState!.dailyDistributed.toString()).to.equal("500000");
+    });
+  });
+
+  describe("Vault stats correction", () => {
+    async function injectVaultStatsDrift(env: CrankEnv, offset: number) {
+      const account = await env.context.banksClient.getAccount(env.vaultStatsPDA);
+      const vaultStats = env.program.coder.accounts.decode("vaultStats", Buffer.from(account!.data));
+      vaultStats.currentTotalUsdc = vaultStats.currentTotalUsdc.add(new BN(offset));
+      env.context.setAccount(env.vaultStatsPDA, {
+        ...account!,
+        data: await env.program.coder.accounts.encode("vaultStats", vaultStats),
+      });
+    }
+
+    it("Should bring the vault stats back in line after injected drift", async () => {
+      const env = await setupCrankEnv();
+      const first = await createInvestorWithDeposit(env, new BN(600_000_000));
+      const second = await createInvestorWithDeposit(env, new BN(400_000_000));
+
+      await injectVaultStatsDrift(env, 5_000);
+      const meta = await reconcileVaultStats(env, false);
+      const corrected = parseEvents(env, meta).find((event) => event.name === "vaultStatsCorrected");
+      expect(corrected!.data.fromRecords).to.be.false;
+      expect(corrected!.data.oldTotalUsdc.toString()).to.equal("1000005000");
+      expect(corrected!.data.newTotalUsdc.toString()).to.equal("1000000000");
+
+      let vaultStats = await fetchAccount(env.context.banksClient, env.program, "VaultStats", env.vaultStatsPDA);
+      expect(vaultStats!.currentTotalUsdc.toString()).to.equal("1000000000");
+
+      await injectVaultStatsDrift(env, -7_000);
+      await reconcileVaultStats(env, true, [first.investor.publicKey, second.investor.publicKey]);
+      vaultStats = await fetchAccount(env.context.banksClient, env.program, "VaultStats", env.vaultStatsPDA);
+      expect(vaultStats!.currentTotalUsdc.toString()).to.equal("1000000000");
+    });
+
+    it("Should refuse a correction while a day is in progress", async () => {
+      const env = await setupCrankEnv();
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+
+      try {
+        await reconcileVaultStats(env, false);
+        assert.fail("Should have refused the correction");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have refused the correction");
+        // DistributionInProgress = 6051
+        expect(error.toString()).to.include("0x17a3");
+      }
+    });
+  });
+
+  describe("Creator inactivity", () => {
+    async function runDay(env: CrankEnv) {
+      const { investor, usdcAccount } = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+      const meta = await routeCreatorRemainder(env, creatorQuoteAccount);
+      const creatorAccount = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+      return { meta, creatorAccount };
+    }
+
+    it("Should pay the creator within the inactivity window", async () => {
+      const env = await setupCrankEnv({ creatorInactivitySeconds: new BN(7 * 86400) });
+      await warpTimeBy(env.context, 6 * 86400);
+
+      const { meta, creatorAccount } = await runDay(env);
+      expect(parseEvents(env, meta).some((event) => event.name === "creatorInactivityTriggered")).to.be.false;
+      expect(creatorAccount!.amount.toString()).to.equal("500000");
+
+      const config = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DistributionConfig",
+        env.distributionConfigPDA
+      );
+      const clock = await env.context.banksClient.getClock();
+      expect(config!.lastCreatorPayoutTs.toString()).to.equal(clock.unixTimestamp.toString());
+    });
+
+    it("Should route the remainder to investors once the creator is inactive", async () => {
+      const env = await setupCrankEnv({ creatorInactivitySeconds: new BN(7 * 86400) });
+      await warpTimeBy(env.context, 8 * 86400);
+
+      const { meta, creatorAccount } = await runDay(env);
+      const triggered = parseEvents(env, meta).find((event) => event.name === "creatorInactivityTriggered");
+      expect(triggered!.data.remainderToInvestors.toString()).to.equal("500000");
+      expect(creatorAccount!.amount.toString()).to.equal("0");
+
+      // The forfeited remainder is carried over to the next day's investor payouts
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.dayState).to.equal(2);
+      expect(crankState!.carryOver.toString()).to.equal("500000");
+    });
+  });
+
+  describe("Batch withdrawal", () => {
+    it("Should withdraw every approved investor in one transaction", async () => {
+      const env = await setupCrankEnv();
+      const amounts = [100_000_000, 200_000_000, 300_000_000];
+      const investors = [];
+      for (const amount of amounts) {
+        const created = await createInvestorWithDeposit(env, new BN(amount));
+        await setBatchWithdrawApproval(env, created.investor, true);
+        investors.push(created);
+      }
+      const balancesBefore = [];
+      for (const { usdcAccount } of investors) {
+        balancesBefore.push((await getTokenAccount(env.context.banksClient, usdcAccount))!.amount);
+      }
+
+      const meta = await batchWithdraw(
+        env,
+        investors.map(({ investor, usdcAccount }) => ({
+          investor: investor.publicKey,
+          quoteAccount: usdcAccount,
+        }))
+      );
+
+      for (const [index, { investor, usdcAccount }] of investors.entries()) {
+        const account = await getTokenAccount(env.context.banksClient, usdcAccount);
+        expect((account!.amount - balancesBefore[index]).toString()).to.equal(amounts[index].toString());
+
+        const record = await fetchAccount(
+          env.context.banksClient,
+          env.program,
+          "DepositorRecord",
+          getDepositorRecordPDA(env, investor.publicKey)
+        );
+        expect(record!.currentUsdcBalance.toString()).to.equal("0");
+        expect(record!.totalUsdcWithdrawn.toString()).to.equal(amounts[index].toString());
+        expect(record!.withdrawalCount).to.equal(1);
+        expect(record!.batchWithdrawApproved).to.be.false;
+      }
+
+      const vaultStats = await fetchAccount(env.context.banksClient, env.program, "VaultStats", env.vaultStatsPDA);
+      expect(vaultStats!.currentTotalUsdc.toString()).to.equal("0");
+      expect(vaultStats!.totalUsdcWithdrawn.toString()).to.equal("600000000");
+      expect(vaultStats!.currentTotalWeightedUsdc.toString()).to.equal("0");
+      expect(vaultStats!.depositorCount).to.equal(0);
+
+      const batch = parseEvents(env, meta).find((event) => event.name === "batchWithdrawal");
+      expect(batch!.data.investorsCount).to.equal(3);
+      expect(batch!.data.totalUsdcWithdrawn.toString()).to.equal("600000000");
+    });
+
+    it("Should reject an investor who has not approved", async () => {
+      const env = await setupCrankEnv();
+      const approved = await createInvestorWithDeposit(env, new BN(100_000_000));
+      const unapproved = await createInvestorWithDeposit(env, new BN(100_000_000));
+      await setBatchWithdrawApproval(env, approved.investor, true);
+
+      try {
+        await batchWithdraw(env, [
+          { investor: approved.investor.publicKey, quoteAccount: approved.usdcAccount },
+          { investor: unapproved.investor.publicKey, quoteAccount: unapproved.usdcAccount },
+        ]);
+        assert.fail("Should have rejected the unapproved investor");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the unapproved investor");
+        // BatchWithdrawNotApproved = 6090
+        expect(error.toString()).to.include("0x17ca");
+      }
+    });
+  });
+
+  describe("Creator share floor", () => {
+    it("Should cap a high f_locked to preserve the creator floor", async () => {
+      const env = await setupCrankEnv({ investorFeeShareBps: 9000, creatorMinShareBps: 2000 });
+      // Y0 fully locked: f_locked is 10000 bps
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: false });
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.dayInvestorFeeQuote.toString()).to.equal("800000");
+    });
+
+    it("Should reject a floor that takes the whole claim from a non-zero investor share", async () => {
+      const env = await setupCrankEnv();
+      try {
+        await updateDistributionConfig(env, { creatorMinShareBps: 10000 });
+        assert.fail("Should have rejected the creator floor");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the creator floor");
+        // InvalidCreatorMinShare = 6091
+        expect(error.toString()).to.include("0x17cb");
+      }
+    });
+  });
+
+  describe("Investor fee guard", () => {
+    it("Should reject a total investor fee that differs from the crank's", async () => {
+      const env = await setupCrankEnv();
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.currentPageInvestorFee.toString()).to.equal("500000");
+
+      const balanceBefore = await getTokenAccount(env.context.banksClient, usdcAccount);
+      for (const totalInvestorFee of [1_000_000, 499_999]) {
+        try {
+          await distributeToInvestor(env, investor, usdcAccount, new BN(totalInvestorFee));
+          assert.fail("Should have rejected the mismatched fee");
+        } catch (error) {
+          expect(error.toString()).to.not.include("Should have rejected the mismatched fee");
+          // FeeAmountMismatch = 6092
+          expect(error.toString()).to.include("0x17cc");
+        }
+      }
+
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+      const balanceAfter = await getTokenAccount(env.context.banksClient, usdcAccount);
+      expect((balanceAfter!.amount - balanceBefore!.amount).toString()).to.equal("500000");
+    });
+  });
+
+  describe("Deposit mint", () => {
+    it("Should reject deposits of a mint other than the configured deposit mint", async () => {
+      const env = await setupCrankEnv();
+      const investor = Keypair.generate();
+      await fundSol(env.context.banksClient, env.admin, [investor.publicKey]);
+
+      // A 6-decimal SPL token that is not the configured deposit mint
+      const otherMint = Keypair.generate().publicKey;
+      env.context.setAccount(otherMint, {
+        executable: false,
+        owner: TOKEN_PROGRAM_ID,
+        lamports: 1_000_000_000,
+        data: createMintData(otherMint, 6, env.admin.publicKey),
+      });
+      const otherAccount = Keypair.generate().publicKey;
+      setTokenAccount(env.context, otherAccount, otherMint, investor.publicKey, BigInt(1_000_000_000));
+
+      try {
+        await depositUsdc(env, investor, otherAccount, new BN(1_000_000_000), otherMint);
+        assert.fail("Should have rejected the deposit mint");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the deposit mint");
+        // WrongDepositMint = 6093
+        expect(error.toString()).to.include("0x17cd");
+      }
+
+      // No vault was created for the rejected mint
+      const [otherVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("deposit_vault"), otherMint.toBuffer()],
+        env.program.programId
+      );
+      expect(await env.context.banksClient.getAccount(otherVault)).to.be.null;
+    });
+  });
+
+  describe("Investor weight query", () => {
+    it("Should return the investor's share of the locked total", async () => {
+      const env = await setupCrankEnv();
+      const small = await createInvestorWithDeposit(env, new BN(300_000_000));
+      const large = await createInvestorWithDeposit(env, new BN(700_000_123));
+
+      const vaultStats = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "VaultStats",
+        env.vaultStatsPDA
+      );
+      for (const { investor } of [small, large]) {
+        const record = await fetchAccount(
+          env.context.banksClient,
+          env.program,
+          "DepositorRecord",
+          getDepositorRecordPDA(env, investor.publicKey)
+        );
+        const expected = record!.currentUsdcBalance
+          .muln(10000)
+          .div(vaultStats!.currentTotalUsdc);
+
+        const weight = await queryInvestorWeight(env, investor.publicKey);
+        expect(weight.investor.toString()).to.equal(investor.publicKey.toString());
+        expect(weight.currentUsdcBalance.toString()).to.equal(record!.currentUsdcBalance.toString());
+        expect(weight.weightBps.toString()).to.equal(expected.toString());
+      }
+
+      // 300 / 1000.000123 USDC = 2999 bps
+      const weight = await queryInvestorWeight(env, small.investor.publicKey);
+      expect(weight.weightBps.toString()).to.equal("2999");
+    });
+
+    it("Should return a zero weight when nothing is locked", async () => {
+      const env = await setupCrankEnv();
+      const { investor, usdcAccount } = await createInvestorWithDeposit(env, new BN(1_000_000));
+      await withdrawUsdc(env, investor, usdcAccount, new BN(1_000_000));
+
+      const weight = await queryInvestorWeight(env, investor.publicKey);
+      expect(weight.totalWeightedUsdc.toString()).to.equal("0");
+      expect(weight.weightBps.toString()).to.equal("0");
+    });
+  });
+
+  describe("Scaled dust threshold", () => {
+    it("Should derive the dust threshold from the investor count", async () => {
+      // The fixed minimum would turn every payout into dust
+      const env = await setupCrankEnv({
+        minPayoutLamports: new BN(1_000_000),
+        dustThresholdK: 2,
+      });
+      const first = await createInvestorWithDeposit(env, new BN(500_000_000));
+      const second = await createInvestorWithDeposit(env, new BN(500_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      // investor_fee = 500_000 over 2 investors: threshold = 500_000 / (2 * 2) = 125_000
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+
+      for (const { investor, usdcAccount } of [first, second]) {
+        const meta = await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+        const payout = parseEvents(env, meta).find((e) => e.name === "investorPayout");
+        expect(payout!.data.minPayout.toString()).to.equal("125000");
+        expect(payout!.data.actualPayout.toString()).to.equal("250000");
+        expect(payout!.data.dust.toString()).to.equal("0");
+      }
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.carryOver.toString()).to.equal("0");
+    });
+  });
+
+  describe("Unrouted creator remainder", () => {
+    it("Should not let a skipped creator routing inflate the next day's investor share", async () => {
+      const env = await setupCrankEnv();
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      // Day 1 pays investors but is force-closed without reaching the creator
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+      await warpTimeBy(env.context, 172_800);
+      const closeMeta = await forceCloseDay(env, null);
+
+      const deferred = parseEvents(env, closeMeta).find(
+        (e) => e.name === "creatorRemainderDeferred"
+      );
+      expect(deferred!.data.unroutedCreatorRemainder.toString()).to.equal("500000");
+      const closedState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(closedState!.dayState).to.equal(2);
+      expect(closedState!.unroutedCreatorRemainder.toString()).to.equal("500000");
+
+      // Day 2: 1 USDC of new fees on top of the 0.5 USDC left in the vault
+      seedFeeVaults(env, BigInt(1_500_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+
+      const day2State = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(day2State!.dayClaimedQuote.toString()).to.equal("1000000");
+      expect(day2State!.roundInvestorFeeQuote.toString()).to.equal("500000");
+
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+
+      // The creator receives both days' remainders once routing succeeds
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+      const creatorBefore = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+      await routeCreatorRemainder(env, creatorQuoteAccount);
+      const creatorAfter = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+      expect((creatorAfter!.amount - creatorBefore!.amount).toString()).to.equal("1000000");
+
+      const routedState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(routedState!.unroutedCreatorRemainder.toString()).to.equal("0");
+    });
+
+    it("Should still require the creator account outside a force close", async () => {
+      const env = await setupCrankEnv();
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+
+      try {
+        await routeCreatorRemainder(env, null);
+        assert.fail("Should have required the creator quote account");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have required the creator quote account");
+        // CreatorQuoteAccountRequired = 6030
+        expect(error.toString()).to.include("0x178e");
+      }
+    });
+  });
+
+  describe("No investors", () => {
+    it("Should route the fees to the creator without paginating when nobody is depositing", async () => {
+      const env = await createCrankEnv();
+      await bootstrapProgram(env);
+      seedFeeVaults(env, BigInt(1_000_000));
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+
+      await beginDistributionDay(env);
+      const meta = await crankPage(
+        env,
+        { pageIndex: 0, investorsCount: 0, isFinalPage: false },
+        creatorQuoteAccount
+      );
+
+      const events = parseEvents(env, meta);
+      const noInvestors = events.find((event) => event.name === "noInvestorsToDistribute");
+      expect(noInvestors!.data.day).to.equal(1);
+      expect(noInvestors!.data.quoteFeesAvailable.toString()).to.equal("1000000");
+      expect(events.some((event) => event.name === "investorPayoutPage")).to.be.false;
+      expect(events.some((event) => event.name === "creatorPayoutDayClosed")).to.be.true;
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.dayState).to.equal(2);
+      expect(crankState!.paginationCursor).to.equal(0);
+      expect(crankState!.dayInvestorFeeQuote.toString()).to.equal("0");
+      const creatorAccount = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+      expect(creatorAccount!.amount.toString()).to.equal("1000000");
+    });
+  });
+
+  describe("Fixed page size", () => {
+    it("Should accept full batches and a short final page", async () => {
+      const env = await setupCrankEnv({ fixedPageSize: true });
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      // DISTRIBUTION_BATCH_SIZE = 10
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 10, isFinalPage: false });
+      await crankPage(env, { pageIndex: 1, investorsCount: 10, isFinalPage: false });
+      await crankPage(env, { pageIndex: 2, investorsCount: 3, isFinalPage: true });
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.paginationCursor).to.equal(3);
+      expect(crankState!.investorsProcessedToday).to.equal(23);
+    });
+
+    it("Should reject an undersized non-final page", async () => {
+      const env = await setupCrankEnv({ fixedPageSize: true });
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+      try {
+        await crankPage(env, { pageIndex: 0, investorsCount: 9, isFinalPage: false });
+        assert.fail("Should have rejected the undersized page");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the undersized page");
+        // InvalidPageSize = 6094
+        expect(error.toString()).to.include("0x17ce");
+      }
+
+      // Turning the requirement off accepts the same page
+      await updateDistributionConfig(env, { fixedPageSize: false });
+      await crankPage(env, { pageIndex: 0, investorsCount: 9, isFinalPage: false });
+    });
+  });
+
+  describe("Rescue tokens", () => {
+    it("Should return a stray fee_collector token account to the admin", async () => {
+      const env = await setupCrankEnv();
+      const stray = Keypair.generate().publicKey;
+      setTokenAccount(env.context, stray, USDC_MINT, env.feeCollectorPDA, BigInt(7_500));
+      const adminAccount = await getOrCreateAta(
+        env.context.banksClient,
+        env.admin,
+        USDC_MINT,
+        env.admin.publicKey
+      );
+      const adminBefore = await getTokenAccount(env.context.banksClient, adminAccount);
+
+      const meta = await rescueTokens(env, stray, adminAccount);
+
+      const rescued = parseEvents(env, meta).find((event) => event.name === "tokensRescued");
+      expect(rescued!.data.amount.toString()).to.equal("7500");
+      const adminAfter = await getTokenAccount(env.context.banksClient, adminAccount);
+      expect((adminAfter!.amount - adminBefore!.amount).toString()).to.equal("7500");
+      const strayAfter = await getTokenAccount(env.context.banksClient, stray);
+      expect(strayAfter!.amount.toString()).to.equal("0");
+    });
+
+    it("Should refuse to touch the canonical fee and deposit vaults", async () => {
+      const env = await setupCrankEnv();
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      const adminAccount = await getOrCreateAta(
+        env.context.banksClient,
+        env.admin,
+        USDC_MINT,
+        env.admin.publicKey
+      );
+
+      for (const vault of [env.quoteFeeVaultPDA, env.usdcVaultPDA]) {
+        try {
+          await rescueTokens(env, vault, adminAccount);
+          assert.fail("Should have protected the canonical vault");
+        } catch (error) {
+          expect(error.toString()).to.not.include("Should have protected the canonical vault");
+          // CanonicalVaultProtected = 6095
+          expect(error.toString()).to.include("0x17cf");
+        }
+      }
+
+      const quoteVault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      expect(quoteVault!.amount.toString()).to.equal("1000000");
+      const depositVault = await getTokenAccount(env.context.banksClient, env.usdcVaultPDA);
+      expect(depositVault!.amount.toString()).to.equal("1000000000");
+    });
+  });
+
+  describe("Program end", () => {
+    it("Should let investors exit a locked day once the program has ended", async () => {
+      const env = await setupCrankEnv();
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      // Beginning the day locks withdrawals
+      await beginDistributionDay(env);
+      try {
+        await withdrawUsdc(env, investor, usdcAccount, new BN(1_000_000_000));
+        assert.fail("Should have been locked");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have been locked");
+        // DepositsLocked = 6061
+        expect(error.toString()).to.include("0x17ad");
+      }
+
+      await updateDistributionConfig(env, { programEnded: true });
+      const before = await getTokenAccount(env.context.banksClient, usdcAccount);
+      await withdrawUsdc(env, investor, usdcAccount, new BN(1_000_000_000));
+      const after = await getTokenAccount(env.context.banksClient, usdcAccount);
+      expect((after!.amount - before!.amount).toString()).to.equal("1000000000");
+
+      const record = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DepositorRecord",
+        getDepositorRecordPDA(env, investor.publicKey)
+      );
+      expect(record!.currentUsdcBalance.toString()).to.equal("0");
+    });
+
+    it("Should reject deposits and keep the flag set", async () => {
+      const env = await setupCrankEnv();
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      await updateDistributionConfig(env, { programEnded: true });
+
+      try {
+        await depositUsdc(env, investor, usdcAccount, new BN(1_000_000));
+        assert.fail("Should have rejected the deposit");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the deposit");
+        // ProgramEnded = 6096
+        expect(error.toString()).to.include("0x17d0");
+      }
+
+      try {
+        await updateDistributionConfig(env, { programEnded: false });
+        assert.fail("Should have kept the program ended");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have kept the program ended");
+        // ProgramEnded = 6096
+        expect(error.toString()).to.include("0x17d0");
+      }
+    });
+  });
+  describe("Day close callback", () => {
+    async function runDay(env: CrankEnv, callbackProgram: PublicKey | null) {
+      const { investor, usdcAccount } = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+      return routeCreatorRemainder(env, creatorQuoteAccount, null, new BN(0), null, callbackProgram);
+    }
+
+    it("Should notify the configured program when the day closes", async () => {
+      const env = await setupCrankEnv({ callbackProgram: MOCK_DAY_CALLBACK_PROGRAM_ID });
+      const meta = await runDay(env, MOCK_DAY_CALLBACK_PROGRAM_ID);
+
+      expect(meta.logMessages).to.include(`Program ${MOCK_DAY_CALLBACK_PROGRAM_ID.toBase58()} invoke [2]`);
+      expect(
+        meta.logMessages.some((log) => log.includes("distributed 500000, routed 500000, investors 1"))
+      ).to.be.true;
+
+      const event = parseEvents(env, meta).find((event) => event.name === "dayCloseCallbackInvoked");
+      expect(event).to.not.be.undefined;
+      expect(event!.data.callbackProgram.toString()).to.equal(MOCK_DAY_CALLBACK_PROGRAM_ID.toString());
+      expect(event!.data.totalDistributedToInvestors.toString()).to.equal("500000");
+      expect(event!.data.quoteRoutedAtClose.toString()).to.equal("500000");
+    });
+
+    it("Should close the day without a notification when the program is not passed", async () => {
+      const env = await setupCrankEnv({ callbackProgram: MOCK_DAY_CALLBACK_PROGRAM_ID });
+      const meta = await runDay(env, null);
+
+      expect(parseEvents(env, meta).some((event) => event.name === "dayCloseCallbackInvoked")).to.be.false;
+      const crankState = await fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+      expect(crankState!.dayState).to.equal(2);
+    });
+
+    it("Should skip the notification when no callback is configured", async () => {
+      const env = await setupCrankEnv();
+      const meta = await runDay(env, null);
+
+      expect(meta.logMessages).to.not.include(`Program ${MOCK_DAY_CALLBACK_PROGRAM_ID.toBase58()} invoke [2]`);
+      expect(parseEvents(env, meta).some((event) => event.name === "dayCloseCallbackInvoked")).to.be.false;
+    });
+
+    it("Should reject a program other than the configured callback", async () => {
+      const env = await setupCrankEnv({ callbackProgram: MOCK_DAY_CALLBACK_PROGRAM_ID });
+
+      try {
+        await runDay(env, TOKEN_PROGRAM_ID);
+        assert.fail("Should have rejected the callback program");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the callback program");
+        // CallbackProgramMismatch = 6098
+        expect(error.toString()).to.include("0x17d2");
+      }
+    });
+  });
+  describe("Concentration limit", () => {
+    it("Should cap a whale's deposit at the maximum investor share", async () => {
+      const env = await setupCrankEnv();
+      await createInvestorWithDeposit(env, new BN(2_000_000_000));
+      await createInvestorWithDeposit(env, new BN(2_000_000_000));
+      await updateDistributionConfig(env, { maxInvestorShareBps: 2_000 });
+
+      const whale = Keypair.generate();
+      await fundSol(env.context.banksClient, env.admin, [whale.publicKey]);
+      await fundUsdc(env.context.banksClient, [whale.publicKey]);
+      const usdcAccount = await getOrCreateAta(env.context.banksClient, env.admin, USDC_MINT, whale.publicKey);
+
+      // 1.1B of 5.1B is 2156 bps
+      try {
+        await depositUsdc(env, whale, usdcAccount, new BN(1_100_000_000));
+        assert.fail("Should have capped the whale's deposit");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have capped the whale's deposit");
+        // ConcentrationLimitExceeded = 6099
+        expect(error.toString()).to.include("0x17d3");
+      }
+
+      // Exactly 1B of 5B is the 2000 bps limit
+      await depositUsdc(env, whale, usdcAccount, new BN(1_000_000_000));
+      const record = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DepositorRecord",
+        getDepositorRecordPDA(env, whale.publicKey)
+      );
+      expect(record!.currentUsdcBalance.toString()).to.equal("1000000000");
+
+      try {
+        await depositUsdc(env, whale, usdcAccount, new BN(10_000_000));
+        assert.fail("Should have rejected a deposit past the limit");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected a deposit past the limit");
+        // ConcentrationLimitExceeded = 6099
+        expect(error.toString()).to.include("0x17d3");
+      }
+    });
+
+    it("Should accept any deposit without a cap", async () => {
+      const env = await setupCrankEnv();
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      const { investor } = await createInvestorWithDeposit(env, new BN(9_000_000_000));
+
+      const record = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DepositorRecord",
+        getDepositorRecordPDA(env, investor.publicKey)
+      );
+      expect(record!.currentUsdcBalance.toString()).to.equal("9000000000");
+    });
+  });
+  describe("Simulate day", () => {
+    it("Should predict the payouts of an actual run without changing state", async () => {
+      const env = await setupCrankEnv();
+      const small = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      const large = await createInvestorWithDeposit(env, new BN(3_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+
+      const simulated = await simulateDay(env, new BN(1_000_000), [
+        small.investor.publicKey,
+        large.investor.publicKey,
+      ]);
+      expect(simulated.day).to.equal(1);
+      expect(simulated.eligibleInvestorShareBps).to.equal(5000);
+      expect(simulated.investorFeeQuote.toString()).to.equal("500000");
+      expect(simulated.payouts.map((payout) => payout.payout.toString())).to.deep.equal(["125000", "375000"]);
+      expect(simulated.totalToInvestors.toString()).to.equal("500000");
+      expect(simulated.totalDust.toString()).to.equal("0");
+      expect(simulated.creatorRemainder.toString()).to.equal("500000");
+
+      // The simulation leaves the crank untouched
+      const crankState = await fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+      expect(crankState).to.be.null;
+
+      const balancesBefore = await Promise.all(
+        [small, large].map(async ({ usdcAccount }) =>
+          (await getTokenAccount(env.context.banksClient, usdcAccount))!.amount
+        )
+      );
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+      await distributeToInvestor(env, small.investor, small.usdcAccount, simulated.investorFeeQuote);
+      await distributeToInvestor(env, large.investor, large.usdcAccount, simulated.investorFeeQuote);
+      await routeCreatorRemainder(env, creatorQuoteAccount);
+
+      const paid = await Promise.all(
+        [small, large].map(async ({ usdcAccount }, index) =>
+          ((await getTokenAccount(env.context.banksClient, usdcAccount))!.amount - balancesBefore[index]).toString()
+        )
+      );
+      expect(paid).to.deep.equal(simulated.payouts.map((payout) => payout.payout.toString()));
+      const creatorAccount = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+      expect(creatorAccount!.amount.toString()).to.equal(simulated.creatorRemainder.toString());
+    });
+
+    it("Should reject a duplicated depositor record", async () => {
+      const env = await setupCrankEnv();
+      const { investor } = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+
+      const tx = await env.program.methods
+        .simulateDay({ claimedQuote: new BN(1_000_000) })
+        .accountsStrict({
+          distributionConfig: env.distributionConfigPDA,
+          vaultStats: env.vaultStatsPDA,
+          crankState: env.crankStatePDA,
+        })
+        .remainingAccounts([
+          { pubkey: getDepositorRecordPDA(env, investor.publicKey), isSigner: false, isWritable: false },
+          { pubkey: getDepositorRecordPDA(env, investor.publicKey), isSigner: false, isWritable: false },
+        ])
+        .transaction();
+
+      try {
+        await sendTx(env.context, tx, [env.admin]);
+        assert.fail("Should have rejected the duplicate record");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the duplicate record");
+        // InvalidDepositorRecord = 6053
+        expect(error.toString()).to.include("0x17a5");
+      }
+    });
+  });
+
+  describe("Keeper reward", () => {
+    const KEEPER_REWARD = 50_000;
+    // Lamport fee bankrun charges the payer for a single-signature transaction
+    const TX_FEE = 5_000;
+
+    it("Should pay the crank payer the SOL reward from the keeper incentive account", async () => {
+      const env = await setupCrankEnv({ keeperRewardLamports: new BN(KEEPER_REWARD) });
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      const keeperIncentive = getKeeperIncentivePDA(env);
+      await fundKeeperIncentive(env, env.admin, new BN(1_000_000_000));
+      const incentiveBefore = await getBalance(env.context.banksClient, keeperIncentive);
+
+      await beginDistributionDay(env);
+      const payerBefore = await getBalance(env.context.banksClient, env.admin.publicKey);
+      const meta = await crankPage(
+        env,
+        { pageIndex: 0, investorsCount: 1, isFinalPage: true },
+        null,
+        [],
+        null,
+        keeperIncentive
+      );
+
+      const payerAfter = await getBalance(env.context.banksClient, env.admin.publicKey);
+      expect((BigInt(payerAfter) - BigInt(payerBefore)).toString()).to.equal(String(KEEPER_REWARD - TX_FEE));
+      const incentiveAfter = await getBalance(env.context.banksClient, keeperIncentive);
+      expect((BigInt(incentiveBefore) - BigInt(incentiveAfter)).toString()).to.equal(String(KEEPER_REWARD));
+
+      const event = parseEvents(env, meta).find((event) => event.name === "keeperRewardPaid");
+      expect(event).to.not.be.undefined;
+      expect(event!.data.keeper.toString()).to.equal(env.admin.publicKey.toString());
+      expect(event!.data.amount.toString()).to.equal(String(KEEPER_REWARD));
+    });
+
+    it("Should crank without a reward when the keeper incentive account is not passed", async () => {
+      const env = await setupCrankEnv({ keeperRewardLamports: new BN(KEEPER_REWARD) });
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+      const meta = await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+
+      expect(parseEvents(env, meta).some((event) => event.name === "keeperRewardPaid")).to.be.false;
+      const crankState = await fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+      expect(crankState!.investorsProcessedToday).to.equal(1);
+    });
+
+    it("Should skip the reward when the keeper incentive account is underfunded", async () => {
+      const env = await setupCrankEnv({ keeperRewardLamports: new BN(KEEPER_REWARD) });
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      // Funded, but not above the rent-exempt minimum by the reward
+      const keeperIncentive = getKeeperIncentivePDA(env);
+      await fundKeeperIncentive(env, env.admin, new BN(900_000));
+
+      await beginDistributionDay(env);
+      const meta = await crankPage(
+        env,
+        { pageIndex: 0, investorsCount: 1, isFinalPage: true },
+        null,
+        [],
+        null,
+        keeperIncentive
+      );
+
+      expect(parseEvents(env, meta).some((event) => event.name === "keeperRewardPaid")).to.be.false;
+      const incentiveBalance = await getBalance(env.context.banksClient, keeperIncentive);
+      expect(incentiveBalance.toString()).to.equal("900000");
+    });
+
+    it("Should not reward empty pages or pages past the expected investors", async () => {
+      const env = await setupCrankEnv({ keeperRewardLamports: new BN(KEEPER_REWARD) });
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      const keeperIncentive = getKeeperIncentivePDA(env);
+      await fundKeeperIncentive(env, env.admin, new BN(1_000_000_000));
+
+      await beginDistributionDay(env);
+      const rewarded = [];
+      for (const page of [
+        { pageIndex: 0, investorsCount: 1, isFinalPage: false },
+        { pageIndex: 1, investorsCount: 0, isFinalPage: false },
+        { pageIndex: 2, investorsCount: 1, isFinalPage: false },
+      ]) {
+        const meta = await crankPage(env, page, null, [], null, keeperIncentive);
+        rewarded.push(parseEvents(env, meta).some((event) => event.name === "keeperRewardPaid"));
+      }
+
+      // One expected investor fits on page 0
+      expect(rewarded).to.deep.equal([true, false, false]);
+    });
+  });
+
+  describe("Earnings lock", () => {
+    const LOCK_SECONDS = 7 * 86_400;
+
+    async function compoundOneDay(env: CrankEnv) {
+      const { investor, usdcAccount } = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      await setAutoCompound(env, investor, true);
+      seedFeeVaults(env, BigInt(1_000_000));
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000), null, env.usdcVaultPDA);
+      await routeCreatorRemainder(env, creatorQuoteAccount);
+      return { investor, usdcAccount };
+    }
+
+    async function fetchRecord(env: CrankEnv, investor: Keypair) {
+      return fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DepositorRecord",
+        getDepositorRecordPDA(env, investor.publicKey)
+      );
+    }
+
+    it("Should track deposits as principal and compounded payouts as earnings", async () => {
+      const env = await setupCrankEnv();
+      const { investor, usdcAccount } = await compoundOneDay(env);
+
+      const record = await fetchRecord(env, investor);
+      expect(record!.principalBalance.toString()).to.equal("1000000000");
+      expect(record!.earningsBalance.toString()).to.equal("500000");
+      expect(record!.currentUsdcBalance.toString()).to.equal("1000500000");
+
+      // Without a lock a withdrawal draws principal first, then earnings
+      await withdrawUsdc(env, investor, usdcAccount, new BN(1_000_200_000));
+      const afterWithdrawal = await fetchRecord(env, investor);
+      expect(afterWithdrawal!.principalBalance.toString()).to.equal("0");
+      expect(afterWithdrawal!.earningsBalance.toString()).to.equal("300000");
+    });
+
+    it("Should limit withdrawals to principal until the earnings unlock", async () => {
+      const env = await setupCrankEnv();
+      const now = Number((await env.context.banksClient.getClock()).unixTimestamp);
+      await updateDistributionConfig(env, { earningsUnlockTs: new BN(now + LOCK_SECONDS) });
+      const { investor, usdcAccount } = await compoundOneDay(env);
+
+      try {
+        await withdrawUsdc(env, investor, usdcAccount, new BN(1_000_000_001));
+        assert.fail("Should have kept the earnings locked");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have kept the earnings locked");
+        // EarningsLocked = 6103
+        expect(error.toString()).to.include("0x17d7");
+      }
+
+      // The principal stays withdrawable
+      await withdrawUsdc(env, investor, usdcAccount, new BN(1_000_000_000));
+      const locked = await fetchRecord(env, investor);
+      expect(locked!.principalBalance.toString()).to.equal("0");
+      expect(locked!.earningsBalance.toString()).to.equal("500000");
+
+      await warpTimeBy(env.context, LOCK_SECONDS);
+      await withdrawUsdc(env, investor, usdcAccount, new BN(500_000));
+      const unlocked = await fetchRecord(env, investor);
+      expect(unlocked!.earningsBalance.toString()).to.equal("0");
+      expect(unlocked!.currentUsdcBalance.toString()).to.equal("0");
+    });
+  });
+
+  describe("Deposit age brackets", () => {
+    const THIRTY_DAYS = 30 * 86_400;
+    const UNUSED_BRACKET = { minAgeSeconds: new BN(0), multiplierBps: 0 };
+
+    it("Should pay an older investor proportionally more than a newer one with an equal balance", async () => {
+      const env = await setupCrankEnv({
+        ageBrackets: [
+          { minAgeSeconds: new BN(THIRTY_DAYS), multiplierBps: 15_000 },
+          UNUSED_BRACKET,
+          UNUSED_BRACKET,
+          UNUSED_BRACKET,
+        ],
+      });
+      const older = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      await warpTimeBy(env.context, THIRTY_DAYS + 86_400);
+      const newer = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+      const [olderBefore, newerBefore] = await Promise.all(
+        [older, newer].map(async ({ usdcAccount }) =>
+          (await getTokenAccount(env.context.banksClient, usdcAccount))!.amount
+        )
+      );
+      await distributeToInvestor(env, older.investor, older.usdcAccount, new BN(500_000));
+      await distributeToInvestor(env, newer.investor, newer.usdcAccount, new BN(500_000));
+
+      // 1.5x is the largest multiplier, so the older investor keeps their full pro-rata share
+      // and the newer one receives 1x / 1.5x of theirs
+      const olderAccount = await getTokenAccount(env.context.banksClient, older.usdcAccount);
+      const newerAccount = await getTokenAccount(env.context.banksClient, newer.usdcAccount);
+      const olderPaid = olderAccount!.amount - olderBefore;
+      const newerPaid = newerAccount!.amount - newerBefore;
+      expect(olderPaid.toString()).to.equal("250000");
+      expect(newerPaid.toString()).to.equal("166650");
+      expect(Number(olderPaid) / Number(newerPaid)).to.be.closeTo(1.5, 0.001);
+
+      // The share withheld from the newer investor stays in the vault as carry-over
+      const crankState = await fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+      expect(crankState!.carryOver.toString()).to.equal("83350");
+      expect(crankState!.dailyDistributed.toString()).to.equal("416650");
+    });
+
+    it("Should reject age brackets out of ascending order", async () => {
+      const env = await setupCrankEnv();
+
+      try {
+        await updateDistributionConfig(env, {
+          ageBrackets: [
+            { minAgeSeconds: new BN(THIRTY_DAYS), multiplierBps: 15_000 },
+            { minAgeSeconds: new BN(86_400), multiplierBps: 12_000 },
+            UNUSED_BRACKET,
+            UNUSED_BRACKET,
+          ],
+        });
+        assert.fail("Should have rejected unordered brackets");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected unordered brackets");
+        // InvalidAgeBrackets = 6104
+        expect(error.toString()).to.include("0x17d8");
+      }
+    });
+  });
+
+  describe("Distribution rate", () => {
+    it("Should release half of the accumulated quote each day over three days", async () => {
+      const env = await setupCrankEnv({ distributionRateBps: 5000 });
+      const { investor, usdcAccount } = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      const creatorQuote = await getCreatorQuoteAccount(env);
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      // No new fees after day 1: each day releases half of what is still buffered
+      const expectedPools = ["250000", "125000", "62500"];
+      const expectedBuffers = ["500000", "250000", "125000"];
+      for (let day = 0; day < 3; day++) {
+        if (day > 0) {
+          await warpTimeBy(env.context, 86_400);
+        }
+        await beginDistributionDay(env);
+        await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+
+        const crankState = await fetchAccount(
+          env.context.banksClient,
+          env.program,
+          "CrankState",
+          env.crankStatePDA
+        );
+        expect(crankState!.currentPageInvestorFee.toString()).to.equal(expectedPools[day]);
+        expect(crankState!.bufferedQuote.toString()).to.equal(expectedBuffers[day]);
+
+        const before = await getTokenAccount(env.context.banksClient, usdcAccount);
+        await distributeToInvestor(env, investor, usdcAccount, new BN(expectedPools[day]));
+        const after = await getTokenAccount(env.context.banksClient, usdcAccount);
+        expect((after!.amount - before!.amount).toString()).to.equal(expectedPools[day]);
+
+        // The creator remainder is the released claim's other half; the buffer stays in the vault
+        const creatorBefore = await getTokenAccount(env.context.banksClient, creatorQuote);
+        await routeCreatorRemainder(env, creatorQuote);
+        const creatorAfter = await getTokenAccount(env.context.banksClient, creatorQuote);
+        expect((creatorAfter!.amount - creatorBefore!.amount).toString()).to.equal(expectedPools[day]);
+
+        const vault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+        expect(vault!.amount.toString()).to.equal(expectedBuffers[day]);
+      }
+    });
+  });
+
+  describe("Pagination cursor across days", () => {
+    it("Should restart the cursor at 0 on every reopened day", async () => {
+      const env = await setupCrankEnv();
+      const { investor, usdcAccount } = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      const creatorQuote = await getCreatorQuoteAccount(env);
+
+      const fetchCrankState = () =>
+        fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+
+      for (let day = 1; day <= 5; day++) {
+        if (day > 1) {
+          await warpTimeBy(env.context, 86_400);
+        }
+        seedFeeVaults(env, BigInt(1_000_000));
+        await beginDistributionDay(env);
+        expect((await fetchCrankState())!.paginationCursor).to.equal(0);
+
+        await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+        await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+        await routeCreatorRemainder(env, creatorQuote);
+
+        const closed = await fetchCrankState();
+        expect(closed!.currentDay).to.equal(day);
+        expect(closed!.paginationCursor).to.equal(1);
+      }
+    });
+  });
+
+  describe("Fresh claim requirement", () => {
+    // Stands in for a position state stamped by claim_fees_to_pda
+    const setPositionState = async (env: CrankEnv, lastClaimDay: number) => {
+      const pool = Keypair.generate().publicKey;
+      const [positionState, bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position_state"), pool.toBuffer()],
+        env.program.programId
+      );
+      env.context.setAccount(positionState, {
+        executable: false,
+        owner: env.program.programId,
+        lamports: 1_000_000_000,
+        data: await env.program.coder.accounts.encode("positionState", {
+          pool,
+          position: Keypair.generate().publicKey,
+          positionNftMint: Keypair.generate().publicKey,
+          baseMint: env.baseMint,
+          quoteMint: USDC_MINT,
+          createdTimestamp: new BN(1),
+          lastClaimDay,
+          bump,
+        }),
+      });
+      return positionState;
+    };
+
+    it("Should reject cranking a day whose fees were not freshly claimed", async () => {
+      const env = await setupCrankEnv({ requireFreshClaim: true });
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+
+      // Leftover quote already in the vault when the day begins
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+
+      for (const positionState of [null, await setPositionState(env, 1)]) {
+        try {
+          await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true }, null, [], null, null, positionState);
+          assert.fail("Should have rejected the stale vault balance");
+        } catch (error) {
+          expect(error.toString()).to.not.include("Should have rejected the stale vault balance");
+          // NoFreshClaim = 6107
+          expect(error.toString()).to.include("0x17db");
+        }
+      }
+    });
+
+    it("Should crank once fees were claimed during the day", async () => {
+      const env = await setupCrankEnv({ requireFreshClaim: true });
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(0));
+      await beginDistributionDay(env);
+
+      // A claim after the day began stamps the position and grows the vault
+      seedFeeVaults(env, BigInt(1_000_000));
+      const positionState = await setPositionState(env, 1);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true }, null, [], null, null, positionState);
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.paginationCursor).to.equal(1);
+    });
+  });
+
+  describe("Query day record", () => {
+    const setDayRecord = async (env: CrankEnv, day: number, totalDistributed: BN, leafCount: number) => {
+      const address = getDayRecordPDA(env, day);
+      const [, bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("day_record"), new BN(day).toArrayLike(Buffer, "le", 4)],
+        env.program.programId
+      );
+      env.context.setAccount(address, {
+        executable: false,
+        owner: env.program.programId,
+        lamports: 1_000_000_000,
+        data: await env.program.coder.accounts.encode("dayRecord", {
+          day,
+          payoutRoot: Array(32).fill(day),
+          totalDistributed,
+          leafCount,
+          submittedBy: env.admin.publicKey,
+          submittedTimestamp: new BN(1_700_000_000 + day),
+          bump,
+        }),
+      });
+    };
+
+    it("Should return each day's record by day number", async () => {
+      const env = await setupCrankEnv();
+      await setDayRecord(env, 1, new BN(500_000), 2);
+      await setDayRecord(env, 2, new BN(250_000), 3);
+
+      const first = await queryDayRecord(env, 1);
+      expect(first.day).to.equal(1);
+      expect(first.totalDistributed.toString()).to.equal("500000");
+      expect(first.leafCount).to.equal(2);
+      expect(first.payoutRoot).to.deep.equal(Array(32).fill(1));
+
+      const second = await queryDayRecord(env, 2);
+      expect(second.day).to.equal(2);
+      expect(second.totalDistributed.toString()).to.equal("250000");
+      expect(second.leafCount).to.equal(3);
+      expect(second.submittedTimestamp.toString()).to.equal("1700000002");
+    });
+
+    it("Should reject a day without a record", async () => {
+      const env = await setupCrankEnv();
+      await setDayRecord(env, 1, new BN(500_000), 2);
+
+      try {
+        await queryDayRecord(env, 2);
+        assert.fail("Should have rejected the missing day record");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the missing day record");
+        // DayRecordNotFound = 6108
+        expect(error.toString()).to.include("0x17dc");
+      }
+    });
+
+    it("Should return the record of a day closed by the crank or force-closed", async () => {
+      // Auto-close on the final page
+      const autoClose = await setupCrankEnv({ autoCloseOnFinalPage: true });
+      const { investor, usdcAccount } = await createInvestorWithDeposit(autoClose, new BN(1_000_000_000));
+      seedFeeVaults(autoClose, BigInt(1_000_000));
+      await beginDistributionDay(autoClose);
+      await crankPage(autoClose, { pageIndex: 0, investorsCount: 1, isFinalPage: false });
+      await distributeToInvestor(autoClose, investor, usdcAccount, new BN(500_000));
+      await crankPage(
+        autoClose,
+        { pageIndex: 1, investorsCount: 0, isFinalPage: true },
+        await getCreatorQuoteAccount(autoClose)
+      );
+      expect((await queryDayRecord(autoClose, 1)).day).to.equal(1);
+
+      // Nobody depositing, so the first page routes everything to the creator
+      const noInvestors = await createCrankEnv();
+      await bootstrapProgram(noInvestors);
+      seedFeeVaults(noInvestors, BigInt(1_000_000));
+      await beginDistributionDay(noInvestors);
+      await crankPage(
+        noInvestors,
+        { pageIndex: 0, investorsCount: 0, isFinalPage: false },
+        await getCreatorQuoteAccount(noInvestors)
+      );
+      expect((await queryDayRecord(noInvestors, 1)).day).to.equal(1);
+
+      // Force-closed after the maximum day duration
+      const forceClosed = await setupCrankEnv();
+      await createInvestorWithDeposit(forceClosed, new BN(1_000_000_000));
+      seedFeeVaults(forceClosed, BigInt(1_000_000));
+      await beginDistributionDay(forceClosed);
+      await crankPage(forceClosed, { pageIndex: 0, investorsCount: 1, isFinalPage: false });
+      await warpTimeBy(forceClosed.context, 172_800);
+      await forceCloseDay(forceClosed, await getCreatorQuoteAccount(forceClosed));
+      const record = await queryDayRecord(forceClosed, 1);
+      expect(record.day).to.equal(1);
+      expect(record.leafCount).to.equal(0);
+    });
+  });
+
+  describe("Auto-claim", () => {
+    const page = { pageIndex: 0, investorsCount: 1, isFinalPage: true, autoClaim: true };
+
+    it("Should claim the position's fees below the threshold and distribute them", async () => {
+      const env = await setupCrankEnv({ minCrankableQuote: new BN(1_000_000) });
+      const { investor, usdcAccount } = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(0));
+      await beginDistributionDay(env);
+
+      const claimAccounts = setupMockClaimPosition(env, BigInt(1_000_000));
+      await addPosition(env, claimAccounts.position);
+      await crankPage(env, page, null, [], null, null, null, claimAccounts);
+
+      const vault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      expect(vault!.amount.toString()).to.equal("1000000");
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.currentPageInvestorFee.toString()).to.equal("500000");
+
+      const before = await getTokenAccount(env.context.banksClient, usdcAccount);
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+      const after = await getTokenAccount(env.context.banksClient, usdcAccount);
+      expect((after!.amount - before!.amount).toString()).to.equal("500000");
+    });
+
+    it("Should skip the claim once the vault holds the threshold", async () => {
+      const env = await setupCrankEnv({ minCrankableQuote: new BN(1_000_000) });
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+
+      // No position accounts are needed when nothing is claimed
+      await crankPage(env, page);
+
+      const vault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      expect(vault!.amount.toString()).to.equal("1000000");
+    });
+
+    it("Should reject an auto-claim without the position accounts", async () => {
+      const env = await setupCrankEnv({ minCrankableQuote: new BN(1_000_000) });
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(0));
+      await beginDistributionDay(env);
+
+      try {
+        await crankPage(env, page);
+        assert.fail("Should have required the position accounts");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have required the position accounts");
+        // AutoClaimAccountsRequired = 6109
+        expect(error.toString()).to.include("0x17dd");
+      }
+    });
+
+    it("Should keep the quote-only enforcement when the claim pays base fees", async () => {
+      const env = await setupCrankEnv({ minCrankableQuote: new BN(1_000_000) });
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(0));
+      await beginDistributionDay(env);
+
+      const claimAccounts = setupMockClaimPosition(env, BigInt(1_000_000), BigInt(1_000));
+      await addPosition(env, claimAccounts.position);
+      try {
+        await crankPage(env, page, null, [], null, null, null, claimAccounts);
+        assert.fail("Should have rejected the base fees");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the base fees");
+        // BaseFeesDetected = 6019
+        expect(error.toString()).to.include("0x1783");
+      }
+    });
+  });
+
+  describe("Minimum payout and daily cap", () => {
+    it("Should reject a minimum payout at or above the daily cap on initialization", async () => {
+      for (const overrides of [
+        { minPayoutLamports: new BN(1_000_000), dailyCapLamports: new BN(1_000_000) },
+        // The quote-unit cap takes precedence over the legacy field
+        {
+          minPayoutLamports: new BN(1_000_000),
+          dailyCapLamports: new BN(10_000_000),
+          dailyCapQuoteUnits: new BN(500_000),
+        },
+        // A zero minimum falls back to the 10_000 default
+        { minPayoutLamports: new BN(0), dailyCapLamports: new BN(10_000) },
+      ]) {
+        try {
+          await setupCrankEnv(overrides);
+          assert.fail("Should have rejected the degenerate combination");
+        } catch (error) {
+          expect(error.toString()).to.not.include("Should have rejected the degenerate combination");
+          // InvalidConfigCombination = 6110
+          expect(error.toString()).to.include("0x17de");
+        }
+      }
+
+      // No cap leaves the minimum unconstrained
+      await setupCrankEnv({ minPayoutLamports: new BN(1_000_000), dailyCapLamports: new BN(0) });
+    });
+
+    it("Should reject updates that leave the minimum payout at or above the cap", async () => {
+      const env = await setupCrankEnv({ dailyCapQuoteUnits: new BN(1_000_000) });
+
+      for (const update of [
+        { minPayoutLamports: new BN(1_000_000) },
+        { dailyCapQuoteUnits: new BN(1_000) },
+      ]) {
+        try {
+          await updateDistributionConfig(env, update);
+          assert.fail("Should have rejected the degenerate combination");
+        } catch (error) {
+          expect(error.toString()).to.not.include("Should have rejected the degenerate combination");
+          // InvalidConfigCombination = 6110
+          expect(error.toString()).to.include("0x17de");
+        }
+      }
+
+      // Lowering the minimum together with the cap is accepted
+      await updateDistributionConfig(env, { minPayoutLamports: new BN(100), dailyCapQuoteUnits: new BN(1_000) });
+      const config = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DistributionConfig",
+        env.distributionConfigPDA
+      );
+      expect(config!.dailyCapQuoteUnits.toString()).to.equal("1000");
+    });
+  });
+
+  describe("Pausing new days", () => {
+    it("Should let the day in progress finish while no new day can start", async () => {
+      const env = await setupCrankEnv();
+      const { investor, usdcAccount } = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      const creatorQuote = await getCreatorQuoteAccount(env);
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+
+      // Pause mid-day: pages, payouts and the creator routing are unaffected
+      await updateDistributionConfig(env, { newDaysPaused: true });
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      const before = await getTokenAccount(env.context.banksClient, usdcAccount);
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+      const after = await getTokenAccount(env.context.banksClient, usdcAccount);
+      expect((after!.amount - before!.amount).toString()).to.equal("500000");
+      await routeCreatorRemainder(env, creatorQuote);
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.dayState).to.equal(2);
+
+      await warpTimeBy(env.context, 86_400);
+      try {
+        await beginDistributionDay(env);
+        assert.fail("Should have rejected a new day while paused");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected a new day while paused");
+        // NewDaysPaused = 6111
+        expect(error.toString()).to.include("0x17df");
+      }
+
+      // Unpausing lets the next day begin
+      await updateDistributionConfig(env, { newDaysPaused: false });
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      const reopened = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(reopened!.currentDay).to.equal(2);
+    });
+  });
+
+  describe("Y0-relative deposit cap", () => {
+    it("Should reject a deposit taking the investor's total above the share of Y0", async () => {
+      // 10% of the 1000 USDC Y0 allowance: at most 100 USDC deposited per investor
+      const env = await setupCrankEnv({ maxInvestorShareOfY0Bps: 1000 });
+      const { investor, usdcAccount } = await createInvestorWithDeposit(env, new BN(60_000_000));
+
+      try {
+        await depositUsdc(env, investor, usdcAccount, new BN(40_000_001));
+        assert.fail("Should have rejected the deposit above the Y0 cap");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the deposit above the Y0 cap");
+        // Y0ShareCapExceeded = 6112
+        expect(error.toString()).to.include("0x17e0");
+      }
+
+      // Exactly reaching the cap is allowed
+      await depositUsdc(env, investor, usdcAccount, new BN(40_000_000));
+      const record = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DepositorRecord",
+        getDepositorRecordPDA(env, investor.publicKey)
+      );
+      expect(record!.totalUsdcDeposited.toString()).to.equal("100000000");
+
+      // Other investors have their own allowance
+      await createInvestorWithDeposit(env, new BN(100_000_000));
+    });
+  });
+
+  describe("Withdraw return data", () => {
+    let env: CrankEnv;
+
+    before(async () => {
+      env = await setupCrankEnv();
+    });
+
+    it("Should return the withdrawn amounts and the remaining balances", async () => {
+      const { investor, usdcAccount } = await createInvestorWithDeposit(env, new BN(1_000_000));
+
+      const meta = await withdrawUsdc(env, investor, usdcAccount, new BN(400_000));
+      const result = env.program.coder.types.decode(
+        "WithdrawResult",
+        Buffer.from(meta.returnData!.data)
+      );
+      expect(result.solWithdrawn.toString()).to.equal("0");
+      expect(result.usdcWithdrawn.toString()).to.equal("400000");
+      expect(result.remainingSol.toString()).to.equal("0");
+      expect(result.remainingUsdc.toString()).to.equal("600000");
+
+      const depositorRecord = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DepositorRecord",
+        getDepositorRecordPDA(env, investor.publicKey)
+      );
+      expect(result.remainingUsdc.toString()).to.equal(depositorRecord!.currentUsdcBalance.toString());
+    });
+  });
+
+  describe("Config change cool-off", () => {
+    it("Should keep the previous fee share for a day begun during the cool-off", async () => {
+      const env = await setupCrankEnv({ configChangeCooloffSeconds: new BN(3_600) });
+      const { investor, usdcAccount } = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      const creatorQuote = await getCreatorQuoteAccount(env);
+
+      // Raise the fee share right before the day starts: the crank still uses 5000 bps
+      await updateDistributionConfig(env, { investorFeeShareBps: 8000 });
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      const firstMeta = await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      const firstPage = parseEvents(env, firstMeta).find((event) => event.name === "investorPayoutPage");
+      expect(firstPage!.data.eligibleInvestorShareBps).to.equal(5000);
+      expect(firstPage!.data.investorFeeQuote.toString()).to.equal("500000");
+      await distributeToInvestor(env, investor, usdcAccount, firstPage!.data.investorFeeQuote);
+      await routeCreatorRemainder(env, creatorQuote);
+
+      // The next day begins after the cool-off and uses the new share
+      await warpTimeBy(env.context, 86_400);
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      const secondMeta = await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      const secondPage = parseEvents(env, secondMeta).find((event) => event.name === "investorPayoutPage");
+      expect(secondPage!.data.eligibleInvestorShareBps).to.equal(8000);
+      expect(secondPage!.data.investorFeeQuote.toString()).to.equal("800000");
+    });
+  });
+
+  describe("Distribution opt-out", () => {
+    it("Should redistribute an opted-out investor's share to the other investors", async () => {
+      const env = await setupCrankEnv();
+      const first = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      const second = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      const optedOut = await createInvestorWithDeposit(env, new BN(2_000_000_000));
+      const creatorQuote = await getCreatorQuoteAccount(env);
+
+      await setOptOut(env, optedOut.investor, true);
+      const stats = await fetchAccount(env.context.banksClient, env.program, "VaultStats", env.vaultStatsPDA);
+      expect(stats!.currentTotalWeightedUsdc.toString()).to.equal("2000000000");
+
+      // The 500000 pool is split over the two remaining investors instead of all three
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 3, isFinalPage: true });
+      const paid: string[] = [];
+      for (const { investor, usdcAccount } of [first, second, optedOut]) {
+        const before = await getTokenAccount(env.context.banksClient, usdcAccount);
+        await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+        const after = await getTokenAccount(env.context.banksClient, usdcAccount);
+        paid.push((after!.amount - before!.amount).toString());
+      }
+      expect(paid).to.deep.equal(["250000", "250000", "0"]);
+
+      // Nothing is held back as carry-over for the opted-out investor
+      await routeCreatorRemainder(env, creatorQuote);
+      const crankState = await fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+      expect(crankState!.carryOver.toString()).to.equal("0");
+    });
+
+    it("Should reject changing the opt-out while a day is in progress", async () => {
+      const env = await setupCrankEnv();
+      const { investor } = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+
+      try {
+        await setOptOut(env, investor, true);
+        assert.fail("Should have rejected an opt-out mid-day");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected an opt-out mid-day");
+      }
+    });
+  });
+
+  describe("Fee collector derivation", () => {
+    it("Should derive the same fee_collector PDA in every instruction", async () => {
+      const env = await setupCrankEnv();
+      const derivations = IDL.instructions.flatMap((instruction) =>
+        (instruction.accounts as any[])
+          .filter((account) => account.name === "fee_collector")
+          .map((account) => ({ instruction: instruction.name, pda: account.pda }))
+      );
+      const instructions = derivations.map(({ instruction }) => instruction);
+      for (const name of ["deposit", "withdraw", "crank_fee_distribution", "claim_fees_to_pda"]) {
+        expect(instructions).to.include(name);
+      }
+
+      for (const { instruction, pda } of derivations) {
+        // A single constant seed, FEE_COLLECTOR_SEED, with the canonical bump
+        expect(pda, instruction).to.exist;
+        expect(pda.seeds, instruction).to.have.length(1);
+        expect(pda.seeds[0].kind, instruction).to.equal("const");
+        const seed = Buffer.from(pda.seeds[0].value);
+        expect(seed.toString(), instruction).to.equal("fee_collector");
+        const [derived] = PublicKey.findProgramAddressSync([seed], env.program.programId);
+        expect(derived.toString(), instruction).to.equal(env.feeCollectorPDA.toString());
+      }
+    });
+  });
+
+  describe("Maximum claim per transaction", () => {
+    it("Should reject a claim above max_claim_per_tx", async () => {
+      const env = await setupCrankEnv({ maxClaimPerTx: new BN(500_000) });
+      seedFeeVaults(env, BigInt(0));
+
+      const oversized = setupMockClaimPosition(env, BigInt(1_000_000));
+      await addPosition(env, oversized.position);
+
+      try {
+        await claimFeesToPda(env, oversized);
+        assert.fail("Should have rejected the oversized claim");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the oversized claim");
+        // ClaimExceedsMax = 6114
+        expect(error.toString()).to.include("0x17e2");
+      }
+      const vault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      expect(vault!.amount.toString()).to.equal("0");
+
+      // A claim within the maximum goes through
+      const withinMax = setupMockClaimPosition(env, BigInt(500_000));
+      await addPosition(env, withinMax.position);
+      await claimFeesToPda(env, withinMax);
+      const claimed = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      expect(claimed!.amount.toString()).to.equal("500000");
+    });
+
+    it("Should apply the maximum to the crank's auto-claim", async () => {
+      const env = await setupCrankEnv({
+        minCrankableQuote: new BN(1_000_000),
+        maxClaimPerTx: new BN(500_000),
+      });
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(0));
+      await beginDistributionDay(env);
+
+      const claimAccounts = setupMockClaimPosition(env, BigInt(1_000_000));
+      await addPosition(env, claimAccounts.position);
+      try {
+        await crankPage(
+          env,
+          { pageIndex: 0, investorsCount: 1, isFinalPage: true, autoClaim: true },
+          null,
+          [],
+          null,
+          null,
+          null,
+          claimAccounts
+        );
+        assert.fail("Should have rejected the oversized auto-claim");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the oversized auto-claim");
+        // ClaimExceedsMax = 6114
+        expect(error.toString()).to.include("0x17e2");
+      }
+    });
+  });
+
+  describe("Position registry", () => {
+    it("Should register a position and let it claim", async () => {
+      const env = await setupCrankEnv();
+      seedFeeVaults(env, BigInt(0));
+      const claimAccounts = setupMockClaimPosition(env, BigInt(1_000_000));
+
+      await addPosition(env, claimAccounts.position);
+      const registry = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "PositionRegistry",
+        getPositionRegistryPDA(env)
+      );
+      expect(registry.config.toString()).to.equal(env.distributionConfigPDA.toString());
+      expect(registry.positions.map((position) => position.toString())).to.deep.equal([
+        claimAccounts.position.toString(),
+      ]);
+
+      await claimFeesToPda(env, claimAccounts);
+      const vault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      expect(vault!.amount.toString()).to.equal("1000000");
+    });
+
+    it("Should reject a claim from an unregistered position", async () => {
+      const env = await setupCrankEnv();
+      seedFeeVaults(env, BigInt(0));
+      await addPosition(env, setupMockClaimPosition(env, BigInt(0)).position);
+
+      try {
+        await claimFeesToPda(env, setupMockClaimPosition(env, BigInt(1_000_000)));
+        assert.fail("Should have rejected the unregistered position");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the unregistered position");
+        // PositionNotRegistered = 6115
+        expect(error.toString()).to.include("0x17e3");
+      }
+      const vault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      expect(vault!.amount.toString()).to.equal("0");
+    });
+
+    it("Should reject claims from a removed position", async () => {
+      const env = await setupCrankEnv();
+      seedFeeVaults(env, BigInt(0));
+      const claimAccounts = setupMockClaimPosition(env, BigInt(1_000_000));
+      await addPosition(env, claimAccounts.position);
+      await removePosition(env, claimAccounts.position);
+
+      try {
+        await claimFeesToPda(env, claimAccounts);
+        assert.fail("Should have rejected the removed position");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the removed position");
+        // PositionNotRegistered = 6115
+        expect(error.toString()).to.include("0x17e3");
+      }
+    });
+  });
+
+  describe("Dust recipient", () => {
+    it("Should send the day's dust to the dust recipient at close", async () => {
+      // Minimum payout above the investor share, so the whole payout becomes dust
+      const dustRecipient = Keypair.generate();
+      const env = await setupCrankEnv({
+        minPayoutLamports: new BN(1_000_000),
+        dustRecipient: dustRecipient.publicKey,
+      });
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+      const creatorQuoteAccount = await getCreatorQuoteAccount(env);
+      const dustRecipientQuoteAccount = await getOrCreateAta(
+        env.context.banksClient,
+        env.admin,
+        USDC_MINT,
+        dustRecipient.publicKey
+      );
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+
+      // DustRecipientQuoteAccountRequired = 6118, DustRecipientQuoteAccountMismatch = 6119
+      for (const [account, code] of [
+        [null, "0x17e6"],
+        [creatorQuoteAccount, "0x17e7"],
+      ] as [PublicKey | null, string][]) {
+        try {
+          await routeCreatorRemainder(env, creatorQuoteAccount, null, new BN(0), null, null, account);
+          assert.fail("Should have rejected the dust recipient account");
+        } catch (error) {
+          expect(error.toString()).to.not.include("Should have rejected the dust recipient account");
+          expect(error.toString()).to.include(code);
+        }
+      }
+
+      const meta = await routeCreatorRemainder(
+        env,
+        creatorQuoteAccount,
+        null,
+        new BN(0),
+        null,
+        null,
+        dustRecipientQuoteAccount
+      );
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.carryOver.toString()).to.equal("0");
+
+      // The creator keeps only its own share; the investor's dust goes to the dust recipient
+      const vault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      const creatorAccount = await getTokenAccount(env.context.banksClient, creatorQuoteAccount);
+      const dustAccount = await getTokenAccount(env.context.banksClient, dustRecipientQuoteAccount);
+      expect(vault!.amount.toString()).to.equal("0");
+      expect(creatorAccount!.amount.toString()).to.equal("500000");
+      expect(dustAccount!.amount.toString()).to.equal("500000");
+
+      const dustRouted = parseEvents(env, meta).find((event) => event.name === "dustRouted");
+      expect(dustRouted!.data.amount.toString()).to.equal("500000");
+      expect(dustRouted!.data.dustRecipient.toString()).to.equal(dustRecipient.publicKey.toString());
+    });
+  });
+
+  describe("Delegated payout accounts", () => {
+    it("Should reject a payout into a delegated quote account under reject_delegated_accounts", async () => {
+      const env = await setupCrankEnv({ rejectDelegatedAccounts: true });
+      const { investor, usdcAccount } = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: true });
+
+      const delegate = Keypair.generate().publicKey;
+      await sendTx(
+        env.context,
+        new Transaction().add(
+          createApproveInstruction(usdcAccount, delegate, investor.publicKey, BigInt(1_000_000))
+        ),
+        [investor]
+      );
+
+      try {
+        await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+        assert.fail("Should have rejected the delegated quote account");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the delegated quote account");
+        // DelegatedPayoutAccount = 6120
+        expect(error.toString()).to.include("0x17e8");
+      }
+
+      // Once the delegate is revoked the payout goes through
+      await sendTx(
+        env.context,
+        new Transaction().add(createRevokeInstruction(usdcAccount, investor.publicKey)),
+        [investor]
+      );
+      const before = await getTokenAccount(env.context.banksClient, usdcAccount);
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+      const after = await getTokenAccount(env.context.banksClient, usdcAccount);
+      expect((after!.amount - before!.amount).toString()).to.equal("500000");
+    });
+
+    it("Should reject a vested-share payout into a delegated quote account", async () => {
+      const env = await setupCrankEnv({ rejectDelegatedAccounts: true, vestedInvestorShareBps: 2000 });
+      const alumni = await createInvestorWithDeposit(env, new BN(500_000_000));
+      await withdrawUsdc(env, alumni.investor, alumni.usdcAccount, new BN(500_000_000));
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+
+      await sendTx(
+        env.context,
+        new Transaction().add(
+          createApproveInstruction(alumni.usdcAccount, Keypair.generate().publicKey, alumni.investor.publicKey, BigInt(1_000_000))
+        ),
+        [alumni.investor]
+      );
+
+      try {
+        await distributeToInvestor(env, alumni.investor, alumni.usdcAccount, new BN(500_000));
+        assert.fail("Should have rejected the delegated quote account");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the delegated quote account");
+        // DelegatedPayoutAccount = 6120
+        expect(error.toString()).to.include("0x17e8");
+      }
+    });
+  });
+
+  describe("Vested share gating", () => {
+    it("Should count vested-share payouts toward the daily cap", async () => {
+      const env = await setupCrankEnv({ vestedInvestorShareBps: 2000, dailyCapLamports: new BN(500_000) });
+      const alumni = await createInvestorWithDeposit(env, new BN(500_000_000));
+      await withdrawUsdc(env, alumni.investor, alumni.usdcAccount, new BN(500_000_000));
+      const locked = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+      await distributeToInvestor(env, locked.investor, locked.usdcAccount, new BN(500_000));
+
+      try {
+        await distributeToInvestor(env, alumni.investor, alumni.usdcAccount, new BN(500_000));
+        assert.fail("Should have rejected the vested payout past the cap");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the vested payout past the cap");
+        // DailyCapExceeded = 6020
+        expect(error.toString()).to.include("0x1784");
+      }
+    });
+
+    it("Should not pay the vested share before the first eligible day", async () => {
+      // Depositors before day 1 are only eligible from day 2
+      const env = await setupCrankEnv({ vestedInvestorShareBps: 2000, eligibilityDelayDays: 2 });
+      const alumni = await createInvestorWithDeposit(env, new BN(500_000_000));
+      await withdrawUsdc(env, alumni.investor, alumni.usdcAccount, new BN(500_000_000));
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+
+      const before = await getTokenAccount(env.context.banksClient, alumni.usdcAccount);
+      const meta = await distributeToInvestor(env, alumni.investor, alumni.usdcAccount, new BN(500_000));
+      const after = await getTokenAccount(env.context.banksClient, alumni.usdcAccount);
+      expect((after!.amount - before!.amount).toString()).to.equal("0");
+      const payout = parseEvents(env, meta).find((event) => event.name === "vestedInvestorPayout");
+      expect(payout!.data.actualPayout.toString()).to.equal("0");
+    });
+  });
+
+  describe("Remaining daily cap", () => {
+    async function payoutRemainingCaps(configOverrides: Record<string, any>) {
+      const env = await setupCrankEnv(configOverrides);
+      const first = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      const second = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+
+      const remaining: string[] = [];
+      for (const { investor, usdcAccount } of [first, second]) {
+        const meta = await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+        const payout = parseEvents(env, meta).find((event) => event.name === "investorPayout");
+        expect(payout!.data.actualPayout.toString()).to.equal("250000");
+        remaining.push(payout!.data.remainingDailyCap.toString());
+      }
+      return remaining;
+    }
+
+    it("Should report the cap left after each payout", async () => {
+      const remaining = await payoutRemainingCaps({ dailyCapLamports: new BN(1_000_000) });
+      expect(remaining).to.deep.equal(["750000", "500000"]);
+    });
+
+    it("Should report 0 when uncapped", async () => {
+      const remaining = await payoutRemainingCaps({});
+      expect(remaining).to.deep.equal(["0", "0"]);
+    });
+  });
+
+  describe("Repeat investor payouts", () => {
+    async function expectAlreadyPaid(env: CrankEnv, investor: Keypair, usdcAccount: PublicKey) {
+      try {
+        await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+        assert.fail("Should have rejected the repeat payout");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the repeat payout");
+        // InvestorAlreadyPaid = 6121
+        expect(error.toString()).to.include("0x17e9");
+      }
+    }
+
+    it("Should pay an investor once per round", async () => {
+      const env = await setupCrankEnv();
+      const first = await createInvestorWithDeposit(env, new BN(600_000_000));
+      const second = await createInvestorWithDeposit(env, new BN(400_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: false });
+
+      await distributeToInvestor(env, first.investor, first.usdcAccount, new BN(500_000));
+      const paidOnce = await getTokenAccount(env.context.banksClient, first.usdcAccount);
+      await expectAlreadyPaid(env, first.investor, first.usdcAccount);
+
+      const paidAfterRepeat = await getTokenAccount(env.context.banksClient, first.usdcAccount);
+      expect(paidAfterRepeat!.amount.toString()).to.equal(paidOnce!.amount.toString());
+      const crankState = await fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+      expect(crankState!.dailyDistributed.toString()).to.equal("300000");
+      expect(crankState!.investorsPaidToday).to.equal(1);
+    });
+
+    it("Should pay a vested investor once per round", async () => {
+      const env = await setupCrankEnv({ vestedInvestorShareBps: 2000 });
+      const alumni = await createInvestorWithDeposit(env, new BN(500_000_000));
+      await withdrawUsdc(env, alumni.investor, alumni.usdcAccount, new BN(500_000_000));
+      await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: false });
+
+      await distributeToInvestor(env, alumni.investor, alumni.usdcAccount, new BN(500_000));
+      await expectAlreadyPaid(env, alumni.investor, alumni.usdcAccount);
+
+      const crankState = await fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+      expect(crankState!.vestedDistributedToday.toString()).to.equal("33330");
+    });
+  });
+
+  describe("Investor allocation guard", () => {
+    it("Should reject payouts past the day's investor allocation", async () => {
+      const env = await setupCrankEnv();
+      const { investor, usdcAccount } = await createInvestorWithDeposit(env, new BN(1_000_000_000));
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: false });
+
+      // Shrink the day's allocation below the page's pool, as a skewed weight would overdraw it
+      const crankState = await fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+      const raw = await env.context.banksClient.getAccount(env.crankStatePDA);
+      env.context.setAccount(env.crankStatePDA, {
+        executable: false,
+        owner: env.program.programId,
+        lamports: raw!.lamports,
+        data: await env.program.coder.accounts.encode("crankState", {
+          ...crankState,
+          dayInvestorFeeQuote: new BN(100_000),
+        }),
+      });
+
+      try {
+        await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+        assert.fail("Should have rejected the payout past the allocation");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the payout past the allocation");
+        // InvestorPoolExhausted = 6123
+        expect(error.toString()).to.include("0x17eb");
+      }
+    });
+  });
+});
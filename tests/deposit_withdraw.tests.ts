@@ -36,6 +36,8 @@ describe("Deposit and Withdraw Tests (Bankrun)", () => {
   let solVaultPDA: PublicKey;
   let usdcVaultPDA: PublicKey;
   let vaultStatsPDA: PublicKey;
+  let crankStatePDA: PublicKey;
+  let distributionConfigPDA: PublicKey;
 
   before(async () => {
     context = await startTest();
@@ -84,6 +86,16 @@ describe("Deposit and Withdraw Tests (Bankrun)", () => {
       [Buffer.from("deposit_vault"), Buffer.from("stats")],
       program.programId
     );
+
+    [crankStatePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("crank_state")],
+      program.programId
+    );
+
+    [distributionConfigPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("distribution_config")],
+      program.programId
+    );
   });
 
   describe("Deposit - SOL Only", () => {
@@ -120,6 +132,8 @@ describe("Deposit and Withdraw Tests (Bankrun)", () => {
           investorUsdcAccount: investor1UsdcAccount,
           depositorRecord: depositorRecordPDA,
           vaultStats: vaultStatsPDA,
+          crankState: crankStatePDA,
+          distributionConfig: distributionConfigPDA,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -210,6 +224,8 @@ describe("Deposit and Withdraw Tests (Bankrun)", () => {
           investorUsdcAccount: investor1UsdcAccount,
           depositorRecord: depositorRecordPDA,
           vaultStats: vaultStatsPDA,
+          crankState: crankStatePDA,
+          distributionConfig: distributionConfigPDA,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -267,6 +283,8 @@ describe("Deposit and Withdraw Tests (Bankrun)", () => {
           investorUsdcAccount: investor2UsdcAccount,
           depositorRecord: depositorRecordPDA,
           vaultStats: vaultStatsPDA,
+          crankState: crankStatePDA,
+          distributionConfig: distributionConfigPDA,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -323,6 +341,8 @@ describe("Deposit and Withdraw Tests (Bankrun)", () => {
           investorUsdcAccount: investor3UsdcAccount,
           depositorRecord: depositorRecordPDA,
           vaultStats: vaultStatsPDA,
+          crankState: crankStatePDA,
+          distributionConfig: distributionConfigPDA,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -392,6 +412,8 @@ describe("Deposit and Withdraw Tests (Bankrun)", () => {
           investorUsdcAccount: investor3UsdcAccount,
           depositorRecord: depositorRecordPDA,
           vaultStats: vaultStatsPDA,
+          crankState: crankStatePDA,
+          distributionConfig: distributionConfigPDA,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -450,6 +472,8 @@ describe("Deposit and Withdraw Tests (Bankrun)", () => {
             investorUsdcAccount: investor1UsdcAccount,
             depositorRecord: depositorRecordPDA,
             vaultStats: vaultStatsPDA,
+            crankState: crankStatePDA,
+            distributionConfig: distributionConfigPDA,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
@@ -495,6 +519,8 @@ describe("Deposit and Withdraw Tests (Bankrun)", () => {
             investorUsdcAccount: investor1UsdcAccount,
             depositorRecord: depositorRecordPDA,
             vaultStats: vaultStatsPDA,
+            crankState: crankStatePDA,
+            distributionConfig: distributionConfigPDA,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
@@ -513,4 +539,65 @@ describe("Deposit and Withdraw Tests (Bankrun)", () => {
     });
   });
 
+  describe("SOL Vault Validation", () => {
+    it("Should reject a spoofed sol_vault holding token account data", async () => {
+      const [depositorRecordPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("investor_record"), investor1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const investor1UsdcAccount = await getOrCreateAta(
+        context.banksClient,
+        admin,
+        USDC_MINT,
+        investor1.publicKey
+      );
+
+      // Replace the SOL vault with a token-program-owned account at the same address
+      const originalSolVault = await context.banksClient.getAccount(solVaultPDA);
+      context.setAccount(solVaultPDA, {
+        executable: false,
+        owner: TOKEN_PROGRAM_ID,
+        lamports: LAMPORTS_PER_SOL,
+        data: new Uint8Array(165),
+      });
+
+      try {
+        const tx = await program.methods
+          .deposit({
+            solAmount: new BN(LAMPORTS_PER_SOL),
+            usdcAmount: new BN(0),
+          })
+          .accountsStrict({
+            investor: investor1.publicKey,
+            feeCollector: feeCollectorPDA,
+            solVault: solVaultPDA,
+            usdcVault: usdcVaultPDA,
+            usdcMint: USDC_MINT,
+            investorUsdcAccount: investor1UsdcAccount,
+            depositorRecord: depositorRecordPDA,
+            vaultStats: vaultStatsPDA,
+            crankState: crankStatePDA,
+            distributionConfig: distributionConfigPDA,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .transaction();
+
+        const [recentBlockhash] =
+          await context.banksClient.getLatestBlockhash();
+        tx.recentBlockhash = recentBlockhash;
+        tx.sign(investor1);
+
+        await context.banksClient.processTransaction(tx);
+        assert.fail("Should have rejected the spoofed sol_vault");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the spoofed sol_vault");
+      } finally {
+        if (originalSolVault) {
+          context.setAccount(solVaultPDA, originalSolVault);
+        }
+      }
+    });
+  });
 });
@@ -15,6 +15,7 @@ import {
   USDC_MINT,
   fetchAccount,
 } from "./utils/bankrun";
+import { replaceConfig, setupCrankEnv } from "./utils/crank";
 import { StarFeeDistribution } from "../target/types/star_fee_distribution";
 import IDL from "../target/idl/star_fee_distribution.json";
 
@@ -60,6 +61,50 @@ describe("Distribution Config Tests (Bankrun)", () => {
           dailyCapLamports,
           creatorWallet: creatorWallet.publicKey,
           quoteMint: USDC_MINT,
+          autoCloseOnFinalPage: false,
+          vestingProgram: PublicKey.default,
+          vestedInvestorShareBps: 0,
+          creatorWalletTimelockSeconds: new BN(0),
+          remainderPolicy: 0,
+          treasuryWallet: PublicKey.default,
+          emitEvents: true,
+          eligibilityDelayDays: 1,
+          eligibilityRoot: Array(32).fill(0),
+          dailyCapQuoteUnits: new BN(0),
+          dayMaxDurationSeconds: new BN(0),
+          minDepositIntervalSeconds: new BN(0),
+          carryOverPolicy: 0,
+          fLockedFloorBps: 0,
+          fLockedCeilingBps: 0,
+          verifyTotalLocked: false,
+          totalLockedToleranceUnits: new BN(0),
+          creatorPayoutMint: PublicKey.default,
+          depositWindowStartTs: new BN(0),
+          depositWindowEndTs: new BN(0),
+          maxCarryOver: new BN(0),
+          carryOverCapPolicy: 0,
+          zeroBalancePolicy: 0,
+          roundsPerDay: 1,
+          minTotalLockedToDistribute: new BN(0),
+          weightByRemainingLock: false,
+          creatorInactivitySeconds: new BN(0),
+          creatorMinShareBps: 0,
+          depositMint: USDC_MINT,
+          dustThresholdK: 0,
+          fixedPageSize: false,
+          callbackProgram: PublicKey.default,
+          maxInvestorShareBps: 0,
+          keeperRewardLamports: new BN(0),
+          earningsUnlockTs: new BN(0),
+          ageBrackets: Array(4).fill({ minAgeSeconds: new BN(0), multiplierBps: 0 }),
+          distributionRateBps: 0,
+          requireFreshClaim: false,
+          minCrankableQuote: new BN(0),
+          maxInvestorShareOfY0Bps: 0,
+          configChangeCooloffSeconds: new BN(0),
+          maxClaimPerTx: new BN(0),
+          dustRecipient: PublicKey.default,
+          rejectDelegatedAccounts: false,
         })
         .accountsStrict({
           admin: admin.publicKey,
@@ -129,6 +174,50 @@ describe("Distribution Config Tests (Bankrun)", () => {
             dailyCapLamports: new BN(100 * LAMPORTS_PER_SOL),
             creatorWallet: creatorWallet.publicKey,
             quoteMint: USDC_MINT,
+            autoCloseOnFinalPage: false,
+            vestingProgram: PublicKey.default,
+            vestedInvestorShareBps: 0,
+            creatorWalletTimelockSeconds: new BN(0),
+            remainderPolicy: 0,
+            treasuryWallet: PublicKey.default,
+            emitEvents: true,
+            eligibilityDelayDays: 1,
+            eligibilityRoot: Array(32).fill(0),
+            dailyCapQuoteUnits: new BN(0),
+            dayMaxDurationSeconds: new BN(0),
+            minDepositIntervalSeconds: new BN(0),
+            carryOverPolicy: 0,
+            fLockedFloorBps: 0,
+            fLockedCeilingBps: 0,
+            verifyTotalLocked: false,
+            totalLockedToleranceUnits: new BN(0),
+            creatorPayoutMint: PublicKey.default,
+            depositWindowStartTs: new BN(0),
+            depositWindowEndTs: new BN(0),
+            maxCarryOver: new BN(0),
+            carryOverCapPolicy: 0,
+            zeroBalancePolicy: 0,
+            roundsPerDay: 1,
+            minTotalLockedToDistribute: new BN(0),
+            weightByRemainingLock: false,
+            creatorInactivitySeconds: new BN(0),
+            creatorMinShareBps: 0,
+            depositMint: USDC_MINT,
+            dustThresholdK: 0,
+            fixedPageSize: false,
+            callbackProgram: PublicKey.default,
+            maxInvestorShareBps: 0,
+            keeperRewardLamports: new BN(0),
+            earningsUnlockTs: new BN(0),
+            ageBrackets: Array(4).fill({ minAgeSeconds: new BN(0), multiplierBps: 0 }),
+            distributionRateBps: 0,
+            requireFreshClaim: false,
+            minCrankableQuote: new BN(0),
+            maxInvestorShareOfY0Bps: 0,
+            configChangeCooloffSeconds: new BN(0),
+            maxClaimPerTx: new BN(0),
+            dustRecipient: PublicKey.default,
+            rejectDelegatedAccounts: false,
           })
           .accountsStrict({
             admin: newAdmin.publicKey,
@@ -162,6 +251,50 @@ describe("Distribution Config Tests (Bankrun)", () => {
             dailyCapLamports: new BN(100 * LAMPORTS_PER_SOL),
             creatorWallet: creatorWallet.publicKey,
             quoteMint: USDC_MINT,
+            autoCloseOnFinalPage: false,
+            vestingProgram: PublicKey.default,
+            vestedInvestorShareBps: 0,
+            creatorWalletTimelockSeconds: new BN(0),
+            remainderPolicy: 0,
+            treasuryWallet: PublicKey.default,
+            emitEvents: true,
+            eligibilityDelayDays: 1,
+            eligibilityRoot: Array(32).fill(0),
+            dailyCapQuoteUnits: new BN(0),
+            dayMaxDurationSeconds: new BN(0),
+            minDepositIntervalSeconds: new BN(0),
+            carryOverPolicy: 0,
+            fLockedFloorBps: 0,
+            fLockedCeilingBps: 0,
+            verifyTotalLocked: false,
+            totalLockedToleranceUnits: new BN(0),
+            creatorPayoutMint: PublicKey.default,
+            depositWindowStartTs: new BN(0),
+            depositWindowEndTs: new BN(0),
+            maxCarryOver: new BN(0),
+            carryOverCapPolicy: 0,
+            zeroBalancePolicy: 0,
+            roundsPerDay: 1,
+            minTotalLockedToDistribute: new BN(0),
+            weightByRemainingLock: false,
+            creatorInactivitySeconds: new BN(0),
+            creatorMinShareBps: 0,
+            depositMint: USDC_MINT,
+            dustThresholdK: 0,
+            fixedPageSize: false,
+            callbackProgram: PublicKey.default,
+            maxInvestorShareBps: 0,
+            keeperRewardLamports: new BN(0),
+            earningsUnlockTs: new BN(0),
+            ageBrackets: Array(4).fill({ minAgeSeconds: new BN(0), multiplierBps: 0 }),
+            distributionRateBps: 0,
+            requireFreshClaim: false,
+            minCrankableQuote: new BN(0),
+            maxInvestorShareOfY0Bps: 0,
+            configChangeCooloffSeconds: new BN(0),
+            maxClaimPerTx: new BN(0),
+            dustRecipient: PublicKey.default,
+            rejectDelegatedAccounts: false,
           })
           .accountsStrict({
             admin: newAdmin.publicKey,
@@ -195,6 +328,50 @@ describe("Distribution Config Tests (Bankrun)", () => {
             dailyCapLamports: new BN(100 * LAMPORTS_PER_SOL),
             creatorWallet: PublicKey.default, // Invalid: default pubkey
             quoteMint: USDC_MINT,
+            autoCloseOnFinalPage: false,
+            vestingProgram: PublicKey.default,
+            vestedInvestorShareBps: 0,
+            creatorWalletTimelockSeconds: new BN(0),
+            remainderPolicy: 0,
+            treasuryWallet: PublicKey.default,
+            emitEvents: true,
+            eligibilityDelayDays: 1,
+            eligibilityRoot: Array(32).fill(0),
+            dailyCapQuoteUnits: new BN(0),
+            dayMaxDurationSeconds: new BN(0),
+            minDepositIntervalSeconds: new BN(0),
+            carryOverPolicy: 0,
+            fLockedFloorBps: 0,
+            fLockedCeilingBps: 0,
+            verifyTotalLocked: false,
+            totalLockedToleranceUnits: new BN(0),
+            creatorPayoutMint: PublicKey.default,
+            depositWindowStartTs: new BN(0),
+            depositWindowEndTs: new BN(0),
+            maxCarryOver: new BN(0),
+            carryOverCapPolicy: 0,
+            zeroBalancePolicy: 0,
+            roundsPerDay: 1,
+            minTotalLockedToDistribute: new BN(0),
+            weightByRemainingLock: false,
+            creatorInactivitySeconds: new BN(0),
+            creatorMinShareBps: 0,
+            depositMint: USDC_MINT,
+            dustThresholdK: 0,
+            fixedPageSize: false,
+            callbackProgram: PublicKey.default,
+            maxInvestorShareBps: 0,
+            keeperRewardLamports: new BN(0),
+            earningsUnlockTs: new BN(0),
+            ageBrackets: Array(4).fill({ minAgeSeconds: new BN(0), multiplierBps: 0 }),
+            distributionRateBps: 0,
+            requireFreshClaim: false,
+            minCrankableQuote: new BN(0),
+            maxInvestorShareOfY0Bps: 0,
+            configChangeCooloffSeconds: new BN(0),
+            maxClaimPerTx: new BN(0),
+            dustRecipient: PublicKey.default,
+            rejectDelegatedAccounts: false,
           })
           .accountsStrict({
             admin: newAdmin.publicKey,
@@ -288,6 +465,50 @@ describe("Distribution Config Tests (Bankrun)", () => {
             dailyCapLamports: new BN(50 * LAMPORTS_PER_SOL),
             creatorWallet: creatorWallet.publicKey,
             quoteMint: USDC_MINT,
+            autoCloseOnFinalPage: false,
+            vestingProgram: PublicKey.default,
+            vestedInvestorShareBps: 0,
+            creatorWalletTimelockSeconds: new BN(0),
+            remainderPolicy: 0,
+            treasuryWallet: PublicKey.default,
+            emitEvents: true,
+            eligibilityDelayDays: 1,
+            eligibilityRoot: Array(32).fill(0),
+            dailyCapQuoteUnits: new BN(0),
+            dayMaxDurationSeconds: new BN(0),
+            minDepositIntervalSeconds: new BN(0),
+            carryOverPolicy: 0,
+            fLockedFloorBps: 0,
+            fLockedCeilingBps: 0,
+            verifyTotalLocked: false,
+            totalLockedToleranceUnits: new BN(0),
+            creatorPayoutMint: PublicKey.default,
+            depositWindowStartTs: new BN(0),
+            depositWindowEndTs: new BN(0),
+            maxCarryOver: new BN(0),
+            carryOverCapPolicy: 0,
+            zeroBalancePolicy: 0,
+            roundsPerDay: 1,
+            minTotalLockedToDistribute: new BN(0),
+            weightByRemainingLock: false,
+            creatorInactivitySeconds: new BN(0),
+            creatorMinShareBps: 0,
+            depositMint: USDC_MINT,
+            dustThresholdK: 0,
+            fixedPageSize: false,
+            callbackProgram: PublicKey.default,
+            maxInvestorShareBps: 0,
+            keeperRewardLamports: new BN(0),
+            earningsUnlockTs: new BN(0),
+            ageBrackets: Array(4).fill({ minAgeSeconds: new BN(0), multiplierBps: 0 }),
+            distributionRateBps: 0,
+            requireFreshClaim: false,
+            minCrankableQuote: new BN(0),
+            maxInvestorShareOfY0Bps: 0,
+            configChangeCooloffSeconds: new BN(0),
+            maxClaimPerTx: new BN(0),
+            dustRecipient: PublicKey.default,
+            rejectDelegatedAccounts: false,
           })
           .accountsStrict({
             admin: admin.publicKey,
@@ -344,4 +565,103 @@ describe("Distribution Config Tests (Bankrun)", () => {
       );
     });
   });
+
+  describe("Quote Mint Allowlist", () => {
+    it("Should accept an allowlisted quote mint", async () => {
+      const env = await setupCrankEnv({ quoteMint: USDC_MINT });
+
+      const config = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DistributionConfig",
+        env.distributionConfigPDA
+      );
+      expect(config!.quoteMint.toString()).to.equal(USDC_MINT.toString());
+    });
+
+    it("Should reject a quote mint that is not allowlisted", async () => {
+      try {
+        await setupCrankEnv({ quoteMint: Keypair.generate().publicKey });
+        assert.fail("Should have rejected the unknown quote mint");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the unknown quote mint");
+        // QuoteMintNotAllowed = 6036
+        expect(error.toString()).to.include("0x1794");
+      }
+    });
+  });
+
+  describe("Replace Config", () => {
+    it("Should replace every field as a set and keep the PDA and bump", async () => {
+      const env = await setupCrankEnv();
+      const before = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DistributionConfig",
+        env.distributionConfigPDA
+      );
+
+      await replaceConfig(env, {
+        investorFeeShareBps: 7000,
+        vestedInvestorShareBps: 2500,
+        maxInvestorShareBps: 4000,
+        roundsPerDay: 2,
+      });
+
+      const config = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DistributionConfig",
+        env.distributionConfigPDA
+      );
+      expect(config!.investorFeeShareBps).to.equal(7000);
+      expect(config!.vestedInvestorShareBps).to.equal(2500);
+      expect(config!.maxInvestorShareBps).to.equal(4000);
+      expect(config!.roundsPerDay).to.equal(2);
+      expect(config!.bump).to.equal(before!.bump);
+      expect(config!.admin.toString()).to.equal(before!.admin.toString());
+    });
+
+    it("Should reject the whole replacement when one field is invalid", async () => {
+      const env = await setupCrankEnv();
+
+      // Valid fee share and vesting changes alongside an out-of-range f_locked band
+      try {
+        await replaceConfig(env, {
+          investorFeeShareBps: 7000,
+          vestedInvestorShareBps: 2500,
+          fLockedFloorBps: 9000,
+          fLockedCeilingBps: 1000,
+        });
+        assert.fail("Should have rejected the replacement");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the replacement");
+        // InvalidFLockedBand = 6067
+        expect(error.toString()).to.include("0x17b3");
+      }
+
+      const config = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "DistributionConfig",
+        env.distributionConfigPDA
+      );
+      expect(config!.investorFeeShareBps).to.equal(5000);
+      expect(config!.vestedInvestorShareBps).to.equal(0);
+      expect(config!.fLockedFloorBps).to.equal(0);
+    });
+
+    it("Should reject replacing the creator wallet", async () => {
+      const env = await setupCrankEnv();
+
+      try {
+        await replaceConfig(env, { creatorWallet: Keypair.generate().publicKey });
+        assert.fail("Should have rejected the creator wallet change");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the creator wallet change");
+        // ConfigFieldNotReplaceable = 6106
+        expect(error.toString()).to.include("0x17da");
+      }
+    });
+  });
 });
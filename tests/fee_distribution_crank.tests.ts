@@ -20,6 +20,16 @@ import {
   USDC_MINT,
   fetchAccount,
 } from "./utils/bankrun";
+import {
+  beginDistributionDay,
+  crankPage,
+  createInvestorWithDeposit,
+  distributeToInvestor,
+  getDepositorRecordPDA,
+  seedFeeVaults,
+  sendTx,
+  setupCrankEnv,
+} from "./utils/crank";
 import { StarFeeDistribution } from "../target/types/star_fee_distribution";
 import IDL from "../target/idl/star_fee_distribution.json";
 
@@ -112,6 +122,50 @@ describe("Fee Distribution Crank Tests (Bankrun)", () => {
           dailyCapLamports: new BN(100_000_000_000),
           creatorWallet: creatorWallet.publicKey,
           quoteMint: USDC_MINT,
+          autoCloseOnFinalPage: false,
+          vestingProgram: PublicKey.default,
+          vestedInvestorShareBps: 0,
+          creatorWalletTimelockSeconds: new BN(0),
+          remainderPolicy: 0,
+          treasuryWallet: PublicKey.default,
+          emitEvents: true,
+          eligibilityDelayDays: 1,
+          eligibilityRoot: Array(32).fill(0),
+          dailyCapQuoteUnits: new BN(0),
+          dayMaxDurationSeconds: new BN(0),
+          minDepositIntervalSeconds: new BN(0),
+          carryOverPolicy: 0,
+          fLockedFloorBps: 0,
+          fLockedCeilingBps: 0,
+          verifyTotalLocked: false,
+          totalLockedToleranceUnits: new BN(0),
+          creatorPayoutMint: PublicKey.default,
+          depositWindowStartTs: new BN(0),
+          depositWindowEndTs: new BN(0),
+          maxCarryOver: new BN(0),
+          carryOverCapPolicy: 0,
+          zeroBalancePolicy: 0,
+          roundsPerDay: 1,
+          minTotalLockedToDistribute: new BN(0),
+          weightByRemainingLock: false,
+          creatorInactivitySeconds: new BN(0),
+          creatorMinShareBps: 0,
+          depositMint: USDC_MINT,
+          dustThresholdK: 0,
+          fixedPageSize: false,
+          callbackProgram: PublicKey.default,
+          maxInvestorShareBps: 0,
+          keeperRewardLamports: new BN(0),
+          earningsUnlockTs: new BN(0),
+          ageBrackets: Array(4).fill({ minAgeSeconds: new BN(0), multiplierBps: 0 }),
+          distributionRateBps: 0,
+          requireFreshClaim: false,
+          minCrankableQuote: new BN(0),
+          maxInvestorShareOfY0Bps: 0,
+          configChangeCooloffSeconds: new BN(0),
+          maxClaimPerTx: new BN(0),
+          dustRecipient: PublicKey.default,
+          rejectDelegatedAccounts: false,
         })
         .accountsStrict({
           admin: admin.publicKey,
@@ -148,6 +202,8 @@ describe("Fee Distribution Crank Tests (Bankrun)", () => {
             program.programId
           )[0],
           vaultStats: vaultStatsPDA,
+          crankState: distributionStatePDA,
+          distributionConfig: distributionConfigPDA,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -172,4 +228,59 @@ describe("Fee Distribution Crank Tests (Bankrun)", () => {
       );
     });
   });
+
+  describe("Quote Vault Enforcement", () => {
+    it("Should reject a non-canonical quote vault for investor payouts", async () => {
+      // The investor's record and the day's crank state exist, so the vault is the only bad account
+      const env = await setupCrankEnv();
+      const { investor, usdcAccount } = await createInvestorWithDeposit(
+        env,
+        new BN(1_000_000_000)
+      );
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 1, isFinalPage: false });
+
+      // The investor's own USDC account is a valid quote token account,
+      // but it is not the [fee_vault, quote_mint] PDA the crank reads from
+      const tx = await env.program.methods
+        .distributeToInvestor({
+          totalInvestorFee: new BN(500_000),
+        })
+        .accountsStrict({
+          feeCollector: env.feeCollectorPDA,
+          programQuoteVault: usdcAccount,
+          quoteMint: USDC_MINT,
+          investorQuoteAccount: usdcAccount,
+          depositorRecord: getDepositorRecordPDA(env, investor.publicKey),
+          depositVault: null,
+          vaultStats: env.vaultStatsPDA,
+          distributionConfig: env.distributionConfigPDA,
+          crankState: env.crankStatePDA,
+          investor: investor.publicKey,
+          vestingAccount: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .transaction();
+
+      try {
+        await sendTx(env.context, tx, [investor]);
+        assert.fail("Should have rejected a non-canonical quote vault");
+      } catch (error) {
+        expect(error.toString()).to.not.include(
+          "Should have rejected a non-canonical quote vault"
+        );
+        // ConstraintSeeds = 2006, raised for program_quote_vault
+        expect(error.toString()).to.include("0x7d6");
+      }
+
+      // The same payout from the canonical vault goes through
+      const investorBefore = await getTokenAccount(env.context.banksClient, usdcAccount);
+      await distributeToInvestor(env, investor, usdcAccount, new BN(500_000));
+      const investorAfter = await getTokenAccount(env.context.banksClient, usdcAccount);
+      expect(
+        (investorAfter!.amount - investorBefore!.amount).toString()
+      ).to.equal("500000");
+    });
+  });
 });
@@ -0,0 +1,777 @@
+import { BN, Program } from "@coral-xyz/anchor";
+import { ProgramTestContext } from "solana-bankrun";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect, assert } from "chai";
+import {
+  LOCAL_ADMIN_KEYPAIR,
+  setTokenAccount,
+  startTest,
+  USDC_MINT,
+  fetchAccount,
+} from "./utils/bankrun";
+import { DAMM_V2_PROGRAM_ID } from "./utils/damm_v2_integration";
+import { StarFeeDistribution } from "../target/types/star_fee_distribution";
+import IDL from "../target/idl/star_fee_distribution.json";
+
+describe("Honorary Position Tests (Bankrun)", () => {
+  let context: ProgramTestContext;
+  let program: Program<StarFeeDistribution>;
+  let admin: Keypair;
+
+  // Mock pool accounts (bankrun only loads a DAMM v2 mock implementing claim_position_fee)
+  let pool: Keypair;
+  let position: Keypair;
+  let positionNftMint: Keypair;
+  let baseMint: Keypair;
+
+  // PDAs
+  let positionStatePDA: PublicKey;
+  let distributionConfigPDA: PublicKey;
+  let positionRegistryPDA: PublicKey;
+
+  // Authorizes a mock position to claim, as add_position would
+  async function registerPosition(claimPosition: PublicKey) {
+    const registry = await program.coder.accounts.encode("positionRegistry", {
+      config: distributionConfigPDA,
+      positions: [claimPosition],
+      bump: 255,
+    });
+    context.setAccount(positionRegistryPDA, {
+      executable: false,
+      owner: program.programId,
+      lamports: 1_000_000_000,
+      data: registry,
+    });
+  }
+
+  before(async () => {
+    context = await startTest();
+    admin = LOCAL_ADMIN_KEYPAIR;
+    pool = Keypair.generate();
+    position = Keypair.generate();
+    positionNftMint = Keypair.generate();
+    baseMint = Keypair.generate();
+
+    program = new Program<StarFeeDistribution>(
+      IDL as StarFeeDistribution,
+      {
+        connection: context.banksClient as any,
+      } as any
+    );
+
+    [positionStatePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("position_state"), pool.publicKey.toBuffer()],
+      program.programId
+    );
+    [distributionConfigPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("distribution_config")],
+      program.programId
+    );
+    [positionRegistryPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("position_registry")],
+      program.programId
+    );
+  });
+
+  describe("Initialization Guard", () => {
+    it("Should reject a second initialization for the same pool", async () => {
+      // Simulate a pool whose honorary position has already been initialized
+      const existingState = await program.coder.accounts.encode(
+        "positionState",
+        {
+          pool: pool.publicKey,
+          position: position.publicKey,
+          positionNftMint: positionNftMint.publicKey,
+          baseMint: baseMint.publicKey,
+          quoteMint: USDC_MINT,
+          createdTimestamp: new BN(1),
+          lastClaimDay: 0,
+          bump: 255,
+        }
+      );
+      context.setAccount(positionStatePDA, {
+        executable: false,
+        owner: program.programId,
+        lamports: 1_000_000_000,
+        data: existingState,
+      });
+
+      const storedState = await fetchAccount(
+        context.banksClient,
+        program,
+        "PositionState",
+        positionStatePDA
+      );
+      expect(storedState).to.not.be.null;
+      expect(storedState!.position.toString()).to.equal(
+        position.publicKey.toString()
+      );
+
+      const secondPosition = Keypair.generate();
+      const secondNftMint = Keypair.generate();
+
+      try {
+        const tx = await program.methods
+          .initializeHonoraryPosition({
+            baseWeightBps: 0,
+            quoteWeightBps: 10000,
+            lowerTick: -443636,
+            upperTick: 443636,
+            feeTier: 100,
+            liquidityDelta: new BN(0),
+          })
+          .accountsStrict({
+            signer: admin.publicKey,
+            ammProgram: DAMM_V2_PROGRAM_ID,
+            pool: pool.publicKey,
+            position: secondPosition.publicKey,
+            positionState: positionStatePDA,
+            positionNftMint: secondNftMint.publicKey,
+            positionNftAccount: Keypair.generate().publicKey,
+            poolAuthority: Keypair.generate().publicKey,
+            baseMint: baseMint.publicKey,
+            quoteMint: USDC_MINT,
+            tokenAVault: Keypair.generate().publicKey,
+            tokenBVault: Keypair.generate().publicKey,
+            userTokenAAccount: Keypair.generate().publicKey,
+            userTokenBAccount: Keypair.generate().publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            eventAuthority: Keypair.generate().publicKey,
+          })
+          .transaction();
+
+        const [recentBlockhash] = await context.banksClient.getLatestBlockhash();
+        tx.recentBlockhash = recentBlockhash;
+        tx.sign(admin);
+
+        await context.banksClient.processTransaction(tx);
+        assert.fail("Should have failed to initialize the position twice");
+      } catch (error) {
+        expect(error).to.exist;
+        expect(error.toString()).to.not.include(
+          "Should have failed to initialize the position twice"
+        );
+      }
+
+      // The canonical position must be unchanged
+      const stateAfter = await fetchAccount(
+        context.banksClient,
+        program,
+        "PositionState",
+        positionStatePDA
+      );
+      expect(stateAfter!.position.toString()).to.equal(
+        position.publicKey.toString()
+      );
+    });
+  });
+
+  describe("Quote-only pre-check", () => {
+    // Layout of DAMM v2 Position/Pool accounts (8-byte discriminator + bytemuck body)
+    const POSITION_DISCRIMINATOR = [170, 188, 143, 228, 122, 64, 247, 208];
+    const POSITION_SIZE = 8 + 400;
+    const POSITION_FEE_A_PENDING_OFFSET = 8 + 128;
+    const POOL_DISCRIMINATOR = [241, 154, 109, 4, 17, 177, 109, 188];
+    const POOL_SIZE = 8 + 1104;
+
+    it("Should reject a position with pending base fees before the claim CPI", async () => {
+      const claimPool = Keypair.generate().publicKey;
+      const claimPosition = Keypair.generate().publicKey;
+
+      const positionData = Buffer.alloc(POSITION_SIZE);
+      Buffer.from(POSITION_DISCRIMINATOR).copy(positionData, 0);
+      claimPool.toBuffer().copy(positionData, 8);
+      positionData.writeBigUInt64LE(BigInt(1_000), POSITION_FEE_A_PENDING_OFFSET);
+      context.setAccount(claimPosition, {
+        executable: false,
+        owner: DAMM_V2_PROGRAM_ID,
+        lamports: 1_000_000_000,
+        data: positionData,
+      });
+
+      const poolData = Buffer.alloc(POOL_SIZE);
+      Buffer.from(POOL_DISCRIMINATOR).copy(poolData, 0);
+      context.setAccount(claimPool, {
+        executable: false,
+        owner: DAMM_V2_PROGRAM_ID,
+        lamports: 1_000_000_000,
+        data: poolData,
+      });
+
+      const positionNftAccount = Keypair.generate().publicKey;
+      context.setAccount(positionNftAccount, {
+        executable: false,
+        owner: TOKEN_PROGRAM_ID,
+        lamports: 1_000_000_000,
+        data: Buffer.alloc(165),
+      });
+
+      const [feeCollectorPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_collector")],
+        program.programId
+      );
+      const [baseFeeVaultPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_vault"), baseMint.publicKey.toBuffer()],
+        program.programId
+      );
+      const [quoteFeeVaultPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_vault"), USDC_MINT.toBuffer()],
+        program.programId
+      );
+      setTokenAccount(context, baseFeeVaultPDA, baseMint.publicKey, feeCollectorPDA, BigInt(0));
+      setTokenAccount(context, quoteFeeVaultPDA, USDC_MINT, feeCollectorPDA, BigInt(0));
+
+      await registerPosition(claimPosition);
+      const tx = await program.methods
+        .claimFeesToPda()
+        .accountsStrict({
+          feeCollector: feeCollectorPDA,
+          ammProgram: DAMM_V2_PROGRAM_ID,
+          pool: claimPool,
+          position: claimPosition,
+          positionNftAccount,
+          poolAuthority: Keypair.generate().publicKey,
+          baseMint: baseMint.publicKey,
+          quoteMint: USDC_MINT,
+          tokenAVault: Keypair.generate().publicKey,
+          tokenBVault: Keypair.generate().publicKey,
+          programTokenAVault: baseFeeVaultPDA,
+          programTokenBVault: quoteFeeVaultPDA,
+          positionState: null,
+          crankState: null,
+          distributionConfig: distributionConfigPDA,
+          positionRegistry: positionRegistryPDA,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          eventAuthority: Keypair.generate().publicKey,
+        })
+        .transaction();
+
+      const [recentBlockhash] = await context.banksClient.getLatestBlockhash();
+      tx.recentBlockhash = recentBlockhash;
+      tx.feePayer = admin.publicKey;
+      tx.sign(admin);
+
+      // The DAMM v2 mock rejects these accounts, so reaching the CPI would fail with a different error
+      try {
+        await context.banksClient.processTransaction(tx);
+        assert.fail("Should have rejected pending base fees");
+      } catch (error) {
+        expect(error.toString()).to.include("0x17a7");
+      }
+    });
+  });
+
+  describe("Degenerate quote == base pool", () => {
+    it("Should reject initializing a position on a pool quoting USDC against itself", async () => {
+      const degeneratePool = Keypair.generate();
+      const [degenerateStatePDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position_state"), degeneratePool.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const tx = await program.methods
+        .initializeHonoraryPosition({
+          baseWeightBps: 0,
+          quoteWeightBps: 10000,
+          lowerTick: -443636,
+          upperTick: 443636,
+          feeTier: 100,
+          liquidityDelta: new BN(0),
+        })
+        .accountsStrict({
+          signer: admin.publicKey,
+          ammProgram: DAMM_V2_PROGRAM_ID,
+          pool: degeneratePool.publicKey,
+          position: Keypair.generate().publicKey,
+          positionState: degenerateStatePDA,
+          positionNftMint: Keypair.generate().publicKey,
+          positionNftAccount: Keypair.generate().publicKey,
+          poolAuthority: Keypair.generate().publicKey,
+          baseMint: USDC_MINT,
+          quoteMint: USDC_MINT,
+          tokenAVault: Keypair.generate().publicKey,
+          tokenBVault: Keypair.generate().publicKey,
+          userTokenAAccount: Keypair.generate().publicKey,
+          userTokenBAccount: Keypair.generate().publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          eventAuthority: Keypair.generate().publicKey,
+        })
+        .transaction();
+
+      const [recentBlockhash] = await context.banksClient.getLatestBlockhash();
+      tx.recentBlockhash = recentBlockhash;
+      tx.sign(admin);
+
+      try {
+        await context.banksClient.processTransaction(tx);
+        assert.fail("Should have rejected identical base and quote mints");
+      } catch (error) {
+        expect(error.toString()).to.not.include(
+          "Should have rejected identical base and quote mints"
+        );
+        expect(error.toString()).to.include("0x1778");
+      }
+
+      // No position state is left behind for the degenerate pool
+      const state = await fetchAccount(
+        context.banksClient,
+        program,
+        "PositionState",
+        degenerateStatePDA
+      );
+      expect(state).to.be.null;
+    });
+
+    it("Should reject claiming fees when base and quote mints are identical", async () => {
+      const claimPool = Keypair.generate().publicKey;
+      const claimPosition = Keypair.generate().publicKey;
+      const positionNftAccount = Keypair.generate().publicKey;
+      for (const [account, owner, size] of [
+        [claimPosition, DAMM_V2_PROGRAM_ID, 8 + 400],
+        [claimPool, DAMM_V2_PROGRAM_ID, 8 + 1104],
+        [positionNftAccount, TOKEN_PROGRAM_ID, 165],
+      ] as [PublicKey, PublicKey, number][]) {
+        context.setAccount(account, {
+          executable: false,
+          owner,
+          lamports: 1_000_000_000,
+          data: Buffer.alloc(size),
+        });
+      }
+
+      const [feeCollectorPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_collector")],
+        program.programId
+      );
+      const [quoteFeeVaultPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_vault"), USDC_MINT.toBuffer()],
+        program.programId
+      );
+      setTokenAccount(context, quoteFeeVaultPDA, USDC_MINT, feeCollectorPDA, BigInt(0));
+
+      await registerPosition(claimPosition);
+
+      // Identical mints collapse both program vaults onto the same PDA
+      const tx = await program.methods
+        .claimFeesToPda()
+        .accountsStrict({
+          feeCollector: feeCollectorPDA,
+          ammProgram: DAMM_V2_PROGRAM_ID,
+          pool: claimPool,
+          position: claimPosition,
+          positionNftAccount,
+          poolAuthority: Keypair.generate().publicKey,
+          baseMint: USDC_MINT,
+          quoteMint: USDC_MINT,
+          tokenAVault: Keypair.generate().publicKey,
+          tokenBVault: Keypair.generate().publicKey,
+          programTokenAVault: quoteFeeVaultPDA,
+          programTokenBVault: quoteFeeVaultPDA,
+          positionState: null,
+          crankState: null,
+          distributionConfig: distributionConfigPDA,
+          positionRegistry: positionRegistryPDA,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          eventAuthority: Keypair.generate().publicKey,
+        })
+        .transaction();
+
+      const [recentBlockhash] = await context.banksClient.getLatestBlockhash();
+      tx.recentBlockhash = recentBlockhash;
+      tx.feePayer = admin.publicKey;
+      tx.sign(admin);
+
+      try {
+        await context.banksClient.processTransaction(tx);
+        assert.fail("Should have rejected identical base and quote mints");
+      } catch (error) {
+        expect(error.toString()).to.not.include(
+          "Should have rejected identical base and quote mints"
+        );
+        expect(error.toString()).to.include("0x1778");
+      }
+    });
+  });
+
+  describe("Fee tier check", () => {
+    it("Should reject a fee tier the pool does not charge before the create_position CPI", async () => {
+      // 0.3% pool: DAMM v2 base fee numerators are over 1e9
+      const tieredPool = Keypair.generate().publicKey;
+      const poolData = Buffer.alloc(8 + 1104);
+      Buffer.from([241, 154, 109, 4, 17, 177, 109, 188]).copy(poolData, 0);
+      poolData.writeBigUInt64LE(BigInt(3_000_000), 8);
+      context.setAccount(tieredPool, {
+        executable: false,
+        owner: DAMM_V2_PROGRAM_ID,
+        lamports: 1_000_000_000,
+        data: poolData,
+      });
+      const [tieredStatePDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position_state"), tieredPool.toBuffer()],
+        program.programId
+      );
+
+      const tx = await program.methods
+        .initializeHonoraryPosition({
+          baseWeightBps: 0,
+          quoteWeightBps: 10000,
+          lowerTick: -443636,
+          upperTick: 443636,
+          feeTier: 100,
+          liquidityDelta: new BN(0),
+        })
+        .accountsStrict({
+          signer: admin.publicKey,
+          ammProgram: DAMM_V2_PROGRAM_ID,
+          pool: tieredPool,
+          position: Keypair.generate().publicKey,
+          positionState: tieredStatePDA,
+          positionNftMint: Keypair.generate().publicKey,
+          positionNftAccount: Keypair.generate().publicKey,
+          poolAuthority: Keypair.generate().publicKey,
+          baseMint: baseMint.publicKey,
+          quoteMint: USDC_MINT,
+          tokenAVault: Keypair.generate().publicKey,
+          tokenBVault: Keypair.generate().publicKey,
+          userTokenAAccount: Keypair.generate().publicKey,
+          userTokenBAccount: Keypair.generate().publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          eventAuthority: Keypair.generate().publicKey,
+        })
+        .transaction();
+
+      const [recentBlockhash] = await context.banksClient.getLatestBlockhash();
+      tx.recentBlockhash = recentBlockhash;
+      tx.sign(admin);
+
+      // The DAMM v2 mock rejects these accounts, so reaching the CPI would fail with a different error
+      try {
+        await context.banksClient.processTransaction(tx);
+        assert.fail("Should have rejected the mismatched fee tier");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected the mismatched fee tier");
+        // FeeTierMismatch = 6079
+        expect(error.toString()).to.include("0x17bf");
+      }
+    });
+  });
+  describe("Quote-only liquidity", () => {
+    const mockPool = (): PublicKey => {
+      const key = Keypair.generate().publicKey;
+      const poolData = Buffer.alloc(8 + 1104);
+      Buffer.from([241, 154, 109, 4, 17, 177, 109, 188]).copy(poolData, 0);
+      // Fee tier 100 and a price of 1 (sqrt_price = 2^64, current tick 0)
+      poolData.writeBigUInt64LE(BigInt(100_000), 8);
+      poolData.writeBigUInt64LE(BigInt(0), 8 + 448);
+      poolData.writeBigUInt64LE(BigInt(1), 8 + 448 + 8);
+      context.setAccount(key, {
+        executable: false,
+        owner: DAMM_V2_PROGRAM_ID,
+        lamports: 1_000_000_000,
+        data: poolData,
+      });
+      return key;
+    };
+
+    const sendWithLiquidity = async (lowerTick: number, upperTick: number) => {
+      const liquidityPool = mockPool();
+      const [liquidityStatePDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position_state"), liquidityPool.toBuffer()],
+        program.programId
+      );
+
+      const tx = await program.methods
+        .initializeHonoraryPosition({
+          baseWeightBps: 0,
+          quoteWeightBps: 10000,
+          lowerTick,
+          upperTick,
+          feeTier: 100,
+          liquidityDelta: new BN(1_000_000),
+        })
+        .accountsStrict({
+          signer: admin.publicKey,
+          ammProgram: DAMM_V2_PROGRAM_ID,
+          pool: liquidityPool,
+          position: Keypair.generate().publicKey,
+          positionState: liquidityStatePDA,
+          positionNftMint: Keypair.generate().publicKey,
+          positionNftAccount: Keypair.generate().publicKey,
+          poolAuthority: Keypair.generate().publicKey,
+          baseMint: baseMint.publicKey,
+          quoteMint: USDC_MINT,
+          tokenAVault: Keypair.generate().publicKey,
+          tokenBVault: Keypair.generate().publicKey,
+          userTokenAAccount: Keypair.generate().publicKey,
+          userTokenBAccount: Keypair.generate().publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          eventAuthority: Keypair.generate().publicKey,
+        })
+        .transaction();
+
+      const [recentBlockhash] = await context.banksClient.getLatestBlockhash();
+      tx.recentBlockhash = recentBlockhash;
+      tx.sign(admin);
+      await context.banksClient.processTransaction(tx);
+    };
+
+    it("Rejects non-zero liquidity across the full range", async () => {
+      try {
+        await sendWithLiquidity(-443636, 443636);
+        assert.fail("Should have rejected full-range liquidity");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected full-range liquidity");
+        // QuoteOnlyViolation = 6097
+        expect(error.toString()).to.include("0x17d1");
+      }
+    });
+
+    it("Rejects non-zero liquidity straddling the current price", async () => {
+      try {
+        await sendWithLiquidity(-1000, 1000);
+        assert.fail("Should have rejected liquidity holding base");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected liquidity holding base");
+        // QuoteOnlyViolation = 6097
+        expect(error.toString()).to.include("0x17d1");
+      }
+    });
+
+    it("Allows single-sided quote liquidity below the current price", async () => {
+      // The DAMM v2 mock has no create_position, so the transaction fails past the quote-only guard
+      try {
+        await sendWithLiquidity(-1000, -10);
+        assert.fail("Mock accounts should fail the CPI");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Mock accounts should fail the CPI");
+        expect(error.toString()).to.not.include("0x17d1");
+      }
+    });
+  });
+
+  describe("Fee destination", () => {
+    const POSITION_DISCRIMINATOR = [170, 188, 143, 228, 122, 64, 247, 208];
+    const POSITION_SIZE = 8 + 400;
+    const POSITION_FEE_A_PENDING_OFFSET = 8 + 128;
+    const POOL_DISCRIMINATOR = [241, 154, 109, 4, 17, 177, 109, 188];
+    const POOL_SIZE = 8 + 1104;
+
+    const [feeCollectorPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_collector")],
+      new PublicKey(IDL.address)
+    );
+
+    // Claims from a position with pending base fees into `destination`, so a destination that
+    // passes account validation fails on the quote-only pre-check instead
+    async function claimInto(destination: PublicKey) {
+      const claimPool = Keypair.generate().publicKey;
+      const claimPosition = Keypair.generate().publicKey;
+
+      const positionData = Buffer.alloc(POSITION_SIZE);
+      Buffer.from(POSITION_DISCRIMINATOR).copy(positionData, 0);
+      claimPool.toBuffer().copy(positionData, 8);
+      positionData.writeBigUInt64LE(BigInt(1_000), POSITION_FEE_A_PENDING_OFFSET);
+      context.setAccount(claimPosition, {
+        executable: false,
+        owner: DAMM_V2_PROGRAM_ID,
+        lamports: 1_000_000_000,
+        data: positionData,
+      });
+
+      const poolData = Buffer.alloc(POOL_SIZE);
+      Buffer.from(POOL_DISCRIMINATOR).copy(poolData, 0);
+      context.setAccount(claimPool, {
+        executable: false,
+        owner: DAMM_V2_PROGRAM_ID,
+        lamports: 1_000_000_000,
+        data: poolData,
+      });
+
+      const positionNftAccount = Keypair.generate().publicKey;
+      context.setAccount(positionNftAccount, {
+        executable: false,
+        owner: TOKEN_PROGRAM_ID,
+        lamports: 1_000_000_000,
+        data: Buffer.alloc(165),
+      });
+
+      const [baseFeeVaultPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_vault"), baseMint.publicKey.toBuffer()],
+        program.programId
+      );
+      setTokenAccount(context, baseFeeVaultPDA, baseMint.publicKey, feeCollectorPDA, BigInt(0));
+
+      await registerPosition(claimPosition);
+      const tx = await program.methods
+        .claimFeesToPda()
+        .accountsStrict({
+          feeCollector: feeCollectorPDA,
+          ammProgram: DAMM_V2_PROGRAM_ID,
+          pool: claimPool,
+          position: claimPosition,
+          positionNftAccount,
+          poolAuthority: Keypair.generate().publicKey,
+          baseMint: baseMint.publicKey,
+          quoteMint: USDC_MINT,
+          tokenAVault: Keypair.generate().publicKey,
+          tokenBVault: Keypair.generate().publicKey,
+          programTokenAVault: baseFeeVaultPDA,
+          programTokenBVault: destination,
+          positionState: null,
+          crankState: null,
+          distributionConfig: distributionConfigPDA,
+          positionRegistry: positionRegistryPDA,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          eventAuthority: Keypair.generate().publicKey,
+        })
+        .transaction();
+
+      const [recentBlockhash] = await context.banksClient.getLatestBlockhash();
+      tx.recentBlockhash = recentBlockhash;
+      tx.feePayer = admin.publicKey;
+      tx.sign(admin);
+
+      await context.banksClient.processTransaction(tx);
+    }
+
+    it("Should accept an alternate fee_collector-owned quote account as the destination", async () => {
+      const alternateFeePool = Keypair.generate().publicKey;
+      setTokenAccount(context, alternateFeePool, USDC_MINT, feeCollectorPDA, BigInt(0));
+
+      // PendingBaseFeesDetected = 6055: the destination passed validation
+      try {
+        await claimInto(alternateFeePool);
+        assert.fail("Should have rejected pending base fees");
+      } catch (error) {
+        expect(error.toString()).to.include("0x17a7");
+      }
+    });
+
+    it("Should reject the deposit vault as the destination", async () => {
+      const [depositVaultPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("deposit_vault"), USDC_MINT.toBuffer()],
+        program.programId
+      );
+      setTokenAccount(context, depositVaultPDA, USDC_MINT, feeCollectorPDA, BigInt(0));
+
+      // InvalidFeeDestination = 6101
+      try {
+        await claimInto(depositVaultPDA);
+        assert.fail("Should have rejected the deposit vault");
+      } catch (error) {
+        expect(error.toString()).to.include("0x17d5");
+      }
+    });
+
+    it("Should reject a quote account not owned by the fee_collector", async () => {
+      const foreignAccount = Keypair.generate().publicKey;
+      setTokenAccount(context, foreignAccount, USDC_MINT, admin.publicKey, BigInt(0));
+
+      // ConstraintTokenOwner = 2015
+      try {
+        await claimInto(foreignAccount);
+        assert.fail("Should have rejected a foreign-owned destination");
+      } catch (error) {
+        expect(error.toString()).to.include("0x7df");
+      }
+    });
+  });
+
+  describe("DAMM v2 version", () => {
+    const POSITION_DISCRIMINATOR = [170, 188, 143, 228, 122, 64, 247, 208];
+    const POSITION_SIZE = 8 + 400;
+    const POOL_DISCRIMINATOR = [241, 154, 109, 4, 17, 177, 109, 188];
+    const POOL_SIZE = 8 + 1104;
+
+    it("Should reject claiming from a pool with an unsupported layout", async () => {
+      const claimPool = Keypair.generate().publicKey;
+      const claimPosition = Keypair.generate().publicKey;
+
+      const positionData = Buffer.alloc(POSITION_SIZE);
+      Buffer.from(POSITION_DISCRIMINATOR).copy(positionData, 0);
+      claimPool.toBuffer().copy(positionData, 8);
+      context.setAccount(claimPosition, {
+        executable: false,
+        owner: DAMM_V2_PROGRAM_ID,
+        lamports: 1_000_000_000,
+        data: positionData,
+      });
+
+      // A pool written by an upgraded DAMM v2 program with a grown layout
+      const poolData = Buffer.alloc(POOL_SIZE + 64);
+      Buffer.from(POOL_DISCRIMINATOR).copy(poolData, 0);
+      context.setAccount(claimPool, {
+        executable: false,
+        owner: DAMM_V2_PROGRAM_ID,
+        lamports: 1_000_000_000,
+        data: poolData,
+      });
+
+      const positionNftAccount = Keypair.generate().publicKey;
+      context.setAccount(positionNftAccount, {
+        executable: false,
+        owner: TOKEN_PROGRAM_ID,
+        lamports: 1_000_000_000,
+        data: Buffer.alloc(165),
+      });
+
+      const [feeCollectorPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_collector")],
+        program.programId
+      );
+      const [baseFeeVaultPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_vault"), baseMint.publicKey.toBuffer()],
+        program.programId
+      );
+      const [quoteFeeVaultPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("fee_vault"), USDC_MINT.toBuffer()],
+        program.programId
+      );
+      setTokenAccount(context, baseFeeVaultPDA, baseMint.publicKey, feeCollectorPDA, BigInt(0));
+      setTokenAccount(context, quoteFeeVaultPDA, USDC_MINT, feeCollectorPDA, BigInt(0));
+
+      await registerPosition(claimPosition);
+      const tx = await program.methods
+        .claimFeesToPda()
+        .accountsStrict({
+          feeCollector: feeCollectorPDA,
+          ammProgram: DAMM_V2_PROGRAM_ID,
+          pool: claimPool,
+          position: claimPosition,
+          positionNftAccount,
+          poolAuthority: Keypair.generate().publicKey,
+          baseMint: baseMint.publicKey,
+          quoteMint: USDC_MINT,
+          tokenAVault: Keypair.generate().publicKey,
+          tokenBVault: Keypair.generate().publicKey,
+          programTokenAVault: baseFeeVaultPDA,
+          programTokenBVault: quoteFeeVaultPDA,
+          positionState: null,
+          crankState: null,
+          distributionConfig: distributionConfigPDA,
+          positionRegistry: positionRegistryPDA,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          eventAuthority: Keypair.generate().publicKey,
+        })
+        .transaction();
+
+      const [recentBlockhash] = await context.banksClient.getLatestBlockhash();
+      tx.recentBlockhash = recentBlockhash;
+      tx.feePayer = admin.publicKey;
+      tx.sign(admin);
+
+      // UnsupportedAmmVersion = 6102
+      try {
+        await context.banksClient.processTransaction(tx);
+        assert.fail("Should have rejected the unsupported pool layout");
+      } catch (error) {
+        expect(error.toString()).to.include("0x17d6");
+      }
+    });
+  });
+});
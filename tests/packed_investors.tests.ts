@@ -0,0 +1,228 @@
+import { BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect, assert } from "chai";
+import {
+  fetchAccount,
+  fundSol,
+  fundUsdc,
+  getOrCreateAta,
+  getTokenAccount,
+  USDC_MINT,
+} from "./utils/bankrun";
+import {
+  CrankEnv,
+  beginDistributionDay,
+  crankPage,
+  distributePackedPage,
+  getPackedInvestorsPDA,
+  initializePackedInvestors,
+  packedBalanceChange,
+  parseEvents,
+  seedFeeVaults,
+  setupCrankEnv,
+} from "./utils/crank";
+
+describe("Packed Investors Tests (Bankrun)", () => {
+  async function createPackedInvestor(env: CrankEnv) {
+    const investor = Keypair.generate();
+    await fundSol(env.context.banksClient, env.admin, [investor.publicKey]);
+    await fundUsdc(env.context.banksClient, [investor.publicKey]);
+    const usdcAccount = await getOrCreateAta(
+      env.context.banksClient,
+      env.admin,
+      USDC_MINT,
+      investor.publicKey
+    );
+    return { investor, usdcAccount };
+  }
+
+  async function fetchPacked(env: CrankEnv) {
+    return fetchAccount(
+      env.context.banksClient,
+      env.program,
+      "PackedInvestors",
+      getPackedInvestorsPDA(env)
+    );
+  }
+
+  describe("Slot management", () => {
+    it("Should add, update and remove investors within the packed account", async () => {
+      const env = await setupCrankEnv();
+      await initializePackedInvestors(env);
+      const alice = await createPackedInvestor(env);
+      const bob = await createPackedInvestor(env);
+      const carol = await createPackedInvestor(env);
+
+      await packedBalanceChange(env, "deposit", alice.investor, alice.usdcAccount, new BN(1_000_000_000), null);
+      await packedBalanceChange(env, "deposit", bob.investor, bob.usdcAccount, new BN(3_000_000_000), null);
+      await packedBalanceChange(env, "deposit", alice.investor, alice.usdcAccount, new BN(500_000_000), 0);
+
+      let packed = await fetchPacked(env);
+      expect(packed!.entries.length).to.equal(2);
+      expect(packed!.entries[0].investor.toString()).to.equal(alice.investor.publicKey.toString());
+      expect(packed!.entries[0].usdcBalance.toString()).to.equal("1500000000");
+      expect(packed!.entries[1].usdcBalance.toString()).to.equal("3000000000");
+      expect(packed!.totalUsdcBalance.toString()).to.equal("4500000000");
+
+      // A slot can only be used by its own investor
+      try {
+        await packedBalanceChange(env, "deposit", alice.investor, alice.usdcAccount, new BN(1_000_000), 1);
+        assert.fail("Should have rejected another investor's slot");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected another investor's slot");
+      }
+
+      const bobBefore = await getTokenAccount(env.context.banksClient, bob.usdcAccount);
+      await packedBalanceChange(env, "withdraw", bob.investor, bob.usdcAccount, new BN(3_000_000_000), 1);
+      const bobAfter = await getTokenAccount(env.context.banksClient, bob.usdcAccount);
+      expect((bobAfter!.amount - bobBefore!.amount).toString()).to.equal("3000000000");
+
+      packed = await fetchPacked(env);
+      expect(packed!.activeCount).to.equal(1);
+      expect(packed!.entries[1].flags).to.equal(0);
+      expect(packed!.entries[1].investor.toString()).to.equal(PublicKey.default.toString());
+
+      // The freed slot is reused
+      await packedBalanceChange(env, "deposit", carol.investor, carol.usdcAccount, new BN(2_000_000_000), null);
+      packed = await fetchPacked(env);
+      expect(packed!.entries.length).to.equal(2);
+      expect(packed!.entries[1].investor.toString()).to.equal(carol.investor.publicKey.toString());
+
+      const vaultStats = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "VaultStats",
+        env.vaultStatsPDA
+      );
+      expect(vaultStats!.currentTotalWeightedUsdc.toString()).to.equal("3500000000");
+      expect(vaultStats!.depositorCount).to.equal(2);
+    });
+  });
+
+  describe("Page distribution", () => {
+    it("Should pay a slice of packed investors per page by weight", async () => {
+      const env = await setupCrankEnv();
+      await initializePackedInvestors(env);
+      const small = await createPackedInvestor(env);
+      const large = await createPackedInvestor(env);
+      await packedBalanceChange(env, "deposit", small.investor, small.usdcAccount, new BN(1_000_000_000), null);
+      await packedBalanceChange(env, "deposit", large.investor, large.usdcAccount, new BN(3_000_000_000), null);
+
+      // 500_000 of the 1_000_000 claimed goes to investors
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+
+      // Pages must start at the packed cursor
+      try {
+        await distributePackedPage(env, 1, 1, [large.usdcAccount]);
+        assert.fail("Should have rejected a page past the cursor");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected a page past the cursor");
+      }
+
+      const smallBefore = await getTokenAccount(env.context.banksClient, small.usdcAccount);
+      const largeBefore = await getTokenAccount(env.context.banksClient, large.usdcAccount);
+      await distributePackedPage(env, 0, 1, [small.usdcAccount]);
+      await distributePackedPage(env, 1, 1, [large.usdcAccount]);
+      const smallAfter = await getTokenAccount(env.context.banksClient, small.usdcAccount);
+      const largeAfter = await getTokenAccount(env.context.banksClient, large.usdcAccount);
+      expect((smallAfter!.amount - smallBefore!.amount).toString()).to.equal("125000");
+      expect((largeAfter!.amount - largeBefore!.amount).toString()).to.equal("375000");
+
+      // A slice that was already paid today cannot be replayed
+      try {
+        await distributePackedPage(env, 0, 1, [small.usdcAccount]);
+        assert.fail("Should have rejected a replayed page");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected a replayed page");
+      }
+
+      const crankState = await fetchAccount(
+        env.context.banksClient,
+        env.program,
+        "CrankState",
+        env.crankStatePDA
+      );
+      expect(crankState!.dailyDistributed.toString()).to.equal("500000");
+      expect(crankState!.investorsPaidToday).to.equal(2);
+    });
+  });
+
+  describe("Page payouts event", () => {
+    it("Should list exactly the investors paid by a page", async () => {
+      const env = await setupCrankEnv();
+      await initializePackedInvestors(env);
+      const small = await createPackedInvestor(env);
+      const leaver = await createPackedInvestor(env);
+      const large = await createPackedInvestor(env);
+      await packedBalanceChange(env, "deposit", small.investor, small.usdcAccount, new BN(1_000_000_000), null);
+      await packedBalanceChange(env, "deposit", leaver.investor, leaver.usdcAccount, new BN(2_000_000_000), null);
+      await packedBalanceChange(env, "deposit", large.investor, large.usdcAccount, new BN(3_000_000_000), null);
+      // The emptied middle slot is skipped by the page
+      await packedBalanceChange(env, "withdraw", leaver.investor, leaver.usdcAccount, new BN(2_000_000_000), 1);
+
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+
+      // Pages are bounded so the event fits in the logs
+      try {
+        await distributePackedPage(env, 0, 33, [small.usdcAccount, large.usdcAccount]);
+        assert.fail("Should have rejected an oversized page");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have rejected an oversized page");
+        // PageTooLarge = 6073
+        expect(error.toString()).to.include("0x17b9");
+      }
+
+      const meta = await distributePackedPage(env, 0, 3, [small.usdcAccount, large.usdcAccount]);
+      const pageEvent = parseEvents(env, meta).find((event) => event.name === "packedPagePayouts");
+      expect(pageEvent).to.exist;
+      expect(pageEvent!.data.startIndex).to.equal(0);
+      expect(
+        pageEvent!.data.payouts.map((payout: any) => [payout.investor.toString(), payout.amount.toString()])
+      ).to.deep.equal([
+        [small.investor.publicKey.toString(), "125000"],
+        [large.investor.publicKey.toString(), "375000"],
+      ]);
+    });
+  });
+
+  describe("Vault balance tracking", () => {
+    it("Should catch a page whose last transfer would overdraw the vault", async () => {
+      const env = await setupCrankEnv();
+      await initializePackedInvestors(env);
+      const small = await createPackedInvestor(env);
+      const large = await createPackedInvestor(env);
+      await packedBalanceChange(env, "deposit", small.investor, small.usdcAccount, new BN(1_000_000_000), null);
+      await packedBalanceChange(env, "deposit", large.investor, large.usdcAccount, new BN(3_000_000_000), null);
+
+      seedFeeVaults(env, BigInt(1_000_000));
+      await beginDistributionDay(env);
+      await crankPage(env, { pageIndex: 0, investorsCount: 2, isFinalPage: true });
+
+      // The vault drops below the snapshotted 500_000 allocation: 125_000 for the first slot
+      // fits, but only 175_000 is left for the second slot's 375_000
+      seedFeeVaults(env, BigInt(300_000));
+
+      try {
+        await distributePackedPage(env, 0, 2, [small.usdcAccount, large.usdcAccount]);
+        assert.fail("Should have caught the overdraw");
+      } catch (error) {
+        expect(error.toString()).to.not.include("Should have caught the overdraw");
+        // InsufficientTokenBalance = 6009
+        expect(error.toString()).to.include("0x1779");
+      }
+
+      // Nothing moved: the whole page is rolled back
+      const vault = await getTokenAccount(env.context.banksClient, env.quoteFeeVaultPDA);
+      expect(vault!.amount.toString()).to.equal("300000");
+
+      // The first slot alone still fits
+      await distributePackedPage(env, 0, 1, [small.usdcAccount]);
+      const crankState = await fetchAccount(env.context.banksClient, env.program, "CrankState", env.crankStatePDA);
+      expect(crankState!.dailyDistributed.toString()).to.equal("125000");
+    });
+  });
+});
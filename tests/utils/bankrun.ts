@@ -7,7 +7,7 @@ import {
   TransactionInstruction,
 } from "@solana/web3.js";
 import type { BanksClient, ProgramTestContext } from "solana-bankrun";
-import { startAnchor } from "solana-bankrun";
+import { Clock, startAnchor } from "solana-bankrun";
 import {
   ACCOUNT_SIZE,
   AccountLayout,
@@ -32,6 +32,16 @@ export const USDC_MINT = new PublicKey(
   "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
 );
 
+// Test stand-in for the DAMM v2 position fee claim (programs/mock-damm-v2)
+export const MOCK_DAMM_V2_PROGRAM_ID = new PublicKey(
+  "cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG"
+);
+
+// Test program receiving day close callbacks (programs/mock-day-callback)
+export const MOCK_DAY_CALLBACK_PROGRAM_ID = new PublicKey(
+  "EaSCyPskuVaHqMPPEuk76xbAdEFJh4d76tGPmVZYgzER"
+);
+
 export const ADMIN_USDC_ATA = getAssociatedTokenAddressSync(
   USDC_MINT,
   LOCAL_ADMIN_KEYPAIR.publicKey,
@@ -65,6 +75,14 @@ export async function startTest() {
         name: "star_fee_distribution",
         programId: new PublicKey("FAAk54pcwJFvHD76YaB5sZzqXCEhUCVpP3cBvggKofuS"),
       },
+      {
+        name: "mock_day_callback",
+        programId: MOCK_DAY_CALLBACK_PROGRAM_ID,
+      },
+      {
+        name: "mock_damm_v2",
+        programId: MOCK_DAMM_V2_PROGRAM_ID,
+      },
     ],
     [
       {
@@ -98,7 +116,7 @@ export async function startTest() {
   );
 }
 
-function createMintData(mint: PublicKey, decimals: number, mintAuthority: PublicKey): Uint8Array {
+export function createMintData(mint: PublicKey, decimals: number, mintAuthority: PublicKey): Uint8Array {
   const mintData = Buffer.alloc(82);
   MintLayout.encode(
     {
@@ -222,6 +240,19 @@ export async function warpSlotBy(context: ProgramTestContext, slots: number) {
   await context.warpToSlot(clock.slot + BigInt(slots.toString()));
 }
 
+export async function warpTimeBy(context: ProgramTestContext, seconds: number) {
+  const clock = await context.banksClient.getClock();
+  context.setClock(
+    new Clock(
+      clock.slot,
+      clock.epochStartTimestamp,
+      clock.epoch,
+      clock.leaderScheduleEpoch,
+      clock.unixTimestamp + BigInt(seconds)
+    )
+  );
+}
+
 export async function fetchAccount(
   banksClient: BanksClient,
   program: any,
@@ -251,4 +282,36 @@ export async function fetchAccount(
   } catch (error: any) {
     return null;
   }
-}
\ No newline at end of file
+}
+
+export function setTokenAccount(
+  context: ProgramTestContext,
+  address: PublicKey,
+  mint: PublicKey,
+  owner: PublicKey,
+  amount: bigint
+) {
+  const data = Buffer.alloc(ACCOUNT_SIZE);
+  AccountLayout.encode(
+    {
+      mint,
+      owner,
+      amount,
+      delegateOption: 0,
+      delegate: PublicKey.default,
+      delegatedAmount: BigInt(0),
+      state: 1,
+      isNativeOption: 0,
+      isNative: BigInt(0),
+      closeAuthorityOption: 0,
+      closeAuthority: PublicKey.default,
+    },
+    data
+  );
+  context.setAccount(address, {
+    lamports: 1_000_000_000,
+    data,
+    owner: TOKEN_PROGRAM_ID,
+    executable: false,
+  });
+}
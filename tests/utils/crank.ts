@@ -0,0 +1,1343 @@
+import { BN, EventParser, Program } from "@coral-xyz/anchor";
+import { BanksTransactionMeta, ProgramTestContext } from "solana-bankrun";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { createHash } from "crypto";
+import {
+  fetchAccount,
+  fundSol,
+  fundUsdc,
+  getOrCreateAta,
+  LOCAL_ADMIN_KEYPAIR,
+  MOCK_DAMM_V2_PROGRAM_ID,
+  setTokenAccount,
+  startTest,
+  USDC_MINT,
+} from "./bankrun";
+import { StarFeeDistribution } from "../../target/types/star_fee_distribution";
+import IDL from "../../target/idl/star_fee_distribution.json";
+
+export interface CrankEnv {
+  context: ProgramTestContext;
+  program: Program<StarFeeDistribution>;
+  admin: Keypair;
+  creatorWallet: Keypair;
+  baseMint: PublicKey;
+  feeCollectorPDA: PublicKey;
+  solVaultPDA: PublicKey;
+  usdcVaultPDA: PublicKey;
+  vaultStatsPDA: PublicKey;
+  distributionConfigPDA: PublicKey;
+  crankStatePDA: PublicKey;
+  baseFeeVaultPDA: PublicKey;
+  quoteFeeVaultPDA: PublicKey;
+}
+
+export function defaultDistributionConfigParams(creatorWallet: PublicKey) {
+  return {
+    y0Allocation: new BN(1_000_000_000),
+    investorFeeShareBps: 5000,
+    minPayoutLamports: new BN(1_000),
+    dailyCapLamports: new BN(0),
+    creatorWallet,
+    quoteMint: USDC_MINT,
+    autoCloseOnFinalPage: false,
+    vestingProgram: PublicKey.default,
+    vestedInvestorShareBps: 0,
+    creatorWalletTimelockSeconds: new BN(0),
+    remainderPolicy: 0,
+    treasuryWallet: PublicKey.default,
+    emitEvents: true,
+    eligibilityDelayDays: 1,
+    eligibilityRoot: Array(32).fill(0),
+    dailyCapQuoteUnits: new BN(0),
+    dayMaxDurationSeconds: new BN(0),
+    minDepositIntervalSeconds: new BN(0),
+    carryOverPolicy: 0,
+    fLockedFloorBps: 0,
+    fLockedCeilingBps: 0,
+    verifyTotalLocked: false,
+    totalLockedToleranceUnits: new BN(0),
+    creatorPayoutMint: PublicKey.default,
+    depositWindowStartTs: new BN(0),
+    depositWindowEndTs: new BN(0),
+    maxCarryOver: new BN(0),
+    carryOverCapPolicy: 0,
+    zeroBalancePolicy: 0,
+    roundsPerDay: 1,
+    minTotalLockedToDistribute: new BN(0),
+    weightByRemainingLock: false,
+    creatorInactivitySeconds: new BN(0),
+    creatorMinShareBps: 0,
+    depositMint: USDC_MINT,
+    dustThresholdK: 0,
+    fixedPageSize: false,
+    callbackProgram: PublicKey.default,
+    maxInvestorShareBps: 0,
+    keeperRewardLamports: new BN(0),
+    earningsUnlockTs: new BN(0),
+    ageBrackets: Array(4).fill({ minAgeSeconds: new BN(0), multiplierBps: 0 }),
+    distributionRateBps: 0,
+    requireFreshClaim: false,
+    minCrankableQuote: new BN(0),
+    maxInvestorShareOfY0Bps: 0,
+    configChangeCooloffSeconds: new BN(0),
+    maxClaimPerTx: new BN(0),
+    dustRecipient: PublicKey.default,
+    rejectDelegatedAccounts: false,
+  };
+}
+
+export async function sendTx(
+  context: ProgramTestContext,
+  tx: Transaction,
+  signers: Keypair[]
+) {
+  const [recentBlockhash] = await context.banksClient.getLatestBlockhash();
+  tx.recentBlockhash = recentBlockhash;
+  tx.sign(...signers);
+  return context.banksClient.processTransaction(tx);
+}
+
+// Decodes the program events emitted in a processed transaction
+export function parseEvents(env: CrankEnv, meta: BanksTransactionMeta) {
+  const parser = new EventParser(env.program.programId, env.program.coder);
+  return Array.from(parser.parseLogs(meta.logMessages));
+}
+
+// Starts a fresh bankrun context with no program accounts initialized
+export async function createCrankEnv(): Promise<CrankEnv> {
+  const context = await startTest();
+  const admin = LOCAL_ADMIN_KEYPAIR;
+  const creatorWallet = Keypair.generate();
+  const baseMint = Keypair.generate().publicKey;
+
+  const program = new Program<StarFeeDistribution>(
+    IDL as StarFeeDistribution,
+    {
+      connection: context.banksClient as any,
+    } as any
+  );
+  const programId = program.programId;
+
+  await fundSol(context.banksClient, admin, [creatorWallet.publicKey]);
+
+  const env: CrankEnv = {
+    context,
+    program,
+    admin,
+    creatorWallet,
+    baseMint,
+    feeCollectorPDA: PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_collector")],
+      programId
+    )[0],
+    solVaultPDA: PublicKey.findProgramAddressSync(
+      [Buffer.from("deposit_vault"), Buffer.from("sol")],
+      programId
+    )[0],
+    usdcVaultPDA: PublicKey.findProgramAddressSync(
+      [Buffer.from("deposit_vault"), USDC_MINT.toBuffer()],
+      programId
+    )[0],
+    vaultStatsPDA: PublicKey.findProgramAddressSync(
+      [Buffer.from("deposit_vault"), Buffer.from("stats")],
+      programId
+    )[0],
+    distributionConfigPDA: PublicKey.findProgramAddressSync(
+      [Buffer.from("distribution_config")],
+      programId
+    )[0],
+    crankStatePDA: PublicKey.findProgramAddressSync(
+      [Buffer.from("crank_state")],
+      programId
+    )[0],
+    baseFeeVaultPDA: PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), baseMint.toBuffer()],
+      programId
+    )[0],
+    quoteFeeVaultPDA: PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), USDC_MINT.toBuffer()],
+      programId
+    )[0],
+  };
+
+  return env;
+}
+
+// Starts a fresh bankrun context with an initialized distribution config
+export async function setupCrankEnv(
+  configOverrides: Record<string, any> = {}
+): Promise<CrankEnv> {
+  const env = await createCrankEnv();
+
+  const tx = await env.program.methods
+    .initializeDistributionConfig({
+      ...defaultDistributionConfigParams(env.creatorWallet.publicKey),
+      ...configOverrides,
+    })
+    .accountsStrict({
+      admin: env.admin.publicKey,
+      distributionConfig: env.distributionConfigPDA,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  await sendTx(env.context, tx, [env.admin]);
+
+  return env;
+}
+
+// Creates the config, vault stats, crank state and quote fee vault in one transaction
+export async function bootstrapProgram(
+  env: CrankEnv,
+  configOverrides: Record<string, any> = {}
+) {
+  const tx = await env.program.methods
+    .bootstrapProgram({
+      ...defaultDistributionConfigParams(env.creatorWallet.publicKey),
+      ...configOverrides,
+    })
+    .accountsStrict({
+      admin: env.admin.publicKey,
+      distributionConfig: env.distributionConfigPDA,
+      vaultStats: env.vaultStatsPDA,
+      crankState: env.crankStatePDA,
+      feeCollector: env.feeCollectorPDA,
+      quoteMint: USDC_MINT,
+      programQuoteVault: env.quoteFeeVaultPDA,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [env.admin]);
+}
+
+export function getDepositorRecordPDA(env: CrankEnv, investor: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("investor_record"), investor.toBuffer()],
+    env.program.programId
+  )[0];
+}
+
+// Creates a funded investor and deposits the given USDC amount
+export async function createInvestorWithDeposit(
+  env: CrankEnv,
+  usdcAmount: BN
+): Promise<{ investor: Keypair; usdcAccount: PublicKey; meta: BanksTransactionMeta }> {
+  const investor = Keypair.generate();
+  await fundSol(env.context.banksClient, env.admin, [investor.publicKey]);
+  await fundUsdc(env.context.banksClient, [investor.publicKey]);
+
+  const usdcAccount = await getOrCreateAta(
+    env.context.banksClient,
+    env.admin,
+    USDC_MINT,
+    investor.publicKey
+  );
+  const meta = await depositUsdc(env, investor, usdcAccount, usdcAmount);
+
+  return { investor, usdcAccount, meta };
+}
+
+// Deposits `usdcAmount` of `mint` (the configured deposit mint unless overridden)
+export async function depositUsdc(
+  env: CrankEnv,
+  investor: Keypair,
+  usdcAccount: PublicKey,
+  usdcAmount: BN,
+  mint: PublicKey = USDC_MINT
+) {
+  const [usdcVault] = PublicKey.findProgramAddressSync(
+    [Buffer.from("deposit_vault"), mint.toBuffer()],
+    env.program.programId
+  );
+  const tx = await env.program.methods
+    .deposit({
+      solAmount: new BN(0),
+      usdcAmount,
+    })
+    .accountsStrict({
+      investor: investor.publicKey,
+      feeCollector: env.feeCollectorPDA,
+      solVault: env.solVaultPDA,
+      usdcVault,
+      usdcMint: mint,
+      investorUsdcAccount: usdcAccount,
+      depositorRecord: getDepositorRecordPDA(env, investor.publicKey),
+      vaultStats: env.vaultStatsPDA,
+      crankState: env.crankStatePDA,
+      distributionConfig: env.distributionConfigPDA,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [investor]);
+}
+
+export async function depositUsdcWithProof(
+  env: CrankEnv,
+  investor: Keypair,
+  usdcAccount: PublicKey,
+  usdcAmount: BN,
+  proof: number[][]
+) {
+  const tx = await env.program.methods
+    .depositWithProof({
+      solAmount: new BN(0),
+      usdcAmount,
+      proof,
+    })
+    .accountsStrict({
+      investor: investor.publicKey,
+      feeCollector: env.feeCollectorPDA,
+      solVault: env.solVaultPDA,
+      usdcVault: env.usdcVaultPDA,
+      usdcMint: USDC_MINT,
+      investorUsdcAccount: usdcAccount,
+      depositorRecord: getDepositorRecordPDA(env, investor.publicKey),
+      vaultStats: env.vaultStatsPDA,
+      crankState: env.crankStatePDA,
+      distributionConfig: env.distributionConfigPDA,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [investor]);
+}
+
+export function getPackedInvestorsPDA(env: CrankEnv) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("packed_investors")],
+    env.program.programId
+  )[0];
+}
+
+export function getKeeperIncentivePDA(env: CrankEnv) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("keeper_incentive")],
+    env.program.programId
+  )[0];
+}
+
+export async function fundKeeperIncentive(env: CrankEnv, funder: Keypair, amount: BN) {
+  const tx = await env.program.methods
+    .fundKeeperIncentive({ amount })
+    .accountsStrict({
+      funder: funder.publicKey,
+      keeperIncentive: getKeeperIncentivePDA(env),
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [funder]);
+}
+
+export async function initializePackedInvestors(env: CrankEnv) {
+  const tx = await env.program.methods
+    .initializePackedInvestors()
+    .accountsStrict({
+      admin: env.admin.publicKey,
+      distributionConfig: env.distributionConfigPDA,
+      packedInvestors: getPackedInvestorsPDA(env),
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [env.admin]);
+}
+
+// Deposits into (index = null allocates a slot) or withdraws from a packed investor slot
+export async function packedBalanceChange(
+  env: CrankEnv,
+  kind: "deposit" | "withdraw",
+  investor: Keypair,
+  usdcAccount: PublicKey,
+  usdcAmount: BN,
+  index: number | null
+) {
+  const params = { usdcAmount, index };
+  const builder =
+    kind === "deposit"
+      ? env.program.methods.packedDeposit(params)
+      : env.program.methods.packedWithdraw(params);
+  const tx = await builder
+    .accountsStrict({
+      investor: investor.publicKey,
+      feeCollector: env.feeCollectorPDA,
+      usdcVault: env.usdcVaultPDA,
+      usdcMint: USDC_MINT,
+      investorUsdcAccount: usdcAccount,
+      packedInvestors: getPackedInvestorsPDA(env),
+      vaultStats: env.vaultStatsPDA,
+      crankState: env.crankStatePDA,
+      distributionConfig: env.distributionConfigPDA,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [investor]);
+}
+
+// Pays packed slots [startIndex, startIndex + count); one quote account per active slot
+export async function distributePackedPage(
+  env: CrankEnv,
+  startIndex: number,
+  count: number,
+  quoteAccounts: PublicKey[]
+) {
+  const tx = await env.program.methods
+    .distributePackedPage({ startIndex, count })
+    .accountsStrict({
+      feeCollector: env.feeCollectorPDA,
+      programQuoteVault: env.quoteFeeVaultPDA,
+      packedInvestors: getPackedInvestorsPDA(env),
+      vaultStats: env.vaultStatsPDA,
+      distributionConfig: env.distributionConfigPDA,
+      crankState: env.crankStatePDA,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .remainingAccounts(
+      quoteAccounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+    )
+    .transaction();
+  return sendTx(env.context, tx, [env.admin]);
+}
+
+export async function withdrawUsdc(
+  env: CrankEnv,
+  investor: Keypair,
+  usdcAccount: PublicKey,
+  usdcAmount: BN
+) {
+  const tx = await env.program.methods
+    .withdraw({
+      solAmount: new BN(0),
+      usdcAmount,
+    })
+    .accountsStrict({
+      investor: investor.publicKey,
+      feeCollector: env.feeCollectorPDA,
+      solVault: env.solVaultPDA,
+      usdcVault: env.usdcVaultPDA,
+      usdcMint: USDC_MINT,
+      investorUsdcAccount: usdcAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      depositorRecord: getDepositorRecordPDA(env, investor.publicKey),
+      vaultStats: env.vaultStatsPDA,
+      crankState: env.crankStatePDA,
+      distributionConfig: env.distributionConfigPDA,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [investor]);
+}
+
+// Queries the top-1 / top-5 USDC concentration over the given investors' records
+export async function queryVaultConcentration(env: CrankEnv, investors: PublicKey[]) {
+  const tx = await env.program.methods
+    .queryVaultConcentration()
+    .accountsStrict({
+      vaultStats: env.vaultStatsPDA,
+    })
+    .remainingAccounts(
+      investors.map((investor) => ({
+        pubkey: getDepositorRecordPDA(env, investor),
+        isSigner: false,
+        isWritable: false,
+      }))
+    )
+    .transaction();
+  const meta = await sendTx(env.context, tx, [env.admin]);
+  return env.program.coder.types.decode(
+    "VaultConcentration",
+    Buffer.from(meta.returnData!.data)
+  );
+}
+
+// Queries the lifetime distribution totals across all closed days
+export async function queryGlobalStats(env: CrankEnv) {
+  const tx = await env.program.methods
+    .queryGlobalStats()
+    .accountsStrict({
+      vaultStats: env.vaultStatsPDA,
+    })
+    .transaction();
+  const meta = await sendTx(env.context, tx, [env.admin]);
+  return env.program.coder.types.decode(
+    "GlobalStats",
+    Buffer.from(meta.returnData!.data)
+  );
+}
+
+export async function queryInvestorWeight(env: CrankEnv, investor: PublicKey) {
+  const tx = await env.program.methods
+    .queryInvestorWeight()
+    .accountsStrict({
+      investor,
+      depositorRecord: getDepositorRecordPDA(env, investor),
+      vaultStats: env.vaultStatsPDA,
+    })
+    .transaction();
+  const meta = await sendTx(env.context, tx, [env.admin]);
+  return env.program.coder.types.decode(
+    "InvestorWeight",
+    Buffer.from(meta.returnData!.data)
+  );
+}
+
+// Queries the day record of a given day
+export async function queryDayRecord(env: CrankEnv, day: number) {
+  const tx = await env.program.methods
+    .queryDayRecord({ day })
+    .accountsStrict({
+      dayRecord: getDayRecordPDA(env, day),
+    })
+    .transaction();
+  const meta = await sendTx(env.context, tx, [env.admin]);
+  return env.program.coder.types.decode(
+    "DayRecord",
+    Buffer.from(meta.returnData!.data)
+  );
+}
+
+// Simulates the next day's payouts over the given investors' records for a hypothetical claim
+export async function simulateDay(env: CrankEnv, claimedQuote: BN, investors: PublicKey[]) {
+  const tx = await env.program.methods
+    .simulateDay({ claimedQuote })
+    .accountsStrict({
+      distributionConfig: env.distributionConfigPDA,
+      vaultStats: env.vaultStatsPDA,
+      crankState: env.crankStatePDA,
+    })
+    .remainingAccounts(
+      investors.map((investor) => ({
+        pubkey: getDepositorRecordPDA(env, investor),
+        isSigner: false,
+        isWritable: false,
+      }))
+    )
+    .transaction();
+  const meta = await sendTx(env.context, tx, [env.admin]);
+  return env.program.coder.types.decode(
+    "SimulatedDay",
+    Buffer.from(meta.returnData!.data)
+  );
+}
+
+export async function closeDepositorRecord(env: CrankEnv, investor: Keypair) {
+  const tx = await env.program.methods
+    .closeDepositorRecord()
+    .accountsStrict({
+      investor: investor.publicKey,
+      depositorRecord: getDepositorRecordPDA(env, investor.publicKey),
+    })
+    .transaction();
+  return sendTx(env.context, tx, [investor]);
+}
+
+export async function registerQuoteAccount(
+  env: CrankEnv,
+  investor: Keypair,
+  quoteAccount: PublicKey
+) {
+  const tx = await env.program.methods
+    .registerQuoteAccount()
+    .accountsStrict({
+      investor: investor.publicKey,
+      depositorRecord: getDepositorRecordPDA(env, investor.publicKey),
+      distributionConfig: env.distributionConfigPDA,
+      quoteAccount,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [investor]);
+}
+
+export async function setAutoCompound(
+  env: CrankEnv,
+  investor: Keypair,
+  autoCompound: boolean
+) {
+  const tx = await env.program.methods
+    .setAutoCompound({ autoCompound })
+    .accountsStrict({
+      investor: investor.publicKey,
+      depositorRecord: getDepositorRecordPDA(env, investor.publicKey),
+    })
+    .transaction();
+  return sendTx(env.context, tx, [investor]);
+}
+
+export async function setOptOut(env: CrankEnv, investor: Keypair, optedOut: boolean) {
+  const tx = await env.program.methods
+    .setOptOut({ optedOut })
+    .accountsStrict({
+      investor: investor.publicKey,
+      depositorRecord: getDepositorRecordPDA(env, investor.publicKey),
+      vaultStats: env.vaultStatsPDA,
+      crankState: env.crankStatePDA,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [investor]);
+}
+
+// Resets the vault stats SOL/USDC totals to the vault balances, or to the sum of the given investors' records
+export async function reconcileVaultStats(
+  env: CrankEnv,
+  fromRecords: boolean,
+  investors: PublicKey[] = []
+) {
+  const tx = await env.program.methods
+    .reconcileVaultStats({ fromRecords })
+    .accountsStrict({
+      admin: env.admin.publicKey,
+      distributionConfig: env.distributionConfigPDA,
+      vaultStats: env.vaultStatsPDA,
+      crankState: env.crankStatePDA,
+      feeCollector: env.feeCollectorPDA,
+      solVault: env.solVaultPDA,
+      usdcVault: env.usdcVaultPDA,
+      usdcMint: USDC_MINT,
+    })
+    .remainingAccounts(
+      investors.map((investor) => ({
+        pubkey: getDepositorRecordPDA(env, investor),
+        isSigner: false,
+        isWritable: false,
+      }))
+    )
+    .transaction();
+  return sendTx(env.context, tx, [env.admin]);
+}
+
+export async function setBatchWithdrawApproval(
+  env: CrankEnv,
+  investor: Keypair,
+  approved: boolean
+) {
+  const tx = await env.program.methods
+    .setBatchWithdrawApproval({ approved })
+    .accountsStrict({
+      investor: investor.publicKey,
+      depositorRecord: getDepositorRecordPDA(env, investor.publicKey),
+    })
+    .transaction();
+  return sendTx(env.context, tx, [investor]);
+}
+
+// Withdraws the full USDC balance of each approved investor to their registered quote account
+export async function batchWithdraw(
+  env: CrankEnv,
+  withdrawals: { investor: PublicKey; quoteAccount: PublicKey }[]
+) {
+  const tx = await env.program.methods
+    .batchWithdraw()
+    .accountsStrict({
+      admin: env.admin.publicKey,
+      distributionConfig: env.distributionConfigPDA,
+      feeCollector: env.feeCollectorPDA,
+      usdcVault: env.usdcVaultPDA,
+      usdcMint: USDC_MINT,
+      vaultStats: env.vaultStatsPDA,
+      crankState: env.crankStatePDA,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .remainingAccounts(
+      withdrawals.flatMap(({ investor, quoteAccount }) => [
+        { pubkey: getDepositorRecordPDA(env, investor), isSigner: false, isWritable: true },
+        { pubkey: quoteAccount, isSigner: false, isWritable: true },
+      ])
+    )
+    .transaction();
+  return sendTx(env.context, tx, [env.admin]);
+}
+
+export async function setInvestorMultiplier(
+  env: CrankEnv,
+  investor: PublicKey,
+  feeShareMultiplierBps: number,
+  signer: Keypair = env.admin
+) {
+  const tx = await env.program.methods
+    .setInvestorMultiplier({ investor, feeShareMultiplierBps })
+    .accountsStrict({
+      admin: signer.publicKey,
+      distributionConfig: env.distributionConfigPDA,
+      depositorRecord: getDepositorRecordPDA(env, investor),
+      vaultStats: env.vaultStatsPDA,
+      crankState: env.crankStatePDA,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [signer]);
+}
+
+// Writes the program's base and quote fee vaults as if fees had been claimed
+// Moves the balance of a stray fee_collector-owned token account to the admin's account
+export async function rescueTokens(
+  env: CrankEnv,
+  strayTokenAccount: PublicKey,
+  destination: PublicKey
+) {
+  const tx = await env.program.methods
+    .rescueTokens()
+    .accountsStrict({
+      admin: env.admin.publicKey,
+      distributionConfig: env.distributionConfigPDA,
+      feeCollector: env.feeCollectorPDA,
+      strayTokenAccount,
+      destination,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [env.admin]);
+}
+
+export function seedFeeVaults(
+  env: CrankEnv,
+  quoteAmount: bigint,
+  baseAmount: bigint = BigInt(0)
+) {
+  setTokenAccount(
+    env.context,
+    env.baseFeeVaultPDA,
+    env.baseMint,
+    env.feeCollectorPDA,
+    baseAmount
+  );
+  setTokenAccount(
+    env.context,
+    env.quoteFeeVaultPDA,
+    USDC_MINT,
+    env.feeCollectorPDA,
+    quoteAmount
+  );
+}
+
+export async function beginDistributionDay(env: CrankEnv) {
+  const tx = await env.program.methods
+    .beginDistributionDay()
+    .accountsStrict({
+      payer: env.admin.publicKey,
+      feeCollector: env.feeCollectorPDA,
+      programQuoteVault: env.quoteFeeVaultPDA,
+      vaultStats: env.vaultStatsPDA,
+      distributionConfig: env.distributionConfigPDA,
+      crankState: env.crankStatePDA,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [env.admin]);
+}
+
+export async function distributeFees(
+  env: CrankEnv,
+  recipientTokenAAccount: PublicKey,
+  recipientTokenBAccount: PublicKey
+) {
+  const tx = await env.program.methods
+    .distributeFees({ baseAmount: new BN(0), quoteAmount: new BN(0) })
+    .accountsStrict({
+      feeCollector: env.feeCollectorPDA,
+      programTokenAVault: env.baseFeeVaultPDA,
+      programTokenBVault: env.quoteFeeVaultPDA,
+      baseMint: env.baseMint,
+      quoteMint: USDC_MINT,
+      recipientTokenAAccount,
+      recipientTokenBAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [env.admin]);
+}
+
+// DAMM v2 position accounts the crank claims through when auto-claiming
+export interface CrankClaimAccounts {
+  pool: PublicKey;
+  position: PublicKey;
+  positionNftAccount: PublicKey;
+  poolAuthority: PublicKey;
+  tokenAVault: PublicKey;
+  tokenBVault: PublicKey;
+}
+
+// Sets up a position on the DAMM v2 mock whose pool vaults hold `quoteFees` (and `baseFees`)
+// as pending fees, paid out in full by the next claim
+export function setupMockClaimPosition(
+  env: CrankEnv,
+  quoteFees: bigint,
+  baseFees: bigint = BigInt(0)
+): CrankClaimAccounts {
+  const pool = Keypair.generate().publicKey;
+  const position = Keypair.generate().publicKey;
+
+  // Pool and position layouts as read by the quote-only pre-check, with no liquidity
+  const poolData = Buffer.alloc(8 + 1104);
+  Buffer.from([241, 154, 109, 4, 17, 177, 109, 188]).copy(poolData, 0);
+  env.context.setAccount(pool, {
+    executable: false,
+    owner: MOCK_DAMM_V2_PROGRAM_ID,
+    lamports: 1_000_000_000,
+    data: poolData,
+  });
+  const positionData = Buffer.alloc(8 + 400);
+  Buffer.from([170, 188, 143, 228, 122, 64, 247, 208]).copy(positionData, 0);
+  pool.toBuffer().copy(positionData, 8);
+  env.context.setAccount(position, {
+    executable: false,
+    owner: MOCK_DAMM_V2_PROGRAM_ID,
+    lamports: 1_000_000_000,
+    data: positionData,
+  });
+
+  const positionNftAccount = Keypair.generate().publicKey;
+  env.context.setAccount(positionNftAccount, {
+    executable: false,
+    owner: TOKEN_PROGRAM_ID,
+    lamports: 1_000_000_000,
+    data: Buffer.alloc(165),
+  });
+
+  const [poolAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from("pool_authority")],
+    MOCK_DAMM_V2_PROGRAM_ID
+  );
+  const tokenAVault = Keypair.generate().publicKey;
+  const tokenBVault = Keypair.generate().publicKey;
+  setTokenAccount(env.context, tokenAVault, env.baseMint, poolAuthority, baseFees);
+  setTokenAccount(env.context, tokenBVault, USDC_MINT, poolAuthority, quoteFees);
+
+  return { pool, position, positionNftAccount, poolAuthority, tokenAVault, tokenBVault };
+}
+
+// Claims a mock position's fees into the program fee vaults
+export function getPositionRegistryPDA(env: CrankEnv) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("position_registry")],
+    env.program.programId
+  )[0];
+}
+
+export async function addPosition(env: CrankEnv, position: PublicKey) {
+  const tx = await env.program.methods
+    .addPosition({ position })
+    .accountsStrict({
+      admin: env.admin.publicKey,
+      distributionConfig: env.distributionConfigPDA,
+      positionRegistry: getPositionRegistryPDA(env),
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [env.admin]);
+}
+
+export async function removePosition(env: CrankEnv, position: PublicKey) {
+  const tx = await env.program.methods
+    .removePosition({ position })
+    .accountsStrict({
+      admin: env.admin.publicKey,
+      distributionConfig: env.distributionConfigPDA,
+      positionRegistry: getPositionRegistryPDA(env),
+    })
+    .transaction();
+  return sendTx(env.context, tx, [env.admin]);
+}
+
+export async function claimFeesToPda(env: CrankEnv, claimAccounts: CrankClaimAccounts) {
+  const tx = await env.program.methods
+    .claimFeesToPda()
+    .accountsStrict({
+      feeCollector: env.feeCollectorPDA,
+      ammProgram: MOCK_DAMM_V2_PROGRAM_ID,
+      pool: claimAccounts.pool,
+      position: claimAccounts.position,
+      positionNftAccount: claimAccounts.positionNftAccount,
+      poolAuthority: claimAccounts.poolAuthority,
+      baseMint: env.baseMint,
+      quoteMint: USDC_MINT,
+      tokenAVault: claimAccounts.tokenAVault,
+      tokenBVault: claimAccounts.tokenBVault,
+      programTokenAVault: env.baseFeeVaultPDA,
+      programTokenBVault: env.quoteFeeVaultPDA,
+      positionState: null,
+      crankState: null,
+      distributionConfig: env.distributionConfigPDA,
+      positionRegistry: getPositionRegistryPDA(env),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      eventAuthority: Keypair.generate().publicKey,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [env.admin]);
+}
+
+export async function crankPage(
+  env: CrankEnv,
+  params: {
+    pageIndex: number;
+    investorsCount: number;
+    isFinalPage: boolean;
+    roundIndex?: number;
+    autoClaim?: boolean;
+  },
+  creatorQuoteAccount: PublicKey | null = null,
+  recordInvestors: PublicKey[] = [],
+  topInvestorQuoteAccount: PublicKey | null = null,
+  keeperIncentive: PublicKey | null = null,
+  positionState: PublicKey | null = null,
+  claimAccounts: CrankClaimAccounts | null = null,
+  dustRecipientQuoteAccount: PublicKey | null = null
+) {
+  const crankState = await fetchAccount(
+    env.context.banksClient,
+    env.program,
+    "CrankState",
+    env.crankStatePDA
+  );
+  const tx = await env.program.methods
+    .crankFeeDistribution({ roundIndex: 0, autoClaim: false, ...params })
+    .accountsStrict({
+      payer: env.admin.publicKey,
+      feeCollector: env.feeCollectorPDA,
+      programTokenAVault: env.baseFeeVaultPDA,
+      programTokenBVault: env.quoteFeeVaultPDA,
+      baseMint: env.baseMint,
+      quoteMint: USDC_MINT,
+      vaultStats: env.vaultStatsPDA,
+      distributionConfig: env.distributionConfigPDA,
+      crankState: env.crankStatePDA,
+      creatorQuoteAccount,
+      topInvestorQuoteAccount,
+      dustRecipientQuoteAccount,
+      keeperIncentive,
+      positionState,
+      ammProgram: claimAccounts ? MOCK_DAMM_V2_PROGRAM_ID : null,
+      pool: claimAccounts?.pool ?? null,
+      position: claimAccounts?.position ?? null,
+      positionNftAccount: claimAccounts?.positionNftAccount ?? null,
+      poolAuthority: claimAccounts?.poolAuthority ?? null,
+      tokenAVault: claimAccounts?.tokenAVault ?? null,
+      tokenBVault: claimAccounts?.tokenBVault ?? null,
+      eventAuthority: claimAccounts ? Keypair.generate().publicKey : null,
+      positionRegistry: claimAccounts ? getPositionRegistryPDA(env) : null,
+      // Only used when the page closes the day; the crank state is missing before the first day
+      dayRecord: getDayRecordPDA(env, crankState?.currentDay ?? 0),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .remainingAccounts(
+      recordInvestors.map((investor) => ({
+        pubkey: getDepositorRecordPDA(env, investor),
+        isSigner: false,
+        isWritable: false,
+      }))
+    )
+    .transaction();
+  return sendTx(env.context, tx, [env.admin]);
+}
+
+export async function distributeToInvestor(
+  env: CrankEnv,
+  investor: Keypair,
+  investorQuoteAccount: PublicKey,
+  totalInvestorFee: BN,
+  vestingAccount: PublicKey | null = null,
+  depositVault: PublicKey | null = null
+) {
+  const tx = await env.program.methods
+    .distributeToInvestor({ totalInvestorFee })
+    .accountsStrict({
+      feeCollector: env.feeCollectorPDA,
+      programQuoteVault: env.quoteFeeVaultPDA,
+      quoteMint: USDC_MINT,
+      investorQuoteAccount,
+      depositorRecord: getDepositorRecordPDA(env, investor.publicKey),
+      depositVault,
+      vaultStats: env.vaultStatsPDA,
+      distributionConfig: env.distributionConfigPDA,
+      crankState: env.crankStatePDA,
+      investor: investor.publicKey,
+      vestingAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [investor]);
+}
+
+/** DAMM v2 accounts used to swap the creator remainder into `creator_payout_mint` */
+export interface CreatorPayoutSwapAccounts {
+  ammProgram: PublicKey;
+  pool: PublicKey;
+  poolAuthority: PublicKey;
+  poolTokenAVault: PublicKey;
+  poolTokenBVault: PublicKey;
+  poolTokenAMint: PublicKey;
+  poolTokenBMint: PublicKey;
+  eventAuthority: PublicKey;
+}
+
+export async function routeCreatorRemainder(
+  env: CrankEnv,
+  creatorQuoteAccount: PublicKey | null,
+  swap: CreatorPayoutSwapAccounts | null = null,
+  minOut: BN = new BN(0),
+  topInvestorQuoteAccount: PublicKey | null = null,
+  callbackProgram: PublicKey | null = null,
+  dustRecipientQuoteAccount: PublicKey | null = null
+) {
+  const crankState = await fetchAccount(
+    env.context.banksClient,
+    env.program,
+    "CrankState",
+    env.crankStatePDA
+  );
+  const tx = await env.program.methods
+    .routeCreatorRemainder({ minOut })
+    .accountsStrict({
+      payer: env.admin.publicKey,
+      feeCollector: env.feeCollectorPDA,
+      programQuoteVault: env.quoteFeeVaultPDA,
+      quoteMint: USDC_MINT,
+      creatorQuoteAccount,
+      topInvestorQuoteAccount,
+      dustRecipientQuoteAccount,
+      distributionConfig: env.distributionConfigPDA,
+      crankState: env.crankStatePDA,
+      vaultStats: env.vaultStatsPDA,
+      dayRecord: getDayRecordPDA(env, crankState!.currentDay),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      ammProgram: swap?.ammProgram ?? null,
+      pool: swap?.pool ?? null,
+      poolAuthority: swap?.poolAuthority ?? null,
+      poolTokenAVault: swap?.poolTokenAVault ?? null,
+      poolTokenBVault: swap?.poolTokenBVault ?? null,
+      poolTokenAMint: swap?.poolTokenAMint ?? null,
+      poolTokenBMint: swap?.poolTokenBMint ?? null,
+      eventAuthority: swap?.eventAuthority ?? null,
+      callbackProgram,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [env.admin]);
+}
+
+export async function forceCloseDay(
+  env: CrankEnv,
+  creatorQuoteAccount: PublicKey | null,
+  topInvestorQuoteAccount: PublicKey | null = null,
+  dustRecipientQuoteAccount: PublicKey | null = null
+) {
+  const crankState = await fetchAccount(
+    env.context.banksClient,
+    env.program,
+    "CrankState",
+    env.crankStatePDA
+  );
+  const tx = await env.program.methods
+    .forceCloseDay()
+    .accountsStrict({
+      payer: env.admin.publicKey,
+      feeCollector: env.feeCollectorPDA,
+      programQuoteVault: env.quoteFeeVaultPDA,
+      quoteMint: USDC_MINT,
+      creatorQuoteAccount,
+      topInvestorQuoteAccount,
+      dustRecipientQuoteAccount,
+      distributionConfig: env.distributionConfigPDA,
+      crankState: env.crankStatePDA,
+      vaultStats: env.vaultStatsPDA,
+      dayRecord: getDayRecordPDA(env, crankState!.currentDay),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  return sendTx(env.context, tx, [env.admin]);
+}
+
+export async function creatorPartialClaim(
+  env: CrankEnv,
+  creatorQuoteAccount: PublicKey,
+  maxAmount: BN
+) {
+  const tx = await env.program.methods
+    .creatorPartialClaim({ maxAmount })
+    .accountsStrict({
+      creator: env.creatorWallet.publicKey,
+      feeCollector: env.feeCollectorPDA,
+      programQuoteVault: env.quoteFeeVaultPDA,
+      quoteMint: USDC_MINT,
+      creatorQuoteAccount,
+      distributionConfig: env.distributionConfigPDA,
+      crankState: env.crankStatePDA,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .transaction();
+  await sendTx(env.context, tx, [env.creatorWallet]);
+}
+
+export async function getCreatorQuoteAccount(env: CrankEnv) {
+  return getOrCreateAta(
+    env.context.banksClient,
+    env.admin,
+    USDC_MINT,
+    env.creatorWallet.publicKey
+  );
+}
+
+export async function scheduleCreatorWalletUpdate(
+  env: CrankEnv,
+  newCreatorWallet: PublicKey,
+  signer: Keypair = env.admin
+) {
+  const tx = await env.program.methods
+    .scheduleCreatorWalletUpdate({ newCreatorWallet })
+    .accountsStrict({
+      admin: signer.publicKey,
+      distributionConfig: env.distributionConfigPDA,
+    })
+    .transaction();
+  await sendTx(env.context, tx, [signer]);
+}
+
+export async function updateDistributionConfig(
+  env: CrankEnv,
+  updates: Record<string, any>,
+  signer: Keypair = env.admin
+) {
+  const tx = await env.program.methods
+    .updateDistributionConfig({
+      y0Allocation: null,
+      investorFeeShareBps: null,
+      vestingProgram: null,
+      vestedInvestorShareBps: null,
+      minPayoutLamports: null,
+      dailyCapLamports: null,
+      autoCloseOnFinalPage: null,
+      emitEvents: null,
+      eligibilityRoot: null,
+      dailyCapQuoteUnits: null,
+      dayMaxDurationSeconds: null,
+      minDepositIntervalSeconds: null,
+      carryOverPolicy: null,
+      fLockedFloorBps: null,
+      fLockedCeilingBps: null,
+      verifyTotalLocked: null,
+      totalLockedToleranceUnits: null,
+      creatorPayoutMint: null,
+      depositWindowStartTs: null,
+      depositWindowEndTs: null,
+      maxCarryOver: null,
+      carryOverCapPolicy: null,
+      zeroBalancePolicy: null,
+      roundsPerDay: null,
+      minTotalLockedToDistribute: null,
+      weightByRemainingLock: null,
+      creatorInactivitySeconds: null,
+      creatorMinShareBps: null,
+      dustThresholdK: null,
+      fixedPageSize: null,
+      programEnded: null,
+      callbackProgram: null,
+      maxInvestorShareBps: null,
+      keeperRewardLamports: null,
+      earningsUnlockTs: null,
+      ageBrackets: null,
+      distributionRateBps: null,
+      requireFreshClaim: null,
+      minCrankableQuote: null,
+      newDaysPaused: null,
+      maxInvestorShareOfY0Bps: null,
+      configChangeCooloffSeconds: null,
+      maxClaimPerTx: null,
+      dustRecipient: null,
+      rejectDelegatedAccounts: null,
+      ...updates,
+    })
+    .accountsStrict({
+      admin: signer.publicKey,
+      distributionConfig: env.distributionConfigPDA,
+      crankState: env.crankStatePDA,
+    })
+    .transaction();
+  await sendTx(env.context, tx, [signer]);
+}
+
+export async function replaceConfig(
+  env: CrankEnv,
+  overrides: Record<string, any>,
+  signer: Keypair = env.admin
+) {
+  const tx = await env.program.methods
+    .replaceConfig({
+      ...defaultDistributionConfigParams(env.creatorWallet.publicKey),
+      ...overrides,
+    })
+    .accountsStrict({
+      admin: signer.publicKey,
+      distributionConfig: env.distributionConfigPDA,
+      crankState: env.crankStatePDA,
+    })
+    .transaction();
+  await sendTx(env.context, tx, [signer]);
+}
+
+export async function updateQuoteMint(
+  env: CrankEnv,
+  newQuoteMint: PublicKey,
+  oldQuoteVault: PublicKey | null,
+  sweepDestination: PublicKey | null,
+  signer: Keypair = env.admin
+) {
+  const tx = await env.program.methods
+    .updateQuoteMint({ newQuoteMint })
+    .accountsStrict({
+      admin: signer.publicKey,
+      feeCollector: env.feeCollectorPDA,
+      distributionConfig: env.distributionConfigPDA,
+      crankState: env.crankStatePDA,
+      oldQuoteVault,
+      sweepDestination,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .transaction();
+  await sendTx(env.context, tx, [signer]);
+}
+
+// Flushes carry-over to the given (investor, quote account) pairs
+export async function flushCarryOver(
+  env: CrankEnv,
+  recipients: { investor: PublicKey; quoteAccount: PublicKey }[],
+  signer: Keypair = env.admin
+) {
+  const tx = await env.program.methods
+    .flushCarryOver()
+    .accountsStrict({
+      admin: signer.publicKey,
+      feeCollector: env.feeCollectorPDA,
+      programQuoteVault: env.quoteFeeVaultPDA,
+      vaultStats: env.vaultStatsPDA,
+      distributionConfig: env.distributionConfigPDA,
+      crankState: env.crankStatePDA,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .remainingAccounts(
+      recipients.flatMap(({ investor, quoteAccount }) => [
+        { pubkey: getDepositorRecordPDA(env, investor), isSigner: false, isWritable: false },
+        { pubkey: quoteAccount, isSigner: false, isWritable: true },
+      ])
+    )
+    .transaction();
+  return sendTx(env.context, tx, [signer]);
+}
+
+export interface PayoutLeaf {
+  investor: PublicKey;
+  amount: BN;
+}
+
+const sha256 = (...parts: Buffer[]) =>
+  createHash("sha256").update(Buffer.concat(parts)).digest();
+
+// Mirrors utils::merkle_root: leaves are sha256(0x00 || investor || amount_le),
+// nodes are sha256(0x01 || sorted pair) and an odd node is promoted as-is
+export function computePayoutRoot(leaves: PayoutLeaf[]): number[] {
+  let nodes = leaves.map((leaf) =>
+    sha256(
+      Buffer.from([0]),
+      leaf.investor.toBuffer(),
+      leaf.amount.toArrayLike(Buffer, "le", 8)
+    )
+  );
+  if (nodes.length === 0) {
+    return Array(32).fill(0);
+  }
+
+  while (nodes.length > 1) {
+    const next: Buffer[] = [];
+    for (let i = 0; i < nodes.length; i += 2) {
+      if (i + 1 === nodes.length) {
+        next.push(nodes[i]);
+        continue;
+      }
+      const [lo, hi] =
+        Buffer.compare(nodes[i], nodes[i + 1]) <= 0
+          ? [nodes[i], nodes[i + 1]]
+          : [nodes[i + 1], nodes[i]];
+      next.push(sha256(Buffer.from([1]), lo, hi));
+    }
+    nodes = next;
+  }
+
+  return Array.from(nodes[0]);
+}
+
+// Mirrors utils::eligibility_leaf_hash and utils::verify_merkle_proof: leaves are
+// sha256(0x02 || investor) and each proof lists the siblings of every non-promoted level
+export function computeEligibilityTree(investors: PublicKey[]): {
+  root: number[];
+  proofs: number[][][];
+} {
+  let nodes = investors.map((investor) =>
+    sha256(Buffer.from([2]), investor.toBuffer())
+  );
+  // Current position of each investor's node at the working level
+  let positions = investors.map((_, i) => i);
+  const proofs: number[][][] = investors.map(() => []);
+
+  while (nodes.length > 1) {
+    const next: Buffer[] = [];
+    for (let i = 0; i < nodes.length; i += 2) {
+      if (i + 1 === nodes.length) {
+        next.push(nodes[i]);
+        continue;
+      }
+      const [lo, hi] =
+        Buffer.compare(nodes[i], nodes[i + 1]) <= 0
+          ? [nodes[i], nodes[i + 1]]
+          : [nodes[i + 1], nodes[i]];
+      next.push(sha256(Buffer.from([1]), lo, hi));
+    }
+    positions.forEach((position, i) => {
+      const sibling = position ^ 1;
+      if (sibling < nodes.length) {
+        proofs[i].push(Array.from(nodes[sibling]));
+      }
+    });
+    positions = positions.map((position) => position >> 1);
+    nodes = next;
+  }
+
+  return {
+    root: nodes.length === 0 ? Array(32).fill(0) : Array.from(nodes[0]),
+    proofs,
+  };
+}
+
+export function getDayRecordPDA(env: CrankEnv, day: number) {
+  const dayBytes = Buffer.alloc(4);
+  dayBytes.writeUInt32LE(day);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("day_record"), dayBytes],
+    env.program.programId
+  )[0];
+}
+
+export async function submitPayoutRoot(
+  env: CrankEnv,
+  day: number,
+  leaves: PayoutLeaf[],
+  payoutRoot: number[],
+  submitter: Keypair = env.admin
+) {
+  const tx = await env.program.methods
+    .submitPayoutRoot({ leaves, payoutRoot })
+    .accountsStrict({
+      submitter: submitter.publicKey,
+      distributionConfig: env.distributionConfigPDA,
+      crankState: env.crankStatePDA,
+      dayRecord: getDayRecordPDA(env, day),
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  await sendTx(env.context, tx, [submitter]);
+}